@@ -96,6 +96,68 @@ pub struct Args {
     #[clap(long = "verbose")]
     verbose: bool,
 
+    /// Wait for a debugger such as `lldb` to connect on the local TCP port before execution.
+    ///
+    /// The debugger communicates via the GDB remote serial protocol with the
+    /// LLDB WebAssembly extensions. The execution is paused before the first
+    /// executed Wasm operator.
+    #[clap(long = "gdb-port", value_name = "PORT")]
+    gdb_port: Option<u16>,
+
+    /// Profile the execution and write the profile to the file.
+    ///
+    /// Records the calls and consumed fuel of all executed Wasm functions.
+    /// Writes the Firefox Profiler format if the file extension is `.json`
+    /// and folded stacks for flamegraphs otherwise.
+    ///
+    /// Enables fuel metering with unlimited fuel unless `--fuel` is given.
+    #[clap(
+        long = "profile",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    profile: Option<PathBuf>,
+
+    /// Record the code coverage of the execution and write it to the file in the LCOV format.
+    ///
+    /// Source lines are taken from the DWARF debug information of the Wasm module if any.
+    /// Otherwise the Wasm byte offsets are reported as lines of the Wasm file.
+    ///
+    /// Compiles the Wasm module eagerly so that never executed functions are reported.
+    #[clap(
+        long = "coverage",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    coverage: Option<PathBuf>,
+
+    /// Print the translated Wasmi bytecode of all functions defined by the Wasm module and exit.
+    ///
+    /// The output format is meant for humans and may change between Wasmi versions.
+    #[clap(long = "emit-ir")]
+    emit_ir: bool,
+
+    /// Print every executed Wasmi instruction to stderr.
+    ///
+    /// Each line consists of the function index, the position of the instruction
+    /// within its function, the Wasm offset of its originating Wasm operator and
+    /// the instruction itself in the format of `--emit-ir`.
+    ///
+    /// Requires the `trace` crate feature.
+    #[clap(long = "trace")]
+    trace: bool,
+
+    /// Write a Wasm core dump to the file if the execution traps.
+    ///
+    /// The core dump uses the Wasm tool-conventions coredump format and contains
+    /// the call stack with locals and operands, the linear memories and the globals.
+    #[clap(
+        long = "coredump-on-trap",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    coredump_on_trap: Option<PathBuf>,
+
     /// Arguments given to the Wasm module or the invoked function.
     #[clap(value_name = "ARGS")]
     func_args: Vec<String>,
@@ -151,6 +213,36 @@ impl Args {
         self.verbose
     }
 
+    /// Returns the local TCP port to serve the GDB remote serial protocol if any.
+    pub fn gdb_port(&self) -> Option<u16> {
+        self.gdb_port
+    }
+
+    /// Returns the path of the profile output file if profiling is enabled.
+    pub fn profile(&self) -> Option<&Path> {
+        self.profile.as_deref()
+    }
+
+    /// Returns `true` if the translated Wasmi bytecode shall be printed instead of executed.
+    pub fn emit_ir(&self) -> bool {
+        self.emit_ir
+    }
+
+    /// Returns `true` if every executed Wasmi instruction shall be printed.
+    pub fn trace(&self) -> bool {
+        self.trace
+    }
+
+    /// Returns the path of the LCOV output file if code coverage is enabled.
+    pub fn coverage(&self) -> Option<&Path> {
+        self.coverage.as_deref()
+    }
+
+    /// Returns the path of the core dump output file if core dumps on traps are enabled.
+    pub fn coredump_on_trap(&self) -> Option<&Path> {
+        self.coredump_on_trap.as_deref()
+    }
+
     /// Pre-opens all directories given in `--dir` and returns them for use by the [`WasiCtx`].
     ///
     /// # Errors
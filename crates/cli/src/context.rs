@@ -27,17 +27,28 @@ impl Context {
         wasi_ctx: WasiCtx,
         fuel: Option<u64>,
         compilation_mode: CompilationMode,
+        debugging: bool,
+        coverage: bool,
+        coredump_on_trap: bool,
     ) -> Result<Self, Error> {
         let mut config = Config::default();
         if fuel.is_some() {
             config.consume_fuel(true);
         }
         config.compilation_mode(compilation_mode);
+        config.debugging(debugging);
+        config.coverage(coverage);
+        config.coredump_on_trap(coredump_on_trap);
         let engine = wasmi::Engine::new(&config);
         let wasm =
             fs::read(wasm_file).map_err(|_| anyhow!("failed to read Wasm file {wasm_file:?}"))?;
         let module = wasmi::Module::new(&engine, wasm).map_err(|error| {
-            anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
+            let message =
+                format!("failed to parse and validate Wasm module {wasm_file:?}: {error}");
+            match error.module_error() {
+                Some(location) => anyhow!("{message}\n  --> {}: {location}", wasm_file.display()),
+                None => anyhow!("{message}"),
+            }
         })?;
         let mut store = wasmi::Store::new(&engine, wasi_ctx);
         if let Some(fuel) = fuel {
@@ -59,14 +70,21 @@ impl Context {
         })
     }
 
+    /// Returns a shared reference to the Wasm [`Module`] of the [`Context`].
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
     /// Returns the exported named functions of the Wasm [`Module`].
     ///
+    /// Also yields the names of the functions in the `name` custom section if any.
+    ///
     /// [`Module`]: wasmi::Module
-    pub fn exported_funcs(&self) -> impl Iterator<Item = (&str, FuncType)> {
+    pub fn exported_funcs(&self) -> impl Iterator<Item = (&str, Option<&str>, FuncType)> {
         self.module.exports().filter_map(|export| {
             let name = export.name();
             match export.ty() {
-                ExternType::Func(func_type) => Some((name, func_type.clone())),
+                ExternType::Func(func_type) => Some((name, export.debug_name(), func_type.clone())),
                 _ => None,
             }
         })
@@ -0,0 +1,118 @@
+//! Exports the [`Coverage`] recorded by the Wasmi CLI via `--coverage` in the LCOV format.
+//!
+//! If the Wasm module has a DWARF `.debug_line` section its line tables are used
+//! to map the Wasm offsets of basic blocks to source file lines. Otherwise the
+//! source file is the Wasm file itself and lines are the Wasm byte offsets
+//! of the basic blocks.
+//!
+//! Lines that are part of multiple basic blocks report the highest hit count.
+
+use crate::profile::FuncNames;
+use anyhow::{anyhow, Error};
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+use wasmi::{Coverage, FuncCoverage, Module};
+
+#[cfg(test)]
+mod tests;
+
+/// Writes the `coverage` of the execution of `module` read from `wasm_file` to the file at `path`.
+///
+/// # Errors
+///
+/// If the file at `path` cannot be written.
+pub fn write_lcov(
+    path: &Path,
+    coverage: &Coverage,
+    module: &Module,
+    wasm_file: &Path,
+) -> Result<(), Error> {
+    let names = FuncNames::new(module);
+    let output = lcov(&coverage.funcs(module), &names, module, wasm_file);
+    fs::write(path, output)
+        .map_err(|error| anyhow!("failed to write coverage to {path:?}: {error}"))
+}
+
+/// The coverage of a single source file.
+#[derive(Debug, Default)]
+struct FileCoverage {
+    /// The first line, name and number of calls of all functions in the source file.
+    funcs: Vec<(u64, String, u64)>,
+    /// The hit counts by source line.
+    lines: BTreeMap<u64, u64>,
+}
+
+/// Returns the coverage of `funcs` of `module` in the LCOV tracefile format.
+///
+/// Uses the DWARF line tables of `module` to map Wasm offsets to source lines if any.
+fn lcov(funcs: &[FuncCoverage], names: &FuncNames, module: &Module, wasm_file: &Path) -> String {
+    let has_lines = module
+        .custom_sections()
+        .any(|section| section.name() == ".debug_line");
+    let mut files = BTreeMap::<PathBuf, FileCoverage>::new();
+    for func in funcs {
+        let mut entry = None;
+        for block in func.blocks() {
+            let (file, line) = match has_lines {
+                true => match source_line(module, block.offset()) {
+                    Some(location) => location,
+                    // Note: code without line information is not reported.
+                    None => continue,
+                },
+                false => (wasm_file.to_path_buf(), u64::from(block.offset())),
+            };
+            entry.get_or_insert_with(|| (file.clone(), line));
+            let hits = files
+                .entry(file)
+                .or_default()
+                .lines
+                .entry(line)
+                .or_default();
+            *hits = (*hits).max(block.hits());
+        }
+        if let Some((file, line)) = entry {
+            let name = names.get_index(func.func_index());
+            files
+                .entry(file)
+                .or_default()
+                .funcs
+                .push((line, name, func.hits()));
+        }
+    }
+    let mut output = String::new();
+    for (file, coverage) in &files {
+        // Note: writing to a `String` never fails.
+        let _ = writeln!(output, "TN:");
+        let _ = writeln!(output, "SF:{}", file.display());
+        for (line, name, _) in &coverage.funcs {
+            let _ = writeln!(output, "FN:{line},{name}");
+        }
+        for (_, name, hits) in &coverage.funcs {
+            let _ = writeln!(output, "FNDA:{hits},{name}");
+        }
+        let funcs_hit = coverage.funcs.iter().filter(|(_, _, hits)| *hits != 0);
+        let _ = writeln!(output, "FNF:{}", coverage.funcs.len());
+        let _ = writeln!(output, "FNH:{}", funcs_hit.count());
+        for (line, hits) in &coverage.lines {
+            let _ = writeln!(output, "DA:{line},{hits}");
+        }
+        let lines_hit = coverage.lines.values().filter(|hits| **hits != 0);
+        let _ = writeln!(output, "LF:{}", coverage.lines.len());
+        let _ = writeln!(output, "LH:{}", lines_hit.count());
+        let _ = writeln!(output, "end_of_record");
+    }
+    output
+}
+
+/// Returns the source file and line of the Wasm operator at `offset` of `module` if any.
+///
+/// Wasm operators of inlined functions are reported at their line in the inlined function.
+fn source_line(module: &Module, offset: u32) -> Option<(PathBuf, u64)> {
+    let frames = module.source_frames(offset);
+    let location = frames.first()?.location()?;
+    Some((PathBuf::from(location.file()), u64::from(location.line())))
+}
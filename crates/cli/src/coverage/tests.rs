@@ -0,0 +1,259 @@
+use super::*;
+use wasmi::{CompilationMode, Config, Engine, Linker, Store};
+use wasmparser::{Parser, Payload};
+
+const WAT: &str = r#"
+    (module
+        (func $abs (export "abs") (param i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get 0) (i32.const 0))
+                (then (i32.sub (i32.const 0) (local.get 0)))
+                (else (local.get 0))
+            )
+        )
+        (func $unused)
+    )
+"#;
+
+/// Calls `abs(-1)` and `abs(2)` of the Wasm module of `wasm` and returns the resulting coverage.
+fn coverage(wasm: &[u8]) -> Vec<FuncCoverage> {
+    let mut config = Config::default();
+    config
+        .coverage(true)
+        .compilation_mode(CompilationMode::Eager);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let abs = instance.get_typed_func::<i32, i32>(&store, "abs").unwrap();
+    abs.call(&mut store, -1).unwrap();
+    abs.call(&mut store, 2).unwrap();
+    store.coverage().funcs(&module)
+}
+
+/// Returns the LCOV output for the `wasm` binary.
+fn lcov_of(wasm: &[u8]) -> String {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let names = FuncNames::new(&module);
+    lcov(&coverage(wasm), &names, &module, Path::new("test.wasm"))
+}
+
+/// Returns the offset of the contents of the code section of `wasm`.
+fn code_offset(wasm: &[u8]) -> u32 {
+    Parser::new(0)
+        .parse_all(wasm)
+        .find_map(|payload| match payload.unwrap() {
+            Payload::CodeSectionStart { range, .. } => Some(range.start as u32),
+            _ => None,
+        })
+        .unwrap()
+}
+
+/// Appends a custom section with `name` and `data` to `wasm`.
+fn push_custom_section(wasm: &mut Vec<u8>, name: &str, data: &[u8]) {
+    let mut section = Vec::new();
+    leb(&mut section, name.len() as u64);
+    section.extend_from_slice(name.as_bytes());
+    section.extend_from_slice(data);
+    wasm.push(0x00);
+    leb(wasm, section.len() as u64);
+    wasm.extend_from_slice(&section);
+}
+
+/// Appends the unsigned LEB128 encoding of `value` to `bytes`.
+fn leb(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Returns a line number program that maps each `(address, line)` row and ends at `end`.
+fn line_program(rows: &[(u32, u8)], end: u32) -> Vec<u8> {
+    let mut program = Vec::new();
+    let mut line = 1;
+    for &(address, row_line) in rows {
+        // DW_LNE_set_address
+        program.extend_from_slice(&[0x00, 0x05, 0x02]);
+        program.extend_from_slice(&address.to_le_bytes());
+        // DW_LNS_advance_line
+        program.push(0x03);
+        program.push((row_line - line) & 0x7F);
+        line = row_line;
+        // DW_LNS_copy
+        program.push(0x01);
+    }
+    // DW_LNE_set_address + DW_LNE_end_sequence
+    program.extend_from_slice(&[0x00, 0x05, 0x02]);
+    program.extend_from_slice(&end.to_le_bytes());
+    program.extend_from_slice(&[0x00, 0x01, 0x01]);
+    program
+}
+
+/// Returns a DWARF `.debug_line` unit of `version` with the `header` and `program`.
+fn line_unit(version: u16, header: &[u8], program: &[u8]) -> Vec<u8> {
+    let mut params = vec![
+        0x01, // minimum_instruction_length
+        0x01, // maximum_operations_per_instruction
+        0x01, // default_is_stmt
+        0xFB, // line_base = -5
+        0x0E, // line_range
+        0x0D, // opcode_base
+    ];
+    params.extend_from_slice(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1]);
+    params.extend_from_slice(header);
+    let mut unit = Vec::new();
+    unit.extend_from_slice(&version.to_le_bytes());
+    if version >= 5 {
+        // address_size and segment_selector_size
+        unit.extend_from_slice(&[0x04, 0x00]);
+    }
+    unit.extend_from_slice(&(params.len() as u32).to_le_bytes());
+    unit.extend_from_slice(&params);
+    unit.extend_from_slice(program);
+    let mut bytes = (unit.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&unit);
+    bytes
+}
+
+/// Appends the `.debug_abbrev` and `.debug_info` sections of a DWARF compilation unit of `version` to `wasm`.
+///
+/// The compilation unit refers to the line number program at the start of the `.debug_line` section.
+fn push_compile_unit(wasm: &mut Vec<u8>, version: u16) {
+    // 1: DW_TAG_compile_unit without children: DW_AT_stmt_list as DW_FORM_sec_offset
+    let abbrevs = [0x01, 0x11, 0x00, 0x10, 0x17, 0x00, 0x00, 0x00];
+    let mut unit = Vec::new();
+    unit.extend_from_slice(&version.to_le_bytes());
+    if version >= 5 {
+        // DW_UT_compile and address_size
+        unit.extend_from_slice(&[0x01, 0x04]);
+        unit.extend_from_slice(&0_u32.to_le_bytes());
+    } else {
+        unit.extend_from_slice(&0_u32.to_le_bytes());
+        unit.push(0x04);
+    }
+    unit.push(0x01);
+    unit.extend_from_slice(&0_u32.to_le_bytes());
+    let mut info = (unit.len() as u32).to_le_bytes().to_vec();
+    info.extend_from_slice(&unit);
+    push_custom_section(wasm, ".debug_abbrev", &abbrevs);
+    push_custom_section(wasm, ".debug_info", &info);
+}
+
+/// Returns the `abs` function block rows for the Wasm module of [`WAT`].
+///
+/// Maps the entry block to line 3, the `then` block to line 4 and all others to line 5.
+fn abs_rows(wasm: &[u8]) -> (Vec<(u32, u8)>, u32) {
+    let code_offset = code_offset(wasm);
+    let funcs = coverage(wasm);
+    let blocks = funcs[0].blocks();
+    let rows = vec![
+        (blocks[0].offset() - code_offset, 3),
+        (blocks[1].offset() - code_offset, 4),
+        (blocks[2].offset() - code_offset, 5),
+    ];
+    let end = funcs[1].blocks()[0].offset() - code_offset;
+    (rows, end)
+}
+
+#[test]
+fn wasm_offsets() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let funcs = coverage(&wasm);
+    let offsets = funcs
+        .iter()
+        .flat_map(|func| func.blocks())
+        .map(|block| block.offset())
+        .collect::<Vec<_>>();
+    let [entry, then, els, after, unused] = offsets[..] else {
+        panic!("unexpected blocks: {offsets:?}")
+    };
+    let expected = format!(
+        "TN:\n\
+         SF:test.wasm\n\
+         FN:{entry},abs\n\
+         FN:{unused},unused\n\
+         FNDA:2,abs\n\
+         FNDA:0,unused\n\
+         FNF:2\n\
+         FNH:1\n\
+         DA:{entry},2\n\
+         DA:{then},1\n\
+         DA:{els},1\n\
+         DA:{after},2\n\
+         DA:{unused},0\n\
+         LF:5\n\
+         LH:4\n\
+         end_of_record\n"
+    );
+    assert_eq!(lcov_of(&wasm), expected);
+}
+
+/// The expected LCOV output for the DWARF line tables of [`abs_rows`].
+const DWARF_LCOV: &str = "\
+TN:
+SF:src/abs.c
+FN:3,abs
+FNDA:2,abs
+FNF:1
+FNH:1
+DA:3,2
+DA:4,1
+DA:5,2
+LF:3
+LH:3
+end_of_record
+";
+
+#[test]
+fn dwarf_v4() {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    let (rows, end) = abs_rows(&wasm);
+    // include_directories followed by file_names
+    let header = b"src\0\0abs.c\0\x01\x00\x00\0";
+    let unit = line_unit(4, header, &line_program(&rows, end));
+    push_compile_unit(&mut wasm, 4);
+    push_custom_section(&mut wasm, ".debug_line", &unit);
+    assert_eq!(lcov_of(&wasm), DWARF_LCOV);
+}
+
+#[test]
+fn dwarf_v5() {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    let (rows, end) = abs_rows(&wasm);
+    let mut header = Vec::new();
+    // directory formats: DW_LNCT_path as DW_FORM_line_strp
+    header.extend_from_slice(&[0x01, 0x01, 0x1F]);
+    // Note: directory 0 is the compilation directory which is why `src` is repeated as directory 1.
+    header.extend_from_slice(&[0x02]);
+    header.extend_from_slice(&0_u32.to_le_bytes());
+    header.extend_from_slice(&0_u32.to_le_bytes());
+    // file formats: DW_LNCT_path as DW_FORM_string, DW_LNCT_directory_index as DW_FORM_udata
+    header.extend_from_slice(&[0x02, 0x01, 0x08, 0x02, 0x0F]);
+    // Note: rows refer to file 1 by default which is why compilers repeat file 0 as file 1.
+    header.extend_from_slice(&[0x02]);
+    header.extend_from_slice(b"abs.c\0\x01abs.c\0\x01");
+    let unit = line_unit(5, &header, &line_program(&rows, end));
+    push_compile_unit(&mut wasm, 5);
+    push_custom_section(&mut wasm, ".debug_line_str", b"src\0");
+    push_custom_section(&mut wasm, ".debug_line", &unit);
+    assert_eq!(lcov_of(&wasm), DWARF_LCOV);
+}
+
+#[test]
+fn malformed_dwarf() {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    push_compile_unit(&mut wasm, 4);
+    push_custom_section(&mut wasm, ".debug_line", &[0x10, 0x00]);
+    // Note: code without line information is not reported.
+    assert_eq!(lcov_of(&wasm), "");
+}
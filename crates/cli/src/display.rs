@@ -159,11 +159,14 @@ impl Display for DisplayExportedFuncs<'_> {
             return write!(f, "No exported functions found for the Wasm module.");
         }
         write!(f, "The Wasm module exports the following functions:\n\n")?;
-        for func in exported_funcs
-            .iter()
-            .map(|(name, func_type)| DisplayFuncType::new(name, func_type))
-        {
-            writeln!(f, " - {func}")?;
+        for (name, debug_name, func_type) in &exported_funcs {
+            write!(f, " - {}", DisplayFuncType::new(name, func_type))?;
+            match debug_name {
+                Some(debug_name) if debug_name != name => {
+                    writeln!(f, " (defined as {debug_name})")?
+                }
+                _ => writeln!(f)?,
+            }
         }
         Ok(())
     }
@@ -0,0 +1,627 @@
+//! A GDB remote serial protocol stub using the LLDB WebAssembly extensions.
+//!
+//! This allows to attach `lldb` to a Wasm module executed by the Wasmi CLI via
+//!
+//! ```text
+//! (lldb) process connect --plugin wasm connect://localhost:<port>
+//! ```
+//!
+//! # Address Space
+//!
+//! Following the LLDB WebAssembly conventions all addresses are 64-bit
+//! where the upper 2 bits denote the address space:
+//!
+//! - `0b01`: The Wasm module binary, e.g. code addresses.
+//! - `0b00`: The default linear memory of the Wasm instance.
+//!
+//! The Wasmi CLI only ever executes a single Wasm module with module id `0`.
+
+use anyhow::{anyhow, bail, Context as _, Error};
+use std::{
+    fmt::Write as _,
+    io::{BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+use wasmi::{DebugAction, DebugContext, DebugFrame, DebugLocation, Debugger, Module, Val};
+use wasmparser::{Parser, Payload, TypeRef};
+
+#[cfg(test)]
+mod tests;
+
+/// The LLDB triple of the debugged target.
+const TRIPLE: &str = "wasm32-unknown-unknown-wasm";
+
+/// The address space tag of code addresses.
+const CODE_ADDRESS: u64 = 0x4000_0000_0000_0000;
+
+/// The mask to extract the address space tag of an address.
+const ADDRESS_SPACE_MASK: u64 = 0xC000_0000_0000_0000;
+
+/// The mask to extract the offset of an address within its address space.
+const OFFSET_MASK: u64 = 0x0000_0000_FFFF_FFFF;
+
+/// The GDB stop reason used for all stops.
+const STOP_REPLY: &str = "T05thread:1;";
+
+/// A GDB remote serial protocol stub serving a single `lldb` session.
+///
+/// Install [`GdbStub::debugger`] on the [`Store`] to serve the session.
+///
+/// [`Store`]: wasmi::Store
+#[derive(Debug, Clone)]
+pub struct GdbStub {
+    session: Arc<Mutex<Session>>,
+}
+
+impl GdbStub {
+    /// Waits for `lldb` to connect on the local TCP `port`.
+    ///
+    /// The `wasm` bytes must be the Wasm binary of `module`.
+    ///
+    /// # Errors
+    ///
+    /// - If it is not possible to listen on `port`.
+    /// - If the `wasm` bytes cannot be parsed.
+    pub fn listen(port: u16, name: &str, wasm: Vec<u8>, module: Module) -> Result<Self, Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("failed to listen on local tcp port {port}"))?;
+        eprintln!(
+            "waiting for debugger to connect on {}",
+            listener.local_addr()?
+        );
+        let (stream, _addr) = listener
+            .accept()
+            .context("failed to accept debugger connection")?;
+        Self::new(Connection::new(stream)?, name, wasm, module)
+    }
+
+    /// Creates a new [`GdbStub`] serving the already established [`Connection`].
+    fn new(conn: Connection, name: &str, wasm: Vec<u8>, module: Module) -> Result<Self, Error> {
+        let funcs = FuncRanges::new(&wasm)?;
+        let session = Session {
+            conn,
+            name: name.into(),
+            wasm,
+            module,
+            funcs,
+            resumed: false,
+            detached: false,
+        };
+        Ok(Self {
+            session: Arc::new(Mutex::new(session)),
+        })
+    }
+
+    /// Returns the [`Debugger`] serving the `lldb` session.
+    pub fn debugger(&self) -> impl Debugger {
+        GdbDebugger {
+            session: self.session.clone(),
+        }
+    }
+
+    /// Informs the debugger that the Wasm execution exited with `status`.
+    pub fn exit(&self, status: u8) {
+        let mut session = self.session.lock().unwrap();
+        if session.detached {
+            return;
+        }
+        // Note: the debugger might already have disconnected and we ignore errors here.
+        let _ = session.conn.send(&format!("W{status:02x}"));
+    }
+}
+
+/// The [`Debugger`] of a [`GdbStub`].
+struct GdbDebugger {
+    session: Arc<Mutex<Session>>,
+}
+
+impl Debugger for GdbDebugger {
+    fn on_breakpoint(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, wasmi::Error> {
+        self.on_stop(ctx)
+    }
+
+    fn on_step(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, wasmi::Error> {
+        self.on_stop(ctx)
+    }
+
+    fn on_trap(&mut self, location: DebugLocation, error: &wasmi::Error) {
+        let mut session = self.session.lock().unwrap();
+        if session.detached {
+            return;
+        }
+        eprintln!("trapped at offset {:#x}: {error}", location.offset());
+        // Note: the execution cannot be resumed after a trap and resuming
+        //       it from the debugger will exit the Wasm execution instead.
+        let _ = session.serve(&mut Stop::Trap(location));
+    }
+}
+
+impl GdbDebugger {
+    /// Serves the `lldb` session while the execution is paused.
+    fn on_stop(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, wasmi::Error> {
+        let mut session = self.session.lock().unwrap();
+        if session.detached {
+            return Ok(DebugAction::Continue);
+        }
+        session
+            .serve(&mut Stop::Paused(ctx))
+            .map_err(|error| wasmi::Error::new(format!("gdb stub: {error}")))
+    }
+}
+
+/// The state of the execution while serving the `lldb` session.
+enum Stop<'a, 'b> {
+    /// The execution is paused at a Wasm operator.
+    Paused(&'a mut DebugContext<'b>),
+    /// The execution trapped at a Wasm operator.
+    Trap(DebugLocation),
+}
+
+impl<'b> Stop<'_, 'b> {
+    /// Returns the location of the [`Stop`].
+    fn location(&self) -> DebugLocation {
+        match self {
+            Self::Paused(ctx) => ctx.location(),
+            Self::Trap(location) => *location,
+        }
+    }
+
+    /// Returns the [`DebugContext`] if the execution is paused.
+    fn context(&mut self) -> Result<&mut DebugContext<'b>, Error> {
+        match self {
+            Self::Paused(ctx) => Ok(ctx),
+            Self::Trap(_) => bail!("execution state is unavailable after a trap"),
+        }
+    }
+
+    /// Returns the call frame at `depth` of the call stack if the execution is paused.
+    ///
+    /// The frame at depth 0 is the frame of the currently executed function.
+    fn frame(&mut self, depth: u64) -> Result<DebugFrame<'_>, Error> {
+        let ctx = self.context()?;
+        usize::try_from(depth)
+            .ok()
+            .and_then(|depth| ctx.frame(depth))
+            .ok_or_else(|| anyhow!("missing call frame at depth {depth}"))
+    }
+}
+
+/// The reply of the [`Session`] to a single packet.
+enum Reply {
+    /// Replies with the packet.
+    Packet(String),
+    /// Resumes the execution.
+    Resume(DebugAction),
+    /// Detaches the debugger and resumes the execution.
+    Detach,
+    /// Kills the execution.
+    Kill,
+}
+
+impl From<String> for Reply {
+    fn from(packet: String) -> Self {
+        Self::Packet(packet)
+    }
+}
+
+impl From<&str> for Reply {
+    fn from(packet: &str) -> Self {
+        Self::Packet(packet.into())
+    }
+}
+
+/// A `lldb` session served by the [`GdbStub`].
+#[derive(Debug)]
+struct Session {
+    /// The connection to `lldb`.
+    conn: Connection,
+    /// The name of the debugged Wasm module.
+    name: String,
+    /// The Wasm binary of the debugged Wasm module.
+    wasm: Vec<u8>,
+    /// The debugged Wasm module.
+    module: Module,
+    /// The byte ranges of all Wasm function bodies.
+    funcs: FuncRanges,
+    /// Is `true` if `lldb` awaits a stop reply after resuming execution.
+    resumed: bool,
+    /// Is `true` if `lldb` detached from the session.
+    detached: bool,
+}
+
+impl Session {
+    /// Serves `lldb` packets until the execution is resumed.
+    fn serve(&mut self, stop: &mut Stop) -> Result<DebugAction, Error> {
+        if self.resumed {
+            self.conn.send(STOP_REPLY)?;
+            self.resumed = false;
+        }
+        loop {
+            let Some(packet) = self.conn.recv()? else {
+                // Note: `lldb` disconnected without detaching.
+                self.detached = true;
+                return Ok(DebugAction::Continue);
+            };
+            let reply = self
+                .handle(&packet, stop)
+                .unwrap_or_else(|_| Reply::from("E01"));
+            match reply {
+                Reply::Packet(reply) => {
+                    self.conn.send(&reply)?;
+                    if packet == "QStartNoAckMode" {
+                        self.conn.ack = false;
+                    }
+                }
+                Reply::Resume(action) => {
+                    self.resumed = true;
+                    return Ok(action);
+                }
+                Reply::Detach => {
+                    self.conn.send("OK")?;
+                    if let Stop::Paused(ctx) = stop {
+                        ctx.breakpoints_mut().clear();
+                    }
+                    self.detached = true;
+                    return Ok(DebugAction::Continue);
+                }
+                Reply::Kill => {
+                    self.conn.send("X09")?;
+                    self.detached = true;
+                    bail!("killed by debugger")
+                }
+            }
+        }
+    }
+
+    /// Handles a single `lldb` packet.
+    fn handle(&mut self, packet: &str, stop: &mut Stop) -> Result<Reply, Error> {
+        let pc = CODE_ADDRESS | u64::from(stop.location().offset());
+        if packet.starts_with("qSupported") {
+            return Ok("PacketSize=1000;QStartNoAckMode+;qXfer:libraries:read+".into());
+        }
+        if let Some(args) = packet.strip_prefix("qXfer:libraries:read::") {
+            return self.read_libraries(args);
+        }
+        if packet.starts_with("qWasmCallStack") {
+            let call_stack = match stop {
+                Stop::Paused(ctx) => ctx.call_stack(),
+                Stop::Trap(location) => vec![*location],
+            };
+            let mut reply = String::new();
+            for location in call_stack {
+                let pc = CODE_ADDRESS | u64::from(location.offset());
+                encode_hex(&mut reply, &pc.to_le_bytes());
+            }
+            return Ok(reply.into());
+        }
+        if let Some(args) = packet.strip_prefix("qWasmLocal:") {
+            let [frame, index] = parse_args(args, ';')?;
+            let value = stop
+                .frame(frame)?
+                .local(index as u32)
+                .ok_or_else(|| anyhow!("missing local variable at index {index}"))?;
+            return encode_val(&value).map(Reply::from);
+        }
+        if let Some(args) = packet.strip_prefix("qWasmGlobal:") {
+            let [_frame, index] = parse_args(args, ';')?;
+            let value = stop
+                .context()?
+                .global(index as u32)
+                .ok_or_else(|| anyhow!("missing global variable at index {index}"))?;
+            return encode_val(&value).map(Reply::from);
+        }
+        if let Some(args) = packet.strip_prefix("qWasmStackValue:") {
+            let [frame, index] = parse_args(args, ';')?;
+            let value = stop
+                .frame(frame)?
+                .operand(index as usize)
+                .ok_or_else(|| anyhow!("missing operand at index {index}"))?;
+            let mut reply = String::new();
+            encode_hex(&mut reply, &u64::from(value).to_le_bytes());
+            return Ok(reply.into());
+        }
+        if let Some(args) = packet.strip_prefix("qWasmMem:") {
+            let [_frame, addr, len] = parse_args(args, ';')?;
+            let memory = stop.context()?.memory(0).unwrap_or(&[]);
+            return read_bytes(memory, addr & OFFSET_MASK, len).map(Reply::from);
+        }
+        if let Some(args) = packet.strip_prefix('m') {
+            let [addr, len] = parse_args(args, ',')?;
+            let offset = addr & OFFSET_MASK;
+            return match addr & ADDRESS_SPACE_MASK {
+                CODE_ADDRESS => read_bytes(&self.wasm, offset, len).map(Reply::from),
+                0 => {
+                    let memory = stop.context()?.memory(0).unwrap_or(&[]);
+                    read_bytes(memory, offset, len).map(Reply::from)
+                }
+                _ => bail!("invalid address: {addr:#x}"),
+            };
+        }
+        if let Some(args) = packet.strip_prefix("Z0,") {
+            let [addr, _kind] = parse_args(args, ',')?;
+            let (func_index, offset) = self.locate(addr)?;
+            let module = self.module.clone();
+            stop.context()?
+                .breakpoints_mut()
+                .insert(&module, func_index, offset);
+            return Ok("OK".into());
+        }
+        if let Some(args) = packet.strip_prefix("z0,") {
+            let [addr, _kind] = parse_args(args, ',')?;
+            let (func_index, offset) = self.locate(addr)?;
+            let module = self.module.clone();
+            stop.context()?
+                .breakpoints_mut()
+                .remove(&module, func_index, offset);
+            return Ok("OK".into());
+        }
+        if let Some(regnum) = packet.strip_prefix("qRegisterInfo") {
+            if u64::from_str_radix(regnum, 16)? != 0 {
+                return Ok("E45".into());
+            }
+            return Ok(
+                "name:pc;alt-name:pc;bitsize:64;offset:0;encoding:uint;format:hex;\
+                set:General Purpose Registers;gcc:16;dwarf:16;generic:pc;"
+                    .into(),
+            );
+        }
+        if packet == "g" || packet == "p0" {
+            let mut reply = String::new();
+            encode_hex(&mut reply, &pc.to_le_bytes());
+            return Ok(reply.into());
+        }
+        if let Some(args) = packet.strip_prefix("vCont") {
+            return match args
+                .split(';')
+                .nth(1)
+                .and_then(|action| action.chars().next())
+            {
+                None => Ok("vCont;c;C;s;S".into()),
+                Some('c' | 'C') => Ok(Reply::Resume(DebugAction::Continue)),
+                Some('s' | 'S') => Ok(Reply::Resume(DebugAction::Step)),
+                Some(_) => bail!("unsupported resume action: {packet}"),
+            };
+        }
+        let reply = match packet {
+            "QStartNoAckMode" | "QThreadSuffixSupported" | "QListThreadsInStopReply" => "OK".into(),
+            "qHostInfo" => {
+                let mut reply = String::from("triple:");
+                encode_hex(&mut reply, TRIPLE.as_bytes());
+                reply.push_str(";ptrsize:4;endian:little;");
+                reply.into()
+            }
+            "qProcessInfo" => {
+                let mut reply = String::from("pid:1;parent-pid:1;triple:");
+                encode_hex(&mut reply, TRIPLE.as_bytes());
+                reply.push_str(";ptrsize:4;endian:little;");
+                reply.into()
+            }
+            "?" | "qThreadStopInfo1" => STOP_REPLY.into(),
+            "qfThreadInfo" => "m1".into(),
+            "qsThreadInfo" => "l".into(),
+            "qC" => "QC1".into(),
+            "qAttached" => "1".into(),
+            "c" => Reply::Resume(DebugAction::Continue),
+            "s" => Reply::Resume(DebugAction::Step),
+            "D" => Reply::Detach,
+            "k" => Reply::Kill,
+            _ if packet.starts_with('H') => "OK".into(),
+            // Note: an empty reply signals an unsupported packet.
+            _ => "".into(),
+        };
+        Ok(reply)
+    }
+
+    /// Handles the `qXfer:libraries:read` packet with its `offset,length` arguments.
+    fn read_libraries(&self, args: &str) -> Result<Reply, Error> {
+        let [offset, len] = parse_args(args, ',')?;
+        let xml = format!(
+            "<library-list><library name=\"{}\"><section address=\"{CODE_ADDRESS:#x}\"/></library></library-list>",
+            self.name
+        );
+        let start = (offset as usize).min(xml.len());
+        let end = start.saturating_add(len as usize).min(xml.len());
+        let prefix = if end == xml.len() { 'l' } else { 'm' };
+        Ok(format!("{prefix}{}", &xml[start..end]).into())
+    }
+
+    /// Returns the function index and offset of the code address `addr`.
+    fn locate(&self, addr: u64) -> Result<(u32, u32), Error> {
+        if addr & ADDRESS_SPACE_MASK != CODE_ADDRESS {
+            bail!("not a code address: {addr:#x}")
+        }
+        let offset = (addr & OFFSET_MASK) as u32;
+        let func_index = self
+            .funcs
+            .func_at(offset)
+            .ok_or_else(|| anyhow!("no function at code address: {addr:#x}"))?;
+        Ok((func_index, offset))
+    }
+}
+
+/// The byte ranges of all Wasm function bodies of a Wasm module.
+#[derive(Debug)]
+struct FuncRanges {
+    /// The number of imported functions.
+    len_imported: u32,
+    /// The byte ranges of all function bodies in order.
+    bodies: Vec<Range<u32>>,
+}
+
+impl FuncRanges {
+    /// Parses the [`FuncRanges`] of the `wasm` binary.
+    fn new(wasm: &[u8]) -> Result<Self, Error> {
+        let mut len_imported = 0;
+        let mut bodies = Vec::new();
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::ImportSection(imports) => {
+                    for import in imports {
+                        if let TypeRef::Func(_) = import?.ty {
+                            len_imported += 1;
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let range = body.range();
+                    bodies.push(range.start as u32..range.end as u32);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            len_imported,
+            bodies,
+        })
+    }
+
+    /// Returns the index of the function whose body contains `offset` if any.
+    fn func_at(&self, offset: u32) -> Option<u32> {
+        let index = self
+            .bodies
+            .binary_search_by(|body| {
+                if body.end <= offset {
+                    core::cmp::Ordering::Less
+                } else if body.start > offset {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .ok()?;
+        Some(self.len_imported + index as u32)
+    }
+}
+
+/// A GDB remote serial protocol connection.
+#[derive(Debug)]
+struct Connection {
+    /// The reading end of the connection.
+    reader: BufReader<TcpStream>,
+    /// The writing end of the connection.
+    writer: TcpStream,
+    /// Is `true` if packets are acknowledged.
+    ack: bool,
+}
+
+impl Connection {
+    /// Creates a new [`Connection`] for the `stream`.
+    fn new(stream: TcpStream) -> Result<Self, Error> {
+        let writer = stream.try_clone()?;
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+            ack: true,
+        })
+    }
+
+    /// Reads a single byte from the connection.
+    ///
+    /// Returns `None` if the connection was closed.
+    fn read_byte(&mut self) -> Result<Option<u8>, Error> {
+        let mut byte = [0x00];
+        match self.reader.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Receives the next packet and returns its payload.
+    ///
+    /// Returns `None` if the connection was closed.
+    fn recv(&mut self) -> Result<Option<String>, Error> {
+        // Skip everything up to the start of the next packet such as
+        // acknowledgements or interrupt requests.
+        loop {
+            match self.read_byte()? {
+                None => return Ok(None),
+                Some(b'$') => break,
+                Some(_) => continue,
+            }
+        }
+        let mut payload = Vec::new();
+        loop {
+            match self.read_byte()? {
+                None => return Ok(None),
+                Some(b'#') => break,
+                Some(b'}') => {
+                    let Some(escaped) = self.read_byte()? else {
+                        return Ok(None);
+                    };
+                    payload.push(escaped ^ 0x20);
+                }
+                Some(byte) => payload.push(byte),
+            }
+        }
+        let mut checksum = [0x00; 2];
+        self.reader.read_exact(&mut checksum)?;
+        if self.ack {
+            self.writer.write_all(b"+")?;
+        }
+        Ok(Some(String::from_utf8(payload)?))
+    }
+
+    /// Sends the `payload` as packet.
+    fn send(&mut self, payload: &str) -> Result<(), Error> {
+        let checksum = payload.bytes().fold(0_u8, u8::wrapping_add);
+        write!(self.writer, "${payload}#{checksum:02x}")?;
+        self.writer.flush()?;
+        if self.ack {
+            // Wait for the acknowledgement of the packet.
+            match self.read_byte()? {
+                Some(b'+') | None => {}
+                Some(byte) => bail!("expected packet acknowledgement but found: {byte:#x}"),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses `N` hexadecimal arguments separated by `sep`.
+fn parse_args<const N: usize>(args: &str, sep: char) -> Result<[u64; N], Error> {
+    let mut parts = args.split(sep);
+    let mut result = [0; N];
+    for arg in &mut result {
+        let part = parts
+            .next()
+            .ok_or_else(|| anyhow!("missing packet argument in: {args}"))?;
+        *arg = u64::from_str_radix(part, 16)
+            .with_context(|| format!("invalid packet argument: {part}"))?;
+    }
+    Ok(result)
+}
+
+/// Returns the hex encoded `len` bytes at `addr` of `bytes`.
+fn read_bytes(bytes: &[u8], addr: u64, len: u64) -> Result<String, Error> {
+    let start = usize::try_from(addr)?;
+    let end = start.saturating_add(usize::try_from(len)?);
+    let bytes = bytes
+        .get(start..end)
+        .ok_or_else(|| anyhow!("out of bounds read at {addr:#x}"))?;
+    let mut reply = String::new();
+    encode_hex(&mut reply, bytes);
+    Ok(reply)
+}
+
+/// Returns the hex encoded little-endian bytes of `value`.
+fn encode_val(value: &Val) -> Result<String, Error> {
+    let mut reply = String::new();
+    match value {
+        Val::I32(value) => encode_hex(&mut reply, &value.to_le_bytes()),
+        Val::I64(value) => encode_hex(&mut reply, &value.to_le_bytes()),
+        Val::F32(value) => encode_hex(&mut reply, &value.to_bits().to_le_bytes()),
+        Val::F64(value) => encode_hex(&mut reply, &value.to_bits().to_le_bytes()),
+        _ => bail!("cannot encode reference value: {value:?}"),
+    }
+    Ok(reply)
+}
+
+/// Appends the hex encoding of `bytes` to `out`.
+fn encode_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        // Note: writing to a `String` never fails.
+        let _ = write!(out, "{byte:02x}");
+    }
+}
@@ -0,0 +1,196 @@
+use super::*;
+use std::thread;
+use wasmi::{Config, Engine, Linker, Store};
+
+/// Runs the exported `main` function of `wat` while being debugged via a loopback connection.
+///
+/// The `client` is used to communicate with the [`GdbStub`] just like `lldb` would.
+/// The execution is paused before the first Wasm operator.
+///
+/// Returns the results of the execution.
+fn debug(wat: &str, client: impl FnOnce(&mut Connection)) -> Result<Vec<Val>, wasmi::Error> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let wasm = wat::parse_str(wat).unwrap();
+    let server = thread::spawn(move || {
+        let (stream, _addr) = listener.accept().unwrap();
+        let mut config = Config::default();
+        config.debugging(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let conn = Connection::new(stream).unwrap();
+        let stub = GdbStub::new(conn, "test.wasm", wasm, module.clone()).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        store.set_debugger(stub.debugger());
+        store.set_single_stepping(true);
+        let func = instance.get_func(&store, "main").unwrap();
+        let ty = func.ty(&store);
+        let params = ty.params().iter().map(|_| Val::I32(41)).collect::<Vec<_>>();
+        let mut results = ty.results().iter().map(|_| Val::I32(0)).collect::<Vec<_>>();
+        let result = func.call(&mut store, &params, &mut results);
+        stub.exit(u8::from(result.is_err()));
+        result.map(|_| results)
+    });
+    let mut conn = Connection::new(TcpStream::connect(addr).unwrap()).unwrap();
+    client(&mut conn);
+    server.join().unwrap()
+}
+
+/// Sends the `packet` and returns the reply.
+fn request(conn: &mut Connection, packet: &str) -> String {
+    conn.send(packet).unwrap();
+    conn.recv().unwrap().unwrap()
+}
+
+/// Decodes the hex encoded little-endian `u64` in `reply`.
+fn decode_u64(reply: &str) -> u64 {
+    let bytes = (0..8)
+        .map(|i| u8::from_str_radix(&reply[2 * i..2 * i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+const ADD_ONE: &str = r#"
+    (module
+        (func (export "main") (param i32) (result i32)
+            (local.get 0)
+            (i32.const 1)
+            (i32.add)
+        )
+    )
+"#;
+
+#[test]
+fn step_and_inspect() {
+    let results = debug(ADD_ONE, |conn| {
+        assert!(request(conn, "qSupported:xmlRegisters=i386").contains("qXfer:libraries:read+"));
+        assert_eq!(request(conn, "QStartNoAckMode"), "OK");
+        conn.ack = false;
+        assert_eq!(request(conn, "?"), STOP_REPLY);
+        assert_eq!(request(conn, "qfThreadInfo"), "m1");
+        assert_eq!(request(conn, "qWasmLocal:0;0"), "29000000");
+        let pc = decode_u64(&request(conn, "p0"));
+        assert_eq!(pc & ADDRESS_SPACE_MASK, CODE_ADDRESS);
+        // Step over `local.get 0` which is encoded in 2 bytes.
+        assert_eq!(request(conn, "s"), STOP_REPLY);
+        assert_eq!(decode_u64(&request(conn, "p0")), pc + 2);
+        assert_eq!(request(conn, "qWasmStackValue:0;0"), "2900000000000000");
+        assert_eq!(decode_u64(&request(conn, "qWasmCallStack:1")), pc + 2);
+        assert_eq!(request(conn, "c"), "W00");
+    });
+    assert_eq!(results.unwrap()[0].i32(), Some(42));
+}
+
+#[test]
+fn breakpoint() {
+    let results = debug(ADD_ONE, |conn| {
+        let pc = decode_u64(&request(conn, "p0"));
+        // Position of `i32.add` after `local.get 0` and `i32.const 1`.
+        let add = pc + 4;
+        assert_eq!(request(conn, &format!("Z0,{add:x},1")), "OK");
+        assert_eq!(request(conn, "vCont;c"), STOP_REPLY);
+        assert_eq!(decode_u64(&request(conn, "p0")), add);
+        assert_eq!(request(conn, "qWasmStackValue:0;1"), "0100000000000000");
+        assert_eq!(request(conn, &format!("z0,{add:x},1")), "OK");
+        assert_eq!(request(conn, "c"), "W00");
+    });
+    assert_eq!(results.unwrap()[0].i32(), Some(42));
+}
+
+#[test]
+fn read_memory() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (data (i32.const 16) "\01\02\03")
+            (func (export "main") (param i32) (result i32)
+                (local.get 0)
+            )
+        )
+    "#;
+    let results = debug(wat, |conn| {
+        let libraries = request(conn, "qXfer:libraries:read::0,1000");
+        assert!(libraries.starts_with("l<library-list><library name=\"test.wasm\">"));
+        assert_eq!(request(conn, &format!("m{CODE_ADDRESS:x},4")), "0061736d");
+        assert_eq!(request(conn, "m10,3"), "010203");
+        assert_eq!(request(conn, "qWasmMem:0;11;2"), "0203");
+        assert_eq!(request(conn, "m10000,1"), "E01");
+        assert_eq!(request(conn, "D"), "OK");
+    });
+    assert_eq!(results.unwrap()[0].i32(), Some(41));
+}
+
+#[test]
+fn caller_frame() {
+    let wat = r#"
+        (module
+            (func $callee (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (export "main") (param i32) (result i32)
+                (i32.mul (i32.const 2) (call $callee (local.get 0)))
+            )
+        )
+    "#;
+    let results = debug(wat, |conn| {
+        // Step over `i32.const 2` and `local.get 0` and into the `call`.
+        for _ in 0..3 {
+            assert_eq!(request(conn, "s"), STOP_REPLY);
+        }
+        assert_eq!(request(conn, "qWasmCallStack:1").len(), 32);
+        assert_eq!(request(conn, "qWasmLocal:0;0"), "29000000");
+        assert_eq!(request(conn, "qWasmLocal:1;0"), "29000000");
+        assert_eq!(request(conn, "qWasmStackValue:1;0"), "0200000000000000");
+        assert_eq!(request(conn, "qWasmStackValue:1;1"), "2900000000000000");
+        assert_eq!(request(conn, "qWasmLocal:2;0"), "E01");
+        assert_eq!(request(conn, "c"), "W00");
+    });
+    assert_eq!(results.unwrap()[0].i32(), Some(84));
+}
+
+#[test]
+fn trap() {
+    let wat = r#"
+        (module
+            (func (export "main")
+                (unreachable)
+            )
+        )
+    "#;
+    let results = debug(wat, |conn| {
+        let pc = decode_u64(&request(conn, "p0"));
+        assert_eq!(request(conn, "c"), STOP_REPLY);
+        assert_eq!(decode_u64(&request(conn, "p0")), pc);
+        assert_eq!(request(conn, "qWasmLocal:0;0"), "E01");
+        assert_eq!(request(conn, "c"), "W01");
+    });
+    assert!(results.is_err());
+}
+
+#[test]
+fn func_ranges() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "f" (func))
+            (func (nop))
+            (func (nop) (nop))
+        )
+    "#,
+    )
+    .unwrap();
+    let funcs = FuncRanges::new(&wasm).unwrap();
+    let [first, second] = &funcs.bodies[..] else {
+        panic!("unexpected function bodies: {:?}", funcs.bodies)
+    };
+    assert_eq!(funcs.func_at(first.start), Some(1));
+    assert_eq!(funcs.func_at(first.end - 1), Some(1));
+    assert_eq!(funcs.func_at(second.start), Some(2));
+    assert_eq!(funcs.func_at(second.end), None);
+    assert_eq!(funcs.func_at(0), None);
+}
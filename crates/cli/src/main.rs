@@ -5,12 +5,16 @@ use crate::{
 use anyhow::{anyhow, bail, Error, Result};
 use clap::Parser;
 use context::Context;
-use std::{path::Path, process};
-use wasmi::{Func, FuncType, Val};
+use gdb::GdbStub;
+use std::{fs, path::Path, process};
+use wasmi::{CompilationMode, ExternType, Func, FuncType, Val, WasmCoreDump};
 
 mod args;
 mod context;
+mod coverage;
 mod display;
+mod gdb;
+mod profile;
 mod utils;
 
 #[cfg(test)]
@@ -20,7 +24,25 @@ fn main() -> Result<()> {
     let args = Args::parse();
     let wasm_file = args.wasm_file();
     let wasi_ctx = args.wasi_context()?;
-    let mut ctx = Context::new(wasm_file, wasi_ctx, args.fuel(), args.compilation_mode())?;
+    // Profiling measures the consumed fuel and thus requires fuel metering.
+    let fuel = args.fuel().or(args.profile().map(|_| u64::MAX));
+    // Coverage reports functions only if they have been compiled.
+    let compilation_mode = match args.coverage() {
+        Some(_) => CompilationMode::Eager,
+        None => args.compilation_mode(),
+    };
+    let mut ctx = Context::new(
+        wasm_file,
+        wasi_ctx,
+        fuel,
+        compilation_mode,
+        args.gdb_port().is_some(),
+        args.coverage().is_some(),
+        args.coredump_on_trap().is_some(),
+    )?;
+    if args.emit_ir() {
+        return emit_ir(&ctx);
+    }
     let (func_name, func) = get_invoked_func(&args, &ctx)?;
     let ty = func.ty(ctx.store());
     let func_args = utils::decode_func_args(&ty, args.func_args())?;
@@ -39,7 +61,31 @@ fn main() -> Result<()> {
         )
     }
 
-    match func.call(ctx.store_mut(), &func_args, &mut func_results) {
+    let gdb = args
+        .gdb_port()
+        .map(|port| serve_gdb(port, wasm_file, &mut ctx))
+        .transpose()?;
+    if args.profile().is_some() {
+        ctx.store_mut().set_profiling(true);
+    }
+    if args.trace() {
+        install_trace(&mut ctx)?;
+    }
+    let result = func.call(ctx.store_mut(), &func_args, &mut func_results);
+    if let Some(gdb) = &gdb {
+        let status = match &result {
+            Ok(()) => 0,
+            Err(error) => error.i32_exit_status().map_or(1, |status| status as u8),
+        };
+        gdb.exit(status);
+    }
+    if let Some(path) = args.profile() {
+        write_profile(path, &args, &ctx)?;
+    }
+    if let Some(path) = args.coverage() {
+        write_coverage(path, wasm_file, &ctx)?;
+    }
+    match result {
         Ok(()) => {
             print_remaining_fuel(&args, &ctx);
             print_pretty_results(&func_results);
@@ -54,11 +100,119 @@ fn main() -> Result<()> {
                 print_pretty_results(&func_results);
                 process::exit(exit_code)
             }
+            if let (Some(path), Some(coredump)) = (args.coredump_on_trap(), error.coredump()) {
+                write_coredump(path, wasm_file, coredump)?;
+            }
             bail!("failed during execution of {func_name}: {error}")
         }
     }
 }
 
+/// Waits for a debugger to connect on `port` and installs it on the [`Store`] of `ctx`.
+///
+/// The execution is paused before the first executed Wasm operator.
+///
+/// [`Store`]: wasmi::Store
+fn serve_gdb(port: u16, wasm_file: &Path, ctx: &mut Context) -> Result<GdbStub, Error> {
+    let wasm =
+        fs::read(wasm_file).map_err(|_| anyhow!("failed to read Wasm file {wasm_file:?}"))?;
+    let name = wasm_file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("module.wasm");
+    let gdb = GdbStub::listen(port, name, wasm, ctx.module().clone())?;
+    let store = ctx.store_mut();
+    store.set_debugger(gdb.debugger());
+    store.set_single_stepping(true);
+    Ok(gdb)
+}
+
+/// Installs a trace hook on the [`Store`] of `ctx` that prints every executed instruction to stderr.
+///
+/// [`Store`]: wasmi::Store
+#[cfg(feature = "trace")]
+fn install_trace(ctx: &mut Context) -> Result<(), Error> {
+    use std::io::{self, Write as _};
+    ctx.store_mut().trace_hook(|_, event| {
+        let pos = event.pos();
+        let offset = match event.offset() {
+            Some(offset) => format!("0x{offset:x}"),
+            None => String::from("-"),
+        };
+        writeln!(
+            io::stderr(),
+            "func[{}] @{pos} {offset}: {}",
+            event.func_index(),
+            event.instr(),
+        )
+        .map_err(|error| wasmi::Error::new(format!("failed to write trace: {error}")))
+    });
+    Ok(())
+}
+
+/// Returns an error since tracing requires the `trace` crate feature.
+#[cfg(not(feature = "trace"))]
+fn install_trace(_ctx: &mut Context) -> Result<(), Error> {
+    bail!("`--trace` requires the Wasmi CLI to be built with the `trace` crate feature")
+}
+
+/// Prints the translated Wasmi bytecode of all functions defined by the Wasm module of `ctx`.
+fn emit_ir(ctx: &Context) -> Result<(), Error> {
+    let names = profile::FuncNames::new(ctx.module());
+    let len_imported = ctx
+        .module()
+        .imports()
+        .filter(|import| matches!(import.ty(), ExternType::Func(_)))
+        .count() as u32;
+    let len_funcs = ctx.module().len_funcs() as u32;
+    for func_index in len_imported..len_funcs {
+        let ir = ctx
+            .module()
+            .disassemble(func_index)
+            .map_err(|error| anyhow!("failed to disassemble function {func_index}: {error}"))?;
+        println!("func[{func_index}] {}:", names.get_index(func_index));
+        for line in ir.lines() {
+            println!("  {line}");
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Writes the [`Profile`] recorded by the [`Store`] of `ctx` to the file at `path`.
+///
+/// Also prints a summary of the [`Profile`] if verbose messaging is enabled.
+///
+/// [`Profile`]: wasmi::Profile
+/// [`Store`]: wasmi::Store
+fn write_profile(path: &Path, args: &Args, ctx: &Context) -> Result<(), Error> {
+    let profile = ctx.store().profile();
+    profile::write_profile(path, profile, ctx.module())?;
+    if args.verbose() {
+        profile::print_summary(profile, ctx.module());
+    }
+    Ok(())
+}
+
+/// Writes the [`Coverage`] recorded by the [`Store`] of `ctx` to the file at `path` in the LCOV format.
+///
+/// [`Coverage`]: wasmi::Coverage
+/// [`Store`]: wasmi::Store
+fn write_coverage(path: &Path, wasm_file: &Path, ctx: &Context) -> Result<(), Error> {
+    let coverage = ctx.store().coverage();
+    coverage::write_lcov(path, coverage, ctx.module(), wasm_file)
+}
+
+/// Writes the `coredump` of the trapped execution of `wasm_file` to the file at `path`.
+fn write_coredump(path: &Path, wasm_file: &Path, coredump: &WasmCoreDump) -> Result<(), Error> {
+    let name = wasm_file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("module.wasm");
+    fs::write(path, coredump.serialize(name))
+        .map_err(|error| anyhow!("failed to write core dump to {path:?}: {error}"))
+}
+
 /// Prints the remaining fuel so far if fuel metering was enabled.
 fn print_remaining_fuel(args: &Args, ctx: &Context) {
     if let Some(given_fuel) = args.fuel() {
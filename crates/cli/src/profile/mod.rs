@@ -0,0 +1,269 @@
+//! Exports the [`Profile`] recorded by the Wasmi CLI via `--profile`.
+//!
+//! The format is chosen by the file extension of the output file:
+//!
+//! - `.json`: The [Firefox Profiler] processed profile format.
+//! - Otherwise: The folded stacks format as used by [`flamegraph.pl`] and [`inferno`].
+//!
+//! In both formats the weight of a call stack is the fuel consumed by its
+//! innermost function, excluding the fuel consumed by its callees.
+//!
+//! [Firefox Profiler]: https://profiler.firefox.com
+//! [`flamegraph.pl`]: https://github.com/brendangregg/FlameGraph
+//! [`inferno`]: https://github.com/jonhoo/inferno
+
+use anyhow::{anyhow, Error};
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::Path};
+use wasmi::{Module, Profile, ProfileFunc};
+
+#[cfg(test)]
+mod tests;
+
+/// Writes `profile` of the execution of `module` to the file at `path`.
+///
+/// # Errors
+///
+/// If the file at `path` cannot be written.
+pub fn write_profile(path: &Path, profile: &Profile, module: &Module) -> Result<(), Error> {
+    let names = FuncNames::new(module);
+    let output = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => firefox(profile, &names),
+        _ => folded(profile, &names),
+    };
+    fs::write(path, output).map_err(|error| anyhow!("failed to write profile to {path:?}: {error}"))
+}
+
+/// Prints the calls and consumed fuel of the functions recorded by `profile`.
+///
+/// Functions are sorted by their exclusively consumed fuel in descending order.
+pub fn print_summary(profile: &Profile, module: &Module) {
+    let names = FuncNames::new(module);
+    let mut funcs = profile.funcs().into_iter().collect::<Vec<_>>();
+    funcs.sort_by_key(|(_, stats)| core::cmp::Reverse(stats.exclusive_fuel()));
+    println!(
+        "{:>12} {:>16} {:>16}  function",
+        "calls", "inclusive fuel", "exclusive fuel"
+    );
+    for (func, stats) in funcs {
+        println!(
+            "{:>12} {:>16} {:>16}  {}",
+            stats.calls(),
+            stats.inclusive_fuel(),
+            stats.exclusive_fuel(),
+            names.get(func),
+        );
+    }
+}
+
+/// Returns `profile` in the folded stacks format.
+///
+/// Each line consists of the `;` separated function names of a call stack
+/// followed by the fuel consumed by its innermost function.
+fn folded(profile: &Profile, names: &FuncNames) -> String {
+    let mut output = String::new();
+    for stack in profile.stacks() {
+        if stack.fuel() == 0 {
+            continue;
+        }
+        let funcs = stack
+            .funcs()
+            .iter()
+            .map(|func| names.get(*func))
+            .collect::<Vec<_>>();
+        // Note: writing to a `String` never fails.
+        let _ = writeln!(output, "{} {}", funcs.join(";"), stack.fuel());
+    }
+    output
+}
+
+/// Returns `profile` in the Firefox Profiler processed profile format.
+///
+/// Every unique call stack is represented by a single sample weighted
+/// by the fuel consumed by its innermost function.
+fn firefox(profile: &Profile, names: &FuncNames) -> String {
+    let stacks = profile.stacks();
+    let mut funcs = BTreeMap::<ProfileFunc, usize>::new();
+    let mut prefixes = BTreeMap::<&[ProfileFunc], usize>::new();
+    let mut strings = Vec::new();
+    let mut stack_table = Table::new(&["frame", "prefix", "category", "subcategory"]);
+    let mut samples = Table::new(&["stack", "time", "weight"]);
+    for (index, stack) in stacks.iter().enumerate() {
+        let (&func, prefix) = stack
+            .funcs()
+            .split_last()
+            .expect("recorded call stacks are never empty");
+        let frame = *funcs.entry(func).or_insert_with(|| {
+            strings.push(json_string(&names.get(func)));
+            strings.len() - 1
+        });
+        let prefix = match prefix.is_empty() {
+            true => String::from("null"),
+            false => prefixes[prefix].to_string(),
+        };
+        prefixes.insert(stack.funcs(), index);
+        stack_table.push([
+            ("frame", frame.to_string()),
+            ("prefix", prefix),
+            ("category", "0".into()),
+            ("subcategory", "0".into()),
+        ]);
+        if stack.fuel() != 0 {
+            samples.push([
+                ("stack", index.to_string()),
+                ("time", samples.len.to_string()),
+                ("weight", stack.fuel().to_string()),
+            ]);
+        }
+    }
+    let mut frame_table = Table::new(&[
+        "address",
+        "inlineDepth",
+        "category",
+        "subcategory",
+        "func",
+        "nativeSymbol",
+        "innerWindowID",
+        "implementation",
+        "line",
+        "column",
+    ]);
+    let mut func_table = Table::new(&[
+        "name",
+        "isJS",
+        "relevantForJS",
+        "resource",
+        "fileName",
+        "lineNumber",
+        "columnNumber",
+    ]);
+    for index in 0..funcs.len() {
+        frame_table.push([
+            ("address", "-1".into()),
+            ("inlineDepth", "0".into()),
+            ("category", "0".into()),
+            ("subcategory", "0".into()),
+            ("func", index.to_string()),
+            ("nativeSymbol", "null".into()),
+            ("innerWindowID", "null".into()),
+            ("implementation", "null".into()),
+            ("line", "null".into()),
+            ("column", "null".into()),
+        ]);
+        func_table.push([
+            ("name", index.to_string()),
+            ("isJS", "false".into()),
+            ("relevantForJS", "false".into()),
+            ("resource", "-1".into()),
+            ("fileName", "null".into()),
+            ("lineNumber", "null".into()),
+            ("columnNumber", "null".into()),
+        ]);
+    }
+    format!(
+        r#"{{"meta":{{"interval":1,"startTime":0,"processType":0,"product":"wasmi","stackwalk":0,"version":27,"preprocessedProfileVersion":46,"symbolicated":true,"categories":[{{"name":"Wasm","color":"blue","subcategories":["Other"]}}],"markerSchema":[]}},"libs":[],"counters":[],"threads":[{{"processType":"default","processStartupTime":0,"processShutdownTime":null,"registerTime":0,"unregisterTime":null,"pausedRanges":[],"name":"main","isMainThread":true,"pid":"0","tid":0,"samples":{samples},"markers":{markers},"stackTable":{stack_table},"frameTable":{frame_table},"funcTable":{func_table},"resourceTable":{resources},"nativeSymbols":{symbols},"stringArray":[{strings}]}}]}}"#,
+        samples = samples.to_json_with("\"weightType\":\"samples\","),
+        markers =
+            Table::new(&["data", "name", "startTime", "endTime", "phase", "category"]).to_json(),
+        stack_table = stack_table.to_json(),
+        frame_table = frame_table.to_json(),
+        func_table = func_table.to_json(),
+        resources = Table::new(&["lib", "name", "host", "type"]).to_json(),
+        symbols = Table::new(&["libIndex", "address", "name", "functionSize"]).to_json(),
+        strings = strings.join(","),
+    )
+}
+
+/// A column oriented table of the Firefox Profiler processed profile format.
+#[derive(Debug)]
+struct Table {
+    /// The JSON encoded values of all columns by their names.
+    columns: BTreeMap<&'static str, Vec<String>>,
+    /// The number of rows of the table.
+    len: usize,
+}
+
+impl Table {
+    /// Creates an empty [`Table`] with the given `columns`.
+    fn new(columns: &[&'static str]) -> Self {
+        Self {
+            columns: columns.iter().map(|&column| (column, Vec::new())).collect(),
+            len: 0,
+        }
+    }
+
+    /// Pushes a row of JSON encoded `values` to their respective columns.
+    fn push<const N: usize>(&mut self, values: [(&'static str, String); N]) {
+        for (column, value) in values {
+            self.columns.entry(column).or_default().push(value);
+        }
+        self.len += 1;
+    }
+
+    /// Returns the JSON encoding of the [`Table`].
+    fn to_json(&self) -> String {
+        self.to_json_with("")
+    }
+
+    /// Returns the JSON encoding of the [`Table`] with additional `fields` prepended.
+    fn to_json_with(&self, fields: &str) -> String {
+        let mut json = format!("{{{fields}\"length\":{}", self.len);
+        for (column, values) in &self.columns {
+            // Note: writing to a `String` never fails.
+            let _ = write!(json, ",\"{column}\":[{}]", values.join(","));
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Returns `s` encoded as JSON string.
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                // Note: writing to a `String` never fails.
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// The names of the functions of a Wasm module.
+#[derive(Debug)]
+pub struct FuncNames<'a> {
+    /// The [`Module`] of the named functions.
+    module: &'a Module,
+}
+
+impl<'a> FuncNames<'a> {
+    /// Creates the [`FuncNames`] of `module` found in its `name` custom section.
+    pub fn new(module: &'a Module) -> Self {
+        Self { module }
+    }
+
+    /// Returns the name of `func`.
+    ///
+    /// Falls back to `wasm-function[N]` for functions without a name.
+    fn get(&self, func: ProfileFunc) -> String {
+        let Some(index) = func.func_index(self.module) else {
+            return String::from("<unknown>");
+        };
+        self.get_index(index)
+    }
+
+    /// Returns the name of the function at `index` within the Wasm module.
+    ///
+    /// Falls back to `wasm-function[N]` for functions without a name.
+    pub fn get_index(&self, index: u32) -> String {
+        match self.module.func_name(index) {
+            Some(name) => name.into(),
+            None => format!("wasm-function[{index}]"),
+        }
+    }
+}
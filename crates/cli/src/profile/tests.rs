@@ -0,0 +1,90 @@
+use super::*;
+use wasmi::{Config, Engine, Linker, Store};
+
+/// Executes the exported `main` function of `wat` while profiling.
+///
+/// Returns the recorded [`Profile`] and the [`FuncNames`] of the Wasm module.
+fn profile(wat: &str, f: impl FnOnce(&Profile, &FuncNames)) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(u64::MAX).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    store.set_profiling(true);
+    instance
+        .get_typed_func::<(), ()>(&store, "main")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    f(store.profile(), &FuncNames::new(&module))
+}
+
+const WAT: &str = r#"
+    (module
+        (func $leaf)
+        (func (call $leaf))
+        (func $main (export "main")
+            (call $leaf)
+            (call 1)
+        )
+    )
+"#;
+
+#[test]
+fn func_names() {
+    profile(WAT, |profile, names| {
+        let names = profile
+            .funcs()
+            .into_keys()
+            .map(|func| names.get(func))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["leaf", "wasm-function[1]", "main"]);
+    });
+}
+
+#[test]
+fn folded_stacks() {
+    profile(WAT, |profile, names| {
+        let folded = folded(profile, names);
+        let stacks = folded
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stacks,
+            [
+                "main",
+                "main;leaf",
+                "main;wasm-function[1]",
+                "main;wasm-function[1];leaf"
+            ]
+        );
+        let total = folded
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(total, profile.total_fuel());
+    });
+}
+
+#[test]
+fn firefox_profile() {
+    profile(WAT, |profile, names| {
+        let json = firefox(profile, names);
+        assert!(json.contains(r#""stackTable":{"length":4,"category":[0,0,0,0],"frame":[0,1,2,1],"prefix":[null,0,0,2],"subcategory":[0,0,0,0]}"#));
+        assert!(json.contains(r#""stringArray":["main","leaf","wasm-function[1]"]"#));
+        assert!(json.contains(r#""samples":{"weightType":"samples","length":4,"#));
+    });
+}
+
+#[test]
+fn json_escape() {
+    assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+}
@@ -59,6 +59,12 @@ impl<T> HeadVec<T> {
         self.head.as_mut()
     }
 
+    /// Returns an iterator over the items of the [`HeadVec`] from first to last.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.rest.iter().chain(self.head.as_ref())
+    }
+
     /// Pushes a new `value` onto the [`HeadVec`].
     #[inline]
     pub fn push(&mut self, value: T) {
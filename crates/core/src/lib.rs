@@ -14,6 +14,8 @@
 pub mod hint;
 mod host_error;
 mod nan_preserving_float;
+#[cfg(feature = "soft-float")]
+mod soft_float;
 mod trap;
 mod typed;
 mod units;
@@ -1,3 +1,4 @@
+#[cfg(not(feature = "soft-float"))]
 macro_rules! impl_binop {
     ($for:ty, $is:ty, $op:ident, $func_name:ident) => {
         impl<T: Into<$for>> ::core::ops::$op<T> for $for {
@@ -17,19 +18,97 @@ macro_rules! impl_binop {
     };
 }
 
+#[cfg(feature = "soft-float")]
+macro_rules! impl_binop {
+    ($for:ty, $is:ty, $op:ident, $func_name:ident) => {
+        impl<T: Into<$for>> ::core::ops::$op<T> for $for {
+            type Output = Self;
+
+            #[inline]
+            fn $func_name(self, other: T) -> Self {
+                Self(
+                    <$is as $crate::soft_float::SoftFloat>::$func_name(
+                        <$is>::from_bits(self.0),
+                        <$is>::from_bits(other.into().0),
+                    )
+                    .to_bits(),
+                )
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "soft-float"))]
+macro_rules! impl_cmp {
+    ($for:ty, $is:ty) => {
+        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialEq<T>
+            for $for
+        {
+            #[inline]
+            fn eq(&self, other: &T) -> ::core::primitive::bool {
+                <$is as ::core::convert::From<Self>>::from(*self)
+                    .eq(&<$is as ::core::convert::From<Self>>::from((*other).into()))
+            }
+        }
+
+        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialOrd<T>
+            for $for
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &T) -> ::core::option::Option<::core::cmp::Ordering> {
+                <$is as ::core::convert::From<Self>>::from(*self)
+                    .partial_cmp(&<$is as ::core::convert::From<Self>>::from((*other).into()))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "soft-float")]
+macro_rules! impl_cmp {
+    ($for:ty, $is:ty) => {
+        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialEq<T>
+            for $for
+        {
+            #[inline]
+            fn eq(&self, other: &T) -> ::core::primitive::bool {
+                ::core::matches!(
+                    ::core::cmp::PartialOrd::partial_cmp(self, other),
+                    ::core::option::Option::Some(::core::cmp::Ordering::Equal)
+                )
+            }
+        }
+
+        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialOrd<T>
+            for $for
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &T) -> ::core::option::Option<::core::cmp::Ordering> {
+                <$is as $crate::soft_float::SoftFloat>::compare(
+                    <$is>::from_bits(self.0),
+                    <$is>::from_bits((*other).into().0),
+                )
+            }
+        }
+    };
+}
+
 macro_rules! float {
     (
         $( #[$docs:meta] )*
-        struct $for:ident($rep:ty as $is:ty);
+        struct $for:ident($rep:ty as $is:ty, #canonical_nan = $canonical_nan:expr);
     ) => {
         float!(
             $(#[$docs])*
-            struct $for($rep as $is, #bits = 1 << (::core::mem::size_of::<$is>() * 8 - 1));
+            struct $for(
+                $rep as $is,
+                #bits = 1 << (::core::mem::size_of::<$is>() * 8 - 1),
+                #canonical_nan = $canonical_nan
+            );
         );
     };
     (
         $( #[$docs:meta] )*
-        struct $for:ident($rep:ty as $is:ty, #bits = $sign_bit:expr);
+        struct $for:ident($rep:ty as $is:ty, #bits = $sign_bit:expr, #canonical_nan = $canonical_nan:expr);
     ) => {
         $(#[$docs])*
         #[derive(Copy, Clone)]
@@ -42,6 +121,13 @@ macro_rules! float {
         impl_binop!($for, $is, Rem, rem);
 
         impl $for {
+            /// The positive canonical NaN as defined by the Wasm specification.
+            ///
+            /// # Note
+            ///
+            /// This is a quiet NaN with only the most significant bit of its payload set.
+            pub const CANONICAL_NAN: Self = Self($canonical_nan);
+
             /// Creates a float from its underlying bits.
             #[inline]
             pub fn from_bits(other: $rep) -> Self {
@@ -72,6 +158,19 @@ macro_rules! float {
                 self.to_float().is_nan()
             }
 
+            /// Returns [`Self::CANONICAL_NAN`] if `self` is NaN and `self` otherwise.
+            ///
+            /// This erases the sign and payload of NaN values which otherwise
+            /// might differ between platforms.
+            #[must_use]
+            #[inline]
+            pub fn canonicalize_nan(self) -> Self {
+                if self.is_nan() {
+                    return Self::CANONICAL_NAN;
+                }
+                self
+            }
+
             /// Returns the absolute value of the float.
             #[must_use]
             #[inline]
@@ -117,21 +216,7 @@ macro_rules! float {
             }
         }
 
-        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialEq<T> for $for {
-            #[inline]
-            fn eq(&self, other: &T) -> ::core::primitive::bool {
-                <$is as ::core::convert::From<Self>>::from(*self)
-                    .eq(&<$is as ::core::convert::From<Self>>::from((*other).into()))
-            }
-        }
-
-        impl<T: ::core::convert::Into<$for> + ::core::marker::Copy> ::core::cmp::PartialOrd<T> for $for {
-            #[inline]
-            fn partial_cmp(&self, other: &T) -> ::core::option::Option<::core::cmp::Ordering> {
-                <$is as ::core::convert::From<Self>>::from(*self)
-                    .partial_cmp(&<$is as ::core::convert::From<Self>>::from((*other).into()))
-            }
-        }
+        impl_cmp!($for, $is);
 
         impl ::core::fmt::Debug for $for {
             fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
@@ -161,12 +246,12 @@ macro_rules! float {
 
 float! {
     /// A NaN preserving `f32` type.
-    struct F32(u32 as f32);
+    struct F32(u32 as f32, #canonical_nan = 0x7FC0_0000);
 }
 
 float! {
     /// A NaN preserving `f64` type.
-    struct F64(u64 as f64);
+    struct F64(u64 as f64, #canonical_nan = 0x7FF8_0000_0000_0000);
 }
 
 impl From<u32> for F32 {
@@ -266,4 +351,40 @@ mod tests {
     fn test_neg_nan_f64() {
         assert_eq!((-F64(0xff80_3210_0000_0000)).0, 0x7f80_3210_0000_0000);
     }
+
+    #[test]
+    fn test_canonicalize_nan_f32() {
+        assert!(F32::CANONICAL_NAN.is_nan());
+        assert_eq!(F32(0xff80_3210).canonicalize_nan().0, 0x7fc0_0000);
+        assert_eq!(F32(0x7fc0_0001).canonicalize_nan().0, 0x7fc0_0000);
+        assert_eq!(
+            F32::from(-1.5_f32).canonicalize_nan().0,
+            (-1.5_f32).to_bits()
+        );
+        assert_eq!(
+            F32::from(f32::INFINITY).canonicalize_nan().0,
+            f32::INFINITY.to_bits()
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_nan_f64() {
+        assert!(F64::CANONICAL_NAN.is_nan());
+        assert_eq!(
+            F64(0xfff0_3210_0000_0000).canonicalize_nan().0,
+            0x7ff8_0000_0000_0000
+        );
+        assert_eq!(
+            F64(0x7ff8_0000_0000_0001).canonicalize_nan().0,
+            0x7ff8_0000_0000_0000
+        );
+        assert_eq!(
+            F64::from(-1.5_f64).canonicalize_nan().0,
+            (-1.5_f64).to_bits()
+        );
+        assert_eq!(
+            F64::from(f64::INFINITY).canonicalize_nan().0,
+            f64::INFINITY.to_bits()
+        );
+    }
 }
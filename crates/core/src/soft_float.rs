@@ -0,0 +1,884 @@
+//! Software implementation of the IEEE 754 floating point operations used by Wasm.
+//!
+//! # Note
+//!
+//! - All operations are implemented using integer arithmetic only and round to nearest,
+//!   ties to even as mandated by the Wasm specification.
+//! - Results are bit-exact and independent of the host platform. NaN inputs are propagated
+//!   as quiet NaNs and invalid operations produce the positive canonical NaN.
+//! - Enabled via the `soft-float` crate feature for targets without FPU or for
+//!   bit-exact reproducibility across platforms.
+
+use core::cmp::Ordering;
+
+/// Floating point operations implemented in software.
+pub trait SoftFloat: Copy {
+    /// Returns `self + rhs`.
+    fn add(self, rhs: Self) -> Self;
+    /// Returns `self - rhs`.
+    fn sub(self, rhs: Self) -> Self;
+    /// Returns `self * rhs`.
+    fn mul(self, rhs: Self) -> Self;
+    /// Returns `self / rhs`.
+    fn div(self, rhs: Self) -> Self;
+    /// Returns the remainder of `self / rhs` with the sign of `self`, same as `%`.
+    fn rem(self, rhs: Self) -> Self;
+    /// Returns the square root of `self`.
+    fn sqrt(self) -> Self;
+    /// Returns the smallest integer greater than or equal to `self`.
+    fn ceil(self) -> Self;
+    /// Returns the largest integer less than or equal to `self`.
+    fn floor(self) -> Self;
+    /// Returns the integer part of `self`.
+    fn trunc(self) -> Self;
+    /// Returns the nearest integer to `self`, rounding ties to even.
+    fn nearest(self) -> Self;
+    /// Returns the minimum of `self` and `rhs` as defined by Wasm `{f32,f64}.min`.
+    fn min(self, rhs: Self) -> Self;
+    /// Returns the maximum of `self` and `rhs` as defined by Wasm `{f32,f64}.max`.
+    fn max(self, rhs: Self) -> Self;
+    /// Returns the absolute value of `self`.
+    fn abs(self) -> Self;
+    /// Returns `self` with the sign of `rhs`.
+    fn copysign(self, rhs: Self) -> Self;
+    /// Returns the ordering of `self` and `rhs` where `-0.0` and `+0.0` are equal.
+    ///
+    /// Returns `None` if `self` or `rhs` is NaN.
+    fn compare(self, rhs: Self) -> Option<Ordering>;
+    /// Returns the float nearest to the integer `value`.
+    fn from_i128(value: i128) -> Self;
+    /// Returns `self` truncated towards zero as integer.
+    ///
+    /// Returns `None` if `self` is NaN.
+    /// Saturates to `i128::MIN` or `i128::MAX` for values beyond `±2^126`.
+    fn to_i128(self) -> Option<i128>;
+}
+
+/// The rounding mode used when rounding floats to integral values.
+#[derive(Debug, Copy, Clone)]
+enum RoundMode {
+    /// Round towards zero.
+    Trunc,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round to nearest integer, ties to even.
+    Nearest,
+}
+
+/// Returns the integer square root of `n` rounded down.
+fn isqrt(n: u128) -> u128 {
+    let mut rem = n;
+    let mut root = 0_u128;
+    let mut bit = 1_u128 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+macro_rules! impl_soft_float {
+    (
+        mod $name:ident for $float:ty as $bits:ty {
+            sig_bits: $sig_bits:literal,
+            exp_bits: $exp_bits:literal $(,)?
+        }
+    ) => {
+        mod $name {
+            use super::{isqrt, RoundMode, SoftFloat};
+            use core::cmp::{self, Ordering};
+
+            /// The number of bits of the significand without the implicit bit.
+            const SIG_BITS: u32 = $sig_bits;
+            /// The number of bits of the biased exponent.
+            const EXP_BITS: u32 = $exp_bits;
+            /// The exponent bias.
+            pub(super) const EXP_BIAS: i32 = (1 << (EXP_BITS - 1)) - 1;
+            /// The sign bit.
+            pub(super) const SIGN_MASK: $bits = 1 << (<$bits>::BITS - 1);
+            /// The bits of the significand without the implicit bit.
+            pub(super) const SIG_MASK: $bits = (1 << SIG_BITS) - 1;
+            /// The bits of the biased exponent.
+            const EXP_MASK: $bits = !(SIGN_MASK | SIG_MASK);
+            /// The implicit leading bit of normal significands.
+            const IMPLICIT_BIT: $bits = 1 << SIG_BITS;
+            /// The most significant bit of the significand distinguishing quiet from signaling NaNs.
+            pub(super) const QUIET_BIT: $bits = 1 << (SIG_BITS - 1);
+            /// The positive infinity.
+            pub(super) const INFINITY: $bits = EXP_MASK;
+            /// The positive canonical NaN.
+            const CANONICAL_NAN: $bits = INFINITY | QUIET_BIT;
+            /// The positive `1.0`.
+            const ONE: $bits = (EXP_BIAS as $bits) << SIG_BITS;
+
+            /// Returns `true` if `x` is NaN.
+            pub(super) fn is_nan(x: $bits) -> bool {
+                x & !SIGN_MASK > INFINITY
+            }
+
+            /// Returns `true` if `x` is positive or negative infinity.
+            pub(super) fn is_inf(x: $bits) -> bool {
+                x & !SIGN_MASK == INFINITY
+            }
+
+            /// Returns `true` if `x` is positive or negative zero.
+            pub(super) fn is_zero(x: $bits) -> bool {
+                x & !SIGN_MASK == 0
+            }
+
+            /// Returns `true` if the sign bit of `x` is set.
+            pub(super) fn is_negative(x: $bits) -> bool {
+                x & SIGN_MASK != 0
+            }
+
+            /// Returns the quieted NaN of either `a` or `b`, preferring `a`.
+            fn propagate_nan(a: $bits, b: $bits) -> $bits {
+                match is_nan(a) {
+                    true => a | QUIET_BIT,
+                    false => b | QUIET_BIT,
+                }
+            }
+
+            /// Returns `(exp, sig)` so that `|x| == sig * 2^exp` with a normalized `sig`.
+            ///
+            /// The most significant bit of `sig` is [`IMPLICIT_BIT`], also for subnormal `x`.
+            ///
+            /// # Note
+            ///
+            /// `x` must be finite and non-zero.
+            pub(super) fn unpack(x: $bits) -> (i32, $bits) {
+                let exp = ((x & EXP_MASK) >> SIG_BITS) as i32;
+                let frac = x & SIG_MASK;
+                if exp == 0 {
+                    let shift = frac.leading_zeros() - (<$bits>::BITS - 1 - SIG_BITS);
+                    let exp = 1 - EXP_BIAS - SIG_BITS as i32 - shift as i32;
+                    return (exp, frac << shift);
+                }
+                (exp - EXP_BIAS - SIG_BITS as i32, frac | IMPLICIT_BIT)
+            }
+
+            /// Returns the float nearest to `sig * 2^exp` with the given sign.
+            ///
+            /// Rounds to nearest, ties to even and handles overflow to infinity as
+            /// well as gradual underflow to subnormals and zero.
+            ///
+            /// # Note
+            ///
+            /// Inexact inputs must have their lost bits ORed into the least significant
+            /// bit of `sig` which must lie at least two bits below the rounding position.
+            pub(super) fn round_pack(negative: bool, exp: i32, sig: u128) -> $bits {
+                let sign = match negative {
+                    true => SIGN_MASK,
+                    false => 0,
+                };
+                if sig == 0 {
+                    return sign;
+                }
+                let msb = 127 - sig.leading_zeros() as i32;
+                // The unbiased exponent of the most significant bit of the result.
+                let exp_msb = exp + msb;
+                if exp_msb > EXP_BIAS {
+                    return sign | INFINITY;
+                }
+                // The exponent of the least significant bit of the rounded result.
+                let exp_lsb = cmp::max(exp_msb, 1 - EXP_BIAS) - SIG_BITS as i32;
+                let shift = exp_lsb - exp;
+                let mant = if shift <= 0 {
+                    sig << -shift
+                } else if shift >= 128 {
+                    0
+                } else {
+                    let mant = sig >> shift;
+                    let rem = sig & ((1 << shift) - 1);
+                    let half = 1 << (shift - 1);
+                    match rem > half || (rem == half && mant & 1 == 1) {
+                        true => mant + 1,
+                        false => mant,
+                    }
+                };
+                // Note: for normal results `mant` contains the implicit bit which
+                //       increments the biased exponent by one. The same applies for
+                //       carries of the rounding as well as subnormals that are
+                //       rounded up to the smallest normal value.
+                let biased_exp = exp_lsb + SIG_BITS as i32 + EXP_BIAS;
+                let bits = (((biased_exp - 1) as u128) << SIG_BITS) + mant;
+                if bits >= u128::from(INFINITY) {
+                    return sign | INFINITY;
+                }
+                sign | bits as $bits
+            }
+
+            fn add(a: $bits, b: $bits) -> $bits {
+                if is_nan(a) || is_nan(b) {
+                    return propagate_nan(a, b);
+                }
+                if is_inf(a) {
+                    if is_inf(b) && is_negative(a) != is_negative(b) {
+                        return CANONICAL_NAN;
+                    }
+                    return a;
+                }
+                if is_inf(b) {
+                    return b;
+                }
+                if is_zero(a) {
+                    if is_zero(b) {
+                        // Note: the sum of two zeros is only negative if both are negative.
+                        return a & b;
+                    }
+                    return b;
+                }
+                if is_zero(b) {
+                    return a;
+                }
+                let (exp_a, sig_a) = unpack(a);
+                let (exp_b, sig_b) = unpack(b);
+                let ((exp_x, sig_x, neg_x), (exp_y, sig_y, neg_y)) = match exp_a >= exp_b {
+                    true => (
+                        (exp_a, sig_a, is_negative(a)),
+                        (exp_b, sig_b, is_negative(b)),
+                    ),
+                    false => (
+                        (exp_b, sig_b, is_negative(b)),
+                        (exp_a, sig_a, is_negative(a)),
+                    ),
+                };
+                // Align both significands to the exponent of `x` with 64 guard bits
+                // and collapse all bits of `y` shifted out into a sticky bit.
+                let x = u128::from(sig_x) << 64;
+                let y = u128::from(sig_y) << 64;
+                let shift = (exp_x - exp_y) as u32;
+                let y = match shift >= 127 {
+                    true => 1,
+                    false => (y >> shift) | u128::from((y >> shift) << shift != y),
+                };
+                let (negative, sum) = if neg_x == neg_y {
+                    (neg_x, x + y)
+                } else if x >= y {
+                    (neg_x, x - y)
+                } else {
+                    (neg_y, y - x)
+                };
+                if sum == 0 {
+                    // Note: exact cancellation yields positive zero.
+                    return 0;
+                }
+                round_pack(negative, exp_x - 64, sum)
+            }
+
+            fn mul(a: $bits, b: $bits) -> $bits {
+                if is_nan(a) || is_nan(b) {
+                    return propagate_nan(a, b);
+                }
+                let sign = (a ^ b) & SIGN_MASK;
+                if is_inf(a) || is_inf(b) {
+                    if is_zero(a) || is_zero(b) {
+                        return CANONICAL_NAN;
+                    }
+                    return sign | INFINITY;
+                }
+                if is_zero(a) || is_zero(b) {
+                    return sign;
+                }
+                let (exp_a, sig_a) = unpack(a);
+                let (exp_b, sig_b) = unpack(b);
+                let product = u128::from(sig_a) * u128::from(sig_b);
+                round_pack(sign != 0, exp_a + exp_b, product)
+            }
+
+            fn div(a: $bits, b: $bits) -> $bits {
+                if is_nan(a) || is_nan(b) {
+                    return propagate_nan(a, b);
+                }
+                let sign = (a ^ b) & SIGN_MASK;
+                if is_inf(a) {
+                    if is_inf(b) {
+                        return CANONICAL_NAN;
+                    }
+                    return sign | INFINITY;
+                }
+                if is_inf(b) {
+                    return sign;
+                }
+                if is_zero(b) {
+                    if is_zero(a) {
+                        return CANONICAL_NAN;
+                    }
+                    return sign | INFINITY;
+                }
+                if is_zero(a) {
+                    return sign;
+                }
+                let (exp_a, sig_a) = unpack(a);
+                let (exp_b, sig_b) = unpack(b);
+                let dividend = u128::from(sig_a) << 64;
+                let divisor = u128::from(sig_b);
+                let quotient = dividend / divisor;
+                let sticky = u128::from(dividend % divisor != 0);
+                round_pack(sign != 0, exp_a - exp_b - 64, quotient | sticky)
+            }
+
+            fn rem(a: $bits, b: $bits) -> $bits {
+                if is_nan(a) || is_nan(b) {
+                    return propagate_nan(a, b);
+                }
+                if is_inf(a) || is_zero(b) {
+                    return CANONICAL_NAN;
+                }
+                if is_inf(b) || is_zero(a) {
+                    return a;
+                }
+                let (exp_a, sig_a) = unpack(a);
+                let (exp_b, sig_b) = unpack(b);
+                if exp_a < exp_b {
+                    // Note: `|a| < |b|` since both significands are normalized.
+                    return a;
+                }
+                // Computes `(sig_a * 2^(exp_a - exp_b)) mod sig_b` in chunks of 64 bits.
+                let divisor = u128::from(sig_b);
+                let mut rem = u128::from(sig_a) % divisor;
+                let mut shift = (exp_a - exp_b) as u32;
+                while shift > 0 {
+                    let chunk = cmp::min(shift, 64);
+                    rem = (rem << chunk) % divisor;
+                    shift -= chunk;
+                }
+                // Note: the remainder is always exact.
+                round_pack(is_negative(a), exp_b, rem)
+            }
+
+            fn sqrt(a: $bits) -> $bits {
+                if is_nan(a) {
+                    return a | QUIET_BIT;
+                }
+                if is_zero(a) {
+                    return a;
+                }
+                if is_negative(a) {
+                    return CANONICAL_NAN;
+                }
+                if is_inf(a) {
+                    return a;
+                }
+                let (exp, sig) = unpack(a);
+                let (exp, sig) = match exp % 2 == 0 {
+                    true => (exp, u128::from(sig)),
+                    false => (exp - 1, u128::from(sig) << 1),
+                };
+                // Scale the significand by `2^72` to compute enough bits of the root.
+                let scaled = sig << 72;
+                let root = isqrt(scaled);
+                let sticky = u128::from(root * root != scaled);
+                round_pack(false, exp / 2 - 36, root | sticky)
+            }
+
+            fn round(a: $bits, mode: RoundMode) -> $bits {
+                if is_nan(a) {
+                    return a | QUIET_BIT;
+                }
+                if is_inf(a) || is_zero(a) {
+                    return a;
+                }
+                let exp = ((a & EXP_MASK) >> SIG_BITS) as i32 - EXP_BIAS;
+                if exp >= SIG_BITS as i32 {
+                    // Note: `a` is already integral.
+                    return a;
+                }
+                let negative = is_negative(a);
+                if exp < 0 {
+                    // Case: `0 < |a| < 1`
+                    let round_up = match mode {
+                        RoundMode::Trunc => false,
+                        RoundMode::Floor => negative,
+                        RoundMode::Ceil => !negative,
+                        RoundMode::Nearest => exp == -1 && a & SIG_MASK != 0,
+                    };
+                    let sign = a & SIGN_MASK;
+                    return match round_up {
+                        true => sign | ONE,
+                        false => sign,
+                    };
+                }
+                let unit: $bits = 1 << (SIG_BITS - exp as u32);
+                let frac = a & (unit - 1);
+                if frac == 0 {
+                    return a;
+                }
+                let trunc = a & !(unit - 1);
+                let round_up = match mode {
+                    RoundMode::Trunc => false,
+                    RoundMode::Floor => negative,
+                    RoundMode::Ceil => !negative,
+                    RoundMode::Nearest => {
+                        let half = unit >> 1;
+                        // Note: for `exp == 0` the integer part is `1` and thus odd.
+                        let is_odd = exp == 0 || trunc & unit != 0;
+                        frac > half || (frac == half && is_odd)
+                    }
+                };
+                match round_up {
+                    // Note: carries propagate into the exponent as intended.
+                    true => trunc + unit,
+                    false => trunc,
+                }
+            }
+
+            fn partial_cmp(a: $bits, b: $bits) -> Option<Ordering> {
+                if is_nan(a) || is_nan(b) {
+                    return None;
+                }
+                if is_zero(a) && is_zero(b) {
+                    return Some(Ordering::Equal);
+                }
+                let key = |x: $bits| {
+                    let magnitude = i128::from(x & !SIGN_MASK);
+                    match is_negative(x) {
+                        true => -magnitude,
+                        false => magnitude,
+                    }
+                };
+                Some(key(a).cmp(&key(b)))
+            }
+
+            impl SoftFloat for $float {
+                #[inline]
+                fn add(self, rhs: Self) -> Self {
+                    Self::from_bits(add(self.to_bits(), rhs.to_bits()))
+                }
+
+                #[inline]
+                fn sub(self, rhs: Self) -> Self {
+                    let (a, b) = (self.to_bits(), rhs.to_bits());
+                    if is_nan(a) || is_nan(b) {
+                        return Self::from_bits(propagate_nan(a, b));
+                    }
+                    Self::from_bits(add(a, b ^ SIGN_MASK))
+                }
+
+                #[inline]
+                fn mul(self, rhs: Self) -> Self {
+                    Self::from_bits(mul(self.to_bits(), rhs.to_bits()))
+                }
+
+                #[inline]
+                fn div(self, rhs: Self) -> Self {
+                    Self::from_bits(div(self.to_bits(), rhs.to_bits()))
+                }
+
+                #[inline]
+                fn rem(self, rhs: Self) -> Self {
+                    Self::from_bits(rem(self.to_bits(), rhs.to_bits()))
+                }
+
+                #[inline]
+                fn sqrt(self) -> Self {
+                    Self::from_bits(sqrt(self.to_bits()))
+                }
+
+                #[inline]
+                fn ceil(self) -> Self {
+                    Self::from_bits(round(self.to_bits(), RoundMode::Ceil))
+                }
+
+                #[inline]
+                fn floor(self) -> Self {
+                    Self::from_bits(round(self.to_bits(), RoundMode::Floor))
+                }
+
+                #[inline]
+                fn trunc(self) -> Self {
+                    Self::from_bits(round(self.to_bits(), RoundMode::Trunc))
+                }
+
+                #[inline]
+                fn nearest(self) -> Self {
+                    Self::from_bits(round(self.to_bits(), RoundMode::Nearest))
+                }
+
+                #[inline]
+                fn min(self, rhs: Self) -> Self {
+                    let (a, b) = (self.to_bits(), rhs.to_bits());
+                    if is_nan(a) || is_nan(b) {
+                        return Self::from_bits(propagate_nan(a, b));
+                    }
+                    if is_zero(a) && is_zero(b) {
+                        // Note: `-0.0` is less than `+0.0` for Wasm `min`.
+                        return Self::from_bits(a | b);
+                    }
+                    match partial_cmp(a, b) {
+                        Some(Ordering::Greater) => rhs,
+                        _ => self,
+                    }
+                }
+
+                #[inline]
+                fn max(self, rhs: Self) -> Self {
+                    let (a, b) = (self.to_bits(), rhs.to_bits());
+                    if is_nan(a) || is_nan(b) {
+                        return Self::from_bits(propagate_nan(a, b));
+                    }
+                    if is_zero(a) && is_zero(b) {
+                        // Note: `+0.0` is greater than `-0.0` for Wasm `max`.
+                        return Self::from_bits(a & b);
+                    }
+                    match partial_cmp(a, b) {
+                        Some(Ordering::Less) => rhs,
+                        _ => self,
+                    }
+                }
+
+                #[inline]
+                fn abs(self) -> Self {
+                    Self::from_bits(self.to_bits() & !SIGN_MASK)
+                }
+
+                #[inline]
+                fn copysign(self, rhs: Self) -> Self {
+                    let magnitude = self.to_bits() & !SIGN_MASK;
+                    let sign = rhs.to_bits() & SIGN_MASK;
+                    Self::from_bits(magnitude | sign)
+                }
+
+                #[inline]
+                fn from_i128(value: i128) -> Self {
+                    Self::from_bits(round_pack(value < 0, 0, value.unsigned_abs()))
+                }
+
+                #[inline]
+                fn to_i128(self) -> Option<i128> {
+                    let a = self.to_bits();
+                    if is_nan(a) {
+                        return None;
+                    }
+                    if is_zero(a) {
+                        return Some(0);
+                    }
+                    let negative = is_negative(a);
+                    let saturated = match negative {
+                        true => i128::MIN,
+                        false => i128::MAX,
+                    };
+                    if is_inf(a) {
+                        return Some(saturated);
+                    }
+                    let (exp, sig) = unpack(a);
+                    if exp + SIG_BITS as i32 >= 126 {
+                        return Some(saturated);
+                    }
+                    let magnitude = match exp >= 0 {
+                        true => u128::from(sig) << exp,
+                        false if -exp >= 128 => 0,
+                        false => u128::from(sig) >> -exp,
+                    };
+                    let magnitude = magnitude as i128;
+                    match negative {
+                        true => Some(-magnitude),
+                        false => Some(magnitude),
+                    }
+                }
+
+                #[inline]
+                fn compare(self, rhs: Self) -> Option<Ordering> {
+                    partial_cmp(self.to_bits(), rhs.to_bits())
+                }
+            }
+        }
+    };
+}
+impl_soft_float! {
+    mod f32_impl for f32 as u32 {
+        sig_bits: 23,
+        exp_bits: 8,
+    }
+}
+impl_soft_float! {
+    mod f64_impl for f64 as u64 {
+        sig_bits: 52,
+        exp_bits: 11,
+    }
+}
+
+/// Returns `value` converted to `f64` as defined by Wasm `f64.promote_f32`.
+pub fn f64_promote_f32(value: f32) -> f64 {
+    let a = value.to_bits();
+    let sign = u64::from(a & f32_impl::SIGN_MASK) << 32;
+    if f32_impl::is_nan(a) {
+        let payload = u64::from(a & f32_impl::SIG_MASK) << (52 - 23);
+        return f64::from_bits(sign | f64_impl::INFINITY | f64_impl::QUIET_BIT | payload);
+    }
+    if f32_impl::is_inf(a) {
+        return f64::from_bits(sign | f64_impl::INFINITY);
+    }
+    if f32_impl::is_zero(a) {
+        return f64::from_bits(sign);
+    }
+    let (exp, sig) = f32_impl::unpack(a);
+    let negative = f32_impl::is_negative(a);
+    f64::from_bits(f64_impl::round_pack(negative, exp, u128::from(sig)))
+}
+
+/// Returns `value` converted to `f32` as defined by Wasm `f32.demote_f64`.
+pub fn f32_demote_f64(value: f64) -> f32 {
+    let a = value.to_bits();
+    let sign = ((a & f64_impl::SIGN_MASK) >> 32) as u32;
+    if f64_impl::is_nan(a) {
+        let payload = ((a & f64_impl::SIG_MASK) >> (52 - 23)) as u32;
+        return f32::from_bits(sign | f32_impl::INFINITY | f32_impl::QUIET_BIT | payload);
+    }
+    if f64_impl::is_inf(a) {
+        return f32::from_bits(sign | f32_impl::INFINITY);
+    }
+    if f64_impl::is_zero(a) {
+        return f32::from_bits(sign);
+    }
+    let (exp, sig) = f64_impl::unpack(a);
+    let negative = f64_impl::is_negative(a);
+    f32::from_bits(f32_impl::round_pack(negative, exp, u128::from(sig)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::{Add, Div, Mul, Rem, Sub};
+
+    /// Interesting `f32` inputs including edge cases around zero, subnormals, rounding and infinity.
+    fn f32_inputs() -> impl Iterator<Item = f32> + Clone {
+        let specials = [
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            0.5,
+            -0.5,
+            1.5,
+            2.5,
+            -2.5,
+            0.1,
+            3.0,
+            1.0e-40,
+            -1.0e-45,
+            f32::MIN_POSITIVE,
+            f32::MAX,
+            f32::MIN,
+            f32::EPSILON,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            8388607.5,
+            8388608.0,
+            16777217.0,
+            1.0e10,
+            -3.4e38,
+        ];
+        let mut state = 0x2545_F491_u32;
+        let randoms = (0..500).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            f32::from_bits(state)
+        });
+        specials.into_iter().chain(randoms.filter(|x| !x.is_nan()))
+    }
+
+    /// Interesting `f64` inputs including edge cases around zero, subnormals, rounding and infinity.
+    fn f64_inputs() -> impl Iterator<Item = f64> + Clone {
+        let specials = [
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            0.5,
+            -0.5,
+            1.5,
+            2.5,
+            -2.5,
+            0.1,
+            3.0,
+            1.0e-310,
+            -5.0e-324,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::MIN,
+            f64::EPSILON,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            4503599627370495.5,
+            4503599627370496.0,
+            9007199254740993.0,
+            1.0e300,
+            -1.7e308,
+        ];
+        let mut state = 0x2545_F491_4F6C_DD1D_u64;
+        let randoms = (0..500).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            f64::from_bits(state)
+        });
+        specials.into_iter().chain(randoms.filter(|x| !x.is_nan()))
+    }
+
+    /// A pair of software and hardware implementations of the same binary operator.
+    type BinaryOps<T> = (fn(T, T) -> T, fn(T, T) -> T);
+
+    /// Asserts that `soft` and `hard` yield the same bits or are both NaN.
+    macro_rules! assert_same {
+        ($soft:expr, $hard:expr, $($args:tt)*) => {{
+            let (soft, hard) = ($soft, $hard);
+            assert!(
+                soft.to_bits() == hard.to_bits() || (soft.is_nan() && hard.is_nan()),
+                "soft = {soft:?}, hard = {hard:?}: {}", format_args!($($args)*),
+            );
+        }};
+    }
+
+    #[test]
+    fn f32_binary_ops_match_hardware() {
+        let binary: [BinaryOps<f32>; 5] = [
+            (SoftFloat::add, Add::add),
+            (SoftFloat::sub, Sub::sub),
+            (SoftFloat::mul, Mul::mul),
+            (SoftFloat::div, Div::div),
+            (SoftFloat::rem, Rem::rem),
+        ];
+        for a in f32_inputs() {
+            for b in f32_inputs().take(120) {
+                for (index, (soft, hard)) in binary.iter().enumerate() {
+                    assert_same!(soft(a, b), hard(a, b), "op #{index}({a:?}, {b:?})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn f64_binary_ops_match_hardware() {
+        let binary: [BinaryOps<f64>; 5] = [
+            (SoftFloat::add, Add::add),
+            (SoftFloat::sub, Sub::sub),
+            (SoftFloat::mul, Mul::mul),
+            (SoftFloat::div, Div::div),
+            (SoftFloat::rem, Rem::rem),
+        ];
+        for a in f64_inputs() {
+            for b in f64_inputs().take(120) {
+                for (index, (soft, hard)) in binary.iter().enumerate() {
+                    assert_same!(soft(a, b), hard(a, b), "op #{index}({a:?}, {b:?})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unary_ops_match_hardware() {
+        for a in f32_inputs() {
+            assert_same!(SoftFloat::sqrt(a), a.sqrt(), "sqrt({a:?})");
+            assert_same!(SoftFloat::ceil(a), a.ceil(), "ceil({a:?})");
+            assert_same!(SoftFloat::floor(a), a.floor(), "floor({a:?})");
+            assert_same!(SoftFloat::trunc(a), a.trunc(), "trunc({a:?})");
+            assert_same!(SoftFloat::nearest(a), libm::rintf(a), "nearest({a:?})");
+            assert_same!(f64_promote_f32(a), f64::from(a), "promote({a:?})");
+            assert_eq!(
+                <f32 as SoftFloat>::from_i128(i128::from(a as i64)),
+                a as i64 as f32
+            );
+        }
+        for a in f64_inputs() {
+            assert_same!(SoftFloat::sqrt(a), a.sqrt(), "sqrt({a:?})");
+            assert_same!(SoftFloat::ceil(a), a.ceil(), "ceil({a:?})");
+            assert_same!(SoftFloat::floor(a), a.floor(), "floor({a:?})");
+            assert_same!(SoftFloat::trunc(a), a.trunc(), "trunc({a:?})");
+            assert_same!(SoftFloat::nearest(a), libm::rint(a), "nearest({a:?})");
+            assert_same!(f32_demote_f64(a), a as f32, "demote({a:?})");
+            assert_eq!(
+                <f64 as SoftFloat>::from_i128(i128::from(a as i64)),
+                a as i64 as f64
+            );
+            assert_eq!(
+                <f64 as SoftFloat>::from_i128(i128::from(a as u64)),
+                a as u64 as f64
+            );
+            assert_eq!(
+                <f32 as SoftFloat>::from_i128(i128::from(a as u64)),
+                a as u64 as f32
+            );
+        }
+    }
+
+    #[test]
+    fn to_i128_truncates() {
+        for a in f64_inputs() {
+            let Some(value) = SoftFloat::to_i128(a) else {
+                panic!("unexpected NaN: {a:?}")
+            };
+            if a.abs() < 1.0e37 {
+                assert_eq!(value, a as i128, "to_i128({a:?})");
+            }
+        }
+        assert_eq!(SoftFloat::to_i128(f32::NAN), None);
+        assert_eq!(SoftFloat::to_i128(-2.5_f32), Some(-2));
+        assert_eq!(SoftFloat::to_i128(f64::INFINITY), Some(i128::MAX));
+    }
+
+    #[test]
+    fn nan_results_are_canonical_or_propagated() {
+        let canonical = f32::from_bits(0x7FC0_0000);
+        let payload = f32::from_bits(0xFFA0_0001);
+        assert_eq!(
+            SoftFloat::sub(f32::INFINITY, f32::INFINITY).to_bits(),
+            canonical.to_bits()
+        );
+        assert_eq!(SoftFloat::div(0.0_f32, 0.0).to_bits(), canonical.to_bits());
+        assert_eq!(SoftFloat::sqrt(-1.0_f32).to_bits(), canonical.to_bits());
+        assert_eq!(SoftFloat::add(payload, 1.0).to_bits(), 0xFFE0_0001);
+        assert_eq!(SoftFloat::min(1.0, payload).to_bits(), 0xFFE0_0001);
+        assert_eq!(f64_promote_f32(payload).to_bits(), 0xFFFC_0000_2000_0000);
+    }
+
+    #[test]
+    fn min_max_follow_wasm() {
+        assert_eq!(
+            SoftFloat::min(0.0_f32, -0.0).to_bits(),
+            (-0.0_f32).to_bits()
+        );
+        assert_eq!(
+            SoftFloat::min(-0.0_f32, 0.0).to_bits(),
+            (-0.0_f32).to_bits()
+        );
+        assert_eq!(SoftFloat::max(0.0_f64, -0.0).to_bits(), 0.0_f64.to_bits());
+        assert_eq!(SoftFloat::max(-0.0_f64, 0.0).to_bits(), 0.0_f64.to_bits());
+        assert_eq!(SoftFloat::min(1.0_f64, -2.0), -2.0);
+        assert_eq!(SoftFloat::max(1.0_f32, -2.0), 1.0);
+        assert!(SoftFloat::max(f32::NAN, 1.0_f32).is_nan());
+    }
+
+    #[test]
+    fn compare_matches_hardware() {
+        for a in f32_inputs().chain([f32::NAN]) {
+            for b in f32_inputs().take(120).chain([f32::NAN]) {
+                assert_eq!(
+                    SoftFloat::compare(a, b),
+                    a.partial_cmp(&b),
+                    "{a:?} <=> {b:?}"
+                );
+            }
+        }
+        for a in f64_inputs().chain([f64::NAN]) {
+            for b in f64_inputs().take(120).chain([f64::NAN]) {
+                assert_eq!(
+                    SoftFloat::compare(a, b),
+                    a.partial_cmp(&b),
+                    "{a:?} <=> {b:?}"
+                );
+            }
+        }
+    }
+}
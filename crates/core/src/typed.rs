@@ -337,6 +337,9 @@ impl TypedVal {
         fn f32_demote_f64(f64) -> f32;
         fn f64_promote_f32(f32) -> f64;
 
+        fn f32_canonicalize_nan(f32) -> f32;
+        fn f64_canonicalize_nan(f64) -> f64;
+
         #[fallible] fn i32_trunc_f32_s(f32) -> Result<i32, TrapCode>;
         #[fallible] fn i32_trunc_f32_u(f32) -> Result<i32, TrapCode>;
         #[fallible] fn i32_trunc_f64_s(f64) -> Result<i32, TrapCode>;
@@ -1163,6 +1163,24 @@ impl UntypedVal {
         self.execute_unary(<i64 as WrapInto<F32>>::wrap_into)
     }
 
+    /// Replaces `self` with the canonical `f32` NaN if `self` is an `f32` NaN value.
+    ///
+    /// # Note
+    ///
+    /// This is used to guarantee deterministic NaN bit patterns across platforms.
+    pub fn f32_canonicalize_nan(self) -> Self {
+        self.execute_unary(F32::canonicalize_nan)
+    }
+
+    /// Replaces `self` with the canonical `f64` NaN if `self` is an `f64` NaN value.
+    ///
+    /// # Note
+    ///
+    /// This is used to guarantee deterministic NaN bit patterns across platforms.
+    pub fn f64_canonicalize_nan(self) -> Self {
+        self.execute_unary(F64::canonicalize_nan)
+    }
+
     /// Execute `f32.convert_i64_u` Wasm operation.
     pub fn f32_convert_i64_u(self) -> Self {
         self.execute_unary(<u64 as WrapInto<F32>>::wrap_into)
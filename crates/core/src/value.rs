@@ -1,3 +1,5 @@
+#[cfg(feature = "soft-float")]
+use crate::soft_float::{self, SoftFloat};
 use crate::{
     hint::unlikely,
     nan_preserving_float::{F32, F64},
@@ -280,12 +282,15 @@ impl_wrap_into!(i32, i16);
 impl_wrap_into!(i64, i8);
 impl_wrap_into!(i64, i16);
 impl_wrap_into!(i64, i32);
+#[cfg(not(feature = "soft-float"))]
 impl_wrap_into!(i64, f32, F32);
+#[cfg(not(feature = "soft-float"))]
 impl_wrap_into!(u64, f32, F32);
 
 impl_wrap_into!(u32, u32);
 impl_wrap_into!(u64, u64);
 
+#[cfg(not(feature = "soft-float"))]
 impl WrapInto<F32> for F64 {
     #[inline]
     fn wrap_into(self) -> F32 {
@@ -293,6 +298,15 @@ impl WrapInto<F32> for F64 {
     }
 }
 
+#[cfg(feature = "soft-float")]
+impl WrapInto<F32> for F64 {
+    #[inline]
+    fn wrap_into(self) -> F32 {
+        soft_float::f32_demote_f64(self.into()).into()
+    }
+}
+
+#[cfg(not(feature = "soft-float"))]
 macro_rules! impl_try_truncate_into {
     (@primitive $from: ident, $into: ident, $rmin:literal, $rmax:literal) => {
         impl TryTruncateInto<$into, TrapCode> for $from {
@@ -341,6 +355,47 @@ macro_rules! impl_try_truncate_into {
     };
 }
 
+#[cfg(feature = "soft-float")]
+macro_rules! impl_try_truncate_into {
+    (@primitive $from: ident, $into: ident, $rmin:literal, $rmax:literal) => {
+        // Note: the bounds are not needed since the truncated `i128` is range checked.
+        impl TryTruncateInto<$into, TrapCode> for $from {
+            #[inline]
+            fn try_truncate_into(self) -> Result<$into, TrapCode> {
+                let Some(value) = <$from as SoftFloat>::to_i128(self) else {
+                    return Err(TrapCode::BadConversionToInteger);
+                };
+                <$into>::try_from(value).map_err(|_| TrapCode::IntegerOverflow)
+            }
+        }
+
+        impl TruncateSaturateInto<$into> for $from {
+            #[inline]
+            fn truncate_saturate_into(self) -> $into {
+                let Some(value) = <$from as SoftFloat>::to_i128(self) else {
+                    return <$into as Default>::default();
+                };
+                value.clamp(i128::from(<$into>::MIN), i128::from(<$into>::MAX)) as $into
+            }
+        }
+    };
+    (@wrapped $from:ident, $intermediate:ident, $into:ident) => {
+        impl TryTruncateInto<$into, TrapCode> for $from {
+            #[inline]
+            fn try_truncate_into(self) -> Result<$into, TrapCode> {
+                $intermediate::from(self).try_truncate_into()
+            }
+        }
+
+        impl TruncateSaturateInto<$into> for $from {
+            #[inline]
+            fn truncate_saturate_into(self) -> $into {
+                $intermediate::from(self).truncate_saturate_into()
+            }
+        }
+    };
+}
+
 impl_try_truncate_into!(@primitive f32, i32, -2147483904.0_f32, 2147483648.0_f32);
 impl_try_truncate_into!(@primitive f32, u32,          -1.0_f32, 4294967296.0_f32);
 impl_try_truncate_into!(@primitive f64, i32, -2147483649.0_f64, 2147483648.0_f64);
@@ -391,18 +446,26 @@ impl_extend_into!(i32, i64);
 impl_extend_into!(u32, i64);
 impl_extend_into!(u32, u64);
 
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(i32, f32, F32);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(i32, f64, F64);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(u32, f32, F32);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(u32, f64, F64);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(i64, f64, F64);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(u64, f64, F64);
+#[cfg(not(feature = "soft-float"))]
 impl_extend_into!(f32, f64, F64);
 
 // Casting to self
 impl_extend_into!(u32, u32);
 impl_extend_into!(u64, u64);
 
+#[cfg(not(feature = "soft-float"))]
 impl ExtendInto<F64> for F32 {
     #[inline]
     fn extend_into(self) -> F64 {
@@ -410,6 +473,48 @@ impl ExtendInto<F64> for F32 {
     }
 }
 
+#[cfg(feature = "soft-float")]
+macro_rules! impl_soft_float_convert_into {
+    ( $( impl $trait:ident<$into:ident as $repr:ident> for $from:ident { fn $method:ident } )* ) => {
+        $(
+            impl $trait<$into> for $from {
+                #[inline]
+                fn $method(self) -> $into {
+                    <$repr as SoftFloat>::from_i128(i128::from(self)).into()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "soft-float")]
+impl_soft_float_convert_into! {
+    impl WrapInto<F32 as f32> for i64 { fn wrap_into }
+    impl WrapInto<F32 as f32> for u64 { fn wrap_into }
+    impl ExtendInto<F32 as f32> for i32 { fn extend_into }
+    impl ExtendInto<F64 as f64> for i32 { fn extend_into }
+    impl ExtendInto<F32 as f32> for u32 { fn extend_into }
+    impl ExtendInto<F64 as f64> for u32 { fn extend_into }
+    impl ExtendInto<F64 as f64> for i64 { fn extend_into }
+    impl ExtendInto<F64 as f64> for u64 { fn extend_into }
+}
+
+#[cfg(feature = "soft-float")]
+impl ExtendInto<F64> for f32 {
+    #[inline]
+    fn extend_into(self) -> F64 {
+        soft_float::f64_promote_f32(self).into()
+    }
+}
+
+#[cfg(feature = "soft-float")]
+impl ExtendInto<F64> for F32 {
+    #[inline]
+    fn extend_into(self) -> F64 {
+        soft_float::f64_promote_f32(self.into()).into()
+    }
+}
+
 macro_rules! impl_sign_extend_from {
     ( $( impl SignExtendFrom<$from_type:ty> for $for_type:ty; )* ) => {
         $(
@@ -455,8 +560,9 @@ impl_integer_arithmetic_ops!(u32);
 impl_integer_arithmetic_ops!(i64);
 impl_integer_arithmetic_ops!(u64);
 
+#[cfg(not(feature = "soft-float"))]
 macro_rules! impl_float_arithmetic_ops {
-    ($type:ty) => {
+    (type $type:ty as $repr:ty) => {
         impl ArithmeticOps<Self> for $type {
             #[inline]
             fn add(self, other: Self) -> Self {
@@ -474,10 +580,30 @@ macro_rules! impl_float_arithmetic_ops {
     };
 }
 
-impl_float_arithmetic_ops!(f32);
-impl_float_arithmetic_ops!(f64);
-impl_float_arithmetic_ops!(F32);
-impl_float_arithmetic_ops!(F64);
+#[cfg(feature = "soft-float")]
+macro_rules! impl_float_arithmetic_ops {
+    (type $type:ty as $repr:ty) => {
+        impl ArithmeticOps<Self> for $type {
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                <$repr as SoftFloat>::add(self.into(), other.into()).into()
+            }
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                <$repr as SoftFloat>::sub(self.into(), other.into()).into()
+            }
+            #[inline]
+            fn mul(self, other: Self) -> Self {
+                <$repr as SoftFloat>::mul(self.into(), other.into()).into()
+            }
+        }
+    };
+}
+
+impl_float_arithmetic_ops!( type f32 as f32 );
+impl_float_arithmetic_ops!( type f64 as f64 );
+impl_float_arithmetic_ops!( type F32 as f32 );
+impl_float_arithmetic_ops!( type F64 as f64 );
 
 macro_rules! impl_integer {
     ($type:ty) => {
@@ -534,6 +660,7 @@ impl_integer!(u64);
 // We cannot call the math functions directly, because they are not all available in `core`.
 // In no-std cases we instead rely on `libm`.
 // These wrappers handle that delegation.
+#[cfg(not(feature = "soft-float"))]
 macro_rules! impl_float {
     (type $type:ident as $repr:ty) => {
         // In this particular instance we want to directly compare floating point numbers.
@@ -617,6 +744,55 @@ macro_rules! impl_float {
         }
     };
 }
+// With the `soft-float` crate feature all operations are implemented in software instead.
+#[cfg(feature = "soft-float")]
+macro_rules! impl_float {
+    (type $type:ident as $repr:ty) => {
+        impl Float<Self> for $type {
+            #[inline]
+            fn abs(self) -> Self {
+                <$repr as SoftFloat>::abs(self.into()).into()
+            }
+            #[inline]
+            fn floor(self) -> Self {
+                <$repr as SoftFloat>::floor(self.into()).into()
+            }
+            #[inline]
+            fn ceil(self) -> Self {
+                <$repr as SoftFloat>::ceil(self.into()).into()
+            }
+            #[inline]
+            fn trunc(self) -> Self {
+                <$repr as SoftFloat>::trunc(self.into()).into()
+            }
+            #[inline]
+            fn nearest(self) -> Self {
+                <$repr as SoftFloat>::nearest(self.into()).into()
+            }
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$repr as SoftFloat>::sqrt(self.into()).into()
+            }
+            #[inline]
+            fn div(self, other: Self) -> Self {
+                <$repr as SoftFloat>::div(self.into(), other.into()).into()
+            }
+            #[inline]
+            fn min(self, other: Self) -> Self {
+                <$repr as SoftFloat>::min(self.into(), other.into()).into()
+            }
+            #[inline]
+            fn max(self, other: Self) -> Self {
+                <$repr as SoftFloat>::max(self.into(), other.into()).into()
+            }
+            #[inline]
+            fn copysign(self, other: Self) -> Self {
+                <$repr as SoftFloat>::copysign(self.into(), other.into()).into()
+            }
+        }
+    };
+}
+
 impl_float!( type F32 as f32 );
 impl_float!( type F64 as f64 );
 impl_float!( type f32 as f32 );
@@ -630,6 +806,7 @@ impl_float!( type f64 as f64 );
 /// does not specify all of the below methods for `f32` and `f64`.
 /// Thus this trait serves as an adapter to import this functionality
 /// via `libm`.
+#[cfg(not(feature = "soft-float"))]
 trait WasmFloatExt {
     /// Equivalent to the Wasm `{f32,f64}.abs` instructions.
     fn abs(self) -> Self;
@@ -647,7 +824,7 @@ trait WasmFloatExt {
     fn copysign(self, other: Self) -> Self;
 }
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "soft-float"), not(feature = "std")))]
 macro_rules! impl_wasm_float {
     ($ty:ty) => {
         impl WasmFloatExt for $ty {
@@ -700,7 +877,7 @@ macro_rules! impl_wasm_float {
     };
 }
 
-#[cfg(feature = "std")]
+#[cfg(all(not(feature = "soft-float"), feature = "std"))]
 macro_rules! impl_wasm_float {
     ($ty:ty) => {
         impl WasmFloatExt for $ty {
@@ -742,7 +919,9 @@ macro_rules! impl_wasm_float {
     };
 }
 
+#[cfg(not(feature = "soft-float"))]
 impl_wasm_float!(f32);
+#[cfg(not(feature = "soft-float"))]
 impl_wasm_float!(f64);
 
 #[cfg(test)]
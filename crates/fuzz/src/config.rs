@@ -13,6 +13,12 @@ pub struct FuzzWasmiConfig {
     pub validation_mode: ValidationMode,
     /// Is `true` if Wasmi shall use lazy translation.
     pub translation_mode: CompilationMode,
+    /// Is `true` if Wasmi shall execute deterministically.
+    ///
+    /// # Note
+    ///
+    /// Implies [`CompilationMode::Eager`] since lazy modes are not supported.
+    pub deterministic: bool,
 }
 
 /// The Wasmi parsing mode.
@@ -38,6 +44,7 @@ impl From<FuzzWasmiConfig> for wasmi::Config {
         let mut config = wasmi::Config::default();
         config.compilation_mode(fuzz.translation_mode);
         config.consume_fuel(fuzz.consume_fuel);
+        config.deterministic(fuzz.deterministic);
         config
     }
 }
@@ -54,9 +61,11 @@ impl Arbitrary<'_> for FuzzWasmiConfig {
             0 => ValidationMode::Unchecked,
             _ => ValidationMode::Checked,
         };
-        let translation_mode = match (bits >> 3) & 0b11 {
-            0b00 => CompilationMode::Lazy,
-            0b01 => CompilationMode::LazyTranslation,
+        let deterministic = (bits >> 5) & 0x1 != 0;
+        let translation_mode = match ((bits >> 3) & 0b11, deterministic) {
+            (_, true) => CompilationMode::Eager,
+            (0b00, _) => CompilationMode::Lazy,
+            (0b01, _) => CompilationMode::LazyTranslation,
             _ => CompilationMode::Eager,
         };
         Ok(Self {
@@ -64,6 +73,7 @@ impl Arbitrary<'_> for FuzzWasmiConfig {
             parsing_mode,
             validation_mode,
             translation_mode,
+            deterministic,
         })
     }
 
@@ -45,16 +45,21 @@ impl WasmiOracle {
         }
         exports
     }
-}
 
-impl DifferentialOracleMeta for WasmiOracle {
-    fn configure(_config: &mut FuzzSmithConfig) {}
+    /// Sets up the Wasmi oracle for `wasm` with deterministic execution enabled.
+    ///
+    /// # Note
+    ///
+    /// Results of this oracle must match the results of oracles executing the same
+    /// `wasm_smith` module with NaN canonicalization enabled.
+    pub fn setup_deterministic(wasm: &[u8]) -> Option<Self> {
+        Self::setup_impl(wasm, true)
+    }
 
-    fn setup(wasm: &[u8]) -> Option<Self>
-    where
-        Self: Sized,
-    {
+    /// Sets up the Wasmi oracle for `wasm` with optional `deterministic` execution.
+    fn setup_impl(wasm: &[u8], deterministic: bool) -> Option<Self> {
         let mut config = Config::default();
+        config.deterministic(deterministic);
         // We set custom limits since Wasmi (register) might use more
         // stack space than Wasmi (stack) for some malicious recursive workloads.
         // Wasmtime technically suffers from the same problems (register machine)
@@ -93,6 +98,17 @@ impl DifferentialOracleMeta for WasmiOracle {
     }
 }
 
+impl DifferentialOracleMeta for WasmiOracle {
+    fn configure(_config: &mut FuzzSmithConfig) {}
+
+    fn setup(wasm: &[u8]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        Self::setup_impl(wasm, false)
+    }
+}
+
 impl DifferentialOracle for WasmiOracle {
     fn name(&self) -> &'static str {
         "Wasmi"
@@ -0,0 +1,248 @@
+use crate::{core::TrapCode, for_each_op, index::*, *};
+use ::core::{
+    fmt::{self, Display},
+    num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64},
+};
+
+/// Displays an [`Instruction`] in a human readable text format.
+///
+/// # Format
+///
+/// - The result register(s) come first, followed by `=` and the name of the instruction.
+/// - All other fields follow as `name=value` pairs in their order of definition.
+/// - Registers are displayed as `r{index}` where negative indices refer to function local constants.
+/// - Branch offsets are displayed as the absolute position `@{target}` of the branch target.
+#[derive(Debug, Copy, Clone)]
+pub struct DisplayInstruction {
+    /// The displayed [`Instruction`].
+    instr: Instruction,
+    /// The position of the displayed [`Instruction`] within its function.
+    pos: usize,
+}
+
+impl Instruction {
+    /// Returns a [`DisplayInstruction`] for `self` at position `pos` within its function.
+    ///
+    /// The position is required to resolve relative branch offsets.
+    pub fn display(&self, pos: usize) -> DisplayInstruction {
+        DisplayInstruction { instr: *self, pos }
+    }
+}
+
+/// Types that can be displayed as [`Instruction`] operand.
+trait DisplayOperand {
+    /// Displays `self` as operand of the [`Instruction`] at position `pos`.
+    fn fmt(&self, f: &mut fmt::Formatter, pos: usize) -> fmt::Result;
+}
+
+/// Wrapper to display a [`DisplayOperand`] via [`Display`].
+struct Operand<'a, T>(&'a T, usize);
+
+impl<T: DisplayOperand> Display for Operand<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        DisplayOperand::fmt(self.0, f, self.1)
+    }
+}
+
+macro_rules! define_display {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $(
+                        @ $result_name:ident: $result_ty:ty,
+                    )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        impl Instruction {
+            /// Returns the name of the [`Instruction`] as displayed by [`DisplayInstruction`].
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Instruction::$name { .. } => {
+                            // Note: strip the prefix of raw identifiers such as `r#return`.
+                            let name = stringify!($snake_name);
+                            name.strip_prefix("r#").unwrap_or(name)
+                        }
+                    )*
+                }
+            }
+        }
+
+        impl Display for DisplayInstruction {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match &self.instr {
+                    $(
+                        Instruction::$name $( {
+                            $( $result_name, )?
+                            $( $field_name ),*
+                        } )? => {
+                            $( $(
+                                write!(f, "{} = ", Operand($result_name, self.pos))?;
+                            )? )?
+                            f.write_str(self.instr.name())?;
+                            $( $(
+                                write!(
+                                    f,
+                                    concat!(" ", stringify!($field_name), "={}"),
+                                    Operand($field_name, self.pos),
+                                )?;
+                            )* )?
+                            Ok(())
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+for_each_op::for_each_op!(define_display);
+
+impl DisplayOperand for Reg {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "r{}", self.0)
+    }
+}
+
+impl<const N: usize> DisplayOperand for [Reg; N] {
+    fn fmt(&self, f: &mut fmt::Formatter, pos: usize) -> fmt::Result {
+        f.write_str("[")?;
+        for (n, reg) in self.iter().enumerate() {
+            if n != 0 {
+                f.write_str(", ")?;
+            }
+            DisplayOperand::fmt(reg, f, pos)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl DisplayOperand for RegSpan {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "r{}..", self.head().0)
+    }
+}
+
+impl<const N: u16> DisplayOperand for FixedRegSpan<N> {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        let head = self.span().head();
+        write!(f, "r{}..r{}", head.0, head.next_n(N).0)
+    }
+}
+
+impl DisplayOperand for BoundedRegSpan {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        let head = self.span().head();
+        write!(f, "r{}..r{}", head.0, head.next_n(self.len()).0)
+    }
+}
+
+impl DisplayOperand for BranchOffset {
+    fn fmt(&self, f: &mut fmt::Formatter, pos: usize) -> fmt::Result {
+        let target = pos as i64 + i64::from(self.to_i32());
+        write!(f, "@{target}")
+    }
+}
+
+impl DisplayOperand for BranchOffset16 {
+    fn fmt(&self, f: &mut fmt::Formatter, pos: usize) -> fmt::Result {
+        DisplayOperand::fmt(&BranchOffset::from(*self), f, pos)
+    }
+}
+
+impl DisplayOperand for BlockFuel {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "{}", self.to_u64())
+    }
+}
+
+impl DisplayOperand for TrapCode {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl DisplayOperand for AnyConst16 {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "{}", i16::from(*self))
+    }
+}
+
+impl DisplayOperand for AnyConst32 {
+    fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+        write!(f, "0x{:08X}", u32::from(*self))
+    }
+}
+
+macro_rules! impl_display_operand_for {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl DisplayOperand for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+                    write!(f, "{}", self)
+                }
+            }
+        )*
+    };
+}
+impl_display_operand_for!(u8, i8, u16, i16, u32);
+
+macro_rules! impl_display_operand_via {
+    ( $( $ty:ty => $via:ty ),* $(,)? ) => {
+        $(
+            impl DisplayOperand for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+                    write!(f, "{}", <$via>::from(*self))
+                }
+            }
+        )*
+    };
+}
+impl_display_operand_via! {
+    Const16<i32> => i32,
+    Const16<u32> => u32,
+    Const16<i64> => i64,
+    Const16<u64> => u64,
+    Const16<NonZeroI32> => NonZeroI32,
+    Const16<NonZeroU32> => NonZeroU32,
+    Const16<NonZeroI64> => NonZeroI64,
+    Const16<NonZeroU64> => NonZeroU64,
+    Const32<i64> => i64,
+    Const32<f64> => f64,
+    ShiftAmount<i32> => i32,
+    ShiftAmount<i64> => i64,
+    Sign<f32> => f32,
+    Sign<f64> => f64,
+}
+
+macro_rules! impl_display_operand_for_index {
+    ( $( $ty:ty => $name:literal ),* $(,)? ) => {
+        $(
+            impl DisplayOperand for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter, _pos: usize) -> fmt::Result {
+                    write!(f, concat!($name, "[{}]"), self.0)
+                }
+            }
+        )*
+    };
+}
+impl_display_operand_for_index! {
+    Func => "func",
+    FuncType => "type",
+    InternalFunc => "internal",
+    Global => "global",
+    Memory => "memory",
+    Table => "table",
+    Data => "data",
+    Elem => "elem",
+}
@@ -23,6 +23,34 @@ macro_rules! for_each_op {
             ConsumeFuel {
                 block_fuel: BlockFuel
             },
+            /// Instruction generated to notify an installed debugger before executing a Wasm operator.
+            ///
+            /// # Note
+            ///
+            /// - These instructions are only generated if debugging is enabled.
+            /// - The `offset` is the byte offset of the Wasm operator within the Wasm binary.
+            #[snake_name(debug_step)]
+            DebugStep {
+                offset: u32
+            },
+            /// Instruction generated to count the executions of a basic block for code coverage.
+            ///
+            /// # Note
+            ///
+            /// - These instructions are only generated if code coverage is enabled.
+            /// - The `index` refers to the coverage counter of the executed function.
+            #[snake_name(coverage_counter)]
+            CoverageCounter {
+                index: u32
+            },
+            /// Instruction generated to count calls and loop iterations of a Wasm function for tiering.
+            ///
+            /// # Note
+            ///
+            /// - These instructions are only generated by the baseline tier if tiering is enabled.
+            /// - Placed at the function entry and at every loop header.
+            #[snake_name(tier_up_counter)]
+            TierUpCounter,
 
             /// A Wasm `return` instruction.
             ///
@@ -4,6 +4,7 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod display;
 mod r#enum;
 mod error;
 mod for_each_op;
@@ -11,6 +12,7 @@ mod immeditate;
 pub mod index;
 mod primitive;
 mod span;
+mod visit_offsets;
 mod visit_regs;
 
 #[cfg(test)]
@@ -20,6 +22,7 @@ use wasmi_core as core;
 
 #[doc(inline)]
 pub use self::{
+    display::DisplayInstruction,
     error::Error,
     immeditate::{AnyConst16, AnyConst32, Const16, Const32},
     index::Reg,
@@ -35,5 +38,6 @@ pub use self::{
     },
     r#enum::Instruction,
     span::{BoundedRegSpan, FixedRegSpan, RegSpan, RegSpanIter},
+    visit_offsets::VisitBranchOffsets,
     visit_regs::VisitRegs,
 };
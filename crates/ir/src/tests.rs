@@ -38,3 +38,33 @@ fn has_overlapping_copy_spans_works() {
     assert!(has_overlapping_copy_spans(span(4), span(1), 4));
     assert!(has_overlapping_copy_spans(span(4), span(0), 5));
 }
+
+#[test]
+fn display_instruction_works() {
+    use crate::{BranchOffset, Const16, Instruction};
+    use alloc::string::ToString as _;
+    let display = |instr: Instruction, pos: usize| instr.display(pos).to_string();
+    assert_eq!(
+        display(
+            Instruction::i32_add(Reg::from(2), Reg::from(0), Reg::from(-1)),
+            0
+        ),
+        "r2 = i32_add lhs=r0 rhs=r-1"
+    );
+    assert_eq!(
+        display(
+            Instruction::i32_add_imm16(Reg::from(1), Reg::from(0), Const16::from(-5_i16)),
+            0
+        ),
+        "r1 = i32_add_imm16 lhs=r0 rhs=-5"
+    );
+    assert_eq!(
+        display(Instruction::branch(BranchOffset::from(-3)), 5),
+        "branch offset=@2"
+    );
+    assert_eq!(display(Instruction::Return, 0), "return");
+    assert_eq!(
+        display(Instruction::return_reg2_ext(1, 2), 0),
+        "return_reg2 values=[r1, r2]"
+    );
+}
@@ -0,0 +1,120 @@
+use crate::{core::TrapCode, index::*, *};
+
+impl Instruction {
+    /// Visit [`BranchOffset`]s and [`BranchOffset16`]s of `self` via the `visitor`.
+    ///
+    /// # Note
+    ///
+    /// Branch offsets are relative to the position of the visited [`Instruction`].
+    pub fn visit_branch_offsets<V: VisitBranchOffsets>(&mut self, visitor: &mut V) {
+        HostVisitor::host_visitor(self, visitor)
+    }
+}
+
+/// Implemented by branch offset visitors to visit branch offsets of an [`Instruction`] via [`Instruction::visit_branch_offsets`].
+pub trait VisitBranchOffsets {
+    /// Visits a 32-bit [`BranchOffset`] of an [`Instruction`].
+    fn visit_branch_offset(&mut self, offset: &mut BranchOffset);
+    /// Visits a 16-bit [`BranchOffset16`] of an [`Instruction`].
+    fn visit_branch_offset16(&mut self, offset: &mut BranchOffset16);
+}
+
+/// Internal trait used to dispatch to a [`VisitBranchOffsets`] visitor.
+trait HostVisitor {
+    /// Host the [`VisitBranchOffsets`] visitor in the appropriate way.
+    fn host_visitor<V: VisitBranchOffsets>(self, visitor: &mut V);
+}
+
+impl HostVisitor for &'_ mut BranchOffset {
+    fn host_visitor<V: VisitBranchOffsets>(self, visitor: &mut V) {
+        visitor.visit_branch_offset(self);
+    }
+}
+
+impl HostVisitor for &'_ mut BranchOffset16 {
+    fn host_visitor<V: VisitBranchOffsets>(self, visitor: &mut V) {
+        visitor.visit_branch_offset16(self);
+    }
+}
+
+impl<const N: usize> HostVisitor for &'_ mut [Reg; N] {
+    #[inline]
+    fn host_visitor<V: VisitBranchOffsets>(self, _visitor: &mut V) {}
+}
+
+impl<const N: u16> HostVisitor for &'_ mut FixedRegSpan<N> {
+    #[inline]
+    fn host_visitor<V: VisitBranchOffsets>(self, _visitor: &mut V) {}
+}
+
+macro_rules! impl_host_visitor_for {
+    ( $( $ty:ident $(<$t:ident>)? ),* $(,)? ) => {
+        $(
+            impl $(<$t>)? HostVisitor for &'_ mut $ty $(<$t>)? {
+                #[inline]
+                fn host_visitor<V: VisitBranchOffsets>(self, _visitor: &mut V) {}
+            }
+        )*
+    };
+}
+impl_host_visitor_for!(
+    u8,
+    i8,
+    i16,
+    u16,
+    u32,
+    Reg,
+    RegSpan,
+    BoundedRegSpan,
+    TrapCode,
+    BlockFuel,
+    AnyConst16,
+    AnyConst32,
+    InternalFunc,
+    Func,
+    FuncType,
+    Global,
+    Memory,
+    Table,
+    Elem,
+    Data,
+    Const16<T>,
+    Const32<T>,
+    Sign<T>,
+    ShiftAmount<T>,
+);
+
+macro_rules! impl_host_visitor {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $( @ $result_name:ident: $result_ty:ty, )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        impl<'a> HostVisitor for &'a mut Instruction {
+            fn host_visitor<V: VisitBranchOffsets>(self, visitor: &mut V) {
+                match self {
+                    $(
+                        Instruction::$name { $( $( $field_name, )* .. )? } => {
+                            $(
+                                $( $field_name.host_visitor(visitor); )*
+                            )?
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+for_each_op!(impl_host_visitor);
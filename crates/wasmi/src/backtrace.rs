@@ -0,0 +1,290 @@
+use crate::engine::{CodeMap, EngineFunc};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// A Wasm backtrace captured when a Wasm execution traps.
+///
+/// Backtraces are only captured if [`Config::wasm_backtrace`] is enabled
+/// and are accessible via [`Error::backtrace`].
+///
+/// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+/// [`Error::backtrace`]: crate::Error::backtrace
+#[derive(Debug, Default, Clone)]
+pub struct WasmBacktrace {
+    /// The call frames from the trapping frame to the root frame.
+    frames: Vec<BacktraceFrame>,
+}
+
+impl WasmBacktrace {
+    /// Captures a [`WasmBacktrace`] from `frames` ordered from the trapping frame to the root frame.
+    ///
+    /// Every frame consists of the executed function, its index within its Wasm module
+    /// and the Wasm offset of its executed Wasm operator.
+    pub(crate) fn capture(
+        code_map: &CodeMap,
+        frames: impl IntoIterator<Item = (EngineFunc, u32, u32)>,
+    ) -> Self {
+        let frames = frames
+            .into_iter()
+            .map(|(func, func_index, offset)| {
+                let symbols = code_map.symbols(func);
+                let func_name = symbols
+                    .as_ref()
+                    .and_then(|symbols| symbols.names.func(func_index))
+                    .map(Box::from);
+                #[cfg(feature = "dwarf")]
+                let source_frames = symbols
+                    .as_ref()
+                    .and_then(|symbols| symbols.dwarf.as_ref())
+                    .map(|dwarf| dwarf.source_frames(offset))
+                    .unwrap_or_default();
+                #[cfg(not(feature = "dwarf"))]
+                let source_frames = Vec::new();
+                BacktraceFrame::new(func_index, func_name, offset, source_frames)
+            })
+            .collect();
+        Self { frames }
+    }
+
+    /// Returns the call frames from the trapping frame to the root frame.
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        &self.frames
+    }
+}
+
+impl fmt::Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "wasm backtrace:")?;
+        for (index, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n    {index}: {:#x} - ", frame.offset)?;
+            match frame.func_name() {
+                Some(name) => write!(f, "{name}")?,
+                None => write!(f, "func[{}]", frame.func_index)?,
+            }
+            for source in &frame.source_frames {
+                write!(
+                    f,
+                    "\n           at {}",
+                    source.function().unwrap_or("<unknown>")
+                )?;
+                if let Some(location) = source.location() {
+                    write!(f, " ({location})")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A call frame of a [`WasmBacktrace`].
+#[derive(Debug, Clone)]
+pub struct BacktraceFrame {
+    /// The index of the executed function within its Wasm module.
+    func_index: u32,
+    /// The name of the executed function in the `name` custom section if any.
+    func_name: Option<Box<str>>,
+    /// The byte offset of the executed Wasm operator within the Wasm binary.
+    offset: u32,
+    /// The source frames of the executed Wasm operator.
+    source_frames: Vec<SourceFrame>,
+}
+
+impl BacktraceFrame {
+    /// Creates a new [`BacktraceFrame`].
+    pub(crate) fn new(
+        func_index: u32,
+        func_name: Option<Box<str>>,
+        offset: u32,
+        source_frames: Vec<SourceFrame>,
+    ) -> Self {
+        Self {
+            func_index,
+            func_name,
+            offset,
+            source_frames,
+        }
+    }
+
+    /// Returns the index of the executed function within its Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the executed function in the `name` custom section if any.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the byte offset of the executed Wasm operator within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// For callers this is the offset of their call operator.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the source frames of the executed Wasm operator.
+    ///
+    /// The innermost inlined function comes first. This is empty unless the `dwarf`
+    /// crate feature is enabled and the Wasm module has DWARF debug information.
+    pub fn source_frames(&self) -> &[SourceFrame] {
+        &self.source_frames
+    }
+}
+
+/// A source level function frame of a Wasm operator derived from DWARF debug information.
+///
+/// A single Wasm operator has multiple [`SourceFrame`]s if it belongs to inlined functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFrame {
+    /// The name of the function if known.
+    function: Option<Box<str>>,
+    /// The source location within the function if known.
+    location: Option<SourceLocation>,
+}
+
+impl SourceFrame {
+    /// Creates a new [`SourceFrame`].
+    #[cfg_attr(not(feature = "dwarf"), allow(dead_code))]
+    pub(crate) fn new(function: Option<Box<str>>, location: Option<SourceLocation>) -> Self {
+        Self { function, location }
+    }
+
+    /// Returns the name of the function if known.
+    pub fn function(&self) -> Option<&str> {
+        self.function.as_deref()
+    }
+
+    /// Returns the source location within the function if known.
+    ///
+    /// For callers of inlined functions this is the location of the call site.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+}
+
+/// A location within a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The path of the source file.
+    file: Box<str>,
+    /// The 1-based line within the source file.
+    line: u32,
+    /// The 1-based column within the line if known.
+    column: Option<u32>,
+}
+
+impl SourceLocation {
+    /// Creates a new [`SourceLocation`].
+    #[cfg_attr(not(feature = "dwarf"), allow(dead_code))]
+    pub(crate) fn new(file: Box<str>, line: u32, column: Option<u32>) -> Self {
+        Self { file, line, column }
+    }
+
+    /// Returns the path of the source file.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the 1-based line within the source file.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the 1-based column within the line if known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The Wasm offsets of the instructions of a compiled Wasm function.
+///
+/// Symbolicates the executed instructions of call frames for [`WasmBacktrace`]s,
+/// core dumps, trap details and the trace hook of the `trace` crate feature.
+#[derive(Debug)]
+pub(crate) struct FuncOffsets {
+    /// The index of the function within its Wasm module.
+    func_index: u32,
+    /// The position of the first instruction and the Wasm offset of every translated Wasm operator.
+    offsets: Box<[(u32, u32)]>,
+}
+
+impl FuncOffsets {
+    /// Returns the index of the function within its Wasm module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the Wasm offset of the Wasm operator that produced the instruction at `pos` if any.
+    pub fn offset(&self, pos: usize) -> Option<u32> {
+        let index = self
+            .offsets
+            .partition_point(|&(first, _)| first as usize <= pos)
+            .checked_sub(1)?;
+        Some(self.offsets[index].1)
+    }
+}
+
+/// Records the Wasm offsets of translated Wasm operators for a [`FuncOffsets`].
+#[derive(Debug, Default)]
+pub(crate) struct FuncOffsetsBuilder {
+    /// The position of the first instruction and the Wasm offset of every translated Wasm operator.
+    offsets: Vec<(u32, u32)>,
+}
+
+impl FuncOffsetsBuilder {
+    /// Resets the [`FuncOffsetsBuilder`] for reuse.
+    pub fn reset(&mut self) {
+        self.offsets.clear();
+    }
+
+    /// Registers the Wasm operator at `offset` whose first instruction is at `pos`.
+    pub fn push(&mut self, pos: u32, offset: u32) {
+        if let Some(last) = self.offsets.last_mut() {
+            if last.0 == pos {
+                // Note: the previous Wasm operator did not produce any instructions.
+                last.1 = offset;
+                return;
+            }
+        }
+        self.offsets.push((pos, offset));
+    }
+
+    /// Moves the recorded instruction positions via `remap` after instructions have been removed.
+    pub fn remap(&mut self, mut remap: impl FnMut(u32) -> u32) {
+        for (pos, _) in &mut self.offsets {
+            *pos = remap(*pos);
+        }
+        // Note: Wasm operators whose instructions have all been removed no longer produce any.
+        self.offsets.dedup_by(|next, last| {
+            if next.0 != last.0 {
+                return false;
+            }
+            last.1 = next.1;
+            true
+        });
+    }
+
+    /// Finishes the [`FuncOffsets`] of the function at `func_index`.
+    pub fn finish(&self, func_index: u32) -> FuncOffsets {
+        FuncOffsets {
+            func_index,
+            offsets: self.offsets.as_slice().into(),
+        }
+    }
+}
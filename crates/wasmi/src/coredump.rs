@@ -0,0 +1,467 @@
+use crate::{core::ValType, store::StoreInner, Global, Instance, Memory, Val};
+use alloc::{format, vec::Vec};
+
+/// The size of a Wasm linear memory page in bytes.
+const PAGE_SIZE: usize = 65536;
+
+/// The minimum number of zero bytes that split the data segments of a memory snapshot.
+const MIN_ZERO_GAP: usize = 16;
+
+/// A Wasm core dump captured when a Wasm execution traps.
+///
+/// Core dumps are only captured if [`Config::coredump_on_trap`] is enabled
+/// and are accessible via [`Error::coredump`].
+///
+/// Use [`WasmCoreDump::serialize`] to encode the core dump in the Wasm
+/// tool-conventions coredump format which is understood by existing tooling.
+///
+/// [`Config::coredump_on_trap`]: crate::Config::coredump_on_trap
+/// [`Error::coredump`]: crate::Error::coredump
+#[derive(Debug, Default, Clone)]
+pub struct WasmCoreDump {
+    /// The call frames from the trapping frame to the root frame.
+    frames: Vec<CoreDumpFrame>,
+    /// The instances of all `frames`.
+    instances: Vec<CoreDumpInstance>,
+    /// The snapshots of the linear memories of all `instances`.
+    memories: Vec<CoreDumpMemory>,
+    /// The snapshots of the global variables of all `instances`.
+    globals: Vec<CoreDumpGlobal>,
+}
+
+/// A call frame of a [`WasmCoreDump`].
+#[derive(Debug, Clone)]
+pub struct CoreDumpFrame {
+    /// The index of the [`CoreDumpInstance`] of the executed function.
+    instance: u32,
+    /// The index of the executed function within its Wasm module.
+    func_index: u32,
+    /// The byte offset of the executed Wasm operator within the Wasm binary.
+    offset: u32,
+    /// The byte offset of the executed Wasm operator relative to the function body.
+    code_offset: u32,
+    /// The values of the parameters and local variables if known.
+    locals: Vec<Option<Val>>,
+    /// The values of the Wasm operand stack if known.
+    stack: Vec<Option<Val>>,
+}
+
+impl CoreDumpFrame {
+    /// Returns the index of the executed function within its Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the byte offset of the executed Wasm operator within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// For callers this is the offset of their call operator.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the values of the parameters and local variables in order.
+    ///
+    /// Values are `None` if they are unknown.
+    pub fn locals(&self) -> &[Option<Val>] {
+        &self.locals
+    }
+
+    /// Returns the values of the Wasm operand stack from bottom to top.
+    ///
+    /// Values are `None` if they are unknown.
+    pub fn stack(&self) -> &[Option<Val>] {
+        &self.stack
+    }
+}
+
+/// An instance of a [`WasmCoreDump`].
+#[derive(Debug, Clone)]
+struct CoreDumpInstance {
+    /// The [`Instance`] of the core dump instance.
+    instance: Instance,
+    /// The indices of the linear memories of the instance in [`WasmCoreDump::memories`].
+    memories: Vec<u32>,
+    /// The indices of the global variables of the instance in [`WasmCoreDump::globals`].
+    globals: Vec<u32>,
+}
+
+/// A snapshot of a linear memory of a [`WasmCoreDump`].
+#[derive(Debug, Clone)]
+struct CoreDumpMemory {
+    /// The [`Memory`] of the snapshot.
+    memory: Memory,
+    /// The maximum number of pages of the linear memory if any.
+    maximum_pages: Option<u32>,
+    /// The bytes of the linear memory.
+    data: Vec<u8>,
+}
+
+/// A snapshot of a global variable of a [`WasmCoreDump`].
+#[derive(Debug, Clone)]
+struct CoreDumpGlobal {
+    /// The [`Global`] of the snapshot.
+    global: Global,
+    /// Is `true` if the global variable is mutable.
+    mutable: bool,
+    /// The value of the global variable.
+    value: Val,
+}
+
+impl WasmCoreDump {
+    /// Returns the call frames from the trapping frame to the root frame.
+    pub fn frames(&self) -> &[CoreDumpFrame] {
+        &self.frames
+    }
+
+    /// Pushes a call frame of a function of `instance` onto the [`WasmCoreDump`].
+    ///
+    /// Frames must be pushed from the trapping frame to the root frame.
+    ///
+    /// Snapshots the linear memories and global variables of `instance` if necessary.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn push_frame(
+        &mut self,
+        store: &StoreInner,
+        instance: Instance,
+        func_index: u32,
+        offset: u32,
+        code_offset: u32,
+        locals: Vec<Option<Val>>,
+        stack: Vec<Option<Val>>,
+    ) {
+        let instance = self.snapshot_instance(store, instance);
+        self.frames.push(CoreDumpFrame {
+            instance,
+            func_index,
+            offset,
+            code_offset,
+            locals,
+            stack,
+        });
+    }
+
+    /// Returns the index of the [`CoreDumpInstance`] of `instance`.
+    ///
+    /// Snapshots the linear memories and global variables of `instance` if necessary.
+    fn snapshot_instance(&mut self, store: &StoreInner, instance: Instance) -> u32 {
+        if let Some(index) = self
+            .instances
+            .iter()
+            .position(|entry| entry.instance == instance)
+        {
+            return index as u32;
+        }
+        let entity = store.resolve_instance(&instance);
+        let memories = (0..)
+            .map_while(|index| entity.get_memory(index))
+            .map(|memory| self.snapshot_memory(store, memory))
+            .collect();
+        let globals = (0..)
+            .map_while(|index| entity.get_global(index))
+            .map(|global| self.snapshot_global(store, global))
+            .collect();
+        self.instances.push(CoreDumpInstance {
+            instance,
+            memories,
+            globals,
+        });
+        (self.instances.len() - 1) as u32
+    }
+
+    /// Returns the index of the snapshot of `memory`.
+    fn snapshot_memory(&mut self, store: &StoreInner, memory: Memory) -> u32 {
+        if let Some(index) = self
+            .memories
+            .iter()
+            .position(|entry| entry.memory.as_inner() == memory.as_inner())
+        {
+            return index as u32;
+        }
+        let entity = store.resolve_memory(&memory);
+        self.memories.push(CoreDumpMemory {
+            memory,
+            maximum_pages: entity.ty().maximum_pages().map(u32::from),
+            data: entity.data().into(),
+        });
+        (self.memories.len() - 1) as u32
+    }
+
+    /// Returns the index of the snapshot of `global`.
+    fn snapshot_global(&mut self, store: &StoreInner, global: Global) -> u32 {
+        if let Some(index) = self
+            .globals
+            .iter()
+            .position(|entry| entry.global.as_inner() == global.as_inner())
+        {
+            return index as u32;
+        }
+        let entity = store.resolve_global(&global);
+        self.globals.push(CoreDumpGlobal {
+            global,
+            mutable: entity.ty().mutability().is_mut(),
+            value: entity.get(),
+        });
+        (self.globals.len() - 1) as u32
+    }
+
+    /// Encodes the [`WasmCoreDump`] in the Wasm tool-conventions coredump format.
+    ///
+    /// The `name` is the name of the executable, usually the name of the Wasm file.
+    ///
+    /// # Note
+    ///
+    /// - The core dump is a Wasm module with the linear memories and global
+    ///   variables of all instances on the call stack and `core`, `coremodules`,
+    ///   `coreinstances` and `corestack` custom sections.
+    /// - Every instance refers to its own module named `<anonymous-module-N>`.
+    /// - Non-null reference values cannot be represented in the coredump format.
+    ///   They are encoded as missing values or `ref.null` for global variables.
+    ///
+    /// See <https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md>.
+    pub fn serialize(&self, name: &str) -> Vec<u8> {
+        let mut wasm = Vec::new();
+        wasm.extend_from_slice(b"\0asm");
+        wasm.extend_from_slice(&1u32.to_le_bytes());
+        let mut section = Vec::new();
+        section.push(0x00);
+        encode_name(&mut section, name);
+        encode_custom_section(&mut wasm, "core", &section);
+
+        section.clear();
+        encode_u32(&mut section, self.instances.len() as u32);
+        for index in 0..self.instances.len() {
+            section.push(0x00);
+            encode_name(&mut section, &format!("<anonymous-module-{index}>"));
+        }
+        encode_custom_section(&mut wasm, "coremodules", &section);
+
+        section.clear();
+        encode_u32(&mut section, self.instances.len() as u32);
+        for (index, instance) in self.instances.iter().enumerate() {
+            section.push(0x00);
+            encode_u32(&mut section, index as u32);
+            encode_u32(&mut section, instance.memories.len() as u32);
+            for &memory in &instance.memories {
+                encode_u32(&mut section, memory);
+            }
+            encode_u32(&mut section, instance.globals.len() as u32);
+            for &global in &instance.globals {
+                encode_u32(&mut section, global);
+            }
+        }
+        encode_custom_section(&mut wasm, "coreinstances", &section);
+
+        section.clear();
+        section.push(0x00);
+        encode_name(&mut section, "main");
+        encode_u32(&mut section, self.frames.len() as u32);
+        for frame in &self.frames {
+            section.push(0x00);
+            encode_u32(&mut section, frame.instance);
+            encode_u32(&mut section, frame.func_index);
+            encode_u32(&mut section, frame.code_offset);
+            for values in [&frame.locals, &frame.stack] {
+                encode_u32(&mut section, values.len() as u32);
+                for value in values {
+                    encode_value(&mut section, value.as_ref());
+                }
+            }
+        }
+        encode_custom_section(&mut wasm, "corestack", &section);
+
+        section.clear();
+        encode_u32(&mut section, self.memories.len() as u32);
+        for memory in &self.memories {
+            let pages = (memory.data.len() / PAGE_SIZE) as u32;
+            match memory.maximum_pages {
+                Some(maximum) => {
+                    section.push(0x01);
+                    encode_u32(&mut section, pages);
+                    encode_u32(&mut section, maximum);
+                }
+                None => {
+                    section.push(0x00);
+                    encode_u32(&mut section, pages);
+                }
+            }
+        }
+        encode_section(&mut wasm, 5, &section);
+
+        section.clear();
+        encode_u32(&mut section, self.globals.len() as u32);
+        for global in &self.globals {
+            section.push(encode_val_type(global.value.ty()));
+            section.push(u8::from(global.mutable));
+            encode_const_expr(&mut section, &global.value);
+        }
+        encode_section(&mut wasm, 6, &section);
+
+        section.clear();
+        let segments = self
+            .memories
+            .iter()
+            .enumerate()
+            .flat_map(|(index, memory)| {
+                data_segments(&memory.data).map(move |(offset, bytes)| (index, offset, bytes))
+            })
+            .collect::<Vec<_>>();
+        encode_u32(&mut section, segments.len() as u32);
+        for (index, offset, bytes) in segments {
+            match index {
+                0 => section.push(0x00),
+                _ => {
+                    section.push(0x02);
+                    encode_u32(&mut section, index as u32);
+                }
+            }
+            encode_const_expr(&mut section, &Val::I32(offset as i32));
+            encode_u32(&mut section, bytes.len() as u32);
+            section.extend_from_slice(bytes);
+        }
+        encode_section(&mut wasm, 11, &section);
+        wasm
+    }
+}
+
+/// Returns the offset and bytes of all non-zero regions of the linear memory `data`.
+fn data_segments(data: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        let start = pos + data[pos..].iter().position(|&byte| byte != 0)?;
+        let mut end = start;
+        let mut zeros = 0;
+        for (index, &byte) in data.iter().enumerate().skip(start) {
+            if byte != 0 {
+                end = index + 1;
+                zeros = 0;
+            } else {
+                zeros += 1;
+                if zeros == MIN_ZERO_GAP {
+                    break;
+                }
+            }
+        }
+        pos = end;
+        Some((start, &data[start..end]))
+    })
+}
+
+/// Encodes `value` as unsigned LEB128 into `output`.
+fn encode_u32(output: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            output.push(byte);
+            return;
+        }
+        output.push(byte | 0x80);
+    }
+}
+
+/// Encodes `value` as signed LEB128 into `output`.
+fn encode_i64(output: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            output.push(byte);
+            return;
+        }
+        output.push(byte | 0x80);
+    }
+}
+
+/// Encodes the Wasm `name` into `output`.
+fn encode_name(output: &mut Vec<u8>, name: &str) {
+    encode_u32(output, name.len() as u32);
+    output.extend_from_slice(name.as_bytes());
+}
+
+/// Encodes the Wasm section with `id` and `contents` into `output`.
+fn encode_section(output: &mut Vec<u8>, id: u8, contents: &[u8]) {
+    output.push(id);
+    encode_u32(output, contents.len() as u32);
+    output.extend_from_slice(contents);
+}
+
+/// Encodes the Wasm custom section with `name` and `contents` into `output`.
+fn encode_custom_section(output: &mut Vec<u8>, name: &str, contents: &[u8]) {
+    let mut section = Vec::with_capacity(name.len() + contents.len() + 1);
+    encode_name(&mut section, name);
+    section.extend_from_slice(contents);
+    encode_section(output, 0, &section);
+}
+
+/// Returns the Wasm encoding of the value type `ty`.
+fn encode_val_type(ty: ValType) -> u8 {
+    match ty {
+        ValType::I32 => 0x7F,
+        ValType::I64 => 0x7E,
+        ValType::F32 => 0x7D,
+        ValType::F64 => 0x7C,
+        ValType::FuncRef => 0x70,
+        ValType::ExternRef => 0x6F,
+    }
+}
+
+/// Encodes a Wasm constant expression evaluating to `value` into `output`.
+///
+/// Reference values are encoded as `ref.null`.
+fn encode_const_expr(output: &mut Vec<u8>, value: &Val) {
+    match value {
+        Val::I32(value) => {
+            output.push(0x41);
+            encode_i64(output, i64::from(*value));
+        }
+        Val::I64(value) => {
+            output.push(0x42);
+            encode_i64(output, *value);
+        }
+        Val::F32(value) => {
+            output.push(0x43);
+            output.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        Val::F64(value) => {
+            output.push(0x44);
+            output.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        Val::FuncRef(_) | Val::ExternRef(_) => {
+            output.push(0xD0);
+            output.push(encode_val_type(value.ty()));
+        }
+    }
+    output.push(0x0B);
+}
+
+/// Encodes a coredump `value` into `output`.
+///
+/// Unknown and reference values are encoded as missing values.
+fn encode_value(output: &mut Vec<u8>, value: Option<&Val>) {
+    match value {
+        Some(Val::I32(value)) => {
+            output.push(0x7F);
+            encode_i64(output, i64::from(*value));
+        }
+        Some(Val::I64(value)) => {
+            output.push(0x7E);
+            encode_i64(output, *value);
+        }
+        Some(Val::F32(value)) => {
+            output.push(0x7D);
+            output.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        Some(Val::F64(value)) => {
+            output.push(0x7C);
+            output.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        Some(Val::FuncRef(_) | Val::ExternRef(_)) | None => output.push(0x01),
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{collections::arena::ArenaIndex, engine::EngineFunc, Module};
+use alloc::vec::Vec;
+
+/// The code coverage counters recorded by a [`Store`].
+///
+/// Counters are only recorded for Wasm functions compiled with [`Config::coverage`] enabled.
+///
+/// [`Store`]: crate::Store
+/// [`Config::coverage`]: crate::Config::coverage
+#[derive(Debug, Default, Clone)]
+pub struct Coverage {
+    /// The coverage counters of all executed functions indexed by their [`EngineFunc`].
+    counters: Vec<Vec<u64>>,
+}
+
+impl Coverage {
+    /// Increments the coverage counter at `index` of `func`.
+    #[inline]
+    pub(crate) fn hit(&mut self, func: EngineFunc, index: u32) {
+        let func = func.into_usize();
+        let index = index as usize;
+        if func >= self.counters.len() {
+            self.counters.resize_with(func + 1, Vec::new);
+        }
+        let counters = &mut self.counters[func];
+        if index >= counters.len() {
+            counters.resize(index + 1, 0);
+        }
+        counters[index] += 1;
+    }
+
+    /// Returns `true` if no coverage counters have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.counters.iter().all(Vec::is_empty)
+    }
+
+    /// Returns the [`FuncCoverage`] of all compiled functions defined by `module`.
+    ///
+    /// Functions that have not been compiled, for example due to lazy compilation,
+    /// are not part of the result.
+    pub fn funcs(&self, module: &Module) -> Vec<FuncCoverage> {
+        (0..module.len_funcs() as u32)
+            .filter_map(|func_index| {
+                let func = module.get_engine_func(func_index)?;
+                let offsets = module.engine().coverage_offsets(func)?;
+                let counters = self
+                    .counters
+                    .get(func.into_usize())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let blocks = offsets
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &offset)| BlockCoverage {
+                        offset,
+                        hits: counters.get(index).copied().unwrap_or(0),
+                    })
+                    .collect();
+                Some(FuncCoverage { func_index, blocks })
+            })
+            .collect()
+    }
+}
+
+/// The code coverage of a single Wasm function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuncCoverage {
+    func_index: u32,
+    blocks: Vec<BlockCoverage>,
+}
+
+impl FuncCoverage {
+    /// Returns the index of the function within its Wasm module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the number of calls to the function.
+    pub fn hits(&self) -> u64 {
+        self.blocks.first().map_or(0, BlockCoverage::hits)
+    }
+
+    /// Returns the [`BlockCoverage`] of all basic blocks of the function ordered by their Wasm offset.
+    pub fn blocks(&self) -> &[BlockCoverage] {
+        &self.blocks
+    }
+}
+
+/// The code coverage of a single basic block of a Wasm function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BlockCoverage {
+    offset: u32,
+    hits: u64,
+}
+
+impl BlockCoverage {
+    /// Returns the Wasm offset of the first Wasm operator of the basic block.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the number of executions of the basic block.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+}
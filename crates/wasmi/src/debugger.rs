@@ -0,0 +1,1090 @@
+use crate::{
+    core::{UntypedVal, ValType},
+    coredump::WasmCoreDump,
+    engine::{CodeMap, EngineFunc, FrameRegisters},
+    ir::Reg,
+    store::StoreInner,
+    value::WithType,
+    Config,
+    Error,
+    Instance,
+    Memory,
+    Module,
+    Val,
+};
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use core::{fmt, ops::Range};
+use wasmparser::MemArg;
+
+/// A debugger that can be installed on a [`Store`] via [`Store::set_debugger`].
+///
+/// # Note
+///
+/// - The [`Debugger`] is only notified when executing Wasm functions that have
+///   been compiled with [`Config::debugging`] enabled.
+/// - All callbacks are invoked before the Wasm operator at the reported location is executed.
+/// - At most one callback is invoked per Wasm operator. Watchpoints take precedence
+///   over breakpoints which take precedence over single stepping.
+/// - Returning an error from a callback aborts the execution with that error.
+///
+/// [`Store`]: crate::Store
+/// [`Store::set_debugger`]: crate::Store::set_debugger
+/// [`Config::debugging`]: crate::Config::debugging
+pub trait Debugger: Send + Sync {
+    /// Called when the execution hits a breakpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the execution shall be aborted.
+    fn on_breakpoint(&mut self, _ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+        Ok(DebugAction::Continue)
+    }
+
+    /// Called when a Wasm operator is about to access memory watched by a watchpoint.
+    ///
+    /// The `hit` describes the watched and the accessed memory ranges.
+    ///
+    /// # Errors
+    ///
+    /// If the execution shall be aborted.
+    fn on_watchpoint(
+        &mut self,
+        _ctx: &mut DebugContext,
+        _hit: &WatchpointHit,
+    ) -> Result<DebugAction, Error> {
+        Ok(DebugAction::Continue)
+    }
+
+    /// Called for every Wasm operator while single stepping.
+    ///
+    /// # Errors
+    ///
+    /// If the execution shall be aborted.
+    fn on_step(&mut self, _ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+        Ok(DebugAction::Continue)
+    }
+
+    /// Called when the execution traps with `error`.
+    ///
+    /// The `location` is the Wasm operator that caused the trap.
+    fn on_trap(&mut self, _location: DebugLocation, _error: &Error) {}
+}
+
+/// Tells the executor how to resume execution after a [`Debugger`] callback.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DebugAction {
+    /// Continues the execution until the next breakpoint is hit.
+    Continue,
+    /// Continues the execution until the next Wasm operator.
+    Step,
+}
+
+/// The location of a Wasm operator within an executed Wasm function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DebugLocation {
+    /// The [`Instance`] of the executed function.
+    instance: Instance,
+    /// The index of the executed function within its Wasm module.
+    func_index: u32,
+    /// The byte offset of the Wasm operator within the Wasm binary.
+    offset: u32,
+}
+
+impl DebugLocation {
+    /// Returns the [`Instance`] of the executed function.
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the index of the executed function within its Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the byte offset of the Wasm operator within the Wasm binary.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+/// The state of the paused Wasm execution provided to [`Debugger`] callbacks.
+pub struct DebugContext<'a> {
+    /// The location of the Wasm operator that is executed next.
+    location: DebugLocation,
+    /// The debug information of the executed function.
+    info: &'a FuncDebugInfo,
+    /// The index of the [`DebugPoint`] of the Wasm operator in `info`.
+    point: usize,
+    /// The registers of the executed function frame.
+    regs: &'a FrameRegisters,
+    /// The store owning the executed [`Instance`].
+    store: &'a StoreInner,
+    /// The compiled functions of the [`Engine`](crate::Engine).
+    code_map: &'a CodeMap,
+    /// All call frames on the call stack from the root frame to the executed function frame.
+    frames: &'a [StackFrame],
+    /// The breakpoints of the [`Store`](crate::Store).
+    breakpoints: &'a mut Breakpoints,
+    /// The watchpoints of the [`Store`](crate::Store).
+    watchpoints: &'a mut Watchpoints,
+}
+
+impl fmt::Debug for DebugContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugContext")
+            .field("location", &self.location)
+            .field("breakpoints", &self.breakpoints)
+            .field("watchpoints", &self.watchpoints)
+            .finish()
+    }
+}
+
+impl DebugContext<'_> {
+    /// Returns the location of the Wasm operator that is executed next.
+    pub fn location(&self) -> DebugLocation {
+        self.location
+    }
+
+    /// Returns the number of parameters and local variables of the executed function.
+    pub fn len_locals(&self) -> usize {
+        self.top_frame().len_locals()
+    }
+
+    /// Returns the value of the parameter or local variable at `index` if any.
+    ///
+    /// # Note
+    ///
+    /// Parameters come first followed by the local variables in order of their declaration.
+    pub fn local(&self, index: u32) -> Option<Val> {
+        self.top_frame().local(index)
+    }
+
+    /// Returns the number of values on the Wasm operand stack.
+    pub fn len_operands(&self) -> usize {
+        self.top_frame().len_operands()
+    }
+
+    /// Returns the untyped value on the Wasm operand stack at `index` if any.
+    ///
+    /// # Note
+    ///
+    /// The bottom-most operand has index 0.
+    pub fn operand(&self, index: usize) -> Option<UntypedVal> {
+        self.top_frame().operand(index)
+    }
+
+    /// Returns the Wasm function frame at `depth` of the call stack if any.
+    ///
+    /// # Note
+    ///
+    /// - The frame at depth 0 is the frame of the currently executed function.
+    /// - The frames are ordered the same as the locations of [`DebugContext::call_stack`].
+    pub fn frame(&self, depth: usize) -> Option<DebugFrame<'_>> {
+        match depth.checked_sub(1) {
+            None => Some(self.top_frame()),
+            Some(depth) => self.caller_frames().nth(depth),
+        }
+    }
+
+    /// Returns the value of the global variable at `index` of the executed [`Instance`] if any.
+    pub fn global(&self, index: u32) -> Option<Val> {
+        let global = self
+            .store
+            .resolve_instance(&self.location.instance)
+            .get_global(index)?;
+        Some(self.store.resolve_global(&global).get())
+    }
+
+    /// Returns the bytes of the linear memory at `index` of the executed [`Instance`] if any.
+    pub fn memory(&self, index: u32) -> Option<&[u8]> {
+        let memory = self
+            .store
+            .resolve_instance(&self.location.instance)
+            .get_memory(index)?;
+        Some(self.store.resolve_memory(&memory).data())
+    }
+
+    /// Returns the locations of all Wasm function frames on the call stack.
+    ///
+    /// # Note
+    ///
+    /// - The first location is the location of the currently executed function.
+    /// - The location of callers refers to their call operator.
+    pub fn call_stack(&self) -> Vec<DebugLocation> {
+        core::iter::once(self.location)
+            .chain(self.caller_frames().map(|frame| frame.location))
+            .collect()
+    }
+
+    /// Returns a shared reference to the breakpoints of the [`Store`](crate::Store).
+    pub fn breakpoints(&self) -> &Breakpoints {
+        self.breakpoints
+    }
+
+    /// Returns an exclusive reference to the breakpoints of the [`Store`](crate::Store).
+    pub fn breakpoints_mut(&mut self) -> &mut Breakpoints {
+        self.breakpoints
+    }
+
+    /// Returns a shared reference to the watchpoints of the [`Store`](crate::Store).
+    pub fn watchpoints(&self) -> &Watchpoints {
+        self.watchpoints
+    }
+
+    /// Returns an exclusive reference to the watchpoints of the [`Store`](crate::Store).
+    pub fn watchpoints_mut(&mut self) -> &mut Watchpoints {
+        self.watchpoints
+    }
+
+    /// Returns the [`DebugFrame`] of the executed function.
+    fn top_frame(&self) -> DebugFrame<'_> {
+        DebugFrame {
+            location: self.location,
+            info: self.info,
+            point: self.point,
+            regs: self.regs,
+        }
+    }
+
+    /// Returns the [`DebugFrame`]s of all callers of the executed function.
+    ///
+    /// # Note
+    ///
+    /// - The direct caller comes first.
+    /// - Call frames of functions compiled without debugging information are skipped.
+    fn caller_frames(&self) -> impl Iterator<Item = DebugFrame<'_>> {
+        self.frames.iter().rev().skip(1).filter_map(|frame| {
+            let info = self.code_map.debug_info(frame.func)?;
+            let point = frame.instr.and_then(|instr| info.find_instr(instr))?;
+            Some(DebugFrame {
+                location: DebugLocation {
+                    instance: frame.instance,
+                    func_index: info.func_index,
+                    offset: info.points[point].offset,
+                },
+                info,
+                point,
+                regs: &frame.regs,
+            })
+        })
+    }
+}
+
+/// A Wasm function frame on the call stack of a paused Wasm execution.
+///
+/// Returned by [`DebugContext::frame`].
+pub struct DebugFrame<'a> {
+    /// The location of the last executed Wasm operator of the frame.
+    location: DebugLocation,
+    /// The debug information of the function of the frame.
+    info: &'a FuncDebugInfo,
+    /// The index of the [`DebugPoint`] of the Wasm operator in `info`.
+    point: usize,
+    /// The registers of the frame.
+    regs: &'a FrameRegisters,
+}
+
+impl fmt::Debug for DebugFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugFrame")
+            .field("location", &self.location)
+            .finish()
+    }
+}
+
+impl DebugFrame<'_> {
+    /// Returns the location of the Wasm operator of the frame.
+    ///
+    /// # Note
+    ///
+    /// The location of callers refers to their call operator.
+    pub fn location(&self) -> DebugLocation {
+        self.location
+    }
+
+    /// Returns the number of parameters and local variables of the function of the frame.
+    pub fn len_locals(&self) -> usize {
+        self.info.locals.len()
+    }
+
+    /// Returns the value of the parameter or local variable at `index` if any.
+    ///
+    /// # Note
+    ///
+    /// Parameters come first followed by the local variables in order of their declaration.
+    pub fn local(&self, index: u32) -> Option<Val> {
+        let ty = *self.info.locals.get(index as usize)?;
+        let reg = Reg::try_from(index).ok()?;
+        Some(read_operand(self.regs, DebugOperand::Register(reg)).with_type(ty))
+    }
+
+    /// Returns the number of values on the Wasm operand stack of the frame.
+    pub fn len_operands(&self) -> usize {
+        self.info.operands(self.point).len()
+    }
+
+    /// Returns the untyped value on the Wasm operand stack of the frame at `index` if any.
+    ///
+    /// # Note
+    ///
+    /// The bottom-most operand has index 0.
+    pub fn operand(&self, index: usize) -> Option<UntypedVal> {
+        let operand = *self.info.operands(self.point).get(index)?;
+        Some(read_operand(self.regs, operand))
+    }
+}
+
+/// Returns the value of `operand` of the paused function frame with registers `regs`.
+fn read_operand(regs: &FrameRegisters, operand: DebugOperand) -> UntypedVal {
+    match operand {
+        // Safety: debugger callbacks and core dumps only read the function frame of
+        //         `regs` while it is paused and all registers recorded in its
+        //         [`FuncDebugInfo`] are guaranteed to be valid by the Wasmi translation.
+        DebugOperand::Register(reg) => unsafe { regs.get(reg) },
+        DebugOperand::Const(value) => value,
+    }
+}
+
+/// The set of breakpoints of a [`Store`](crate::Store).
+#[derive(Debug, Default)]
+pub struct Breakpoints {
+    /// The function and Wasm offset of all breakpoints.
+    points: BTreeSet<(EngineFunc, u32)>,
+}
+
+impl Breakpoints {
+    /// Inserts a breakpoint at the Wasm `offset` of the function at `func_index` in `module`.
+    ///
+    /// Returns `false` if `func_index` does not refer to a function defined by `module`.
+    ///
+    /// # Note
+    ///
+    /// The `offset` is the byte offset of a Wasm operator within the Wasm binary of `module`.
+    /// Breakpoints at other offsets are never hit.
+    pub fn insert(&mut self, module: &Module, func_index: u32, offset: u32) -> bool {
+        let Some(func) = module.get_engine_func(func_index) else {
+            return false;
+        };
+        self.points.insert((func, offset));
+        true
+    }
+
+    /// Removes the breakpoint at the Wasm `offset` of the function at `func_index` in `module`.
+    ///
+    /// Returns `true` if the breakpoint existed.
+    pub fn remove(&mut self, module: &Module, func_index: u32, offset: u32) -> bool {
+        let Some(func) = module.get_engine_func(func_index) else {
+            return false;
+        };
+        self.points.remove(&(func, offset))
+    }
+
+    /// Removes all breakpoints.
+    pub fn clear(&mut self) {
+        self.points.clear()
+    }
+
+    /// Returns `true` if there are no breakpoints.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns `true` if there is a breakpoint at `offset` of `func`.
+    fn contains(&self, func: EngineFunc, offset: u32) -> bool {
+        !self.points.is_empty() && self.points.contains(&(func, offset))
+    }
+}
+
+/// The kind of memory accesses observed by a watchpoint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Reads from the watched memory range.
+    Read,
+    /// Writes to the watched memory range.
+    Write,
+    /// Reads from and writes to the watched memory range.
+    ReadWrite,
+}
+
+impl WatchKind {
+    /// Returns `true` if a watchpoint of kind `self` observes accesses of `kind`.
+    fn observes(self, kind: WatchKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::ReadWrite, _) | (Self::Read, Self::Read) | (Self::Write, Self::Write)
+        )
+    }
+}
+
+/// A single watchpoint of [`Watchpoints`].
+#[derive(Debug, Clone)]
+struct Watchpoint {
+    /// The watched linear memory.
+    memory: Memory,
+    /// The watched byte range of `memory`.
+    range: Range<u64>,
+    /// The kind of observed memory accesses.
+    kind: WatchKind,
+}
+
+impl Watchpoint {
+    /// Returns `true` if `self` matches the `memory`, `range` and `kind`.
+    fn matches(&self, memory: &Memory, range: &Range<u64>, kind: WatchKind) -> bool {
+        self.memory.as_inner() == memory.as_inner() && self.range == *range && self.kind == kind
+    }
+}
+
+/// The set of watchpoints of a [`Store`](crate::Store).
+///
+/// # Note
+///
+/// Watchpoints observe Wasm loads and stores as well as the `memory.copy`,
+/// `memory.fill` and `memory.init` Wasm operators. Memory accesses by host
+/// functions are not observed.
+#[derive(Debug, Default)]
+pub struct Watchpoints {
+    /// All watchpoints in order of insertion.
+    points: Vec<Watchpoint>,
+}
+
+impl Watchpoints {
+    /// Inserts a watchpoint observing accesses of `kind` to the byte `range` of `memory`.
+    ///
+    /// Returns `false` if the same watchpoint already existed.
+    pub fn insert(&mut self, memory: Memory, range: Range<u64>, kind: WatchKind) -> bool {
+        if self
+            .points
+            .iter()
+            .any(|point| point.matches(&memory, &range, kind))
+        {
+            return false;
+        }
+        self.points.push(Watchpoint {
+            memory,
+            range,
+            kind,
+        });
+        true
+    }
+
+    /// Removes the watchpoint observing accesses of `kind` to the byte `range` of `memory`.
+    ///
+    /// Returns `true` if the watchpoint existed.
+    pub fn remove(&mut self, memory: Memory, range: Range<u64>, kind: WatchKind) -> bool {
+        let len_points = self.points.len();
+        self.points
+            .retain(|point| !point.matches(&memory, &range, kind));
+        self.points.len() != len_points
+    }
+
+    /// Removes all watchpoints.
+    pub fn clear(&mut self) {
+        self.points.clear()
+    }
+
+    /// Returns `true` if there are no watchpoints.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the first watchpoint hit by an access of `kind` to the byte `range` of `memory`.
+    fn find(&self, memory: Memory, range: Range<u64>, kind: WatchKind) -> Option<WatchpointHit> {
+        if range.is_empty() {
+            return None;
+        }
+        let point = self.points.iter().find(|point| {
+            point.memory.as_inner() == memory.as_inner()
+                && point.kind.observes(kind)
+                && point.range.start < range.end
+                && range.start < point.range.end
+        })?;
+        Some(WatchpointHit {
+            memory,
+            watched: point.range.clone(),
+            accessed: range,
+            kind,
+        })
+    }
+
+    /// Returns the first watchpoint hit by the memory `access` of a Wasm operator if any.
+    ///
+    /// The `operands` are the Wasm operand stack of the paused function frame with registers `regs`.
+    fn find_access(
+        &self,
+        store: &StoreInner,
+        instance: &Instance,
+        access: MemoryAccess,
+        operands: &[DebugOperand],
+        regs: &FrameRegisters,
+    ) -> Option<WatchpointHit> {
+        // Note: returns the operand at `depth` from the top of the Wasm operand stack
+        //       interpreted as linear memory address or length. Wasmi rejects Wasm
+        //       modules using the `memory64` proposal and thus the index type of all
+        //       linear memories is `i32`. Supporting `memory64` requires reading
+        //       the operands of 64-bit linear memories as `i64` instead.
+        let operand = |depth: usize| -> u64 {
+            let index = operands.len() - 1 - depth;
+            u64::from(u32::from(read_operand(regs, operands[index])))
+        };
+        let find = |memory: u32, start: u64, len: u64, kind: WatchKind| {
+            let memory = store.resolve_instance(instance).get_memory(memory)?;
+            self.find(memory, start..start + len, kind)
+        };
+        match access {
+            MemoryAccess::Load {
+                memory,
+                offset,
+                size,
+            } => find(memory, operand(0) + offset, size, WatchKind::Read),
+            MemoryAccess::Store {
+                memory,
+                offset,
+                size,
+            } => find(memory, operand(1) + offset, size, WatchKind::Write),
+            MemoryAccess::Copy { dst, src } => {
+                let len = operand(0);
+                find(src, operand(1), len, WatchKind::Read)
+                    .or_else(|| find(dst, operand(2), len, WatchKind::Write))
+            }
+            MemoryAccess::Fill { memory } | MemoryAccess::Init { memory } => {
+                find(memory, operand(2), operand(0), WatchKind::Write)
+            }
+        }
+    }
+}
+
+/// A memory access that hit a watchpoint reported via [`Debugger::on_watchpoint`].
+#[derive(Debug, Clone)]
+pub struct WatchpointHit {
+    /// The accessed linear memory.
+    memory: Memory,
+    /// The byte range watched by the watchpoint.
+    watched: Range<u64>,
+    /// The accessed byte range.
+    accessed: Range<u64>,
+    /// The kind of the memory access.
+    kind: WatchKind,
+}
+
+impl WatchpointHit {
+    /// Returns the accessed linear [`Memory`].
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Returns the byte range of the [`Memory`] watched by the watchpoint.
+    pub fn watched(&self) -> Range<u64> {
+        self.watched.clone()
+    }
+
+    /// Returns the byte range of the [`Memory`] accessed by the Wasm operator.
+    ///
+    /// # Note
+    ///
+    /// The accessed range is reported before the Wasm operator is executed and
+    /// thus might be out of bounds of the [`Memory`] in which case the Wasm
+    /// operator traps.
+    pub fn accessed(&self) -> Range<u64> {
+        self.accessed.clone()
+    }
+
+    /// Returns [`WatchKind::Read`] or [`WatchKind::Write`] for the kind of the memory access.
+    pub fn kind(&self) -> WatchKind {
+        self.kind
+    }
+}
+
+/// The debugging state of a [`Store`](crate::Store).
+#[derive(Default)]
+pub(crate) struct DebugState {
+    /// The installed [`Debugger`] if any.
+    debugger: Option<Box<dyn Debugger>>,
+    /// The breakpoints of the [`Store`](crate::Store).
+    breakpoints: Breakpoints,
+    /// The watchpoints of the [`Store`](crate::Store).
+    watchpoints: Watchpoints,
+    /// Is `true` if the [`Debugger`] is notified about the next Wasm operator.
+    stepping: bool,
+    /// Is `true` if Wasm functions are compiled with [`Config::debugging`] enabled.
+    debugging: bool,
+    /// Is `true` if a [`WasmCoreDump`] is captured when a Wasm execution traps.
+    coredump: bool,
+    /// Is `true` if a [`WasmBacktrace`](crate::WasmBacktrace) is captured when a Wasm execution traps.
+    backtrace: bool,
+    /// The last executed Wasm operator of all function frames on the call stack.
+    ///
+    /// Only tracked while a [`Debugger`] is installed.
+    frames: Vec<(Instance, EngineFunc, u32)>,
+}
+
+impl fmt::Debug for DebugState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugState")
+            .field("debugger", &self.debugger.is_some())
+            .field("breakpoints", &self.breakpoints)
+            .field("watchpoints", &self.watchpoints)
+            .field("stepping", &self.stepping)
+            .field("debugging", &self.debugging)
+            .field("coredump", &self.coredump)
+            .field("backtrace", &self.backtrace)
+            .finish()
+    }
+}
+
+impl DebugState {
+    /// Creates a new [`DebugState`] for a [`Store`](crate::Store) using `config`.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            debugging: config.get_debugging(),
+            coredump: config.get_coredump_on_trap(),
+            backtrace: config.get_wasm_backtrace(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if Wasm functions are compiled with [`Config::debugging`] enabled.
+    pub fn is_debugging_enabled(&self) -> bool {
+        self.debugging
+    }
+
+    /// Returns `true` if a [`WasmCoreDump`] is captured when a Wasm execution traps.
+    pub fn is_coredump_enabled(&self) -> bool {
+        self.coredump
+    }
+
+    /// Returns `true` if a [`WasmBacktrace`](crate::WasmBacktrace) is captured when a Wasm execution traps.
+    pub fn is_backtrace_enabled(&self) -> bool {
+        self.backtrace
+    }
+
+    /// Installs the [`Debugger`] and returns the previously installed one if any.
+    pub fn set_debugger(&mut self, debugger: Box<dyn Debugger>) -> Option<Box<dyn Debugger>> {
+        self.debugger.replace(debugger)
+    }
+
+    /// Removes the installed [`Debugger`] if any.
+    pub fn take_debugger(&mut self) -> Option<Box<dyn Debugger>> {
+        self.debugger.take()
+    }
+
+    /// Returns a shared reference to the [`Breakpoints`].
+    pub fn breakpoints(&self) -> &Breakpoints {
+        &self.breakpoints
+    }
+
+    /// Returns an exclusive reference to the [`Breakpoints`].
+    pub fn breakpoints_mut(&mut self) -> &mut Breakpoints {
+        &mut self.breakpoints
+    }
+
+    /// Returns a shared reference to the [`Watchpoints`].
+    pub fn watchpoints(&self) -> &Watchpoints {
+        &self.watchpoints
+    }
+
+    /// Returns an exclusive reference to the [`Watchpoints`].
+    pub fn watchpoints_mut(&mut self) -> &mut Watchpoints {
+        &mut self.watchpoints
+    }
+
+    /// Enables or disables single stepping.
+    pub fn set_stepping(&mut self, enable: bool) {
+        self.stepping = enable;
+    }
+
+    /// Notifies the installed [`Debugger`] that the Wasm operator at `offset` of `func` is executed next.
+    ///
+    /// - The `depth` is the number of call frames on the call stack including the frame of `func`.
+    /// - The `call_frames` yield the [`StackFrame`]s of all call frames on the call stack
+    ///   from the root frame to the frame of `func`. They are only collected if the
+    ///   [`Debugger`] is notified.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Debugger`] aborts the execution.
+    #[allow(clippy::too_many_arguments)]
+    pub fn step(
+        &mut self,
+        store: &StoreInner,
+        code_map: &CodeMap,
+        instance: Instance,
+        func: EngineFunc,
+        offset: u32,
+        depth: usize,
+        regs: &FrameRegisters,
+        call_frames: impl IntoIterator<Item = StackFrame>,
+    ) -> Result<(), Error> {
+        if self.debugger.is_none() {
+            return Ok(());
+        }
+        // Note: the frames of all callers remain at their last executed
+        //       Wasm operator which is the call operator that called into
+        //       the currently executed function.
+        self.frames.truncate(depth.saturating_sub(1));
+        self.frames.push((instance, func, offset));
+        let Some(debugger) = self.debugger.as_mut() else {
+            return Ok(());
+        };
+        let is_breakpoint = self.breakpoints.contains(func, offset);
+        if !self.stepping && !is_breakpoint && self.watchpoints.is_empty() {
+            return Ok(());
+        }
+        let Some(info) = code_map.debug_info(func) else {
+            return Ok(());
+        };
+        let Some(point) = info.find(offset) else {
+            return Ok(());
+        };
+        let hit = match self.watchpoints.is_empty() {
+            true => None,
+            false => info.points[point].access.and_then(|access| {
+                let operands = info.operands(point);
+                self.watchpoints
+                    .find_access(store, &instance, access, operands, regs)
+            }),
+        };
+        if !self.stepping && !is_breakpoint && hit.is_none() {
+            return Ok(());
+        }
+        let frames = call_frames.into_iter().collect::<Vec<_>>();
+        let mut ctx = DebugContext {
+            location: DebugLocation {
+                instance,
+                func_index: info.func_index,
+                offset,
+            },
+            info,
+            point,
+            regs,
+            store,
+            code_map,
+            frames: &frames,
+            breakpoints: &mut self.breakpoints,
+            watchpoints: &mut self.watchpoints,
+        };
+        let action = match (hit, is_breakpoint) {
+            (Some(hit), _) => debugger.on_watchpoint(&mut ctx, &hit)?,
+            (None, true) => debugger.on_breakpoint(&mut ctx)?,
+            (None, false) => debugger.on_step(&mut ctx)?,
+        };
+        self.stepping = matches!(action, DebugAction::Step);
+        Ok(())
+    }
+
+    /// Handles the trap of a Wasm execution with `error`.
+    ///
+    /// The `call_frames` yield the [`StackFrame`]s of all call frames on the
+    /// call stack from the root frame to the trapping frame.
+    ///
+    /// # Note
+    ///
+    /// - Attaches a [`WasmCoreDump`] to `error` if core dumps are enabled.
+    /// - The [`Debugger`] is notified only once per trap even if the trap
+    ///   propagates through multiple nested Wasm executions.
+    pub fn trap(
+        &mut self,
+        store: &StoreInner,
+        code_map: &CodeMap,
+        call_frames: impl IntoIterator<Item = StackFrame>,
+        error: &mut Error,
+    ) {
+        if self.coredump && error.i32_exit_status().is_none() && error.coredump().is_none() {
+            let coredump = Self::coredump(store, code_map, call_frames);
+            error.set_coredump(coredump);
+        }
+        let Some(&(instance, func, offset)) = self.frames.last() else {
+            return;
+        };
+        self.frames.clear();
+        let Some(debugger) = self.debugger.as_mut() else {
+            return;
+        };
+        let Some(info) = code_map.debug_info(func) else {
+            return;
+        };
+        let location = DebugLocation {
+            instance,
+            func_index: info.func_index,
+            offset,
+        };
+        debugger.on_trap(location, error);
+    }
+
+    /// Captures a [`WasmCoreDump`] of the trapped Wasm execution from its `call_frames`.
+    ///
+    /// # Note
+    ///
+    /// Call frames of functions compiled without debugging information are skipped.
+    fn coredump(
+        store: &StoreInner,
+        code_map: &CodeMap,
+        call_frames: impl IntoIterator<Item = StackFrame>,
+    ) -> WasmCoreDump {
+        let frames = call_frames.into_iter().collect::<Vec<_>>();
+        let mut coredump = WasmCoreDump::default();
+        for frame in frames.into_iter().rev() {
+            let Some(info) = code_map.debug_info(frame.func) else {
+                continue;
+            };
+            let Some(point) = frame.instr.and_then(|instr| info.find_instr(instr)) else {
+                continue;
+            };
+            let regs = &frame.regs;
+            let locals = info
+                .locals
+                .iter()
+                .enumerate()
+                .map(|(index, &ty)| {
+                    let reg = Reg::try_from(index as u32).ok()?;
+                    Some(read_operand(regs, DebugOperand::Register(reg)).with_type(ty))
+                })
+                .collect();
+            let stack = info
+                .operands(point)
+                .iter()
+                .zip(info.operand_types(point))
+                .map(|(&operand, &ty)| Some(read_operand(regs, operand).with_type(ty?)))
+                .collect();
+            let offset = info.points[point].offset;
+            coredump.push_frame(
+                store,
+                frame.instance,
+                info.func_index,
+                offset,
+                offset - info.body_offset,
+                locals,
+                stack,
+            );
+        }
+        coredump
+    }
+}
+
+/// A call frame on the call stack of a paused or trapped Wasm execution.
+pub(crate) struct StackFrame {
+    /// The [`Instance`] of the executed function.
+    pub instance: Instance,
+    /// The executed function.
+    pub func: EngineFunc,
+    /// The position of the executed instruction within the compiled function if known.
+    pub instr: Option<usize>,
+    /// The registers of the call frame.
+    pub regs: FrameRegisters,
+}
+
+/// The debug information of a compiled Wasm function.
+#[derive(Debug)]
+pub(crate) struct FuncDebugInfo {
+    /// The index of the function within its Wasm module.
+    func_index: u32,
+    /// The byte offset of the function body within the Wasm binary.
+    body_offset: u32,
+    /// The types of the function parameters and local variables in order.
+    locals: Box<[ValType]>,
+    /// The debug information of all translated Wasm operators ordered by offset.
+    points: Box<[DebugPoint]>,
+    /// The Wasm operand stack of all `points`.
+    operands: Box<[DebugOperand]>,
+    /// The types of all `operands` if known.
+    operand_types: Box<[Option<ValType>]>,
+}
+
+impl FuncDebugInfo {
+    /// Returns the index of the [`DebugPoint`] at `offset` if any.
+    fn find(&self, offset: u32) -> Option<usize> {
+        self.points
+            .binary_search_by_key(&offset, |point| point.offset)
+            .ok()
+    }
+
+    /// Returns the index of the [`DebugPoint`] that produced the instruction at `instr` if any.
+    fn find_instr(&self, instr: usize) -> Option<usize> {
+        self.points
+            .partition_point(|point| point.instr as usize <= instr)
+            .checked_sub(1)
+    }
+
+    /// Returns the Wasm operand stack of the [`DebugPoint`] at index `point`.
+    fn operands(&self, point: usize) -> &[DebugOperand] {
+        &self.operands[self.operands_range(point)]
+    }
+
+    /// Returns the types of the Wasm operand stack of the [`DebugPoint`] at index `point`.
+    fn operand_types(&self, point: usize) -> &[Option<ValType>] {
+        &self.operand_types[self.operands_range(point)]
+    }
+
+    /// Returns the range of the Wasm operand stack of the [`DebugPoint`] at index `point`.
+    fn operands_range(&self, point: usize) -> Range<usize> {
+        let point = &self.points[point];
+        let start = point.operands as usize;
+        let end = start + point.len_operands as usize;
+        start..end
+    }
+}
+
+/// The debug information of a single translated Wasm operator.
+#[derive(Debug, Copy, Clone)]
+struct DebugPoint {
+    /// The byte offset of the Wasm operator within the Wasm binary.
+    offset: u32,
+    /// The position of the first instruction of the Wasm operator.
+    instr: u32,
+    /// The index of the first operand in [`FuncDebugInfo::operands`].
+    operands: u32,
+    /// The number of values on the Wasm operand stack.
+    len_operands: u32,
+    /// The linear memory access of the Wasm operator if any.
+    access: Option<MemoryAccess>,
+}
+
+/// The linear memory access of a Wasm operator observed by watchpoints.
+///
+/// The accessed addresses and lengths are taken from the Wasm operand stack.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum MemoryAccess {
+    /// Reads `size` bytes at the address on top of the Wasm operand stack plus `offset`.
+    Load { memory: u32, offset: u64, size: u64 },
+    /// Writes `size` bytes at the address below the stored value plus `offset`.
+    Store { memory: u32, offset: u64, size: u64 },
+    /// A Wasm `memory.copy` from memory `src` to memory `dst`.
+    Copy { dst: u32, src: u32 },
+    /// A Wasm `memory.fill` of `memory`.
+    Fill { memory: u32 },
+    /// A Wasm `memory.init` of `memory`.
+    Init { memory: u32 },
+}
+
+impl MemoryAccess {
+    /// Creates the [`MemoryAccess`] of a Wasm load with `memarg`.
+    pub fn load(memarg: MemArg) -> Self {
+        Self::Load {
+            memory: memarg.memory,
+            offset: memarg.offset,
+            size: Self::size(memarg),
+        }
+    }
+
+    /// Creates the [`MemoryAccess`] of a Wasm store with `memarg`.
+    pub fn store(memarg: MemArg) -> Self {
+        Self::Store {
+            memory: memarg.memory,
+            offset: memarg.offset,
+            size: Self::size(memarg),
+        }
+    }
+
+    /// Returns the number of accessed bytes of a Wasm load or store with `memarg`.
+    fn size(memarg: MemArg) -> u64 {
+        // Note: the natural alignment of Wasm loads and stores equals their size.
+        1 << memarg.max_align
+    }
+}
+
+/// The location of a value on the Wasm operand stack.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum DebugOperand {
+    /// The value is stored in a register.
+    Register(Reg),
+    /// The value is a constant.
+    Const(UntypedVal),
+}
+
+/// Incrementally constructs the [`FuncDebugInfo`] during function translation.
+#[derive(Debug, Default)]
+pub(crate) struct FuncDebugInfoBuilder {
+    /// The byte offset of the function body within the Wasm binary.
+    body_offset: u32,
+    /// The types of the function parameters and local variables in order.
+    locals: Vec<ValType>,
+    /// The debug information of all translated Wasm operators.
+    points: Vec<DebugPoint>,
+    /// The Wasm operand stack of all `points`.
+    operands: Vec<DebugOperand>,
+    /// The types of all `operands` if known.
+    operand_types: Vec<Option<ValType>>,
+}
+
+impl FuncDebugInfoBuilder {
+    /// Resets the [`FuncDebugInfoBuilder`] for reuse.
+    pub fn reset(&mut self) {
+        self.body_offset = 0;
+        self.locals.clear();
+        self.points.clear();
+        self.operands.clear();
+        self.operand_types.clear();
+    }
+
+    /// Registers the byte offset of the function body within the Wasm binary.
+    pub fn set_body_offset(&mut self, offset: u32) {
+        self.body_offset = offset;
+    }
+
+    /// Registers `amount` parameters or local variables of type `ty`.
+    pub fn push_locals(&mut self, amount: u32, ty: ValType) {
+        self.locals
+            .extend(core::iter::repeat(ty).take(amount as usize));
+    }
+
+    /// Registers the Wasm operator at `offset` with its Wasm operand stack.
+    ///
+    /// - The `instr` is the position of the first instruction of the Wasm operator.
+    /// - The type of every operand is `None` if unknown.
+    pub fn push_point(
+        &mut self,
+        offset: u32,
+        instr: u32,
+        operands: impl IntoIterator<Item = (DebugOperand, Option<ValType>)>,
+    ) {
+        let start = self.operands.len();
+        for (operand, ty) in operands {
+            self.operands.push(operand);
+            self.operand_types.push(ty);
+        }
+        let len_operands = self.operands.len() - start;
+        self.points.push(DebugPoint {
+            offset,
+            instr,
+            operands: start as u32,
+            len_operands: len_operands as u32,
+            access: None,
+        });
+    }
+
+    /// Registers the linear memory `access` of the last registered Wasm operator.
+    pub fn push_access(&mut self, access: MemoryAccess) {
+        if let Some(point) = self.points.last_mut() {
+            point.access = Some(access);
+        }
+    }
+
+    /// Moves the recorded instruction positions via `remap` after instructions have been removed.
+    pub fn remap(&mut self, mut remap: impl FnMut(u32) -> u32) {
+        for point in &mut self.points {
+            point.instr = remap(point.instr);
+        }
+    }
+
+    /// Finishes the [`FuncDebugInfo`] for the function at `func_index`.
+    ///
+    /// Remaps all recorded registers via `defrag` since translation may move registers around.
+    pub fn finish(&mut self, func_index: u32, mut defrag: impl FnMut(Reg) -> Reg) -> FuncDebugInfo {
+        for operand in &mut self.operands {
+            if let DebugOperand::Register(reg) = operand {
+                *reg = defrag(*reg);
+            }
+        }
+        FuncDebugInfo {
+            func_index,
+            body_offset: self.body_offset,
+            locals: self.locals.drain(..).collect(),
+            points: self.points.drain(..).collect(),
+            operands: self.operands.drain(..).collect(),
+            operand_types: self.operand_types.drain(..).collect(),
+        }
+    }
+}
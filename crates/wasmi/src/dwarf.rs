@@ -0,0 +1,101 @@
+//! Reads the DWARF debug information of Wasm modules via [`addr2line`].
+//!
+//! Following the WebAssembly DWARF conventions code addresses are offsets
+//! relative to the start of the contents of the Wasm code section. Line number
+//! programs are found via the compilation units of the `.debug_info` section.
+//!
+//! Malformed DWARF sections are not an error: Wasm offsets whose
+//! DWARF debug information cannot be read have no source frames.
+
+use crate::{
+    backtrace::{SourceFrame, SourceLocation},
+    module::CustomSections,
+};
+use addr2line::{
+    gimli::{Dwarf, EndianArcSlice, LittleEndian, SectionId},
+    Context,
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
+use spin::Mutex;
+
+/// The reader of the DWARF sections of a Wasm module.
+type Reader = EndianArcSlice<LittleEndian>;
+
+/// Maps Wasm offsets to source locations and inlined functions via DWARF.
+pub struct DwarfInfo {
+    /// The offset of the contents of the Wasm code section within the Wasm binary.
+    code_offset: u32,
+    /// The lazily parsed DWARF debug information of the Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The [`Context`] parses the DWARF debug information upon first use
+    /// which is not synchronized. Therefore it must not be shared between threads.
+    context: Mutex<Context<Reader>>,
+}
+
+impl fmt::Debug for DwarfInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DwarfInfo")
+            .field("code_offset", &self.code_offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DwarfInfo {
+    /// Reads the DWARF debug information from the `custom_sections` of a Wasm module.
+    ///
+    /// The `code_offset` is the offset of the contents of the Wasm code section.
+    ///
+    /// Returns `None` if there is no DWARF debug information.
+    pub fn new(code_offset: u32, custom_sections: &CustomSections) -> Option<Self> {
+        let section = |id: SectionId| {
+            custom_sections
+                .iter()
+                .find(|section| section.name() == id.name())
+                .map(|section| section.data())
+        };
+        section(SectionId::DebugInfo)?;
+        let dwarf = Dwarf::load(|id| -> Result<Reader, addr2line::gimli::Error> {
+            let data = section(id).unwrap_or_default();
+            Ok(Reader::new(Arc::from(data), LittleEndian))
+        })
+        .ok()?;
+        let context = Context::from_dwarf(dwarf).ok()?;
+        Some(Self {
+            code_offset,
+            context: Mutex::new(context),
+        })
+    }
+
+    /// Returns the source frames of the Wasm operator at `offset` within the Wasm binary.
+    ///
+    /// The innermost inlined function comes first and the function
+    /// that contains the Wasm operator comes last.
+    pub fn source_frames(&self, offset: u32) -> Vec<SourceFrame> {
+        let mut frames = Vec::new();
+        let Some(address) = offset.checked_sub(self.code_offset) else {
+            return frames;
+        };
+        let context = self.context.lock();
+        let Ok(mut iter) = context.find_frames(u64::from(address)).skip_all_loads() else {
+            return frames;
+        };
+        while let Ok(Some(frame)) = iter.next() {
+            let function = frame
+                .function
+                .as_ref()
+                .and_then(|name| name.demangle().ok())
+                .map(Into::into);
+            let location = frame.location.and_then(|location| {
+                let file = location.file?;
+                let line = location.line.filter(|&line| line != 0)?;
+                let column = location.column.filter(|&column| column != 0);
+                Some(SourceLocation::new(file.into(), line, column))
+            });
+            frames.push(SourceFrame::new(function, location));
+        }
+        frames
+    }
+}
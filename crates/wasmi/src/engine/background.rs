@@ -0,0 +1,78 @@
+//! Compilation of lazily compiled Wasm functions on a background thread.
+//!
+//! # Note
+//!
+//! Each [`Engine`] owns at most one background compilation thread which is
+//! spawned upon the first [`Module`] creation that requests it. The thread
+//! compiles the queued functions in order and exits when the [`Engine`] is dropped.
+//!
+//! [`Module`]: crate::Module
+
+use super::{Engine, EngineFuncSpan, EngineWeak};
+use std::sync::mpsc::{channel, Sender};
+
+/// The state of the background compilation thread of an [`Engine`].
+#[derive(Debug, Default)]
+pub enum BackgroundCompiler {
+    /// No background compilation thread has been spawned, yet.
+    #[default]
+    Idle,
+    /// The background compilation thread is running and compiles the sent functions.
+    Running(Sender<EngineFuncSpan>),
+    /// The background compilation thread could not be spawned or has stopped.
+    ///
+    /// Functions are compiled on demand as usual.
+    Unavailable,
+}
+
+impl BackgroundCompiler {
+    /// Queues `funcs` of `engine` for compilation on the background thread.
+    ///
+    /// Spawns the background thread if it is not running, yet.
+    ///
+    /// # Note
+    ///
+    /// If the background thread cannot be spawned this is recorded so that no further
+    /// attempts are made and the `funcs` are compiled on demand as usual.
+    pub fn compile(&mut self, engine: &Engine, funcs: EngineFuncSpan) {
+        if let Self::Idle = self {
+            *self = Self::spawn(engine.weak());
+        }
+        let Self::Running(sender) = self else {
+            return;
+        };
+        if sender.send(funcs).is_err() {
+            // Note: the background thread only stops before its `Engine` is dropped if it panicked.
+            *self = Self::Unavailable;
+        }
+    }
+
+    /// Spawns the background compilation thread for the `engine`.
+    fn spawn(engine: EngineWeak) -> Self {
+        let (sender, receiver) = channel::<EngineFuncSpan>();
+        let spawned = std::thread::Builder::new()
+            .name("wasmi-background-compilation".into())
+            .spawn(move || {
+                // Note: the loop ends once the `Engine` and thus the `Sender` are dropped.
+                for funcs in receiver {
+                    for func in funcs.iter() {
+                        let Some(engine) = engine.upgrade() else {
+                            // Note: nobody is able to call `funcs` anymore.
+                            return;
+                        };
+                        engine.inner.code_map.compile_in_background(func);
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => Self::Running(sender),
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%error, "failed to spawn background compilation thread");
+                #[cfg(not(feature = "tracing"))]
+                let _ = error;
+                Self::Unavailable
+            }
+        }
+    }
+}
@@ -5,6 +5,10 @@
 //! This is the data structure specialized to handle compiled
 //! register machine based bytecode functions.
 
+#[cfg(feature = "threaded-dispatch")]
+use super::executor::{decode_handlers, Handler};
+#[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+use super::jit::{JitEntry, JitFunc};
 use super::{
     FuelCosts,
     FuncTranslationDriver,
@@ -12,23 +16,31 @@ use super::{
     TranslationError,
     ValidatingFuncTranslator,
 };
+#[cfg(feature = "dwarf")]
+use crate::dwarf::DwarfInfo;
 use crate::{
+    backtrace::FuncOffsets,
     collections::arena::{Arena, ArenaIndex},
     core::{TrapCode, UntypedVal},
+    debugger::FuncDebugInfo,
     engine::utils::unreachable_unchecked,
     ir::{index::InternalFunc, Instruction},
-    module::{FuncIdx, ModuleHeader},
+    module::{FuncIdx, ModuleHeader, ModuleNames},
+    stats::{EngineStats, Stopwatch},
     store::{Fuel, FuelError},
     Config,
     Error,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{
     fmt,
     mem::{self, MaybeUninit},
     ops::{self, Range},
     pin::Pin,
+    ptr::NonNull,
     slice,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
 };
 use spin::Mutex;
 use wasmparser::{FuncToValidate, ValidatorResources, WasmFeatures};
@@ -79,6 +91,84 @@ impl ArenaIndex for EngineFunc {
 pub struct CodeMap {
     funcs: Mutex<Arena<EngineFunc, FuncEntity>>,
     features: WasmFeatures,
+    /// The [`ModuleSymbols`] of Wasm modules by the functions they define.
+    symbols: Mutex<Vec<(EngineFuncSpan, ModuleSymbols)>>,
+    /// The [`EngineStats`] of the compiled and uncompiled functions.
+    stats: Mutex<EngineStats>,
+    /// The number of calls and loop iterations after which a function is tiered up.
+    tier_up_threshold: u32,
+    /// The [`CompiledFuncEntity`]s of the baseline tier replaced by tiered up functions.
+    ///
+    /// # Note
+    ///
+    /// - These are kept alive since running executions might still execute them
+    ///   or count their [`Hotness`].
+    /// - Every function is tiered up at most once. Therefore this holds at most one
+    ///   [`CompiledFuncEntity`] per function and is freed together with the [`CodeMap`].
+    retired: Mutex<Vec<CompiledFuncEntity>>,
+    /// Wakes up threads waiting for functions to be initialized during parallel translation.
+    #[cfg(feature = "parallel")]
+    initialized: InitSignal,
+}
+
+/// Signals the initialization of functions to threads waiting for them.
+///
+/// # Note
+///
+/// Waiting threads check the state of their function while holding `lock` so
+/// that an initialization in between the check and the wait is never missed.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Default)]
+struct InitSignal {
+    lock: std::sync::Mutex<()>,
+    cond: std::sync::Condvar,
+}
+
+#[cfg(feature = "parallel")]
+impl InitSignal {
+    /// Locks the [`InitSignal`] before checking whether a function has been initialized.
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Waits until the next function has been initialized.
+    fn wait<'a>(&self, guard: std::sync::MutexGuard<'a, ()>) -> std::sync::MutexGuard<'a, ()> {
+        self.cond
+            .wait(guard)
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Wakes up all threads waiting for functions to be initialized.
+    fn notify(&self) {
+        drop(self.lock());
+        self.cond.notify_all();
+    }
+}
+
+/// The debug symbols of a Wasm module used to symbolicate Wasm backtraces.
+#[derive(Debug, Clone)]
+pub struct ModuleSymbols {
+    /// The names of the `name` custom section of the Wasm module.
+    pub names: Arc<ModuleNames>,
+    /// The number of imported functions of the Wasm module.
+    #[cfg(feature = "tracing")]
+    pub len_func_imports: u32,
+    /// The DWARF debug information of the Wasm module if any.
+    #[cfg(feature = "dwarf")]
+    pub dwarf: Option<Arc<DwarfInfo>>,
+}
+
+impl ModuleSymbols {
+    /// Returns `true` if the [`ModuleSymbols`] contain any debug symbols.
+    pub fn has_symbols(&self) -> bool {
+        #[cfg(feature = "dwarf")]
+        if self.dwarf.is_some() {
+            return true;
+        }
+        !self.names.is_empty()
+    }
 }
 
 /// A range of [`EngineFunc`]s with contiguous indices.
@@ -221,9 +311,30 @@ impl CodeMap {
         Self {
             funcs: Mutex::new(Arena::default()),
             features: config.wasm_features(),
+            symbols: Mutex::new(Vec::new()),
+            stats: Mutex::new(EngineStats::default()),
+            tier_up_threshold: config.get_tier_up_threshold().max(1),
+            retired: Mutex::new(Vec::new()),
+            #[cfg(feature = "parallel")]
+            initialized: InitSignal::default(),
         }
     }
 
+    /// Returns the [`EngineStats`] of the compiled and uncompiled functions.
+    pub fn stats(&self) -> EngineStats {
+        *self.stats.lock()
+    }
+
+    /// Accounts for `duration` spent translating a Wasm function.
+    pub fn add_translation_time(&self, duration: Duration) {
+        self.stats.lock().add_translation_time(duration);
+    }
+
+    /// Accounts for `amount` calls inlined into their callers.
+    pub fn add_inlined_calls(&self, amount: usize) {
+        self.stats.lock().add_inlined_calls(amount);
+    }
+
     /// Allocates `amount` new uninitialized [`EngineFunc`] to the [`CodeMap`].
     ///
     /// # Note
@@ -248,7 +359,38 @@ impl CodeMap {
         let Some(func) = funcs.get_mut(func) else {
             panic!("encountered invalid internal function: {func:?}")
         };
+        self.stats
+            .lock()
+            .add_compiled(entity.bytecode_bytes(), entity.const_bytes());
         func.init_compiled(entity);
+        #[cfg(feature = "parallel")]
+        {
+            drop(funcs);
+            self.initialized.notify();
+        }
+    }
+
+    /// Initializes the [`EngineFunc`] as failed to compile.
+    ///
+    /// # Note
+    ///
+    /// Used for functions that failed, panicked or were skipped during parallel eager
+    /// translation so that no function translated in parallel waits for them.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`EngineFunc`] reference for this [`CodeMap`].
+    #[cfg(feature = "parallel")]
+    pub fn init_func_as_failed(&self, func: EngineFunc) {
+        let mut funcs = self.funcs.lock();
+        let Some(func) = funcs.get_mut(func) else {
+            panic!("encountered invalid internal function: {func:?}")
+        };
+        if matches!(func, FuncEntity::Uninit) {
+            *func = FuncEntity::FailedToCompile;
+        }
+        drop(funcs);
+        self.initialized.notify();
     }
 
     /// Initializes the [`EngineFunc`] for lazy translation.
@@ -261,6 +403,7 @@ impl CodeMap {
         &self,
         func: EngineFunc,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -271,10 +414,12 @@ impl CodeMap {
         };
         func.init_uncompiled(UncompiledFuncEntity::new(
             func_idx,
+            offset,
             bytes,
             module.clone(),
             func_to_validate,
         ));
+        self.stats.lock().add_uncompiled();
     }
 
     /// Returns the [`FuncEntity`] of the [`EngineFunc`].
@@ -309,15 +454,47 @@ impl CodeMap {
         fuel: Option<&mut Fuel>,
         func: EngineFunc,
     ) -> Result<CompiledFuncRef<'a>, Error> {
-        match self.get_uncompiled(func) {
-            Some(entity) => self.compile(fuel, func, entity),
-            None => self.wait_for_compilation(func),
+        loop {
+            if let Some(entity) = self.get_uncompiled(func) {
+                return self.compile(fuel, func, entity);
+            }
+            if let Some(entity) = self.get_tier_up(func) {
+                return self.tier_up(func, entity);
+            }
+            if let Some(result) = self.wait_for_compilation(func) {
+                return result;
+            }
         }
     }
 
+    /// Counts a call or loop iteration of the compiled `func` with `hotness` for tiering.
+    ///
+    /// Once `func` reaches the tier-up threshold it is tiered up upon its next use.
+    ///
+    /// # Note
+    ///
+    /// This locks the [`CodeMap`] only once when `func` reaches the tier-up threshold.
+    #[inline]
+    pub fn count_tier_up(&self, func: EngineFunc, hotness: Hotness) {
+        if hotness.count() == self.tier_up_threshold {
+            self.mark_hot(func);
+        }
+    }
+
+    /// Marks the compiled `func` as hot so that it is tiered up upon its next use.
+    #[cold]
+    #[inline(never)]
+    fn mark_hot(&self, func: EngineFunc) {
+        let mut funcs = self.funcs.lock();
+        let Some(FuncEntity::Compiled(entity)) = funcs.get_mut(func) else {
+            return;
+        };
+        entity.mark_hot();
+    }
+
     /// Returns the [`CompiledFuncRef`] of `func` if possible, otherwise returns `None`.
     #[inline]
-    fn get_compiled(&self, func: EngineFunc) -> Option<CompiledFuncRef> {
+    pub fn get_compiled(&self, func: EngineFunc) -> Option<CompiledFuncRef> {
         let funcs = self.funcs.lock();
         let Some(entity) = funcs.get(func) else {
             // Safety: this is just called internally with function indices
@@ -331,6 +508,92 @@ impl CodeMap {
         Some(self.adjust_cref_lifetime(cref))
     }
 
+    /// Returns the [`InlinableFuncRef`] of `func` if it has been compiled and can be inlined.
+    pub fn get_inlinable(&self, func: EngineFunc) -> Option<InlinableFuncRef<'_>> {
+        let funcs = self.funcs.lock();
+        let FuncEntity::Compiled(entity) = funcs.get(func)? else {
+            return None;
+        };
+        let len_locals = entity.inlining?;
+        let cref = self.adjust_cref_lifetime(CompiledFuncRef::from(entity));
+        Some(InlinableFuncRef { cref, len_locals })
+    }
+
+    /// Returns the [`InlinableFuncRef`] of `func` once it has been initialized.
+    ///
+    /// # Note
+    ///
+    /// Blocks while `func` is still uninitialized which is the case if it is
+    /// translated by another thread during parallel eager translation.
+    pub fn wait_for_inlinable(&self, func: EngineFunc) -> Option<InlinableFuncRef<'_>> {
+        #[cfg(feature = "parallel")]
+        self.wait_for_init(func);
+        self.get_inlinable(func)
+    }
+
+    /// Blocks until `func` has been initialized by another thread.
+    #[cfg(feature = "parallel")]
+    fn wait_for_init(&self, func: EngineFunc) {
+        let mut signal = self.initialized.lock();
+        while matches!(self.funcs.lock().get(func), Some(FuncEntity::Uninit)) {
+            signal = self.initialized.wait(signal);
+        }
+    }
+
+    /// Returns the [`FuncDebugInfo`] of the compiled `func` if any.
+    pub fn debug_info(&self, func: EngineFunc) -> Option<&FuncDebugInfo> {
+        let funcs = self.funcs.lock();
+        let FuncEntity::Compiled(entity) = funcs.get(func)? else {
+            return None;
+        };
+        let debug_info: &FuncDebugInfo = entity.debug_info.as_deref()?;
+        // Safety: we cast the lifetime of `debug_info` to match `&self` instead of the inner
+        //         `MutexGuard` which is safe because `CodeMap` is append-only, compiled
+        //         functions are never dropped and the [`FuncDebugInfo`] is boxed.
+        Some(unsafe { &*(debug_info as *const FuncDebugInfo) })
+    }
+
+    /// Registers the [`ModuleSymbols`] of the Wasm module that defines the `funcs`.
+    pub fn register_symbols(&self, funcs: EngineFuncSpan, symbols: ModuleSymbols) {
+        self.symbols.lock().push((funcs, symbols));
+    }
+
+    /// Returns the [`ModuleSymbols`] of the Wasm module that defines `func` if any.
+    pub fn symbols(&self, func: EngineFunc) -> Option<ModuleSymbols> {
+        self.symbols
+            .lock()
+            .iter()
+            .find(|(funcs, _)| funcs.position(func).is_some())
+            .map(|(_, symbols)| symbols.clone())
+    }
+
+    /// Returns the names of `func` and of the Wasm module that defines it if any.
+    #[cfg(feature = "tracing")]
+    pub fn func_names(&self, func: EngineFunc) -> (Option<Box<str>>, Option<Box<str>>) {
+        let symbols = self.symbols.lock();
+        let Some((position, symbols)) = symbols
+            .iter()
+            .find_map(|(funcs, symbols)| Some((funcs.position(func)?, symbols)))
+        else {
+            return (None, None);
+        };
+        let func_index = symbols.len_func_imports + position;
+        let module = symbols.names.module().map(Box::from);
+        let func = symbols.names.func(func_index).map(Box::from);
+        (module, func)
+    }
+
+    /// Returns the Wasm offsets of the basic blocks of the compiled `func` indexed by their coverage counter.
+    ///
+    /// Returns `None` if `func` has not been compiled with code coverage enabled.
+    pub fn coverage(&self, func: EngineFunc) -> Option<Box<[u32]>> {
+        let funcs = self.funcs.lock();
+        let FuncEntity::Compiled(entity) = funcs.get(func)? else {
+            return None;
+        };
+        entity.coverage.clone()
+    }
+
     /// Returns the [`UncompiledFuncEntity`] of `func` if possible, otherwise returns `None`.
     ///
     /// After this operation `func` will be in [`FuncEntity::Compiling`] state.
@@ -343,6 +606,18 @@ impl CodeMap {
         entity.get_uncompiled()
     }
 
+    /// Returns the Wasm function body of `func` to re-translate if `func` is hot.
+    ///
+    /// After this operation `func` is no longer hot and thus tiered up at most once.
+    #[inline]
+    fn get_tier_up(&self, func: EngineFunc) -> Option<UncompiledFuncEntity> {
+        let mut funcs = self.funcs.lock();
+        let Some(entity) = funcs.get_mut(func) else {
+            panic!("encountered invalid internal function: {func:?}")
+        };
+        entity.get_tier_up()
+    }
+
     /// Prolongs the lifetime of `cref` to `self`.
     ///
     /// # Safety
@@ -351,6 +626,7 @@ impl CodeMap {
     ///
     /// - [`CompiledFuncRef`] only references `Pin`ned data
     /// - [`CodeMap`] is an append-only data structure
+    /// - [`CompiledFuncEntity`]s replaced by tiering are retired instead of dropped
     ///
     /// Thus any shared [`CompiledFuncRef`] can safely outlive the internal `Mutex` lock.
     #[inline]
@@ -376,13 +652,18 @@ impl CodeMap {
     ) -> Result<CompiledFuncRef<'a>, Error> {
         // Note: it is important that compilation happens without locking the `CodeMap`
         //       since compilation can take a prolonged time.
+        let stopwatch = Stopwatch::start();
         let compiled_func = entity.compile(fuel, &self.features);
         let mut funcs = self.funcs.lock();
         let Some(entity) = funcs.get_mut(func) else {
             panic!("encountered invalid internal function: {func:?}")
         };
+        let mut stats = self.stats.lock();
+        stats.add_translation_time(stopwatch.elapsed());
+        stats.remove_uncompiled();
         match compiled_func {
             Ok(compiled_func) => {
+                stats.add_compiled(compiled_func.bytecode_bytes(), compiled_func.const_bytes());
                 let cref = entity.set_compiled(compiled_func);
                 Ok(self.adjust_cref_lifetime(cref))
             }
@@ -393,17 +674,93 @@ impl CodeMap {
         }
     }
 
+    /// Compiles `func` if it is still uncompiled.
+    ///
+    /// # Note
+    ///
+    /// - Used to compile the functions of lazily compiled Wasm modules on a background thread.
+    /// - Does not consume fuel since no execution is accountable for the compilation.
+    /// - If compilation fails `func` is uncompiled again so that the error is
+    ///   reported to the execution that compiles `func` on demand.
+    /// - Functions that still require validation are always compiled on demand
+    ///   since validation consumes their [`UncompiledFuncEntity`].
+    #[cfg(feature = "std")]
+    pub fn compile_in_background(&self, func: EngineFunc) {
+        let Some(entity) = self.get_uncompiled(func) else {
+            return;
+        };
+        let stopwatch = Stopwatch::start();
+        let compiled_func = entity
+            .try_clone()
+            .map(|mut entity| entity.compile(None, &self.features));
+        let mut funcs = self.funcs.lock();
+        let Some(func) = funcs.get_mut(func) else {
+            panic!("encountered invalid internal function: {func:?}")
+        };
+        let Some(Ok(compiled_func)) = compiled_func else {
+            func.reset_uncompiled(entity);
+            return;
+        };
+        let mut stats = self.stats.lock();
+        stats.add_translation_time(stopwatch.elapsed());
+        stats.remove_uncompiled();
+        stats.add_compiled(compiled_func.bytecode_bytes(), compiled_func.const_bytes());
+        func.set_compiled(compiled_func);
+    }
+
+    /// Re-translates the hot `func` with the optimizing pipeline and swaps in the result.
+    ///
+    /// # Note
+    ///
+    /// - The replaced [`CompiledFuncEntity`] is retired so that running executions stay valid.
+    ///   Since `func` is tiered up at most once the retired code is bounded by the baseline tier.
+    /// - Keeps the baseline tier of `func` if re-translation failed which is not
+    ///   expected to happen since `func` has already been translated successfully before.
+    #[cold]
+    #[inline(never)]
+    fn tier_up(
+        &self,
+        func: EngineFunc,
+        mut entity: UncompiledFuncEntity,
+    ) -> Result<CompiledFuncRef, Error> {
+        // Note: it is important that re-translation happens without locking the `CodeMap`
+        //       since re-translation can take a prolonged time.
+        let stopwatch = Stopwatch::start();
+        let optimized = entity.compile_optimized();
+        let mut funcs = self.funcs.lock();
+        let Some(FuncEntity::Compiled(compiled)) = funcs.get_mut(func) else {
+            panic!("encountered invalid tiered up function: {func:?}")
+        };
+        let mut stats = self.stats.lock();
+        stats.add_translation_time(stopwatch.elapsed());
+        #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+        let optimized = optimized.map(CompiledFuncEntity::with_jit);
+        if let Ok(optimized) = optimized {
+            stats.add_tiered_up(optimized.bytecode_bytes(), optimized.const_bytes());
+            #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+            if optimized.jit.is_some() {
+                stats.add_jit_compiled();
+            }
+            let baseline = mem::replace(compiled, optimized);
+            self.retired.lock().push(baseline);
+        }
+        let cref = CompiledFuncRef::from(&*compiled);
+        Ok(self.adjust_cref_lifetime(cref))
+    }
+
     /// Wait until `func` has finished compilation.
     ///
     /// In this case compilation of `func` is driven by another thread.
     ///
+    /// Returns `None` if `func` is uncompiled again since its background compilation failed.
+    ///
     /// # Errors
     ///
     /// - If translation or Wasm validation of `func` failed.
     /// - If `ctx` ran out of fuel in case fuel consumption is enabled.
     #[cold]
     #[inline(never)]
-    fn wait_for_compilation(&self, func: EngineFunc) -> Result<CompiledFuncRef, Error> {
+    fn wait_for_compilation(&self, func: EngineFunc) -> Option<Result<CompiledFuncRef, Error>> {
         'wait: loop {
             let funcs = self.funcs.lock();
             let Some(entity) = funcs.get(func) else {
@@ -413,12 +770,13 @@ impl CodeMap {
                 FuncEntity::Compiling => continue 'wait,
                 FuncEntity::Compiled(func) => {
                     let cref = CompiledFuncRef::from(func);
-                    return Ok(self.adjust_cref_lifetime(cref));
+                    return Some(Ok(self.adjust_cref_lifetime(cref)));
                 }
                 FuncEntity::FailedToCompile => {
-                    return Err(Error::from(TranslationError::LazyCompilationFailed))
+                    return Some(Err(Error::from(TranslationError::LazyCompilationFailed)))
                 }
-                FuncEntity::Uncompiled(_) | FuncEntity::Uninit => {
+                FuncEntity::Uncompiled(_) => return None,
+                FuncEntity::Uninit => {
                     panic!("unexpected function state: {entity:?}")
                 }
             }
@@ -479,11 +837,25 @@ impl FuncEntity {
     #[inline]
     pub fn get_compiled(&self) -> Option<CompiledFuncRef> {
         match self {
-            FuncEntity::Compiled(func) => Some(func.into()),
+            FuncEntity::Compiled(func) if !func.is_hot => Some(func.into()),
             _ => None,
         }
     }
 
+    /// Returns the Wasm function body to re-translate if the [`FuncEntity`] is hot.
+    ///
+    /// Resets the hotness of the [`FuncEntity`].
+    #[inline]
+    pub fn get_tier_up(&mut self) -> Option<UncompiledFuncEntity> {
+        let Self::Compiled(func) = self else {
+            return None;
+        };
+        if !mem::take(&mut func.is_hot) {
+            return None;
+        }
+        func.tier_up.as_deref_mut()?.entity.take()
+    }
+
     /// Returns the [`UncompiledFuncEntity`] if possible.
     ///
     /// # Errors
@@ -525,6 +897,18 @@ impl FuncEntity {
         CompiledFuncRef::from(&*entity)
     }
 
+    /// Resets the compiling [`FuncEntity`] to its uncompiled `entity`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is not in compiling state.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reset_uncompiled(&mut self, entity: UncompiledFuncEntity) {
+        assert!(matches!(self, Self::Compiling));
+        *self = Self::Uncompiled(entity);
+    }
+
     /// Signals a failed compilation for the [`FuncEntity`].
     ///
     /// # Panics
@@ -546,6 +930,8 @@ pub struct TypeIndex(u32);
 pub struct UncompiledFuncEntity {
     /// The index of the function within the Wasm module.
     func_index: FuncIdx,
+    /// The byte offset of the function body within the Wasm binary.
+    offset: usize,
     /// The Wasm binary bytes.
     bytes: SmallByteSlice,
     /// The Wasm module of the Wasm function.
@@ -563,6 +949,7 @@ impl UncompiledFuncEntity {
     /// Creates a new [`UncompiledFuncEntity`].
     pub fn new(
         func_index: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: ModuleHeader,
         func_to_validate: impl Into<Option<FuncToValidate<ValidatorResources>>>,
@@ -580,12 +967,28 @@ impl UncompiledFuncEntity {
         let bytes = bytes.into();
         Self {
             func_index,
+            offset,
             bytes,
             module,
             validation,
         }
     }
 
+    /// Returns a copy of the [`UncompiledFuncEntity`] if it does not require validation.
+    #[cfg(feature = "std")]
+    fn try_clone(&self) -> Option<Self> {
+        if self.validation.is_some() {
+            return None;
+        }
+        Some(Self {
+            func_index: self.func_index,
+            offset: self.offset,
+            bytes: self.bytes.clone(),
+            module: self.module.clone(),
+            validation: None,
+        })
+    }
+
     /// Compile the [`UncompiledFuncEntity`].
     ///
     /// # Panics
@@ -624,6 +1027,9 @@ impl UncompiledFuncEntity {
 
         let func_idx = self.func_index;
         let bytes = mem::take(&mut self.bytes);
+        #[cfg(feature = "tracing")]
+        let _span =
+            crate::instrument::translate_span(func_idx, bytes.as_slice().len(), true).entered();
         let needs_validation = self.validation.is_some();
         let compilation_fuel = |_costs: &FuelCosts| {
             let len_bytes = bytes.as_slice().len() as u64;
@@ -659,26 +1065,60 @@ impl UncompiledFuncEntity {
                 };
                 let validator = func_to_validate.into_validator(allocs.1);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(self.offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         result.write(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_allocs(allocs.translation, allocs.validation);
             }
             None => {
                 let allocs = engine.get_translation_allocs();
                 let translator = FuncTranslator::new(func_idx, module, allocs)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(self.offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         result.write(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_translation_allocs(allocs);
             }
         };
         Ok(unsafe { result.assume_init() })
     }
+
+    /// Re-translates the [`UncompiledFuncEntity`] with the optimizing pipeline.
+    ///
+    /// # Note
+    ///
+    /// This neither validates the Wasm function nor consumes fuel since the
+    /// Wasm function has already been validated and translated before.
+    ///
+    /// # Panics
+    ///
+    /// If the `engine` unexpectedly no longer exists due to weak referencing.
+    ///
+    /// # Errors
+    ///
+    /// If function translation failed.
+    fn compile_optimized(&mut self) -> Result<CompiledFuncEntity, Error> {
+        let func_idx = self.func_index;
+        let bytes = mem::take(&mut self.bytes);
+        let module = self.module.clone();
+        let Some(engine) = module.engine().upgrade() else {
+            panic!(
+                "cannot tier up function since engine does no longer exist: {:?}",
+                module.engine()
+            )
+        };
+        let mut result = MaybeUninit::uninit();
+        let allocs = engine.get_translation_allocs();
+        let translator = FuncTranslator::new_optimizing(func_idx, module, allocs)?;
+        let allocs = FuncTranslationDriver::new(self.offset, &bytes[..], translator)?.translate(
+            |compiled_func| {
+                result.write(compiled_func);
+            },
+        )?;
+        engine.recycle_translation_allocs(allocs);
+        Ok(unsafe { result.assume_init() })
+    }
 }
 
 impl fmt::Debug for UncompiledFuncEntity {
@@ -693,7 +1133,7 @@ impl fmt::Debug for UncompiledFuncEntity {
 }
 
 /// A boxed byte slice that can store some bytes inline.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum SmallByteSlice {
     /// The byte slice fits in the inline buffer.
     Small {
@@ -772,6 +1212,78 @@ pub struct CompiledFuncEntity {
     /// This includes registers to store the function local constant values,
     /// function parameters, function locals and dynamically used registers.
     len_registers: u16,
+    /// The machine code of the [`EngineFunc`].
+    ///
+    /// This is `Some` if the [`EngineFunc`] was tiered up and compiled by the JIT.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    jit: Option<JitFunc>,
+    /// The pre-decoded handlers of the instructions of the [`EngineFunc`].
+    #[cfg(feature = "threaded-dispatch")]
+    handlers: Pin<Box<[Handler]>>,
+    /// The debug information of the [`EngineFunc`].
+    ///
+    /// This is `Some` if the [`EngineFunc`] was compiled with debugging enabled.
+    debug_info: Option<Box<FuncDebugInfo>>,
+    /// The Wasm offsets of the basic blocks of the [`EngineFunc`] indexed by their coverage counter.
+    ///
+    /// This is `Some` if the [`EngineFunc`] was compiled with code coverage enabled.
+    coverage: Option<Box<[u32]>>,
+    /// The Wasm offsets of the instructions of the [`EngineFunc`].
+    ///
+    /// This is `Some` if the [`EngineFunc`] was compiled with symbolication enabled.
+    offsets: Option<Box<FuncOffsets>>,
+    /// The information required to tier up the [`EngineFunc`].
+    ///
+    /// This is `Some` if the [`EngineFunc`] was compiled by the baseline tier with tiering enabled.
+    tier_up: Option<Box<TierUp>>,
+    /// The number of parameters and locals of the [`EngineFunc`] if it can be inlined.
+    ///
+    /// This is `Some` if the [`EngineFunc`] was compiled with inlining enabled and is small enough.
+    inlining: Option<u16>,
+    /// Is `true` if the [`EngineFunc`] reached the tier-up threshold and is tiered up upon its next use.
+    is_hot: bool,
+}
+
+/// The information required to tier up a [`CompiledFuncEntity`].
+#[derive(Debug)]
+struct TierUp {
+    /// The number of calls and loop iterations of the [`CompiledFuncEntity`] so far.
+    ///
+    /// # Note
+    ///
+    /// This is referenced by the [`Hotness`] of running executions and thus
+    /// must stay at its address as long as the [`CompiledFuncEntity`] lives.
+    hotness: AtomicU32,
+    /// The Wasm function body to re-translate with the optimizing pipeline.
+    ///
+    /// This is `None` once the [`CompiledFuncEntity`] has been tiered up.
+    entity: Option<UncompiledFuncEntity>,
+}
+
+/// A shared reference to the hotness counter of a [`CompiledFuncEntity`] of the baseline tier.
+#[derive(Debug, Copy, Clone)]
+pub struct Hotness {
+    /// The pointer to the [`TierUp::hotness`] counter.
+    counter: NonNull<AtomicU32>,
+}
+
+/// It is safe to send a [`Hotness`] to another thread.
+///
+/// # Safety
+///
+/// - The [`Hotness`] only points to an [`AtomicU32`] which can be shared across threads.
+/// - The pointed-to [`TierUp`] is boxed and its [`CompiledFuncEntity`] is either alive
+///   in the [`CodeMap`] or retired which keeps it alive as long as the [`CodeMap`].
+unsafe impl Send for Hotness {}
+
+impl Hotness {
+    /// Counts a call or loop iteration and returns the new hotness.
+    #[inline]
+    pub fn count(self) -> u32 {
+        // Safety: see the `Send` implementation of `Hotness` for why the counter is alive.
+        let counter = unsafe { self.counter.as_ref() };
+        counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1)
+    }
 }
 
 impl CompiledFuncEntity {
@@ -801,12 +1313,94 @@ impl CompiledFuncEntity {
             "compiled function has too many instructions: {}",
             instrs.len(),
         );
+        #[cfg(feature = "threaded-dispatch")]
+        let handlers = Pin::new(decode_handlers(&instrs));
         Self {
             instrs,
             consts,
             len_registers,
+            #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+            jit: None,
+            #[cfg(feature = "threaded-dispatch")]
+            handlers,
+            debug_info: None,
+            coverage: None,
+            offsets: None,
+            tier_up: None,
+            inlining: None,
+            is_hot: false,
         }
     }
+
+    /// Attaches the [`FuncDebugInfo`] to the [`CompiledFuncEntity`].
+    pub fn with_debug_info(mut self, debug_info: FuncDebugInfo) -> Self {
+        self.debug_info = Some(Box::new(debug_info));
+        self
+    }
+
+    /// Attaches the Wasm offsets of the basic blocks with coverage counters to the [`CompiledFuncEntity`].
+    pub fn with_coverage(mut self, offsets: &[u32]) -> Self {
+        self.coverage = Some(offsets.into());
+        self
+    }
+
+    /// Attaches the [`FuncOffsets`] to the [`CompiledFuncEntity`].
+    pub fn with_offsets(mut self, offsets: FuncOffsets) -> Self {
+        self.offsets = Some(Box::new(offsets));
+        self
+    }
+
+    /// Attaches the Wasm function body to re-translate once the [`CompiledFuncEntity`] is hot.
+    pub fn with_tier_up(mut self, entity: UncompiledFuncEntity) -> Self {
+        self.tier_up = Some(Box::new(TierUp {
+            hotness: AtomicU32::new(0),
+            entity: Some(entity),
+        }));
+        self
+    }
+
+    /// Marks the [`CompiledFuncEntity`] as inlinable with `len_locals` parameters and locals.
+    pub fn with_inlining(mut self, len_locals: u16) -> Self {
+        self.inlining = Some(len_locals);
+        self
+    }
+
+    /// Compiles the instructions of the [`CompiledFuncEntity`] to machine code.
+    ///
+    /// Leaves the [`CompiledFuncEntity`] to the interpreter if the JIT does not support it.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    pub fn with_jit(mut self) -> Self {
+        self.jit = JitFunc::compile(&self.instrs);
+        self
+    }
+
+    /// Marks the [`CompiledFuncEntity`] as hot if it has not yet been tiered up.
+    fn mark_hot(&mut self) {
+        if self
+            .tier_up
+            .as_deref()
+            .is_some_and(|tier_up| tier_up.entity.is_some())
+        {
+            self.is_hot = true;
+        }
+    }
+
+    /// Returns the size of the bytecode of the [`CompiledFuncEntity`] in bytes.
+    ///
+    /// # Note
+    ///
+    /// This includes the pre-decoded handlers of the instructions if any.
+    pub fn bytecode_bytes(&self) -> usize {
+        let bytes = mem::size_of_val::<[Instruction]>(&self.instrs);
+        #[cfg(feature = "threaded-dispatch")]
+        let bytes = bytes + mem::size_of_val::<[Handler]>(&self.handlers);
+        bytes
+    }
+
+    /// Returns the size of the function local constants of the [`CompiledFuncEntity`] in bytes.
+    pub fn const_bytes(&self) -> usize {
+        mem::size_of_val::<[UntypedVal]>(&self.consts)
+    }
 }
 
 /// A shared reference to the data of a [`EngineFunc`].
@@ -818,6 +1412,16 @@ pub struct CompiledFuncRef<'a> {
     consts: Pin<&'a [UntypedVal]>,
     /// The number of registers used by the [`EngineFunc`] in total.
     len_registers: u16,
+    /// The entry point of the machine code of the [`CompiledFuncEntity`] if any.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    jit: Option<JitEntry>,
+    /// The hotness counter of the [`CompiledFuncEntity`] if it can be tiered up.
+    hotness: Option<Hotness>,
+    /// The Wasm offsets of the instructions of the [`CompiledFuncEntity`] if any.
+    offsets: Option<&'a FuncOffsets>,
+    /// The pre-decoded handlers of the instructions of the [`CompiledFuncEntity`].
+    #[cfg(feature = "threaded-dispatch")]
+    handlers: Pin<&'a [Handler]>,
 }
 
 impl<'a> From<&'a CompiledFuncEntity> for CompiledFuncRef<'a> {
@@ -827,6 +1431,14 @@ impl<'a> From<&'a CompiledFuncEntity> for CompiledFuncRef<'a> {
             instrs: func.instrs.as_ref(),
             consts: func.consts.as_ref(),
             len_registers: func.len_registers,
+            #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+            jit: func.jit.as_ref().map(JitFunc::entry),
+            hotness: func.tier_up.as_deref().map(|tier_up| Hotness {
+                counter: NonNull::from(&tier_up.hotness),
+            }),
+            offsets: func.offsets.as_deref(),
+            #[cfg(feature = "threaded-dispatch")]
+            handlers: func.handlers.as_ref(),
         }
     }
 }
@@ -838,6 +1450,26 @@ impl<'a> CompiledFuncRef<'a> {
         self.instrs.get_ref()
     }
 
+    /// Returns the entry point of the machine code of the [`EngineFunc`] if any.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    #[inline]
+    pub fn jit(&self) -> Option<JitEntry> {
+        self.jit
+    }
+
+    /// Returns the hotness counter of the [`EngineFunc`] if it can be tiered up.
+    #[inline]
+    pub fn hotness(&self) -> Option<Hotness> {
+        self.hotness
+    }
+
+    /// Returns the pre-decoded handlers of the instructions of the [`EngineFunc`].
+    #[cfg(feature = "threaded-dispatch")]
+    #[inline]
+    pub fn handlers(&self) -> &'a [Handler] {
+        self.handlers.get_ref()
+    }
+
     /// Returns the number of registers used by the [`EngineFunc`].
     #[inline]
     pub fn len_registers(&self) -> u16 {
@@ -849,4 +1481,66 @@ impl<'a> CompiledFuncRef<'a> {
     pub fn consts(&self) -> &'a [UntypedVal] {
         self.consts.get_ref()
     }
+
+    /// Returns the [`FuncOffsets`] of the [`EngineFunc`] if any.
+    #[inline]
+    pub fn offsets(&self) -> Option<&'a FuncOffsets> {
+        self.offsets
+    }
+
+    /// Returns the position of the instruction at `ip` within the [`EngineFunc`].
+    ///
+    /// Returns `None` if `ip` does not point to an instruction of the [`EngineFunc`].
+    pub fn position(&self, ip: *const Instruction) -> Option<usize> {
+        let instrs = self.instrs();
+        let delta = (ip as usize).checked_sub(instrs.as_ptr() as usize)?;
+        let pos = delta / mem::size_of::<Instruction>();
+        (pos < instrs.len()).then_some(pos)
+    }
+
+    /// Prolongs the lifetime of the [`CompiledFuncRef`] to `'static`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the returned [`CompiledFuncRef`] is not used
+    /// after the [`CodeMap`] that owns the referenced [`CompiledFuncEntity`] is dropped.
+    /// Since [`CompiledFuncEntity`]s replaced by tiering are retired instead of dropped
+    /// the [`CompiledFuncRef`] stays valid even after its [`EngineFunc`] was tiered up.
+    #[inline]
+    pub unsafe fn into_static(self) -> CompiledFuncRef<'static> {
+        // Safety: upheld by the caller.
+        unsafe { mem::transmute::<CompiledFuncRef<'a>, CompiledFuncRef<'static>>(self) }
+    }
+}
+
+/// A shared reference to the data of an inlinable [`EngineFunc`].
+#[derive(Debug, Copy, Clone)]
+pub struct InlinableFuncRef<'a> {
+    /// The compiled [`EngineFunc`].
+    cref: CompiledFuncRef<'a>,
+    /// The number of parameters and locals of the [`EngineFunc`].
+    len_locals: u16,
+}
+
+impl<'a> InlinableFuncRef<'a> {
+    /// Returns the sequence of [`Instruction`] of the [`EngineFunc`].
+    pub fn instrs(&self) -> &'a [Instruction] {
+        self.cref.instrs()
+    }
+
+    /// Returns the function local constant values of the [`EngineFunc`].
+    pub fn consts(&self) -> &'a [UntypedVal] {
+        self.cref.consts()
+    }
+
+    /// Returns the number of registers of the [`EngineFunc`] excluding its function local constants.
+    pub fn len_frame_registers(&self) -> u16 {
+        // Note: the function local constants are part of the total number of registers.
+        self.cref.len_registers() - self.consts().len() as u16
+    }
+
+    /// Returns the number of parameters and locals of the [`EngineFunc`].
+    pub fn len_locals(&self) -> u16 {
+        self.len_locals
+    }
 }
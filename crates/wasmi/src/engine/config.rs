@@ -1,11 +1,21 @@
 use super::{EnforcedLimits, StackLimits};
 use crate::core::UntypedVal;
-use core::{mem::size_of, num::NonZeroU64};
+use core::{
+    fmt::{self, Display},
+    mem::size_of,
+    num::NonZeroU64,
+};
 use wasmparser::WasmFeatures;
 
 /// The default amount of stacks kept in the cache at most.
 const DEFAULT_CACHED_STACKS: usize = 2;
 
+/// The default number of calls and loop iterations after which a Wasm function is tiered up.
+const DEFAULT_TIER_UP_THRESHOLD: u32 = 1000;
+
+/// The default maximum number of Wasmi bytecode instructions of an inlined Wasm function.
+const DEFAULT_INLINING_BUDGET: u32 = 32;
+
 /// Configuration for an [`Engine`].
 ///
 /// [`Engine`]: [`crate::Engine`]
@@ -15,6 +25,10 @@ pub struct Config {
     stack_limits: StackLimits,
     /// The amount of Wasm stacks to keep in cache at most.
     cached_stacks: usize,
+    /// The amount of compiled Wasm modules to keep in cache at most.
+    cached_modules: usize,
+    /// The policy to evict a cached Wasm module once the module cache is full.
+    module_cache_eviction: ModuleCacheEviction,
     /// The Wasm features used when validating or translating functions.
     features: WasmFeatures,
     /// Is `true` if Wasmi executions shall consume fuel.
@@ -27,6 +41,35 @@ pub struct Config {
     compilation_mode: CompilationMode,
     /// Enforced limits for Wasm module parsing and compilation.
     limits: EnforcedLimits,
+    /// Is `true` if Wasmi executions shall be deterministic across platforms.
+    deterministic: bool,
+    /// Is `true` if Wasmi translation shall generate hooks for [`Debugger`]s.
+    ///
+    /// [`Debugger`]: crate::Debugger
+    debugging: bool,
+    /// Is `true` if Wasmi translation shall generate code coverage counters.
+    coverage: bool,
+    /// Is `true` if Wasmi captures a [`WasmCoreDump`] when a Wasm execution traps.
+    ///
+    /// [`WasmCoreDump`]: crate::WasmCoreDump
+    coredump_on_trap: bool,
+    /// Is `true` if Wasmi captures a [`WasmBacktrace`] when a Wasm execution traps.
+    ///
+    /// [`WasmBacktrace`]: crate::WasmBacktrace
+    wasm_backtrace: bool,
+    /// Is `true` if hot Wasm functions are re-translated with an optimizing pipeline.
+    tiering: bool,
+    /// The number of calls and loop iterations after which a Wasm function is tiered up.
+    tier_up_threshold: u32,
+    /// The optimizations applied to the Wasmi bytecode of translated Wasm functions.
+    optimization_level: OptimizationLevel,
+    /// Is `true` if calls to small Wasm functions of the same module are inlined.
+    inlining: bool,
+    /// The maximum number of Wasmi bytecode instructions of an inlined Wasm function.
+    inlining_budget: u32,
+    /// Is `true` if lazily compiled Wasm functions are compiled on a background thread.
+    #[cfg(feature = "std")]
+    background_compilation: bool,
 }
 
 /// Type storing all kinds of fuel costs of instructions.
@@ -138,6 +181,11 @@ impl Default for FuelCosts {
 #[derive(Debug, Default, Copy, Clone)]
 pub enum CompilationMode {
     /// The Wasm code is compiled eagerly to Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// With the `parallel` crate feature the Wasm functions of a [`Module`](crate::Module)
+    /// created via [`Module::new`](crate::Module::new) are compiled in parallel.
     #[default]
     Eager,
     /// The Wasm code is validated eagerly and translated lazily on first use.
@@ -151,17 +199,67 @@ pub enum CompilationMode {
     Lazy,
 }
 
+/// The policy to evict a cached Wasm module once the module cache of an [`Engine`] is full.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ModuleCacheEviction {
+    /// Evicts the cached Wasm module that has not been used for the longest time.
+    #[default]
+    LeastRecentlyUsed,
+    /// Evicts the Wasm module that has been cached first.
+    FirstInFirstOut,
+}
+
+/// The optimizations applied to the Wasmi bytecode of translated Wasm functions.
+///
+/// # Note
+///
+/// Optimizations never add Wasmi bytecode instructions but remove redundant ones.
+/// Removed instructions are no longer charged the base fuel costs.
+/// Therefore optimized Wasm functions may consume less fuel.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// The Wasmi bytecode is not optimized.
+    #[default]
+    None,
+    /// The Wasmi bytecode is optimized with cheap passes.
+    ///
+    /// This eliminates redundant `copy` instructions and threads branches.
+    Basic,
+    /// The Wasmi bytecode is optimized with all available passes.
+    ///
+    /// In addition to [`OptimizationLevel::Basic`] this propagates and folds constants
+    /// across basic blocks, resolves conditional branches with constant conditions and
+    /// eliminates stores to registers that are never read afterwards.
+    Full,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             stack_limits: StackLimits::default(),
             cached_stacks: DEFAULT_CACHED_STACKS,
+            cached_modules: 0,
+            module_cache_eviction: ModuleCacheEviction::default(),
             features: Self::default_features(),
             consume_fuel: false,
             ignore_custom_sections: false,
             fuel_costs: FuelCosts::default(),
             compilation_mode: CompilationMode::default(),
             limits: EnforcedLimits::default(),
+            deterministic: false,
+            debugging: false,
+            coverage: false,
+            coredump_on_trap: false,
+            wasm_backtrace: false,
+            tiering: false,
+            tier_up_threshold: DEFAULT_TIER_UP_THRESHOLD,
+            optimization_level: OptimizationLevel::default(),
+            inlining: false,
+            inlining_budget: DEFAULT_INLINING_BUDGET,
+            #[cfg(feature = "std")]
+            background_compilation: false,
         }
     }
 }
@@ -210,6 +308,58 @@ impl Config {
         self.cached_stacks
     }
 
+    /// Sets the maximum amount of compiled Wasm modules cached by the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - [`Module::new`] and [`Module::new_unchecked`] return a cached [`Module`] for
+    ///   identical Wasm bytecode instead of compiling it again. All cached [`Module`]s
+    ///   share the compiled Wasm functions of the first [`Module`] with the same Wasm bytecode.
+    /// - The module cache is keyed by the Wasm bytecode. Since the [`Config`] of an [`Engine`]
+    ///   never changes all cached [`Module`]s have been compiled with the same [`Config`].
+    /// - Modules created via [`Module::new_streaming`] are neither cached nor taken from the cache.
+    /// - Once the module cache is full a cached [`Module`] is evicted according to the
+    ///   [`Config::module_cache_eviction`] policy. Evicting a [`Module`] does not free its
+    ///   compiled Wasm functions since the [`Engine`] never frees compiled Wasm functions.
+    /// - The number of cached [`Module`]s is reported by [`EngineStats::cached_modules`].
+    ///
+    /// Defaults to 0 which disables the module cache.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Module`]: crate::Module
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    /// [`Module::new_streaming`]: crate::Module::new_streaming
+    /// [`EngineStats::cached_modules`]: crate::EngineStats::cached_modules
+    pub fn set_cached_modules(&mut self, amount: usize) -> &mut Self {
+        self.cached_modules = amount;
+        self
+    }
+
+    /// Returns the maximum amount of compiled Wasm modules cached by the [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    pub(crate) fn cached_modules(&self) -> usize {
+        self.cached_modules
+    }
+
+    /// Sets the [`ModuleCacheEviction`] policy of the module cache.
+    ///
+    /// # Note
+    ///
+    /// This only has an effect if the module cache is enabled via [`Config::set_cached_modules`].
+    ///
+    /// Defaults to [`ModuleCacheEviction::LeastRecentlyUsed`].
+    pub fn module_cache_eviction(&mut self, eviction: ModuleCacheEviction) -> &mut Self {
+        self.module_cache_eviction = eviction;
+        self
+    }
+
+    /// Returns the [`ModuleCacheEviction`] policy of the module cache.
+    pub(crate) fn get_module_cache_eviction(&self) -> ModuleCacheEviction {
+        self.module_cache_eviction
+    }
+
     /// Enable or disable the [`mutable-global`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -389,10 +539,42 @@ impl Config {
     /// Returns the [`CompilationMode`] used for the [`Engine`].
     ///
     /// [`Engine`]: crate::Engine
-    pub(super) fn get_compilation_mode(&self) -> CompilationMode {
+    pub(crate) fn get_compilation_mode(&self) -> CompilationMode {
         self.compilation_mode
     }
 
+    /// Configures whether lazily compiled Wasm functions are compiled on a background thread.
+    ///
+    /// # Note
+    ///
+    /// - This only has an effect with [`CompilationMode::LazyTranslation`] or
+    ///   [`CompilationMode::Lazy`].
+    /// - After creating a [`Module`] a background thread keeps compiling its uncompiled Wasm
+    ///   functions in order. All [`Module`]s of an [`Engine`] share a single background thread.
+    ///   Wasm functions that are still uncompiled when they are called are compiled on demand
+    ///   as usual. This avoids the latency of the first call to a Wasm function.
+    /// - Wasm functions compiled on the background thread do not consume fuel for their
+    ///   compilation.
+    /// - Errors of Wasm functions that fail to compile are reported upon their first call as usual.
+    /// - Wasm functions that are validated lazily with [`CompilationMode::Lazy`] are not compiled
+    ///   on the background thread. Use [`CompilationMode::LazyTranslation`] instead.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Module`]: crate::Module
+    #[cfg(feature = "std")]
+    pub fn background_compilation(&mut self, enable: bool) -> &mut Self {
+        self.background_compilation = enable;
+        self
+    }
+
+    /// Returns `true` if lazily compiled Wasm functions are compiled on a background thread.
+    #[cfg(feature = "std")]
+    pub(crate) fn get_background_compilation(&self) -> bool {
+        self.background_compilation
+    }
+
     /// Sets the [`EnforcedLimits`] enforced by the [`Engine`] for Wasm module parsing and compilation.
     ///
     /// By default no limits are enforced.
@@ -410,8 +592,310 @@ impl Config {
         &self.limits
     }
 
+    /// Configures whether Wasmi executions are guaranteed to be deterministic across platforms.
+    ///
+    /// # Note
+    ///
+    /// When enabled, executing the same Wasm code with the same inputs yields bit-identical
+    /// results and consumes the same amount of fuel on every platform:
+    ///
+    /// - All NaN values produced by Wasm float arithmetic, rounding, `min`, `max`,
+    ///   `f32.demote_f64` and `f64.promote_f32` instructions are canonicalized.
+    ///   Bitwise float instructions such as `abs`, `neg` and `copysign` are unaffected.
+    /// - Only [`CompilationMode::Eager`] is supported since lazy compilation modes
+    ///   charge compilation fuel depending on which function is called first.
+    ///   Creating a [`Module`] with any other [`CompilationMode`] fails with a [`ConfigError`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn deterministic(&mut self, enable: bool) -> &mut Self {
+        self.deterministic = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables deterministic execution.
+    pub(crate) fn get_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Configures whether Wasmi translation generates hooks for [`Debugger`]s.
+    ///
+    /// # Note
+    ///
+    /// - A [`Debugger`] installed via [`Store::set_debugger`] is only notified
+    ///   when executing Wasm functions that have been compiled with debugging enabled.
+    /// - When enabled, Wasmi records the Wasm offset, the local variable types and
+    ///   the register layout of the operand stack for every Wasm operator.
+    ///   Also some optimizations across Wasm operators are no longer applied.
+    ///   This slows down both compilation and execution considerably.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Debugger`]: crate::Debugger
+    /// [`Store::set_debugger`]: crate::Store::set_debugger
+    pub fn debugging(&mut self, enable: bool) -> &mut Self {
+        self.debugging = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables debugging.
+    pub(crate) fn get_debugging(&self) -> bool {
+        self.debugging
+    }
+
+    /// Configures whether Wasmi translation generates code coverage counters.
+    ///
+    /// # Note
+    ///
+    /// - When enabled, Wasmi counts how often every basic block of the compiled
+    ///   Wasm functions is executed. The counts are recorded per [`Store`] and
+    ///   can be queried via [`Store::coverage`].
+    /// - Basic blocks are identified by the Wasm offset of their first Wasm operator.
+    ///   The first basic block of a Wasm function counts the calls to the function.
+    /// - Only functions that have been compiled are reported. Therefore use
+    ///   [`CompilationMode::Eager`] to also report functions that are never called.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::coverage`]: crate::Store::coverage
+    pub fn coverage(&mut self, enable: bool) -> &mut Self {
+        self.coverage = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables code coverage.
+    pub(crate) fn get_coverage(&self) -> bool {
+        self.coverage
+    }
+
+    /// Configures whether Wasmi captures a [`WasmCoreDump`] when a Wasm execution traps.
+    ///
+    /// # Note
+    ///
+    /// - The captured [`WasmCoreDump`] is accessible via [`Error::coredump`].
+    /// - Only call frames of Wasm functions that have been compiled with this
+    ///   setting enabled are part of the [`WasmCoreDump`].
+    /// - When enabled, Wasmi records the registers of the local variables and the
+    ///   Wasm operand stack of every translated Wasm operator. Execution is not slowed
+    ///   down but Wasm functions are neither optimized, tiered up nor inlined.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WasmCoreDump`]: crate::WasmCoreDump
+    /// [`Error::coredump`]: crate::Error::coredump
+    pub fn coredump_on_trap(&mut self, enable: bool) -> &mut Self {
+        self.coredump_on_trap = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables core dumps on traps.
+    pub(crate) fn get_coredump_on_trap(&self) -> bool {
+        self.coredump_on_trap
+    }
+
+    /// Configures whether Wasmi captures a [`WasmBacktrace`] when a Wasm execution traps.
+    ///
+    /// # Note
+    ///
+    /// - The captured [`WasmBacktrace`] is accessible via [`Error::backtrace`]
+    ///   and is part of the [`Display`] output of the [`Error`].
+    /// - With the `dwarf` crate feature the [`WasmBacktrace`] contains the source
+    ///   locations and inlined functions of Wasm modules with DWARF debug information.
+    /// - When enabled, Wasmi records the Wasm offset of every translated Wasm operator
+    ///   which slightly increases the memory usage of compiled Wasm functions.
+    ///   Execution is not slowed down and Wasm functions are still optimized,
+    ///   tiered up and inlined.
+    /// - Inlined Wasm functions are attributed to the call frames of their callers
+    ///   at the Wasm offset of their inlined call.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WasmBacktrace`]: crate::WasmBacktrace
+    /// [`Error::backtrace`]: crate::Error::backtrace
+    /// [`Error`]: crate::Error
+    /// [`Display`]: core::fmt::Display
+    pub fn wasm_backtrace(&mut self, enable: bool) -> &mut Self {
+        self.wasm_backtrace = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables Wasm backtraces on traps.
+    pub(crate) fn get_wasm_backtrace(&self) -> bool {
+        self.wasm_backtrace
+    }
+
+    /// Configures whether hot Wasm functions are re-translated with an optimizing pipeline.
+    ///
+    /// # Note
+    ///
+    /// - When enabled, Wasmi counts the calls and loop iterations of every compiled
+    ///   Wasm function. Once a Wasm function reaches the [`Config::tier_up_threshold`]
+    ///   it is re-translated with a slower optimizing pipeline upon its next call.
+    /// - Executions that are already running the Wasm function continue to run the
+    ///   code of the baseline translation. Only subsequent calls use the optimized code.
+    /// - Re-translation does not consume fuel. The optimized code of the Wasm function
+    ///   may consume less fuel since it is charged only for the remaining instructions.
+    /// - Hot Wasm functions are re-translated with [`OptimizationLevel::Full`].
+    ///   Therefore tiering has no effect with [`OptimizationLevel::Full`].
+    /// - Wasm functions compiled with debugging information are never tiered up.
+    ///   This is the case with [`Config::debugging`] or [`Config::coredump_on_trap`].
+    /// - Wasm functions do not become hot while executed by a store with a trace hook
+    ///   installed via `Store::trace_hook` of the `trace` crate feature.
+    /// - The number of tiered up functions is reported by [`EngineStats::tiered_up_funcs`].
+    /// - With the experimental `jit` crate feature tiered up functions are additionally
+    ///   compiled to x86_64 machine code as reported by [`EngineStats::jit_compiled_funcs`].
+    ///   Without tiering or on targets other than `x86_64` Linux the `jit` crate feature
+    ///   has no effect.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`EngineStats::tiered_up_funcs`]: crate::EngineStats::tiered_up_funcs
+    /// [`EngineStats::jit_compiled_funcs`]: crate::EngineStats::jit_compiled_funcs
+    pub fn tiering(&mut self, enable: bool) -> &mut Self {
+        self.tiering = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables tiering of hot Wasm functions.
+    pub(crate) fn get_tiering(&self) -> bool {
+        self.tiering
+    }
+
+    /// Sets the number of calls and loop iterations after which a Wasm function is tiered up.
+    ///
+    /// # Note
+    ///
+    /// - This has no effect unless [`Config::tiering`] is enabled.
+    /// - A Wasm function reaches the threshold once the sum of its calls and loop
+    ///   iterations is equal to it. Thresholds of zero and one are treated the same.
+    ///
+    /// Defaults to 1000.
+    pub fn tier_up_threshold(&mut self, threshold: u32) -> &mut Self {
+        self.tier_up_threshold = threshold;
+        self
+    }
+
+    /// Returns the number of calls and loop iterations after which a Wasm function is tiered up.
+    pub(crate) fn get_tier_up_threshold(&self) -> u32 {
+        self.tier_up_threshold
+    }
+
+    /// Sets the [`OptimizationLevel`] of the Wasmi bytecode of translated Wasm functions.
+    ///
+    /// # Note
+    ///
+    /// - Higher optimization levels make Wasm execution faster at the cost of slower translation.
+    /// - Wasm functions compiled with debugging information are never optimized since their
+    ///   debugging information refers to the registers of the unoptimized Wasmi bytecode.
+    ///   This is the case with [`Config::debugging`] or [`Config::coredump_on_trap`].
+    ///
+    /// By default [`OptimizationLevel::None`] is used.
+    pub fn optimization_level(&mut self, level: OptimizationLevel) -> &mut Self {
+        self.optimization_level = level;
+        self
+    }
+
+    /// Returns the [`OptimizationLevel`] of the Wasmi bytecode of translated Wasm functions.
+    pub(crate) fn get_optimization_level(&self) -> OptimizationLevel {
+        self.optimization_level
+    }
+
+    /// Configures whether calls to small Wasm functions of the same module are inlined.
+    ///
+    /// # Note
+    ///
+    /// - Only calls to Wasm functions that have already been compiled when their caller
+    ///   is translated are inlined. Therefore lazily compiled Wasm functions are inlined
+    ///   only once they have been called before their callers are translated.
+    /// - Only leaf Wasm functions with up to 6 parameters that do not call other functions
+    ///   and whose Wasmi bytecode does not exceed the [`Config::inlining_budget`] are inlined.
+    /// - Inlining does not alter the fuel consumption of Wasm executions.
+    /// - Inlined calls do not count towards the maximum recursion depth of the [`StackLimits`].
+    /// - Wasm functions compiled with debugging information or coverage counters neither
+    ///   inline nor are inlined so that core dumps and coverage stay exact.
+    ///   This is the case with [`Config::debugging`], [`Config::coredump_on_trap`]
+    ///   or [`Config::coverage`].
+    /// - Backtraces attribute inlined Wasm functions to their callers.
+    /// - Profiles attribute the execution of inlined Wasm functions to their callers.
+    ///   Likewise trace hooks report the instructions of inlined Wasm functions at the
+    ///   Wasm offset of their inlined call.
+    /// - The number of inlined calls is reported by [`EngineStats::inlined_calls`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`EngineStats::inlined_calls`]: crate::EngineStats::inlined_calls
+    pub fn inlining(&mut self, enable: bool) -> &mut Self {
+        self.inlining = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables inlining of small Wasm functions.
+    pub(crate) fn get_inlining(&self) -> bool {
+        self.inlining
+    }
+
+    /// Sets the maximum number of Wasmi bytecode instructions of an inlined Wasm function.
+    ///
+    /// # Note
+    ///
+    /// This has no effect unless [`Config::inlining`] is enabled.
+    ///
+    /// Defaults to 32.
+    pub fn inlining_budget(&mut self, budget: u32) -> &mut Self {
+        self.inlining_budget = budget;
+        self
+    }
+
+    /// Returns the maximum number of Wasmi bytecode instructions of an inlined Wasm function.
+    pub(crate) fn get_inlining_budget(&self) -> u32 {
+        self.inlining_budget
+    }
+
+    /// Returns `Ok` if the [`Config`] does not contain contradicting settings.
+    ///
+    /// # Errors
+    ///
+    /// If deterministic execution is enabled together with a non-deterministic [`CompilationMode`].
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        if self.deterministic && !matches!(self.compilation_mode, CompilationMode::Eager) {
+            return Err(ConfigError::NonDeterministicCompilationMode(
+                self.compilation_mode,
+            ));
+        }
+        Ok(())
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
         self.features
     }
 }
+
+/// An error that may occur upon using a [`Config`] with contradicting settings.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// Deterministic execution was enabled with a non-deterministic [`CompilationMode`].
+    NonDeterministicCompilationMode(CompilationMode),
+    /// A watchpoint was added to a [`Store`] without [`Config::debugging`] enabled.
+    ///
+    /// [`Store`]: crate::Store
+    DebuggingDisabled,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonDeterministicCompilationMode(mode) => write!(
+                f,
+                "compilation mode {mode:?} is not supported with deterministic execution"
+            ),
+            Self::DebuggingDisabled => write!(f, "watchpoints require `Config::debugging`"),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::{Engine, EngineFunc};
+use crate::Error;
+use alloc::string::{String, ToString as _};
+use core::fmt::Write as _;
+
+impl Engine {
+    /// Returns the human readable text representation of the compiled `func`.
+    ///
+    /// Compiles `func` if it has not yet been compiled.
+    ///
+    /// The output consists of the register allocation, the `local_names` of the
+    /// registers of function locals, the function local constant values and the
+    /// compiled Wasmi instructions of `func`.
+    /// See [`DisplayInstruction`] for the format of the instructions.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of `func` failed.
+    ///
+    /// [`DisplayInstruction`]: wasmi_ir::DisplayInstruction
+    pub(crate) fn disassemble<'a>(
+        &self,
+        func: EngineFunc,
+        len_params: usize,
+        local_names: impl IntoIterator<Item = (u32, &'a str)>,
+    ) -> Result<String, Error> {
+        let compiled = self.inner.code_map.get(None, func)?;
+        let consts = compiled.consts();
+        let len_consts = consts.len();
+        let len_registers = usize::from(compiled.len_registers()) - len_consts;
+        let mut output = String::new();
+        // Note: writing to a `String` never fails.
+        let _ = writeln!(output, "registers: {len_registers}");
+        let _ = writeln!(output, "  params: r0..r{len_params}");
+        let _ = writeln!(
+            output,
+            "  locals and temporaries: r{len_params}..r{len_registers}"
+        );
+        // Note: function locals are stored in the registers with their local index.
+        for (index, name) in local_names {
+            let _ = writeln!(output, "  r{index} = ${name}");
+        }
+        let _ = writeln!(output, "consts: {len_consts}");
+        // Note: function local constant values are stored in reverse order
+        //       before the registers of the function call frame, thus the
+        //       first constant value is referred to by the lowest register.
+        for (n, value) in consts.iter().enumerate() {
+            let reg = n as isize - len_consts as isize;
+            let _ = writeln!(output, "  r{reg} = 0x{:016X}", value.to_bits());
+        }
+        let instrs = compiled.instrs();
+        let _ = writeln!(output, "instrs: {}", instrs.len());
+        let width = instrs.len().to_string().len();
+        for (pos, instr) in instrs.iter().enumerate() {
+            let _ = writeln!(output, "  {pos:>width$}: {}", instr.display(pos));
+        }
+        Ok(output)
+    }
+}
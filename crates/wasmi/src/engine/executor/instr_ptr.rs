@@ -1,10 +1,20 @@
-use crate::ir::Instruction;
+#[cfg(feature = "threaded-dispatch")]
+use super::instrs::Handler;
+use crate::{engine::code_map::CompiledFuncRef, ir::Instruction};
 
 /// The instruction pointer to the instruction of a function on the call stack.
 #[derive(Debug, Copy, Clone)]
 pub struct InstructionPtr {
     /// The pointer to the instruction.
     ptr: *const Instruction,
+    /// The pointer to the pre-decoded handler of the instruction.
+    ///
+    /// # Note
+    ///
+    /// The handlers of a compiled function are stored in lockstep with its
+    /// instructions and therefore this pointer moves together with `ptr`.
+    #[cfg(feature = "threaded-dispatch")]
+    handler: *const Handler,
 }
 
 /// It is safe to send an [`InstructionPtr`] to another thread.
@@ -18,10 +28,14 @@ pub struct InstructionPtr {
 unsafe impl Send for InstructionPtr {}
 
 impl InstructionPtr {
-    /// Creates a new [`InstructionPtr`] for `instr`.
+    /// Creates a new [`InstructionPtr`] pointing to the first instruction of `func`.
     #[inline]
-    pub fn new(ptr: *const Instruction) -> Self {
-        Self { ptr }
+    pub fn new(func: CompiledFuncRef) -> Self {
+        Self {
+            ptr: func.instrs().as_ptr(),
+            #[cfg(feature = "threaded-dispatch")]
+            handler: func.handlers().as_ptr(),
+        }
     }
 
     /// Offset the [`InstructionPtr`] by the given value.
@@ -37,6 +51,10 @@ impl InstructionPtr {
         //         Wasm validation and Wasmi codegen to never run out
         //         of valid bounds using this method.
         self.ptr = unsafe { self.ptr.offset(by) };
+        #[cfg(feature = "threaded-dispatch")]
+        {
+            self.handler = unsafe { self.handler.offset(by) };
+        }
     }
 
     #[inline(always)]
@@ -45,6 +63,16 @@ impl InstructionPtr {
         //         Wasm validation and Wasmi codegen to never run out
         //         of valid bounds using this method.
         self.ptr = unsafe { self.ptr.add(delta) };
+        #[cfg(feature = "threaded-dispatch")]
+        {
+            self.handler = unsafe { self.handler.add(delta) };
+        }
+    }
+
+    /// Returns the raw pointer to the currently pointed at [`Instruction`].
+    #[inline]
+    pub fn as_ptr(&self) -> *const Instruction {
+        self.ptr
     }
 
     /// Returns a shared reference to the currently pointed at [`Instruction`].
@@ -61,4 +89,20 @@ impl InstructionPtr {
         //         of valid bounds using this method.
         unsafe { &*self.ptr }
     }
+
+    /// Returns the pre-decoded [`Handler`] of the currently pointed at [`Instruction`].
+    ///
+    /// # Safety
+    ///
+    /// The caller is responsible for calling this method only when it is
+    /// guaranteed that the [`InstructionPtr`] is validly pointing inside
+    /// the boundaries of its associated compiled Wasm function.
+    #[cfg(feature = "threaded-dispatch")]
+    #[inline(always)]
+    pub fn handler(&self) -> Handler {
+        // SAFETY: Within Wasm bytecode execution we are guaranteed by
+        //         Wasm validation and Wasmi codegen to never run out
+        //         of valid bounds using this method.
+        unsafe { *self.handler }
+    }
 }
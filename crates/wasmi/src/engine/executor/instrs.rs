@@ -1,7 +1,11 @@
-pub use self::call::{dispatch_host_func, ResumableHostError};
+pub use self::call::{consume_host_func_fuel, dispatch_host_func, ResumableHostError};
+#[cfg(feature = "threaded-dispatch")]
+pub use self::handler::{decode_handlers, Handler};
 use super::{cache::CachedInstance, InstructionPtr, Stack};
 use crate::{
+    backtrace::WasmBacktrace,
     core::{hint, TrapCode, UntypedVal},
+    debugger::StackFrame,
     engine::{
         code_map::CodeMap,
         executor::stack::{CallFrame, FrameRegisters, ValueStack},
@@ -9,6 +13,7 @@ use crate::{
         DedupFuncType,
         EngineFunc,
     },
+    errors::ErrorKind,
     ir::{index, BlockFuel, Const16, Instruction, Reg, ShiftAmount},
     memory::DataSegment,
     store::StoreInner,
@@ -21,6 +26,8 @@ use crate::{
     Store,
     Table,
 };
+#[cfg(feature = "trace")]
+use crate::{TraceEvent, TraceRegisters};
 
 #[cfg(doc)]
 use crate::Instance;
@@ -31,7 +38,12 @@ mod call;
 mod comparison;
 mod conversion;
 mod copy;
+mod fault;
 mod global;
+#[cfg(feature = "threaded-dispatch")]
+mod handler;
+#[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+mod jit;
 mod load;
 mod memory;
 mod return_;
@@ -64,7 +76,108 @@ pub fn execute_instrs<'engine, T>(
 ) -> Result<(), Error> {
     let instance = stack.calls.instance_expect();
     let cache = CachedInstance::new(&mut store.inner, instance);
-    Executor::new(stack, code_map, cache).execute(store)
+    let canonicalize_nans = store.engine().config().get_deterministic();
+    let mut executor = Executor::new(stack, code_map, cache, canonicalize_nans);
+    let mut result = executor.execute(store);
+    let ip = executor.ip;
+    if let Err(error) = &mut result {
+        if !matches!(error.kind(), ErrorKind::ResumableHost(_)) {
+            // Note: the trapped call frames are symbolicated from their instruction pointers
+            //       and only the executor knows the instruction pointer of the top-most one.
+            if let Some(frame) = stack.calls.peek_mut() {
+                frame.update_instr_ptr(ip);
+            }
+            // Note: offsets are also recorded for the `trace` crate feature but
+            //       trap offsets must not depend on enabled crate features.
+            let symbolicate = store.debug.is_debugging_enabled()
+                || store.debug.is_coredump_enabled()
+                || store.debug.is_backtrace_enabled();
+            if symbolicate && error.trap_details().is_some() {
+                attach_trap_offset(stack, error);
+            }
+            if store.debug.is_backtrace_enabled() {
+                capture_backtrace(stack, code_map, error);
+            }
+            let frames = stack_frames(stack);
+            store.debug.trap(&store.inner, code_map, frames, error);
+            store.inner.profile_unwind(stack.calls.len());
+        }
+    }
+    result
+}
+
+/// Attaches the offset of the trapping Wasm operator to the [`TrapDetails`] of `error`.
+///
+/// # Note
+///
+/// The offset is only known for functions compiled with the Wasm offsets of their instructions.
+///
+/// [`TrapDetails`]: crate::TrapDetails
+#[cold]
+fn attach_trap_offset(stack: &Stack, error: &mut Error) {
+    let Some((_, offset)) = stack
+        .calls
+        .peek()
+        .and_then(|frame| frame.symbolicate(false))
+    else {
+        return;
+    };
+    if let Some(details) = error.trap_details_mut() {
+        if details.offset().is_none() {
+            details.set_offset(offset);
+        }
+    }
+}
+
+/// Returns the [`StackFrame`]s of all call frames on the `stack`.
+///
+/// The [`StackFrame`]s are yielded from the root frame to the top-most frame.
+fn stack_frames(stack: &mut Stack) -> impl Iterator<Item = StackFrame> + '_ {
+    let values = &mut stack.values;
+    let len_frames = stack.calls.len();
+    stack
+        .calls
+        .frames_with_instances()
+        .enumerate()
+        .map(move |(index, (frame, instance))| {
+            let is_caller = index + 1 != len_frames;
+            // Safety: the base offsets of all call frames on the call stack
+            //         refer to their live registers.
+            let regs = unsafe { values.stack_ptr_at(frame.base_offset()) };
+            StackFrame {
+                instance: *instance,
+                func: frame.func(),
+                instr: frame.instr_position(is_caller),
+                regs,
+            }
+        })
+}
+
+/// Attaches a [`WasmBacktrace`] of the trapped execution to `error`.
+///
+/// # Note
+///
+/// No [`WasmBacktrace`] is captured for explicit program exits or if
+/// `error` already carries one captured by a nested execution.
+///
+/// [`WasmBacktrace`]: crate::WasmBacktrace
+#[cold]
+fn capture_backtrace(stack: &Stack, code_map: &CodeMap, error: &mut Error) {
+    if error.i32_exit_status().is_some() || error.backtrace().is_some() {
+        return;
+    }
+    let frames = stack.calls.frames();
+    let len_frames = frames.len();
+    let frames = frames
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(index, frame)| {
+            let is_caller = index + 1 != len_frames;
+            let (func_index, offset) = frame.symbolicate(is_caller)?;
+            Some((frame.func(), func_index, offset))
+        });
+    error.set_backtrace(WasmBacktrace::capture(code_map, frames));
 }
 
 /// An execution context for executing a Wasmi function frame.
@@ -82,6 +195,11 @@ struct Executor<'engine> {
     ///
     /// [`Engine`]: crate::Engine
     code_map: &'engine CodeMap,
+    /// Is `true` if NaN results of float instructions must be canonicalized.
+    canonicalize_nans: bool,
+    /// Is `true` if the machine code of JIT compiled functions may be executed.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    jit: bool,
 }
 
 impl<'engine> Executor<'engine> {
@@ -91,6 +209,7 @@ impl<'engine> Executor<'engine> {
         stack: &'engine mut Stack,
         code_map: &'engine CodeMap,
         cache: CachedInstance,
+        canonicalize_nans: bool,
     ) -> Self {
         let frame = stack
             .calls
@@ -107,19 +226,47 @@ impl<'engine> Executor<'engine> {
             cache,
             stack,
             code_map,
+            canonicalize_nans,
+            #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+            jit: true,
         }
     }
 
     /// Executes the function frame until it returns or traps.
     #[inline(always)]
-    fn execute<T>(mut self, store: &mut Store<T>) -> Result<(), Error> {
+    fn execute<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
         use Instruction as Instr;
+        #[cfg(all(
+            feature = "jit",
+            feature = "trace",
+            target_arch = "x86_64",
+            target_os = "linux"
+        ))]
+        {
+            // Note: the machine code does not report its instructions to the trace hook.
+            self.jit = !store.has_trace_hook();
+        }
+        #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+        self.execute_jit_root(&mut store.inner);
         loop {
+            #[cfg(feature = "threaded-dispatch")]
+            if Self::uses_handlers(store) {
+                forward_return!(self.execute_handlers(&mut store.inner)?);
+            }
+            #[cfg(feature = "trace")]
+            if store.has_trace_hook() {
+                self.execute_trace_hook(store)?;
+            }
             match *self.ip.get() {
                 Instr::Trap { trap_code } => self.execute_trap(trap_code)?,
                 Instr::ConsumeFuel { block_fuel } => {
                     self.execute_consume_fuel(&mut store.inner, block_fuel)?
                 }
+                Instr::DebugStep { offset } => self.execute_debug_step(store, offset)?,
+                Instr::CoverageCounter { index } => {
+                    self.execute_coverage_counter(&mut store.inner, index)
+                }
+                Instr::TierUpCounter => self.execute_tier_up_counter(store),
                 Instr::Return => {
                     forward_return!(self.execute_return(&mut store.inner))
                 }
@@ -1684,6 +1831,126 @@ impl Executor<'_> {
         self.try_next_instr()
     }
 
+    /// Executes an [`Instruction::DebugStep`].
+    fn execute_debug_step<T>(&mut self, store: &mut Store<T>, offset: u32) -> Result<(), Error> {
+        let instance = *self.stack.calls.instance_expect();
+        let func = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack")
+            .func();
+        let depth = self.stack.calls.len();
+        store.debug.step(
+            &store.inner,
+            self.code_map,
+            instance,
+            func,
+            offset,
+            depth,
+            &self.sp,
+            stack_frames(self.stack),
+        )?;
+        self.next_instr();
+        Ok(())
+    }
+
+    /// Returns `true` if [`Instruction`]s are executed via their pre-decoded [`Handler`]s.
+    ///
+    /// # Note
+    ///
+    /// The trace hook observes every executed [`Instruction`] and thus
+    /// requires the `match` based dispatch if it is installed.
+    #[cfg(feature = "threaded-dispatch")]
+    #[inline(always)]
+    fn uses_handlers<T>(store: &Store<T>) -> bool {
+        #[cfg(feature = "trace")]
+        return !store.has_trace_hook();
+        #[cfg(not(feature = "trace"))]
+        {
+            let _ = store;
+            true
+        }
+    }
+
+    /// Invokes the trace hook of the [`Store`] for the [`Instruction`] that is executed next.
+    #[cfg(feature = "trace")]
+    #[cold]
+    #[inline(never)]
+    fn execute_trace_hook<T>(&self, store: &mut Store<T>) -> Result<(), Error> {
+        let instance = *self.stack.calls.instance_expect();
+        // Note: the executed code might be a retired tier of the function
+        //       if the function has been tiered up by another execution.
+        let code = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack")
+            .code();
+        let instr = self.ip.get();
+        let pos = code
+            .position(instr)
+            .expect("executed instruction must belong to the executed code");
+        let offsets = code
+            .offsets()
+            .expect("compiled functions must have Wasm offsets with the `trace` crate feature");
+        let len_consts = code.consts().len() as u16;
+        // Safety: the registers of the executed function frame are valid to read.
+        let registers =
+            unsafe { TraceRegisters::new(&self.sp, len_consts, code.len_registers() - len_consts) };
+        let event = TraceEvent {
+            instance,
+            func_index: offsets.func_index(),
+            offset: offsets.offset(pos),
+            pos,
+            instr,
+            registers,
+        };
+        store.invoke_trace_hook(&event)
+    }
+
+    /// Executes an [`Instruction::CoverageCounter`].
+    fn execute_coverage_counter(&mut self, store: &mut StoreInner, index: u32) {
+        let func = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack")
+            .func();
+        store.coverage_mut().hit(func, index);
+        self.next_instr();
+    }
+
+    /// Executes an [`Instruction::TierUpCounter`].
+    ///
+    /// # Note
+    ///
+    /// Calls and loop iterations are not counted while a trace hook is installed
+    /// since the trace hook reports the instructions of the baseline tier.
+    fn execute_tier_up_counter<T>(&mut self, store: &Store<T>) {
+        #[cfg(not(feature = "trace"))]
+        let _ = store;
+        #[cfg(feature = "trace")]
+        if store.has_trace_hook() {
+            self.next_instr();
+            return;
+        }
+        self.execute_tier_up_counter_untraced();
+    }
+
+    /// Executes an [`Instruction::TierUpCounter`] while no trace hook is installed.
+    fn execute_tier_up_counter_untraced(&mut self) {
+        let frame = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack");
+        if let Some(hotness) = frame.hotness() {
+            self.code_map.count_tier_up(frame.func(), hotness);
+        }
+        self.next_instr();
+    }
+
     /// Executes an [`Instruction::RefFunc`].
     fn execute_ref_func(&mut self, result: Reg, func_index: index::Func) {
         let func = self.get_func(func_index);
@@ -1694,7 +1961,7 @@ impl Executor<'_> {
 }
 
 /// Extension method for [`UntypedVal`] required by the [`Executor`].
-trait UntypedValueExt {
+pub(crate) trait UntypedValueExt {
     /// Executes a fused `i32.and` + `i32.eqz` instruction.
     fn i32_and_eqz(x: UntypedVal, y: UntypedVal) -> UntypedVal;
 
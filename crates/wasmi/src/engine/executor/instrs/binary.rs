@@ -50,24 +50,43 @@ impl Executor<'_> {
         (Instruction::I64Rotl, execute_i64_rotl, UntypedVal::i64_rotl),
         (Instruction::I64Rotr, execute_i64_rotr, UntypedVal::i64_rotr),
 
-        (Instruction::F32Add, execute_f32_add, UntypedVal::f32_add),
-        (Instruction::F32Sub, execute_f32_sub, UntypedVal::f32_sub),
-        (Instruction::F32Mul, execute_f32_mul, UntypedVal::f32_mul),
-        (Instruction::F32Div, execute_f32_div, UntypedVal::f32_div),
-        (Instruction::F32Min, execute_f32_min, UntypedVal::f32_min),
-        (Instruction::F32Max, execute_f32_max, UntypedVal::f32_max),
         (Instruction::F32Copysign, execute_f32_copysign, UntypedVal::f32_copysign),
-
-        (Instruction::F64Add, execute_f64_add, UntypedVal::f64_add),
-        (Instruction::F64Sub, execute_f64_sub, UntypedVal::f64_sub),
-        (Instruction::F64Mul, execute_f64_mul, UntypedVal::f64_mul),
-        (Instruction::F64Div, execute_f64_div, UntypedVal::f64_div),
-        (Instruction::F64Min, execute_f64_min, UntypedVal::f64_min),
-        (Instruction::F64Max, execute_f64_max, UntypedVal::f64_max),
         (Instruction::F64Copysign, execute_f64_copysign, UntypedVal::f64_copysign),
     }
 }
 
+macro_rules! impl_binary_float {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr, $canonicalize:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            pub fn $fn_name(&mut self, result: Reg, lhs: Reg, rhs: Reg) {
+                if self.canonicalize_nans {
+                    self.execute_binary(result, lhs, rhs, |lhs, rhs| $canonicalize($op(lhs, rhs)))
+                } else {
+                    self.execute_binary(result, lhs, rhs, $op)
+                }
+            }
+        )*
+    };
+}
+impl Executor<'_> {
+    impl_binary_float! {
+        (Instruction::F32Add, execute_f32_add, UntypedVal::f32_add, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Sub, execute_f32_sub, UntypedVal::f32_sub, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Mul, execute_f32_mul, UntypedVal::f32_mul, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Div, execute_f32_div, UntypedVal::f32_div, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Min, execute_f32_min, UntypedVal::f32_min, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Max, execute_f32_max, UntypedVal::f32_max, UntypedVal::f32_canonicalize_nan),
+
+        (Instruction::F64Add, execute_f64_add, UntypedVal::f64_add, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Sub, execute_f64_sub, UntypedVal::f64_sub, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Mul, execute_f64_mul, UntypedVal::f64_mul, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Div, execute_f64_div, UntypedVal::f64_div, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Min, execute_f64_min, UntypedVal::f64_min, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Max, execute_f64_max, UntypedVal::f64_max, UntypedVal::f64_canonicalize_nan),
+    }
+}
+
 macro_rules! impl_binary_imm16 {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -1,4 +1,9 @@
-use super::{ControlFlow, Executor, InstructionPtr};
+use super::{
+    fault::{bad_signature, indirect_call_to_null, table_out_of_bounds},
+    ControlFlow,
+    Executor,
+    InstructionPtr,
+};
 use crate::{
     core::TrapCode,
     engine::{
@@ -35,6 +40,8 @@ pub fn dispatch_host_func<T>(
 ) -> Result<(u16, u16), Error> {
     let len_params = host_func.len_params();
     let len_results = host_func.len_results();
+    #[cfg(feature = "tracing")]
+    let _span = crate::instrument::host_call_span(len_params, len_results).entered();
     let max_inout = len_params.max(len_results);
     let values = value_stack.as_slice_mut();
     let params_results = FuncParams::new(
@@ -56,6 +63,35 @@ pub fn dispatch_host_func<T>(
     Ok((len_params, len_results))
 }
 
+/// Consumes the fuel charged for calling the host function if it has a [`HostFuncCost`].
+///
+/// # Note
+///
+/// Expects the parameters of the host function call at the top of the `value_stack`.
+/// This must be called before [`dispatch_host_func`] so that running out of fuel
+/// traps without invoking the host function.
+///
+/// # Errors
+///
+/// If the [`Store`] runs out of fuel.
+///
+/// [`HostFuncCost`]: crate::HostFuncCost
+pub fn consume_host_func_fuel<T>(
+    store: &mut Store<T>,
+    value_stack: &ValueStack,
+    host_func: &HostFuncEntity,
+) -> Result<(), TrapCode> {
+    let (trampoline, fuel) = store.resolve_trampoline_and_fuel_mut(host_func.trampoline());
+    let Some(cost) = trampoline.cost() else {
+        return Ok(());
+    };
+    let len_params = usize::from(host_func.len_params());
+    let max_inout = usize::from(host_func.len_params().max(host_func.len_results()));
+    let values = value_stack.as_slice();
+    let params = &values[values.len() - max_inout..][..len_params];
+    fuel.consume_fuel_if(|_| cost.fuel_for(params))
+}
+
 /// The kind of a function call.
 #[derive(Debug, Copy, Clone)]
 pub enum CallKind {
@@ -228,6 +264,7 @@ impl Executor<'_> {
     fn dispatch_compiled_func<C: CallContext>(
         &mut self,
         results: RegSpan,
+        engine_func: EngineFunc,
         func: CompiledFuncRef,
     ) -> Result<CallFrame, Error> {
         // We have to reinstantiate the `self.sp` [`FrameRegisters`] since we just called
@@ -241,8 +278,8 @@ impl Executor<'_> {
             // Safety: We use the base offset of a live call frame on the call stack.
             self.sp = unsafe { this.stack_ptr_at(caller.base_offset()) };
         })?;
-        let instr_ptr = InstructionPtr::new(func.instrs().as_ptr());
-        let frame = CallFrame::new(instr_ptr, offsets, results);
+        let instr_ptr = InstructionPtr::new(func);
+        let frame = CallFrame::new(engine_func, instr_ptr, offsets, results, func);
         if <C as CallContext>::HAS_PARAMS {
             self.copy_call_params(&mut uninit_params);
         }
@@ -313,8 +350,10 @@ impl Executor<'_> {
         func: EngineFunc,
         mut instance: Option<Instance>,
     ) -> Result<(), Error> {
-        let func = self.code_map.get(Some(store.fuel_mut()), func)?;
-        let mut called = self.dispatch_compiled_func::<C>(results, func)?;
+        #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+        let callee_instance = instance;
+        let compiled_func = self.code_map.get(Some(store.fuel_mut()), func)?;
+        let mut called = self.dispatch_compiled_func::<C>(results, func, compiled_func)?;
         match <C as CallContext>::KIND {
             CallKind::Nested => {
                 // We need to update the instruction pointer of the caller call frame.
@@ -335,10 +374,14 @@ impl Executor<'_> {
                 if let Some(caller_instance) = caller_instance {
                     instance.get_or_insert(caller_instance);
                 }
+                store.profile_return();
             }
         }
         self.init_call_frame(&called);
         self.stack.calls.push(called, instance)?;
+        store.profile_call(func);
+        #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+        self.execute_jit(store, compiled_func, callee_instance);
         Ok(())
     }
 
@@ -519,6 +562,9 @@ impl Executor<'_> {
             CallKind::Tail => self.stack.calls.pop(),
         }
         .expect("need to have a caller on the call stack");
+        if matches!(<C as CallContext>::KIND, CallKind::Tail) {
+            store.inner.profile_return();
+        }
         let buffer = self.stack.values.extend_by(max_inout, |this| {
             // Safety: we use the base offset of a live call frame on the call stack.
             self.sp = unsafe { this.stack_ptr_at(caller.base_offset()) };
@@ -527,6 +573,7 @@ impl Executor<'_> {
             let mut uninit_params = FrameParams::new(buffer);
             self.copy_call_params(&mut uninit_params);
         }
+        consume_host_func_fuel(store, &self.stack.values, &host_func)?;
         if matches!(<C as CallContext>::KIND, CallKind::Nested) {
             self.update_instr_ptr_at(1);
         }
@@ -736,18 +783,24 @@ impl Executor<'_> {
         index: u32,
         table: index::Table,
     ) -> Result<ControlFlow, Error> {
-        let table = self.get_table(table);
-        let funcref = store
-            .inner
-            .resolve_table(&table)
+        let table_index = table;
+        let table = store.inner.resolve_table(&self.get_table(table_index));
+        let funcref = table
             .get_untyped(index)
             .map(FuncRef::from)
-            .ok_or(TrapCode::TableOutOfBounds)?;
-        let func = funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
+            .ok_or_else(|| table_out_of_bounds(table_index, index, table.size()))?;
+        let func = funcref
+            .func()
+            .ok_or_else(|| indirect_call_to_null(table_index, index))?;
         let actual_signature = store.inner.resolve_func(func).ty_dedup();
         let expected_signature = &self.get_func_type_dedup(func_type);
         if actual_signature != expected_signature {
-            return Err(Error::from(TrapCode::BadSignature));
+            return Err(bad_signature(
+                table_index,
+                index,
+                store.inner.resolve_func_type(expected_signature),
+                store.inner.resolve_func_type(actual_signature),
+            ));
         }
         self.execute_call_imported_impl::<C, T>(store, results, func)
     }
@@ -15,6 +15,21 @@ macro_rules! impl_conversion_impls {
     };
 }
 
+macro_rules! impl_float_conversion_impls {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr, $canonicalize:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            pub fn $fn_name(&mut self, result: Reg, input: Reg) {
+                if self.canonicalize_nans {
+                    self.execute_unary(result, input, |input| $canonicalize($op(input)))
+                } else {
+                    self.execute_unary(result, input, $op)
+                }
+            }
+        )*
+    };
+}
+
 macro_rules! impl_fallible_conversion_impls {
     ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -45,9 +60,6 @@ impl Executor<'_> {
         (Instruction::I64Extend16S, execute_i64_extend16_s, UntypedVal::i64_extend16_s),
         (Instruction::I64Extend32S, execute_i64_extend32_s, UntypedVal::i64_extend32_s),
 
-        (Instruction::F32DemoteF64, execute_f32_demote_f64, UntypedVal::f32_demote_f64),
-        (Instruction::F64PromoteF32, execute_f64_promote_f32, UntypedVal::f64_promote_f32),
-
         (Instruction::F32ConvertI32S, execute_f32_convert_i32_s, UntypedVal::f32_convert_i32_s),
         (Instruction::F32ConvertI32U, execute_f32_convert_i32_u, UntypedVal::f32_convert_i32_u),
         (Instruction::F32ConvertI64S, execute_f32_convert_i64_s, UntypedVal::f32_convert_i64_s),
@@ -58,6 +70,11 @@ impl Executor<'_> {
         (Instruction::F64ConvertI64U, execute_f64_convert_i64_u, UntypedVal::f64_convert_i64_u),
     }
 
+    impl_float_conversion_impls! {
+        (Instruction::F32DemoteF64, execute_f32_demote_f64, UntypedVal::f32_demote_f64, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F64PromoteF32, execute_f64_promote_f32, UntypedVal::f64_promote_f32, UntypedVal::f64_canonicalize_nan),
+    }
+
     impl_fallible_conversion_impls! {
         (Instruction::I32TruncF32S, execute_i32_trunc_f32_s, UntypedVal::i32_trunc_f32_s),
         (Instruction::I32TruncF32U, execute_i32_trunc_f32_u, UntypedVal::i32_trunc_f32_u),
@@ -0,0 +1,147 @@
+use super::Executor;
+use crate::{
+    core::{TrapCode, UntypedVal},
+    ir::{
+        index::{Memory, Table},
+        Instruction,
+    },
+    Error,
+    FuncType,
+    TrapDetails,
+    TrapFault,
+};
+
+impl Executor<'_> {
+    /// Returns the [`Error`] for the `trap_code` of a load or store at `address` and `offset`.
+    ///
+    /// The number of accessed bytes is decoded from the currently executed [`Instruction`].
+    #[cold]
+    pub(super) fn memory_access_error(
+        &self,
+        trap_code: TrapCode,
+        memory: Memory,
+        address: UntypedVal,
+        offset: u32,
+    ) -> Error {
+        if trap_code != TrapCode::MemoryOutOfBounds {
+            return Error::from(trap_code);
+        }
+        memory_out_of_bounds(
+            memory,
+            u32::from(address),
+            offset,
+            access_width(self.ip.get()),
+        )
+    }
+}
+
+/// Returns the [`Error`] for an out of bounds access of `len` bytes at `address` of `memory`.
+#[cold]
+pub(super) fn memory_out_of_bounds(memory: Memory, address: u32, offset: u32, len: u32) -> Error {
+    Error::from_trap_details(TrapDetails::new(TrapFault::MemoryOutOfBounds {
+        memory: u32::from(memory),
+        address,
+        offset,
+        len,
+    }))
+}
+
+/// Returns the [`Error`] for an out of bounds access at `index` of `table` with `size`.
+#[cold]
+pub(super) fn table_out_of_bounds(table: Table, index: u32, size: u32) -> Error {
+    Error::from_trap_details(TrapDetails::new(TrapFault::TableOutOfBounds {
+        table: u32::from(table),
+        index,
+        size,
+    }))
+}
+
+/// Returns the [`Error`] for an indirect call to a null function reference at `index` of `table`.
+#[cold]
+pub(super) fn indirect_call_to_null(table: Table, index: u32) -> Error {
+    Error::from_trap_details(TrapDetails::new(TrapFault::IndirectCallToNull {
+        table: u32::from(table),
+        index,
+    }))
+}
+
+/// Returns the [`Error`] for an indirect call with mismatching function types at `index` of `table`.
+#[cold]
+pub(super) fn bad_signature(
+    table: Table,
+    index: u32,
+    expected: FuncType,
+    actual: FuncType,
+) -> Error {
+    Error::from_trap_details(TrapDetails::new(TrapFault::BadSignature {
+        table: u32::from(table),
+        index,
+        expected,
+        actual,
+    }))
+}
+
+/// Returns the number of bytes accessed by the load or store `instr`.
+fn access_width(instr: &Instruction) -> u32 {
+    match instr {
+        Instruction::I32Load8s { .. }
+        | Instruction::I32Load8sAt { .. }
+        | Instruction::I32Load8sOffset16 { .. }
+        | Instruction::I32Load8u { .. }
+        | Instruction::I32Load8uAt { .. }
+        | Instruction::I32Load8uOffset16 { .. }
+        | Instruction::I64Load8s { .. }
+        | Instruction::I64Load8sAt { .. }
+        | Instruction::I64Load8sOffset16 { .. }
+        | Instruction::I64Load8u { .. }
+        | Instruction::I64Load8uAt { .. }
+        | Instruction::I64Load8uOffset16 { .. }
+        | Instruction::I32Store8 { .. }
+        | Instruction::I32Store8Imm { .. }
+        | Instruction::I32Store8At { .. }
+        | Instruction::I32Store8AtImm { .. }
+        | Instruction::I32Store8Offset16 { .. }
+        | Instruction::I32Store8Offset16Imm { .. }
+        | Instruction::I64Store8 { .. }
+        | Instruction::I64Store8Imm { .. }
+        | Instruction::I64Store8At { .. }
+        | Instruction::I64Store8AtImm { .. }
+        | Instruction::I64Store8Offset16 { .. }
+        | Instruction::I64Store8Offset16Imm { .. } => 1,
+        Instruction::I32Load16s { .. }
+        | Instruction::I32Load16sAt { .. }
+        | Instruction::I32Load16sOffset16 { .. }
+        | Instruction::I32Load16u { .. }
+        | Instruction::I32Load16uAt { .. }
+        | Instruction::I32Load16uOffset16 { .. }
+        | Instruction::I64Load16s { .. }
+        | Instruction::I64Load16sAt { .. }
+        | Instruction::I64Load16sOffset16 { .. }
+        | Instruction::I64Load16u { .. }
+        | Instruction::I64Load16uAt { .. }
+        | Instruction::I64Load16uOffset16 { .. }
+        | Instruction::I32Store16 { .. }
+        | Instruction::I32Store16Imm { .. }
+        | Instruction::I32Store16At { .. }
+        | Instruction::I32Store16AtImm { .. }
+        | Instruction::I32Store16Offset16 { .. }
+        | Instruction::I32Store16Offset16Imm { .. }
+        | Instruction::I64Store16 { .. }
+        | Instruction::I64Store16Imm { .. }
+        | Instruction::I64Store16At { .. }
+        | Instruction::I64Store16AtImm { .. }
+        | Instruction::I64Store16Offset16 { .. }
+        | Instruction::I64Store16Offset16Imm { .. } => 2,
+        Instruction::Load64 { .. }
+        | Instruction::Load64At { .. }
+        | Instruction::Load64Offset16 { .. }
+        | Instruction::Store64 { .. }
+        | Instruction::Store64At { .. }
+        | Instruction::Store64Offset16 { .. }
+        | Instruction::I64StoreImm16 { .. }
+        | Instruction::I64StoreAtImm16 { .. }
+        | Instruction::I64StoreOffset16Imm16 { .. } => 8,
+        // Note: all remaining load and store instructions access 32-bit values.
+        _ => 4,
+    }
+}
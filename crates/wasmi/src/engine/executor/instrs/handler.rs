@@ -0,0 +1,897 @@
+//! Direct-threaded dispatch of Wasmi bytecode via pre-decoded instruction handlers.
+//!
+//! # Note
+//!
+//! Every [`Instruction`] of a compiled function is pre-decoded into a [`Handler`]
+//! upon compilation. The [`Handler`]s are stored alongside their [`Instruction`]s which
+//! carry their operands. Execution calls the [`Handler`] of the next [`Instruction`]
+//! directly instead of dispatching through the `match` in [`Executor::execute`].
+//!
+//! Stable Rust does not guarantee tail calls. Therefore [`Handler`]s return to a minimal
+//! dispatch loop which calls the next [`Handler`] right away.
+//!
+//! Instructions that require access to the generic [`Store`] such as host function calls
+//! are executed by the `match` based dispatch as a fallback.
+//!
+//! [`Store`]: crate::Store
+
+use super::{ControlFlow, Executor};
+use crate::{
+    core::hint,
+    engine::{utils::unreachable_unchecked, EngineFunc},
+    ir::Instruction,
+    store::StoreInner,
+    Error,
+};
+use alloc::boxed::Box;
+
+/// The signature of [`Handler`] functions.
+type HandlerFn = fn(&mut Executor<'_>, &mut StoreInner) -> Result<Next, Error>;
+
+/// The pre-decoded handler executing an [`Instruction`].
+#[derive(Debug, Copy, Clone)]
+pub struct Handler(HandlerFn);
+
+/// Tells the dispatch loop how to proceed after a [`Handler`] has been executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Next {
+    /// Continue with the [`Handler`] of the next [`Instruction`].
+    Continue,
+    /// Return from the root function of the execution.
+    Return,
+    /// Execute the next [`Instruction`] via the `match` based dispatch.
+    Fallback,
+}
+
+/// Pre-decodes the [`Handler`]s of the compiled `instrs`.
+pub fn decode_handlers(instrs: &[Instruction]) -> Box<[Handler]> {
+    instrs.iter().map(handler).collect()
+}
+
+impl Executor<'_> {
+    /// Executes [`Instruction`]s via their [`Handler`]s until one requires the fallback.
+    ///
+    /// Returns [`ControlFlow::Break`] if execution returned from its root function.
+    #[inline(always)]
+    pub fn execute_handlers(&mut self, store: &mut StoreInner) -> Result<ControlFlow, Error> {
+        loop {
+            let Handler(handler) = self.ip.handler();
+            match handler(self, store)? {
+                Next::Continue => {}
+                Next::Return => return Ok(ControlFlow::Break(())),
+                Next::Fallback => return Ok(ControlFlow::Continue(())),
+            }
+        }
+    }
+}
+
+/// The [`Handler`] of [`Instruction`]s executed via the `match` based dispatch.
+fn fallback(_: &mut Executor<'_>, _: &mut StoreInner) -> Result<Next, Error> {
+    Ok(Next::Fallback)
+}
+
+/// Returns from the [`Handler`] if `$expr` breaks execution of the root function.
+macro_rules! forward_return {
+    ($expr:expr) => {{
+        if hint::unlikely($expr.is_break()) {
+            return Ok(Next::Return);
+        }
+    }};
+}
+
+macro_rules! handlers {
+    (
+        $(
+            fn $name:ident($exec:ident, $store:pat) = $variant:ident $({ $($field:ident),* })?
+                => $body:expr;
+        )*
+    ) => {
+        /// Returns the [`Handler`] of `instr`.
+        fn handler(instr: &Instruction) -> Handler {
+            match instr {
+                $( Instruction::$variant { .. } => Handler($name), )*
+                _ => Handler(fallback),
+            }
+        }
+
+        $(
+            #[doc = concat!("The [`Handler`] of [`Instruction::", stringify!($variant), "`].")]
+            fn $name($exec: &mut Executor<'_>, $store: &mut StoreInner) -> Result<Next, Error> {
+                let Instruction::$variant { $($($field),*)? } = *$exec.ip.get() else {
+                    // Safety: handlers are only ever decoded for their own instructions.
+                    unsafe {
+                        unreachable_unchecked!(
+                            "expected {} but found: {:?}",
+                            stringify!($variant),
+                            $exec.ip.get(),
+                        )
+                    }
+                };
+                $body;
+                Ok(Next::Continue)
+            }
+        )*
+    };
+}
+handlers! {
+    fn trap(exec, _) = Trap { trap_code } => exec.execute_trap(trap_code)?;
+    fn consume_fuel(exec, store) = ConsumeFuel { block_fuel }
+        => exec.execute_consume_fuel(store, block_fuel)?;
+    fn coverage_counter(exec, store) = CoverageCounter { index }
+        => exec.execute_coverage_counter(store, index);
+    fn tier_up_counter(exec, _) = TierUpCounter => exec.execute_tier_up_counter_untraced();
+    fn return_(exec, store) = Return => forward_return!(exec.execute_return(store));
+    fn return_reg(exec, store) = ReturnReg { value }
+        => forward_return!(exec.execute_return_reg(store, value));
+    fn return_reg2(exec, store) = ReturnReg2 { values }
+        => forward_return!(exec.execute_return_reg2(store, values));
+    fn return_reg3(exec, store) = ReturnReg3 { values }
+        => forward_return!(exec.execute_return_reg3(store, values));
+    fn return_imm32(exec, store) = ReturnImm32 { value }
+        => forward_return!(exec.execute_return_imm32(store, value));
+    fn return_i64imm32(exec, store) = ReturnI64Imm32 { value }
+        => forward_return!(exec.execute_return_i64imm32(store, value));
+    fn return_f64imm32(exec, store) = ReturnF64Imm32 { value }
+        => forward_return!(exec.execute_return_f64imm32(store, value));
+    fn return_span(exec, store) = ReturnSpan { values }
+        => forward_return!(exec.execute_return_span(store, values));
+    fn return_many(exec, store) = ReturnMany { values }
+        => forward_return!(exec.execute_return_many(store, values));
+    fn return_nez(exec, store) = ReturnNez { condition }
+        => forward_return!(exec.execute_return_nez(store, condition));
+    fn return_nez_reg(exec, store) = ReturnNezReg { condition, value }
+        => forward_return!(exec.execute_return_nez_reg(store, condition, value));
+    fn return_nez_reg2(exec, store) = ReturnNezReg2 { condition, values }
+        => forward_return!(exec.execute_return_nez_reg2(store, condition, values));
+    fn return_nez_imm32(exec, store) = ReturnNezImm32 { condition, value }
+        => forward_return!(exec.execute_return_nez_imm32(store, condition, value));
+    fn return_nez_i64imm32(exec, store) = ReturnNezI64Imm32 { condition, value }
+        => forward_return!(exec.execute_return_nez_i64imm32(store, condition, value));
+    fn return_nez_f64imm32(exec, store) = ReturnNezF64Imm32 { condition, value }
+        => forward_return!(exec.execute_return_nez_f64imm32(store, condition, value));
+    fn return_nez_span(exec, store) = ReturnNezSpan { condition, values }
+        => forward_return!(exec.execute_return_nez_span(store, condition, values));
+    fn return_nez_many(exec, store) = ReturnNezMany { condition, values }
+        => forward_return!(exec.execute_return_nez_many(store, condition, values));
+    fn branch(exec, _) = Branch { offset } => exec.execute_branch(offset);
+    fn branch_table_0(exec, _) = BranchTable0 { index, len_targets }
+        => exec.execute_branch_table_0(index, len_targets);
+    fn branch_table_1(exec, _) = BranchTable1 { index, len_targets }
+        => exec.execute_branch_table_1(index, len_targets);
+    fn branch_table_2(exec, _) = BranchTable2 { index, len_targets }
+        => exec.execute_branch_table_2(index, len_targets);
+    fn branch_table_3(exec, _) = BranchTable3 { index, len_targets }
+        => exec.execute_branch_table_3(index, len_targets);
+    fn branch_table_span(exec, _) = BranchTableSpan { index, len_targets }
+        => exec.execute_branch_table_span(index, len_targets);
+    fn branch_table_many(exec, _) = BranchTableMany { index, len_targets }
+        => exec.execute_branch_table_many(index, len_targets);
+    fn branch_cmp_fallback(exec, _) = BranchCmpFallback { lhs, rhs, params }
+        => exec.execute_branch_cmp_fallback(lhs, rhs, params);
+    fn branch_i32_and(exec, _) = BranchI32And { lhs, rhs, offset }
+        => exec.execute_branch_i32_and(lhs, rhs, offset);
+    fn branch_i32_and_imm16(exec, _) = BranchI32AndImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_and_imm16(lhs, rhs, offset);
+    fn branch_i32_or(exec, _) = BranchI32Or { lhs, rhs, offset }
+        => exec.execute_branch_i32_or(lhs, rhs, offset);
+    fn branch_i32_or_imm16(exec, _) = BranchI32OrImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_or_imm16(lhs, rhs, offset);
+    fn branch_i32_xor(exec, _) = BranchI32Xor { lhs, rhs, offset }
+        => exec.execute_branch_i32_xor(lhs, rhs, offset);
+    fn branch_i32_xor_imm16(exec, _) = BranchI32XorImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_xor_imm16(lhs, rhs, offset);
+    fn branch_i32_and_eqz(exec, _) = BranchI32AndEqz { lhs, rhs, offset }
+        => exec.execute_branch_i32_and_eqz(lhs, rhs, offset);
+    fn branch_i32_and_eqz_imm16(exec, _) = BranchI32AndEqzImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_and_eqz_imm16(lhs, rhs, offset);
+    fn branch_i32_or_eqz(exec, _) = BranchI32OrEqz { lhs, rhs, offset }
+        => exec.execute_branch_i32_or_eqz(lhs, rhs, offset);
+    fn branch_i32_or_eqz_imm16(exec, _) = BranchI32OrEqzImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_or_eqz_imm16(lhs, rhs, offset);
+    fn branch_i32_xor_eqz(exec, _) = BranchI32XorEqz { lhs, rhs, offset }
+        => exec.execute_branch_i32_xor_eqz(lhs, rhs, offset);
+    fn branch_i32_xor_eqz_imm16(exec, _) = BranchI32XorEqzImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_xor_eqz_imm16(lhs, rhs, offset);
+    fn branch_i32_eq(exec, _) = BranchI32Eq { lhs, rhs, offset }
+        => exec.execute_branch_i32_eq(lhs, rhs, offset);
+    fn branch_i32_eq_imm16(exec, _) = BranchI32EqImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_eq_imm16(lhs, rhs, offset);
+    fn branch_i32_ne(exec, _) = BranchI32Ne { lhs, rhs, offset }
+        => exec.execute_branch_i32_ne(lhs, rhs, offset);
+    fn branch_i32_ne_imm16(exec, _) = BranchI32NeImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i32_ne_imm16(lhs, rhs, offset);
+    fn branch_i32_lt_s(exec, _) = BranchI32LtS { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_s(lhs, rhs, offset);
+    fn branch_i32_lt_s_imm16_lhs(exec, _) = BranchI32LtSImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_s_imm16_lhs(lhs, rhs, offset);
+    fn branch_i32_lt_s_imm16_rhs(exec, _) = BranchI32LtSImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_s_imm16_rhs(lhs, rhs, offset);
+    fn branch_i32_lt_u(exec, _) = BranchI32LtU { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_u(lhs, rhs, offset);
+    fn branch_i32_lt_u_imm16_lhs(exec, _) = BranchI32LtUImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_u_imm16_lhs(lhs, rhs, offset);
+    fn branch_i32_lt_u_imm16_rhs(exec, _) = BranchI32LtUImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_lt_u_imm16_rhs(lhs, rhs, offset);
+    fn branch_i32_le_s(exec, _) = BranchI32LeS { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_s(lhs, rhs, offset);
+    fn branch_i32_le_s_imm16_lhs(exec, _) = BranchI32LeSImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_s_imm16_lhs(lhs, rhs, offset);
+    fn branch_i32_le_s_imm16_rhs(exec, _) = BranchI32LeSImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_s_imm16_rhs(lhs, rhs, offset);
+    fn branch_i32_le_u(exec, _) = BranchI32LeU { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_u(lhs, rhs, offset);
+    fn branch_i32_le_u_imm16_lhs(exec, _) = BranchI32LeUImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_u_imm16_lhs(lhs, rhs, offset);
+    fn branch_i32_le_u_imm16_rhs(exec, _) = BranchI32LeUImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i32_le_u_imm16_rhs(lhs, rhs, offset);
+    fn branch_i64_eq(exec, _) = BranchI64Eq { lhs, rhs, offset }
+        => exec.execute_branch_i64_eq(lhs, rhs, offset);
+    fn branch_i64_eq_imm16(exec, _) = BranchI64EqImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i64_eq_imm16(lhs, rhs, offset);
+    fn branch_i64_ne(exec, _) = BranchI64Ne { lhs, rhs, offset }
+        => exec.execute_branch_i64_ne(lhs, rhs, offset);
+    fn branch_i64_ne_imm16(exec, _) = BranchI64NeImm16 { lhs, rhs, offset }
+        => exec.execute_branch_i64_ne_imm16(lhs, rhs, offset);
+    fn branch_i64_lt_s(exec, _) = BranchI64LtS { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_s(lhs, rhs, offset);
+    fn branch_i64_lt_s_imm16_lhs(exec, _) = BranchI64LtSImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_s_imm16_lhs(lhs, rhs, offset);
+    fn branch_i64_lt_s_imm16_rhs(exec, _) = BranchI64LtSImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_s_imm16_rhs(lhs, rhs, offset);
+    fn branch_i64_lt_u(exec, _) = BranchI64LtU { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_u(lhs, rhs, offset);
+    fn branch_i64_lt_u_imm16_lhs(exec, _) = BranchI64LtUImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_u_imm16_lhs(lhs, rhs, offset);
+    fn branch_i64_lt_u_imm16_rhs(exec, _) = BranchI64LtUImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_lt_u_imm16_rhs(lhs, rhs, offset);
+    fn branch_i64_le_s(exec, _) = BranchI64LeS { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_s(lhs, rhs, offset);
+    fn branch_i64_le_s_imm16_lhs(exec, _) = BranchI64LeSImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_s_imm16_lhs(lhs, rhs, offset);
+    fn branch_i64_le_s_imm16_rhs(exec, _) = BranchI64LeSImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_s_imm16_rhs(lhs, rhs, offset);
+    fn branch_i64_le_u(exec, _) = BranchI64LeU { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_u(lhs, rhs, offset);
+    fn branch_i64_le_u_imm16_lhs(exec, _) = BranchI64LeUImm16Lhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_u_imm16_lhs(lhs, rhs, offset);
+    fn branch_i64_le_u_imm16_rhs(exec, _) = BranchI64LeUImm16Rhs { lhs, rhs, offset }
+        => exec.execute_branch_i64_le_u_imm16_rhs(lhs, rhs, offset);
+    fn branch_f32_eq(exec, _) = BranchF32Eq { lhs, rhs, offset }
+        => exec.execute_branch_f32_eq(lhs, rhs, offset);
+    fn branch_f32_ne(exec, _) = BranchF32Ne { lhs, rhs, offset }
+        => exec.execute_branch_f32_ne(lhs, rhs, offset);
+    fn branch_f32_lt(exec, _) = BranchF32Lt { lhs, rhs, offset }
+        => exec.execute_branch_f32_lt(lhs, rhs, offset);
+    fn branch_f32_le(exec, _) = BranchF32Le { lhs, rhs, offset }
+        => exec.execute_branch_f32_le(lhs, rhs, offset);
+    fn branch_f64_eq(exec, _) = BranchF64Eq { lhs, rhs, offset }
+        => exec.execute_branch_f64_eq(lhs, rhs, offset);
+    fn branch_f64_ne(exec, _) = BranchF64Ne { lhs, rhs, offset }
+        => exec.execute_branch_f64_ne(lhs, rhs, offset);
+    fn branch_f64_lt(exec, _) = BranchF64Lt { lhs, rhs, offset }
+        => exec.execute_branch_f64_lt(lhs, rhs, offset);
+    fn branch_f64_le(exec, _) = BranchF64Le { lhs, rhs, offset }
+        => exec.execute_branch_f64_le(lhs, rhs, offset);
+    fn copy(exec, _) = Copy { result, value } => exec.execute_copy(result, value);
+    fn copy_2(exec, _) = Copy2 { results, values } => exec.execute_copy_2(results, values);
+    fn copy_imm32(exec, _) = CopyImm32 { result, value } => exec.execute_copy_imm32(result, value);
+    fn copy_i64imm32(exec, _) = CopyI64Imm32 { result, value }
+        => exec.execute_copy_i64imm32(result, value);
+    fn copy_f64imm32(exec, _) = CopyF64Imm32 { result, value }
+        => exec.execute_copy_f64imm32(result, value);
+    fn copy_span(exec, _) = CopySpan { results, values, len }
+        => exec.execute_copy_span(results, values, len);
+    fn copy_span_non_overlapping(exec, _) = CopySpanNonOverlapping { results, values, len }
+        => exec.execute_copy_span_non_overlapping(results, values, len);
+    fn copy_many(exec, _) = CopyMany { results, values } => exec.execute_copy_many(results, values);
+    fn copy_many_non_overlapping(exec, _) = CopyManyNonOverlapping { results, values }
+        => exec.execute_copy_many_non_overlapping(results, values);
+    fn return_call_internal_0(exec, store) = ReturnCallInternal0 { func }
+        => exec.execute_return_call_internal_0(store, EngineFunc::from(func))?;
+    fn return_call_internal(exec, store) = ReturnCallInternal { func }
+        => exec.execute_return_call_internal(store, EngineFunc::from(func))?;
+    fn call_internal_0(exec, store) = CallInternal0 { results, func }
+        => exec.execute_call_internal_0(store, results, EngineFunc::from(func))?;
+    fn call_internal(exec, store) = CallInternal { results, func }
+        => exec.execute_call_internal(store, results, EngineFunc::from(func))?;
+    fn select(exec, _) = Select { result, lhs } => exec.execute_select(result, lhs);
+    fn select_imm32_rhs(exec, _) = SelectImm32Rhs { result, lhs }
+        => exec.execute_select_imm32_rhs(result, lhs);
+    fn select_imm32_lhs(exec, _) = SelectImm32Lhs { result, lhs }
+        => exec.execute_select_imm32_lhs(result, lhs);
+    fn select_imm32(exec, _) = SelectImm32 { result, lhs }
+        => exec.execute_select_imm32(result, lhs);
+    fn select_i64imm32_rhs(exec, _) = SelectI64Imm32Rhs { result, lhs }
+        => exec.execute_select_i64imm32_rhs(result, lhs);
+    fn select_i64imm32_lhs(exec, _) = SelectI64Imm32Lhs { result, lhs }
+        => exec.execute_select_i64imm32_lhs(result, lhs);
+    fn select_i64imm32(exec, _) = SelectI64Imm32 { result, lhs }
+        => exec.execute_select_i64imm32(result, lhs);
+    fn select_f64imm32_rhs(exec, _) = SelectF64Imm32Rhs { result, lhs }
+        => exec.execute_select_f64imm32_rhs(result, lhs);
+    fn select_f64imm32_lhs(exec, _) = SelectF64Imm32Lhs { result, lhs }
+        => exec.execute_select_f64imm32_lhs(result, lhs);
+    fn select_f64imm32(exec, _) = SelectF64Imm32 { result, lhs }
+        => exec.execute_select_f64imm32(result, lhs);
+    fn ref_func(exec, _) = RefFunc { result, func } => exec.execute_ref_func(result, func);
+    fn global_get(exec, store) = GlobalGet { result, global }
+        => exec.execute_global_get(store, result, global);
+    fn global_set(exec, store) = GlobalSet { global, input }
+        => exec.execute_global_set(store, global, input);
+    fn global_set_i32imm16(exec, store) = GlobalSetI32Imm16 { global, input }
+        => exec.execute_global_set_i32imm16(store, global, input);
+    fn global_set_i64imm16(exec, store) = GlobalSetI64Imm16 { global, input }
+        => exec.execute_global_set_i64imm16(store, global, input);
+    fn load32(exec, store) = Load32 { result, memory }
+        => exec.execute_load32(store, result, memory)?;
+    fn load32_at(exec, store) = Load32At { result, address }
+        => exec.execute_load32_at(store, result, address)?;
+    fn load32_offset16(exec, _) = Load32Offset16 { result, ptr, offset }
+        => exec.execute_load32_offset16(result, ptr, offset)?;
+    fn load64(exec, store) = Load64 { result, memory }
+        => exec.execute_load64(store, result, memory)?;
+    fn load64_at(exec, store) = Load64At { result, address }
+        => exec.execute_load64_at(store, result, address)?;
+    fn load64_offset16(exec, _) = Load64Offset16 { result, ptr, offset }
+        => exec.execute_load64_offset16(result, ptr, offset)?;
+    fn i32_load8_s(exec, store) = I32Load8s { result, memory }
+        => exec.execute_i32_load8_s(store, result, memory)?;
+    fn i32_load8_s_at(exec, store) = I32Load8sAt { result, address }
+        => exec.execute_i32_load8_s_at(store, result, address)?;
+    fn i32_load8_s_offset16(exec, _) = I32Load8sOffset16 { result, ptr, offset }
+        => exec.execute_i32_load8_s_offset16(result, ptr, offset)?;
+    fn i32_load8_u(exec, store) = I32Load8u { result, memory }
+        => exec.execute_i32_load8_u(store, result, memory)?;
+    fn i32_load8_u_at(exec, store) = I32Load8uAt { result, address }
+        => exec.execute_i32_load8_u_at(store, result, address)?;
+    fn i32_load8_u_offset16(exec, _) = I32Load8uOffset16 { result, ptr, offset }
+        => exec.execute_i32_load8_u_offset16(result, ptr, offset)?;
+    fn i32_load16_s(exec, store) = I32Load16s { result, memory }
+        => exec.execute_i32_load16_s(store, result, memory)?;
+    fn i32_load16_s_at(exec, store) = I32Load16sAt { result, address }
+        => exec.execute_i32_load16_s_at(store, result, address)?;
+    fn i32_load16_s_offset16(exec, _) = I32Load16sOffset16 { result, ptr, offset }
+        => exec.execute_i32_load16_s_offset16(result, ptr, offset)?;
+    fn i32_load16_u(exec, store) = I32Load16u { result, memory }
+        => exec.execute_i32_load16_u(store, result, memory)?;
+    fn i32_load16_u_at(exec, store) = I32Load16uAt { result, address }
+        => exec.execute_i32_load16_u_at(store, result, address)?;
+    fn i32_load16_u_offset16(exec, _) = I32Load16uOffset16 { result, ptr, offset }
+        => exec.execute_i32_load16_u_offset16(result, ptr, offset)?;
+    fn i64_load8_s(exec, store) = I64Load8s { result, memory }
+        => exec.execute_i64_load8_s(store, result, memory)?;
+    fn i64_load8_s_at(exec, store) = I64Load8sAt { result, address }
+        => exec.execute_i64_load8_s_at(store, result, address)?;
+    fn i64_load8_s_offset16(exec, _) = I64Load8sOffset16 { result, ptr, offset }
+        => exec.execute_i64_load8_s_offset16(result, ptr, offset)?;
+    fn i64_load8_u(exec, store) = I64Load8u { result, memory }
+        => exec.execute_i64_load8_u(store, result, memory)?;
+    fn i64_load8_u_at(exec, store) = I64Load8uAt { result, address }
+        => exec.execute_i64_load8_u_at(store, result, address)?;
+    fn i64_load8_u_offset16(exec, _) = I64Load8uOffset16 { result, ptr, offset }
+        => exec.execute_i64_load8_u_offset16(result, ptr, offset)?;
+    fn i64_load16_s(exec, store) = I64Load16s { result, memory }
+        => exec.execute_i64_load16_s(store, result, memory)?;
+    fn i64_load16_s_at(exec, store) = I64Load16sAt { result, address }
+        => exec.execute_i64_load16_s_at(store, result, address)?;
+    fn i64_load16_s_offset16(exec, _) = I64Load16sOffset16 { result, ptr, offset }
+        => exec.execute_i64_load16_s_offset16(result, ptr, offset)?;
+    fn i64_load16_u(exec, store) = I64Load16u { result, memory }
+        => exec.execute_i64_load16_u(store, result, memory)?;
+    fn i64_load16_u_at(exec, store) = I64Load16uAt { result, address }
+        => exec.execute_i64_load16_u_at(store, result, address)?;
+    fn i64_load16_u_offset16(exec, _) = I64Load16uOffset16 { result, ptr, offset }
+        => exec.execute_i64_load16_u_offset16(result, ptr, offset)?;
+    fn i64_load32_s(exec, store) = I64Load32s { result, memory }
+        => exec.execute_i64_load32_s(store, result, memory)?;
+    fn i64_load32_s_at(exec, store) = I64Load32sAt { result, address }
+        => exec.execute_i64_load32_s_at(store, result, address)?;
+    fn i64_load32_s_offset16(exec, _) = I64Load32sOffset16 { result, ptr, offset }
+        => exec.execute_i64_load32_s_offset16(result, ptr, offset)?;
+    fn i64_load32_u(exec, store) = I64Load32u { result, memory }
+        => exec.execute_i64_load32_u(store, result, memory)?;
+    fn i64_load32_u_at(exec, store) = I64Load32uAt { result, address }
+        => exec.execute_i64_load32_u_at(store, result, address)?;
+    fn i64_load32_u_offset16(exec, _) = I64Load32uOffset16 { result, ptr, offset }
+        => exec.execute_i64_load32_u_offset16(result, ptr, offset)?;
+    fn store32(exec, store) = Store32 { ptr, memory } => exec.execute_store32(store, ptr, memory)?;
+    fn store32_offset16(exec, _) = Store32Offset16 { ptr, offset, value }
+        => exec.execute_store32_offset16(ptr, offset, value)?;
+    fn store32_at(exec, store) = Store32At { address, value }
+        => exec.execute_store32_at(store, address, value)?;
+    fn store64(exec, store) = Store64 { ptr, memory } => exec.execute_store64(store, ptr, memory)?;
+    fn store64_offset16(exec, _) = Store64Offset16 { ptr, offset, value }
+        => exec.execute_store64_offset16(ptr, offset, value)?;
+    fn store64_at(exec, store) = Store64At { address, value }
+        => exec.execute_store64_at(store, address, value)?;
+    fn i32_store_imm16(exec, store) = I32StoreImm16 { ptr, memory }
+        => exec.execute_i32_store_imm16(store, ptr, memory)?;
+    fn i32_store_offset16_imm16(exec, _) = I32StoreOffset16Imm16 { ptr, offset, value }
+        => exec.execute_i32_store_offset16_imm16(ptr, offset, value)?;
+    fn i32_store_at_imm16(exec, store) = I32StoreAtImm16 { address, value }
+        => exec.execute_i32_store_at_imm16(store, address, value)?;
+    fn i32_store8(exec, store) = I32Store8 { ptr, memory }
+        => exec.execute_i32_store8(store, ptr, memory)?;
+    fn i32_store8_imm(exec, store) = I32Store8Imm { ptr, memory }
+        => exec.execute_i32_store8_imm(store, ptr, memory)?;
+    fn i32_store8_offset16(exec, _) = I32Store8Offset16 { ptr, offset, value }
+        => exec.execute_i32_store8_offset16(ptr, offset, value)?;
+    fn i32_store8_offset16_imm(exec, _) = I32Store8Offset16Imm { ptr, offset, value }
+        => exec.execute_i32_store8_offset16_imm(ptr, offset, value)?;
+    fn i32_store8_at(exec, store) = I32Store8At { address, value }
+        => exec.execute_i32_store8_at(store, address, value)?;
+    fn i32_store8_at_imm(exec, store) = I32Store8AtImm { address, value }
+        => exec.execute_i32_store8_at_imm(store, address, value)?;
+    fn i32_store16(exec, store) = I32Store16 { ptr, memory }
+        => exec.execute_i32_store16(store, ptr, memory)?;
+    fn i32_store16_imm(exec, store) = I32Store16Imm { ptr, memory }
+        => exec.execute_i32_store16_imm(store, ptr, memory)?;
+    fn i32_store16_offset16(exec, _) = I32Store16Offset16 { ptr, offset, value }
+        => exec.execute_i32_store16_offset16(ptr, offset, value)?;
+    fn i32_store16_offset16_imm(exec, _) = I32Store16Offset16Imm { ptr, offset, value }
+        => exec.execute_i32_store16_offset16_imm(ptr, offset, value)?;
+    fn i32_store16_at(exec, store) = I32Store16At { address, value }
+        => exec.execute_i32_store16_at(store, address, value)?;
+    fn i32_store16_at_imm(exec, store) = I32Store16AtImm { address, value }
+        => exec.execute_i32_store16_at_imm(store, address, value)?;
+    fn i64_store_imm16(exec, store) = I64StoreImm16 { ptr, memory }
+        => exec.execute_i64_store_imm16(store, ptr, memory)?;
+    fn i64_store_offset16_imm16(exec, _) = I64StoreOffset16Imm16 { ptr, offset, value }
+        => exec.execute_i64_store_offset16_imm16(ptr, offset, value)?;
+    fn i64_store_at_imm16(exec, store) = I64StoreAtImm16 { address, value }
+        => exec.execute_i64_store_at_imm16(store, address, value)?;
+    fn i64_store8(exec, store) = I64Store8 { ptr, memory }
+        => exec.execute_i64_store8(store, ptr, memory)?;
+    fn i64_store8_imm(exec, store) = I64Store8Imm { ptr, memory }
+        => exec.execute_i64_store8_imm(store, ptr, memory)?;
+    fn i64_store8_offset16(exec, _) = I64Store8Offset16 { ptr, offset, value }
+        => exec.execute_i64_store8_offset16(ptr, offset, value)?;
+    fn i64_store8_offset16_imm(exec, _) = I64Store8Offset16Imm { ptr, offset, value }
+        => exec.execute_i64_store8_offset16_imm(ptr, offset, value)?;
+    fn i64_store8_at(exec, store) = I64Store8At { address, value }
+        => exec.execute_i64_store8_at(store, address, value)?;
+    fn i64_store8_at_imm(exec, store) = I64Store8AtImm { address, value }
+        => exec.execute_i64_store8_at_imm(store, address, value)?;
+    fn i64_store16(exec, store) = I64Store16 { ptr, memory }
+        => exec.execute_i64_store16(store, ptr, memory)?;
+    fn i64_store16_imm(exec, store) = I64Store16Imm { ptr, memory }
+        => exec.execute_i64_store16_imm(store, ptr, memory)?;
+    fn i64_store16_offset16(exec, _) = I64Store16Offset16 { ptr, offset, value }
+        => exec.execute_i64_store16_offset16(ptr, offset, value)?;
+    fn i64_store16_offset16_imm(exec, _) = I64Store16Offset16Imm { ptr, offset, value }
+        => exec.execute_i64_store16_offset16_imm(ptr, offset, value)?;
+    fn i64_store16_at(exec, store) = I64Store16At { address, value }
+        => exec.execute_i64_store16_at(store, address, value)?;
+    fn i64_store16_at_imm(exec, store) = I64Store16AtImm { address, value }
+        => exec.execute_i64_store16_at_imm(store, address, value)?;
+    fn i64_store32(exec, store) = I64Store32 { ptr, memory }
+        => exec.execute_i64_store32(store, ptr, memory)?;
+    fn i64_store32_imm16(exec, store) = I64Store32Imm16 { ptr, memory }
+        => exec.execute_i64_store32_imm16(store, ptr, memory)?;
+    fn i64_store32_offset16(exec, _) = I64Store32Offset16 { ptr, offset, value }
+        => exec.execute_i64_store32_offset16(ptr, offset, value)?;
+    fn i64_store32_offset16_imm16(exec, _) = I64Store32Offset16Imm16 { ptr, offset, value }
+        => exec.execute_i64_store32_offset16_imm16(ptr, offset, value)?;
+    fn i64_store32_at(exec, store) = I64Store32At { address, value }
+        => exec.execute_i64_store32_at(store, address, value)?;
+    fn i64_store32_at_imm16(exec, store) = I64Store32AtImm16 { address, value }
+        => exec.execute_i64_store32_at_imm16(store, address, value)?;
+    fn i32_eq(exec, _) = I32Eq { result, lhs, rhs } => exec.execute_i32_eq(result, lhs, rhs);
+    fn i32_eq_imm16(exec, _) = I32EqImm16 { result, lhs, rhs }
+        => exec.execute_i32_eq_imm16(result, lhs, rhs);
+    fn i32_ne(exec, _) = I32Ne { result, lhs, rhs } => exec.execute_i32_ne(result, lhs, rhs);
+    fn i32_ne_imm16(exec, _) = I32NeImm16 { result, lhs, rhs }
+        => exec.execute_i32_ne_imm16(result, lhs, rhs);
+    fn i32_lt_s(exec, _) = I32LtS { result, lhs, rhs } => exec.execute_i32_lt_s(result, lhs, rhs);
+    fn i32_lt_s_imm16_lhs(exec, _) = I32LtSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_lt_s_imm16_lhs(result, lhs, rhs);
+    fn i32_lt_s_imm16_rhs(exec, _) = I32LtSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_lt_s_imm16_rhs(result, lhs, rhs);
+    fn i32_lt_u(exec, _) = I32LtU { result, lhs, rhs } => exec.execute_i32_lt_u(result, lhs, rhs);
+    fn i32_lt_u_imm16_lhs(exec, _) = I32LtUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_lt_u_imm16_lhs(result, lhs, rhs);
+    fn i32_lt_u_imm16_rhs(exec, _) = I32LtUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_lt_u_imm16_rhs(result, lhs, rhs);
+    fn i32_le_s(exec, _) = I32LeS { result, lhs, rhs } => exec.execute_i32_le_s(result, lhs, rhs);
+    fn i32_le_s_imm16_lhs(exec, _) = I32LeSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_le_s_imm16_lhs(result, lhs, rhs);
+    fn i32_le_s_imm16_rhs(exec, _) = I32LeSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_le_s_imm16_rhs(result, lhs, rhs);
+    fn i32_le_u(exec, _) = I32LeU { result, lhs, rhs } => exec.execute_i32_le_u(result, lhs, rhs);
+    fn i32_le_u_imm16_lhs(exec, _) = I32LeUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_le_u_imm16_lhs(result, lhs, rhs);
+    fn i32_le_u_imm16_rhs(exec, _) = I32LeUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_le_u_imm16_rhs(result, lhs, rhs);
+    fn i64_eq(exec, _) = I64Eq { result, lhs, rhs } => exec.execute_i64_eq(result, lhs, rhs);
+    fn i64_eq_imm16(exec, _) = I64EqImm16 { result, lhs, rhs }
+        => exec.execute_i64_eq_imm16(result, lhs, rhs);
+    fn i64_ne(exec, _) = I64Ne { result, lhs, rhs } => exec.execute_i64_ne(result, lhs, rhs);
+    fn i64_ne_imm16(exec, _) = I64NeImm16 { result, lhs, rhs }
+        => exec.execute_i64_ne_imm16(result, lhs, rhs);
+    fn i64_lt_s(exec, _) = I64LtS { result, lhs, rhs } => exec.execute_i64_lt_s(result, lhs, rhs);
+    fn i64_lt_s_imm16_lhs(exec, _) = I64LtSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_lt_s_imm16_lhs(result, lhs, rhs);
+    fn i64_lt_s_imm16_rhs(exec, _) = I64LtSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_lt_s_imm16_rhs(result, lhs, rhs);
+    fn i64_lt_u(exec, _) = I64LtU { result, lhs, rhs } => exec.execute_i64_lt_u(result, lhs, rhs);
+    fn i64_lt_u_imm16_lhs(exec, _) = I64LtUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_lt_u_imm16_lhs(result, lhs, rhs);
+    fn i64_lt_u_imm16_rhs(exec, _) = I64LtUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_lt_u_imm16_rhs(result, lhs, rhs);
+    fn i64_le_s(exec, _) = I64LeS { result, lhs, rhs } => exec.execute_i64_le_s(result, lhs, rhs);
+    fn i64_le_s_imm16_lhs(exec, _) = I64LeSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_le_s_imm16_lhs(result, lhs, rhs);
+    fn i64_le_s_imm16_rhs(exec, _) = I64LeSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_le_s_imm16_rhs(result, lhs, rhs);
+    fn i64_le_u(exec, _) = I64LeU { result, lhs, rhs } => exec.execute_i64_le_u(result, lhs, rhs);
+    fn i64_le_u_imm16_lhs(exec, _) = I64LeUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_le_u_imm16_lhs(result, lhs, rhs);
+    fn i64_le_u_imm16_rhs(exec, _) = I64LeUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_le_u_imm16_rhs(result, lhs, rhs);
+    fn f32_eq(exec, _) = F32Eq { result, lhs, rhs } => exec.execute_f32_eq(result, lhs, rhs);
+    fn f32_ne(exec, _) = F32Ne { result, lhs, rhs } => exec.execute_f32_ne(result, lhs, rhs);
+    fn f32_lt(exec, _) = F32Lt { result, lhs, rhs } => exec.execute_f32_lt(result, lhs, rhs);
+    fn f32_le(exec, _) = F32Le { result, lhs, rhs } => exec.execute_f32_le(result, lhs, rhs);
+    fn f64_eq(exec, _) = F64Eq { result, lhs, rhs } => exec.execute_f64_eq(result, lhs, rhs);
+    fn f64_ne(exec, _) = F64Ne { result, lhs, rhs } => exec.execute_f64_ne(result, lhs, rhs);
+    fn f64_lt(exec, _) = F64Lt { result, lhs, rhs } => exec.execute_f64_lt(result, lhs, rhs);
+    fn f64_le(exec, _) = F64Le { result, lhs, rhs } => exec.execute_f64_le(result, lhs, rhs);
+    fn i32_clz(exec, _) = I32Clz { result, input } => exec.execute_i32_clz(result, input);
+    fn i32_ctz(exec, _) = I32Ctz { result, input } => exec.execute_i32_ctz(result, input);
+    fn i32_popcnt(exec, _) = I32Popcnt { result, input } => exec.execute_i32_popcnt(result, input);
+    fn i32_add(exec, _) = I32Add { result, lhs, rhs } => exec.execute_i32_add(result, lhs, rhs);
+    fn i32_add_imm16(exec, _) = I32AddImm16 { result, lhs, rhs }
+        => exec.execute_i32_add_imm16(result, lhs, rhs);
+    fn i32_sub(exec, _) = I32Sub { result, lhs, rhs } => exec.execute_i32_sub(result, lhs, rhs);
+    fn i32_sub_imm16_lhs(exec, _) = I32SubImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_sub_imm16_lhs(result, lhs, rhs);
+    fn i32_mul(exec, _) = I32Mul { result, lhs, rhs } => exec.execute_i32_mul(result, lhs, rhs);
+    fn i32_mul_imm16(exec, _) = I32MulImm16 { result, lhs, rhs }
+        => exec.execute_i32_mul_imm16(result, lhs, rhs);
+    fn i32_div_s(exec, _) = I32DivS { result, lhs, rhs }
+        => exec.execute_i32_div_s(result, lhs, rhs)?;
+    fn i32_div_s_imm16_rhs(exec, _) = I32DivSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_div_s_imm16_rhs(result, lhs, rhs)?;
+    fn i32_div_s_imm16_lhs(exec, _) = I32DivSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_div_s_imm16_lhs(result, lhs, rhs)?;
+    fn i32_div_u(exec, _) = I32DivU { result, lhs, rhs }
+        => exec.execute_i32_div_u(result, lhs, rhs)?;
+    fn i32_div_u_imm16_rhs(exec, _) = I32DivUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_div_u_imm16_rhs(result, lhs, rhs);
+    fn i32_div_u_imm16_lhs(exec, _) = I32DivUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_div_u_imm16_lhs(result, lhs, rhs)?;
+    fn i32_rem_s(exec, _) = I32RemS { result, lhs, rhs }
+        => exec.execute_i32_rem_s(result, lhs, rhs)?;
+    fn i32_rem_s_imm16_rhs(exec, _) = I32RemSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_rem_s_imm16_rhs(result, lhs, rhs)?;
+    fn i32_rem_s_imm16_lhs(exec, _) = I32RemSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_rem_s_imm16_lhs(result, lhs, rhs)?;
+    fn i32_rem_u(exec, _) = I32RemU { result, lhs, rhs }
+        => exec.execute_i32_rem_u(result, lhs, rhs)?;
+    fn i32_rem_u_imm16_rhs(exec, _) = I32RemUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i32_rem_u_imm16_rhs(result, lhs, rhs);
+    fn i32_rem_u_imm16_lhs(exec, _) = I32RemUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i32_rem_u_imm16_lhs(result, lhs, rhs)?;
+    fn i32_and(exec, _) = I32And { result, lhs, rhs } => exec.execute_i32_and(result, lhs, rhs);
+    fn i32_and_eqz(exec, _) = I32AndEqz { result, lhs, rhs }
+        => exec.execute_i32_and_eqz(result, lhs, rhs);
+    fn i32_and_eqz_imm16(exec, _) = I32AndEqzImm16 { result, lhs, rhs }
+        => exec.execute_i32_and_eqz_imm16(result, lhs, rhs);
+    fn i32_and_imm16(exec, _) = I32AndImm16 { result, lhs, rhs }
+        => exec.execute_i32_and_imm16(result, lhs, rhs);
+    fn i32_or(exec, _) = I32Or { result, lhs, rhs } => exec.execute_i32_or(result, lhs, rhs);
+    fn i32_or_eqz(exec, _) = I32OrEqz { result, lhs, rhs }
+        => exec.execute_i32_or_eqz(result, lhs, rhs);
+    fn i32_or_eqz_imm16(exec, _) = I32OrEqzImm16 { result, lhs, rhs }
+        => exec.execute_i32_or_eqz_imm16(result, lhs, rhs);
+    fn i32_or_imm16(exec, _) = I32OrImm16 { result, lhs, rhs }
+        => exec.execute_i32_or_imm16(result, lhs, rhs);
+    fn i32_xor(exec, _) = I32Xor { result, lhs, rhs } => exec.execute_i32_xor(result, lhs, rhs);
+    fn i32_xor_eqz(exec, _) = I32XorEqz { result, lhs, rhs }
+        => exec.execute_i32_xor_eqz(result, lhs, rhs);
+    fn i32_xor_eqz_imm16(exec, _) = I32XorEqzImm16 { result, lhs, rhs }
+        => exec.execute_i32_xor_eqz_imm16(result, lhs, rhs);
+    fn i32_xor_imm16(exec, _) = I32XorImm16 { result, lhs, rhs }
+        => exec.execute_i32_xor_imm16(result, lhs, rhs);
+    fn i32_shl(exec, _) = I32Shl { result, lhs, rhs } => exec.execute_i32_shl(result, lhs, rhs);
+    fn i32_shl_by(exec, _) = I32ShlBy { result, lhs, rhs }
+        => exec.execute_i32_shl_by(result, lhs, rhs);
+    fn i32_shl_imm16(exec, _) = I32ShlImm16 { result, lhs, rhs }
+        => exec.execute_i32_shl_imm16(result, lhs, rhs);
+    fn i32_shr_u(exec, _) = I32ShrU { result, lhs, rhs }
+        => exec.execute_i32_shr_u(result, lhs, rhs);
+    fn i32_shr_u_by(exec, _) = I32ShrUBy { result, lhs, rhs }
+        => exec.execute_i32_shr_u_by(result, lhs, rhs);
+    fn i32_shr_u_imm16(exec, _) = I32ShrUImm16 { result, lhs, rhs }
+        => exec.execute_i32_shr_u_imm16(result, lhs, rhs);
+    fn i32_shr_s(exec, _) = I32ShrS { result, lhs, rhs }
+        => exec.execute_i32_shr_s(result, lhs, rhs);
+    fn i32_shr_s_by(exec, _) = I32ShrSBy { result, lhs, rhs }
+        => exec.execute_i32_shr_s_by(result, lhs, rhs);
+    fn i32_shr_s_imm16(exec, _) = I32ShrSImm16 { result, lhs, rhs }
+        => exec.execute_i32_shr_s_imm16(result, lhs, rhs);
+    fn i32_rotl(exec, _) = I32Rotl { result, lhs, rhs } => exec.execute_i32_rotl(result, lhs, rhs);
+    fn i32_rotl_by(exec, _) = I32RotlBy { result, lhs, rhs }
+        => exec.execute_i32_rotl_by(result, lhs, rhs);
+    fn i32_rotl_imm16(exec, _) = I32RotlImm16 { result, lhs, rhs }
+        => exec.execute_i32_rotl_imm16(result, lhs, rhs);
+    fn i32_rotr(exec, _) = I32Rotr { result, lhs, rhs } => exec.execute_i32_rotr(result, lhs, rhs);
+    fn i32_rotr_by(exec, _) = I32RotrBy { result, lhs, rhs }
+        => exec.execute_i32_rotr_by(result, lhs, rhs);
+    fn i32_rotr_imm16(exec, _) = I32RotrImm16 { result, lhs, rhs }
+        => exec.execute_i32_rotr_imm16(result, lhs, rhs);
+    fn i64_clz(exec, _) = I64Clz { result, input } => exec.execute_i64_clz(result, input);
+    fn i64_ctz(exec, _) = I64Ctz { result, input } => exec.execute_i64_ctz(result, input);
+    fn i64_popcnt(exec, _) = I64Popcnt { result, input } => exec.execute_i64_popcnt(result, input);
+    fn i64_add(exec, _) = I64Add { result, lhs, rhs } => exec.execute_i64_add(result, lhs, rhs);
+    fn i64_add_imm16(exec, _) = I64AddImm16 { result, lhs, rhs }
+        => exec.execute_i64_add_imm16(result, lhs, rhs);
+    fn i64_sub(exec, _) = I64Sub { result, lhs, rhs } => exec.execute_i64_sub(result, lhs, rhs);
+    fn i64_sub_imm16_lhs(exec, _) = I64SubImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_sub_imm16_lhs(result, lhs, rhs);
+    fn i64_mul(exec, _) = I64Mul { result, lhs, rhs } => exec.execute_i64_mul(result, lhs, rhs);
+    fn i64_mul_imm16(exec, _) = I64MulImm16 { result, lhs, rhs }
+        => exec.execute_i64_mul_imm16(result, lhs, rhs);
+    fn i64_div_s(exec, _) = I64DivS { result, lhs, rhs }
+        => exec.execute_i64_div_s(result, lhs, rhs)?;
+    fn i64_div_s_imm16_rhs(exec, _) = I64DivSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_div_s_imm16_rhs(result, lhs, rhs)?;
+    fn i64_div_s_imm16_lhs(exec, _) = I64DivSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_div_s_imm16_lhs(result, lhs, rhs)?;
+    fn i64_div_u(exec, _) = I64DivU { result, lhs, rhs }
+        => exec.execute_i64_div_u(result, lhs, rhs)?;
+    fn i64_div_u_imm16_rhs(exec, _) = I64DivUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_div_u_imm16_rhs(result, lhs, rhs);
+    fn i64_div_u_imm16_lhs(exec, _) = I64DivUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_div_u_imm16_lhs(result, lhs, rhs)?;
+    fn i64_rem_s(exec, _) = I64RemS { result, lhs, rhs }
+        => exec.execute_i64_rem_s(result, lhs, rhs)?;
+    fn i64_rem_s_imm16_rhs(exec, _) = I64RemSImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_rem_s_imm16_rhs(result, lhs, rhs)?;
+    fn i64_rem_s_imm16_lhs(exec, _) = I64RemSImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_rem_s_imm16_lhs(result, lhs, rhs)?;
+    fn i64_rem_u(exec, _) = I64RemU { result, lhs, rhs }
+        => exec.execute_i64_rem_u(result, lhs, rhs)?;
+    fn i64_rem_u_imm16_rhs(exec, _) = I64RemUImm16Rhs { result, lhs, rhs }
+        => exec.execute_i64_rem_u_imm16_rhs(result, lhs, rhs);
+    fn i64_rem_u_imm16_lhs(exec, _) = I64RemUImm16Lhs { result, lhs, rhs }
+        => exec.execute_i64_rem_u_imm16_lhs(result, lhs, rhs)?;
+    fn i64_and(exec, _) = I64And { result, lhs, rhs } => exec.execute_i64_and(result, lhs, rhs);
+    fn i64_and_imm16(exec, _) = I64AndImm16 { result, lhs, rhs }
+        => exec.execute_i64_and_imm16(result, lhs, rhs);
+    fn i64_or(exec, _) = I64Or { result, lhs, rhs } => exec.execute_i64_or(result, lhs, rhs);
+    fn i64_or_imm16(exec, _) = I64OrImm16 { result, lhs, rhs }
+        => exec.execute_i64_or_imm16(result, lhs, rhs);
+    fn i64_xor(exec, _) = I64Xor { result, lhs, rhs } => exec.execute_i64_xor(result, lhs, rhs);
+    fn i64_xor_imm16(exec, _) = I64XorImm16 { result, lhs, rhs }
+        => exec.execute_i64_xor_imm16(result, lhs, rhs);
+    fn i64_shl(exec, _) = I64Shl { result, lhs, rhs } => exec.execute_i64_shl(result, lhs, rhs);
+    fn i64_shl_by(exec, _) = I64ShlBy { result, lhs, rhs }
+        => exec.execute_i64_shl_by(result, lhs, rhs);
+    fn i64_shl_imm16(exec, _) = I64ShlImm16 { result, lhs, rhs }
+        => exec.execute_i64_shl_imm16(result, lhs, rhs);
+    fn i64_shr_u(exec, _) = I64ShrU { result, lhs, rhs }
+        => exec.execute_i64_shr_u(result, lhs, rhs);
+    fn i64_shr_u_by(exec, _) = I64ShrUBy { result, lhs, rhs }
+        => exec.execute_i64_shr_u_by(result, lhs, rhs);
+    fn i64_shr_u_imm16(exec, _) = I64ShrUImm16 { result, lhs, rhs }
+        => exec.execute_i64_shr_u_imm16(result, lhs, rhs);
+    fn i64_shr_s(exec, _) = I64ShrS { result, lhs, rhs }
+        => exec.execute_i64_shr_s(result, lhs, rhs);
+    fn i64_shr_s_by(exec, _) = I64ShrSBy { result, lhs, rhs }
+        => exec.execute_i64_shr_s_by(result, lhs, rhs);
+    fn i64_shr_s_imm16(exec, _) = I64ShrSImm16 { result, lhs, rhs }
+        => exec.execute_i64_shr_s_imm16(result, lhs, rhs);
+    fn i64_rotl(exec, _) = I64Rotl { result, lhs, rhs } => exec.execute_i64_rotl(result, lhs, rhs);
+    fn i64_rotl_by(exec, _) = I64RotlBy { result, lhs, rhs }
+        => exec.execute_i64_rotl_by(result, lhs, rhs);
+    fn i64_rotl_imm16(exec, _) = I64RotlImm16 { result, lhs, rhs }
+        => exec.execute_i64_rotl_imm16(result, lhs, rhs);
+    fn i64_rotr(exec, _) = I64Rotr { result, lhs, rhs } => exec.execute_i64_rotr(result, lhs, rhs);
+    fn i64_rotr_by(exec, _) = I64RotrBy { result, lhs, rhs }
+        => exec.execute_i64_rotr_by(result, lhs, rhs);
+    fn i64_rotr_imm16(exec, _) = I64RotrImm16 { result, lhs, rhs }
+        => exec.execute_i64_rotr_imm16(result, lhs, rhs);
+    fn i32_wrap_i64(exec, _) = I32WrapI64 { result, input }
+        => exec.execute_i32_wrap_i64(result, input);
+    fn i32_extend8_s(exec, _) = I32Extend8S { result, input }
+        => exec.execute_i32_extend8_s(result, input);
+    fn i32_extend16_s(exec, _) = I32Extend16S { result, input }
+        => exec.execute_i32_extend16_s(result, input);
+    fn i64_extend8_s(exec, _) = I64Extend8S { result, input }
+        => exec.execute_i64_extend8_s(result, input);
+    fn i64_extend16_s(exec, _) = I64Extend16S { result, input }
+        => exec.execute_i64_extend16_s(result, input);
+    fn i64_extend32_s(exec, _) = I64Extend32S { result, input }
+        => exec.execute_i64_extend32_s(result, input);
+    fn f32_abs(exec, _) = F32Abs { result, input } => exec.execute_f32_abs(result, input);
+    fn f32_neg(exec, _) = F32Neg { result, input } => exec.execute_f32_neg(result, input);
+    fn f32_ceil(exec, _) = F32Ceil { result, input } => exec.execute_f32_ceil(result, input);
+    fn f32_floor(exec, _) = F32Floor { result, input } => exec.execute_f32_floor(result, input);
+    fn f32_trunc(exec, _) = F32Trunc { result, input } => exec.execute_f32_trunc(result, input);
+    fn f32_nearest(exec, _) = F32Nearest { result, input }
+        => exec.execute_f32_nearest(result, input);
+    fn f32_sqrt(exec, _) = F32Sqrt { result, input } => exec.execute_f32_sqrt(result, input);
+    fn f32_add(exec, _) = F32Add { result, lhs, rhs } => exec.execute_f32_add(result, lhs, rhs);
+    fn f32_sub(exec, _) = F32Sub { result, lhs, rhs } => exec.execute_f32_sub(result, lhs, rhs);
+    fn f32_mul(exec, _) = F32Mul { result, lhs, rhs } => exec.execute_f32_mul(result, lhs, rhs);
+    fn f32_div(exec, _) = F32Div { result, lhs, rhs } => exec.execute_f32_div(result, lhs, rhs);
+    fn f32_min(exec, _) = F32Min { result, lhs, rhs } => exec.execute_f32_min(result, lhs, rhs);
+    fn f32_max(exec, _) = F32Max { result, lhs, rhs } => exec.execute_f32_max(result, lhs, rhs);
+    fn f32_copysign(exec, _) = F32Copysign { result, lhs, rhs }
+        => exec.execute_f32_copysign(result, lhs, rhs);
+    fn f32_copysign_imm(exec, _) = F32CopysignImm { result, lhs, rhs }
+        => exec.execute_f32_copysign_imm(result, lhs, rhs);
+    fn f64_abs(exec, _) = F64Abs { result, input } => exec.execute_f64_abs(result, input);
+    fn f64_neg(exec, _) = F64Neg { result, input } => exec.execute_f64_neg(result, input);
+    fn f64_ceil(exec, _) = F64Ceil { result, input } => exec.execute_f64_ceil(result, input);
+    fn f64_floor(exec, _) = F64Floor { result, input } => exec.execute_f64_floor(result, input);
+    fn f64_trunc(exec, _) = F64Trunc { result, input } => exec.execute_f64_trunc(result, input);
+    fn f64_nearest(exec, _) = F64Nearest { result, input }
+        => exec.execute_f64_nearest(result, input);
+    fn f64_sqrt(exec, _) = F64Sqrt { result, input } => exec.execute_f64_sqrt(result, input);
+    fn f64_add(exec, _) = F64Add { result, lhs, rhs } => exec.execute_f64_add(result, lhs, rhs);
+    fn f64_sub(exec, _) = F64Sub { result, lhs, rhs } => exec.execute_f64_sub(result, lhs, rhs);
+    fn f64_mul(exec, _) = F64Mul { result, lhs, rhs } => exec.execute_f64_mul(result, lhs, rhs);
+    fn f64_div(exec, _) = F64Div { result, lhs, rhs } => exec.execute_f64_div(result, lhs, rhs);
+    fn f64_min(exec, _) = F64Min { result, lhs, rhs } => exec.execute_f64_min(result, lhs, rhs);
+    fn f64_max(exec, _) = F64Max { result, lhs, rhs } => exec.execute_f64_max(result, lhs, rhs);
+    fn f64_copysign(exec, _) = F64Copysign { result, lhs, rhs }
+        => exec.execute_f64_copysign(result, lhs, rhs);
+    fn f64_copysign_imm(exec, _) = F64CopysignImm { result, lhs, rhs }
+        => exec.execute_f64_copysign_imm(result, lhs, rhs);
+    fn i32_trunc_f32_s(exec, _) = I32TruncF32S { result, input }
+        => exec.execute_i32_trunc_f32_s(result, input)?;
+    fn i32_trunc_f32_u(exec, _) = I32TruncF32U { result, input }
+        => exec.execute_i32_trunc_f32_u(result, input)?;
+    fn i32_trunc_f64_s(exec, _) = I32TruncF64S { result, input }
+        => exec.execute_i32_trunc_f64_s(result, input)?;
+    fn i32_trunc_f64_u(exec, _) = I32TruncF64U { result, input }
+        => exec.execute_i32_trunc_f64_u(result, input)?;
+    fn i64_trunc_f32_s(exec, _) = I64TruncF32S { result, input }
+        => exec.execute_i64_trunc_f32_s(result, input)?;
+    fn i64_trunc_f32_u(exec, _) = I64TruncF32U { result, input }
+        => exec.execute_i64_trunc_f32_u(result, input)?;
+    fn i64_trunc_f64_s(exec, _) = I64TruncF64S { result, input }
+        => exec.execute_i64_trunc_f64_s(result, input)?;
+    fn i64_trunc_f64_u(exec, _) = I64TruncF64U { result, input }
+        => exec.execute_i64_trunc_f64_u(result, input)?;
+    fn i32_trunc_sat_f32_s(exec, _) = I32TruncSatF32S { result, input }
+        => exec.execute_i32_trunc_sat_f32_s(result, input);
+    fn i32_trunc_sat_f32_u(exec, _) = I32TruncSatF32U { result, input }
+        => exec.execute_i32_trunc_sat_f32_u(result, input);
+    fn i32_trunc_sat_f64_s(exec, _) = I32TruncSatF64S { result, input }
+        => exec.execute_i32_trunc_sat_f64_s(result, input);
+    fn i32_trunc_sat_f64_u(exec, _) = I32TruncSatF64U { result, input }
+        => exec.execute_i32_trunc_sat_f64_u(result, input);
+    fn i64_trunc_sat_f32_s(exec, _) = I64TruncSatF32S { result, input }
+        => exec.execute_i64_trunc_sat_f32_s(result, input);
+    fn i64_trunc_sat_f32_u(exec, _) = I64TruncSatF32U { result, input }
+        => exec.execute_i64_trunc_sat_f32_u(result, input);
+    fn i64_trunc_sat_f64_s(exec, _) = I64TruncSatF64S { result, input }
+        => exec.execute_i64_trunc_sat_f64_s(result, input);
+    fn i64_trunc_sat_f64_u(exec, _) = I64TruncSatF64U { result, input }
+        => exec.execute_i64_trunc_sat_f64_u(result, input);
+    fn f32_demote_f64(exec, _) = F32DemoteF64 { result, input }
+        => exec.execute_f32_demote_f64(result, input);
+    fn f64_promote_f32(exec, _) = F64PromoteF32 { result, input }
+        => exec.execute_f64_promote_f32(result, input);
+    fn f32_convert_i32_s(exec, _) = F32ConvertI32S { result, input }
+        => exec.execute_f32_convert_i32_s(result, input);
+    fn f32_convert_i32_u(exec, _) = F32ConvertI32U { result, input }
+        => exec.execute_f32_convert_i32_u(result, input);
+    fn f32_convert_i64_s(exec, _) = F32ConvertI64S { result, input }
+        => exec.execute_f32_convert_i64_s(result, input);
+    fn f32_convert_i64_u(exec, _) = F32ConvertI64U { result, input }
+        => exec.execute_f32_convert_i64_u(result, input);
+    fn f64_convert_i32_s(exec, _) = F64ConvertI32S { result, input }
+        => exec.execute_f64_convert_i32_s(result, input);
+    fn f64_convert_i32_u(exec, _) = F64ConvertI32U { result, input }
+        => exec.execute_f64_convert_i32_u(result, input);
+    fn f64_convert_i64_s(exec, _) = F64ConvertI64S { result, input }
+        => exec.execute_f64_convert_i64_s(result, input);
+    fn f64_convert_i64_u(exec, _) = F64ConvertI64U { result, input }
+        => exec.execute_f64_convert_i64_u(result, input);
+    fn table_get(exec, store) = TableGet { result, index }
+        => exec.execute_table_get(store, result, index)?;
+    fn table_get_imm(exec, store) = TableGetImm { result, index }
+        => exec.execute_table_get_imm(store, result, index)?;
+    fn table_size(exec, store) = TableSize { result, table }
+        => exec.execute_table_size(store, result, table);
+    fn table_set(exec, store) = TableSet { index, value }
+        => exec.execute_table_set(store, index, value)?;
+    fn table_set_at(exec, store) = TableSetAt { index, value }
+        => exec.execute_table_set_at(store, index, value)?;
+    fn table_copy(exec, store) = TableCopy { dst, src, len }
+        => exec.execute_table_copy(store, dst, src, len)?;
+    fn table_copy_to(exec, store) = TableCopyTo { dst, src, len }
+        => exec.execute_table_copy_to(store, dst, src, len)?;
+    fn table_copy_from(exec, store) = TableCopyFrom { dst, src, len }
+        => exec.execute_table_copy_from(store, dst, src, len)?;
+    fn table_copy_from_to(exec, store) = TableCopyFromTo { dst, src, len }
+        => exec.execute_table_copy_from_to(store, dst, src, len)?;
+    fn table_copy_exact(exec, store) = TableCopyExact { dst, src, len }
+        => exec.execute_table_copy_exact(store, dst, src, len)?;
+    fn table_copy_to_exact(exec, store) = TableCopyToExact { dst, src, len }
+        => exec.execute_table_copy_to_exact(store, dst, src, len)?;
+    fn table_copy_from_exact(exec, store) = TableCopyFromExact { dst, src, len }
+        => exec.execute_table_copy_from_exact(store, dst, src, len)?;
+    fn table_copy_from_to_exact(exec, store) = TableCopyFromToExact { dst, src, len }
+        => exec.execute_table_copy_from_to_exact(store, dst, src, len)?;
+    fn table_init(exec, store) = TableInit { dst, src, len }
+        => exec.execute_table_init(store, dst, src, len)?;
+    fn table_init_to(exec, store) = TableInitTo { dst, src, len }
+        => exec.execute_table_init_to(store, dst, src, len)?;
+    fn table_init_from(exec, store) = TableInitFrom { dst, src, len }
+        => exec.execute_table_init_from(store, dst, src, len)?;
+    fn table_init_from_to(exec, store) = TableInitFromTo { dst, src, len }
+        => exec.execute_table_init_from_to(store, dst, src, len)?;
+    fn table_init_exact(exec, store) = TableInitExact { dst, src, len }
+        => exec.execute_table_init_exact(store, dst, src, len)?;
+    fn table_init_to_exact(exec, store) = TableInitToExact { dst, src, len }
+        => exec.execute_table_init_to_exact(store, dst, src, len)?;
+    fn table_init_from_exact(exec, store) = TableInitFromExact { dst, src, len }
+        => exec.execute_table_init_from_exact(store, dst, src, len)?;
+    fn table_init_from_to_exact(exec, store) = TableInitFromToExact { dst, src, len }
+        => exec.execute_table_init_from_to_exact(store, dst, src, len)?;
+    fn table_fill(exec, store) = TableFill { dst, len, value }
+        => exec.execute_table_fill(store, dst, len, value)?;
+    fn table_fill_at(exec, store) = TableFillAt { dst, len, value }
+        => exec.execute_table_fill_at(store, dst, len, value)?;
+    fn table_fill_exact(exec, store) = TableFillExact { dst, len, value }
+        => exec.execute_table_fill_exact(store, dst, len, value)?;
+    fn table_fill_at_exact(exec, store) = TableFillAtExact { dst, len, value }
+        => exec.execute_table_fill_at_exact(store, dst, len, value)?;
+    fn element_drop(exec, store) = ElemDrop { index } => exec.execute_element_drop(store, index);
+    fn data_drop(exec, store) = DataDrop { index } => exec.execute_data_drop(store, index);
+    fn memory_size(exec, store) = MemorySize { result, memory }
+        => exec.execute_memory_size(store, result, memory);
+    fn memory_copy(exec, store) = MemoryCopy { dst, src, len }
+        => exec.execute_memory_copy(store, dst, src, len)?;
+    fn memory_copy_to(exec, store) = MemoryCopyTo { dst, src, len }
+        => exec.execute_memory_copy_to(store, dst, src, len)?;
+    fn memory_copy_from(exec, store) = MemoryCopyFrom { dst, src, len }
+        => exec.execute_memory_copy_from(store, dst, src, len)?;
+    fn memory_copy_from_to(exec, store) = MemoryCopyFromTo { dst, src, len }
+        => exec.execute_memory_copy_from_to(store, dst, src, len)?;
+    fn memory_copy_exact(exec, store) = MemoryCopyExact { dst, src, len }
+        => exec.execute_memory_copy_exact(store, dst, src, len)?;
+    fn memory_copy_to_exact(exec, store) = MemoryCopyToExact { dst, src, len }
+        => exec.execute_memory_copy_to_exact(store, dst, src, len)?;
+    fn memory_copy_from_exact(exec, store) = MemoryCopyFromExact { dst, src, len }
+        => exec.execute_memory_copy_from_exact(store, dst, src, len)?;
+    fn memory_copy_from_to_exact(exec, store) = MemoryCopyFromToExact { dst, src, len }
+        => exec.execute_memory_copy_from_to_exact(store, dst, src, len)?;
+    fn memory_fill(exec, store) = MemoryFill { dst, value, len }
+        => exec.execute_memory_fill(store, dst, value, len)?;
+    fn memory_fill_at(exec, store) = MemoryFillAt { dst, value, len }
+        => exec.execute_memory_fill_at(store, dst, value, len)?;
+    fn memory_fill_imm(exec, store) = MemoryFillImm { dst, value, len }
+        => exec.execute_memory_fill_imm(store, dst, value, len)?;
+    fn memory_fill_exact(exec, store) = MemoryFillExact { dst, value, len }
+        => exec.execute_memory_fill_exact(store, dst, value, len)?;
+    fn memory_fill_at_imm(exec, store) = MemoryFillAtImm { dst, value, len }
+        => exec.execute_memory_fill_at_imm(store, dst, value, len)?;
+    fn memory_fill_at_exact(exec, store) = MemoryFillAtExact { dst, value, len }
+        => exec.execute_memory_fill_at_exact(store, dst, value, len)?;
+    fn memory_fill_imm_exact(exec, store) = MemoryFillImmExact { dst, value, len }
+        => exec.execute_memory_fill_imm_exact(store, dst, value, len)?;
+    fn memory_fill_at_imm_exact(exec, store) = MemoryFillAtImmExact { dst, value, len }
+        => exec.execute_memory_fill_at_imm_exact(store, dst, value, len)?;
+    fn memory_init(exec, store) = MemoryInit { dst, src, len }
+        => exec.execute_memory_init(store, dst, src, len)?;
+    fn memory_init_to(exec, store) = MemoryInitTo { dst, src, len }
+        => exec.execute_memory_init_to(store, dst, src, len)?;
+    fn memory_init_from(exec, store) = MemoryInitFrom { dst, src, len }
+        => exec.execute_memory_init_from(store, dst, src, len)?;
+    fn memory_init_from_to(exec, store) = MemoryInitFromTo { dst, src, len }
+        => exec.execute_memory_init_from_to(store, dst, src, len)?;
+    fn memory_init_exact(exec, store) = MemoryInitExact { dst, src, len }
+        => exec.execute_memory_init_exact(store, dst, src, len)?;
+    fn memory_init_to_exact(exec, store) = MemoryInitToExact { dst, src, len }
+        => exec.execute_memory_init_to_exact(store, dst, src, len)?;
+    fn memory_init_from_exact(exec, store) = MemoryInitFromExact { dst, src, len }
+        => exec.execute_memory_init_from_exact(store, dst, src, len)?;
+    fn memory_init_from_to_exact(exec, store) = MemoryInitFromToExact { dst, src, len }
+        => exec.execute_memory_init_from_to_exact(store, dst, src, len)?;
+}
@@ -0,0 +1,81 @@
+//! Execution of Wasmi bytecode functions compiled to machine code by the JIT.
+//!
+//! # Note
+//!
+//! The machine code of a function is entered when the function is called and
+//! runs until it either reaches an instruction that it does not support or
+//! one of its slow paths, for example a trap. The interpreter then resumes
+//! execution of the call frame at this instruction until the call frame returns.
+//!
+//! Only tiered up functions are compiled to machine code, thus nothing is compiled
+//! without [`Config::tiering`](crate::Config::tiering). Since functions compiled
+//! with debugging information are never tiered up the machine code never needs to
+//! notify a debugger. While a trace hook is installed the machine code is not executed.
+
+use super::Executor;
+use crate::{
+    engine::{code_map::CompiledFuncRef, jit::JitContext},
+    store::StoreInner,
+    Instance,
+};
+use core::ptr;
+
+impl Executor<'_> {
+    /// Executes the machine code of the top-most call frame if it has just been entered.
+    ///
+    /// This is used for the root call frame of an execution.
+    pub(super) fn execute_jit_root(&mut self, store: &mut StoreInner) {
+        let func = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack")
+            .func();
+        let Some(cref) = self.code_map.get_compiled(func) else {
+            return;
+        };
+        if ptr::eq(self.ip.get(), cref.instrs().as_ptr()) {
+            self.execute_jit(store, cref, None);
+        }
+    }
+
+    /// Executes the machine code of the just entered `func` if any.
+    ///
+    /// The `instance` is the [`Instance`] of `func` if it differs from the caller's.
+    ///
+    /// Afterwards the instruction pointer points to the instruction at
+    /// which the interpreter resumes execution.
+    pub(super) fn execute_jit(
+        &mut self,
+        store: &mut StoreInner,
+        func: CompiledFuncRef,
+        instance: Option<Instance>,
+    ) {
+        if !self.jit {
+            return;
+        }
+        let Some(jit) = func.jit() else {
+            return;
+        };
+        if let Some(instance) = instance {
+            // Note: the machine code accesses the default linear memory of `instance`.
+            self.cache.update(store, &instance);
+        }
+        // Safety: the cached default linear memory is fresh when entering a function.
+        let memory = unsafe { self.cache.memory.data_mut() };
+        let mut ctx = JitContext {
+            regs: self.sp.as_ptr(),
+            fuel: store.fuel_mut().remaining_mut(),
+            memory: memory.as_mut_ptr(),
+            memory_len: memory.len(),
+        };
+        // Safety:
+        //
+        // - The machine code of `func` is owned by the `CodeMap` which
+        //   keeps it alive for as long as the `Engine` exists.
+        // - The registers of the call frame of `func` are valid for all
+        //   registers that its instructions access.
+        let resume = unsafe { jit.execute(&mut ctx) };
+        self.ip.add(resume as usize);
+    }
+}
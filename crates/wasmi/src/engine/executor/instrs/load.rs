@@ -89,8 +89,9 @@ impl Executor<'_> {
         offset: u32,
         load_extend: WasmLoadOp,
     ) -> Result<(), Error> {
-        let memory = self.fetch_memory_bytes(memory, store);
-        let loaded_value = load_extend(memory, address, offset)?;
+        let bytes = self.fetch_memory_bytes(memory, store);
+        let loaded_value = load_extend(bytes, address, offset)
+            .map_err(|error| self.memory_access_error(error, memory, address, offset))?;
         self.set_register(result, loaded_value);
         Ok(())
     }
@@ -116,7 +117,8 @@ impl Executor<'_> {
         load_extend: WasmLoadOp,
     ) -> Result<(), Error> {
         let memory = self.fetch_default_memory_bytes();
-        let loaded_value = load_extend(memory, address, offset)?;
+        let loaded_value = load_extend(memory, address, offset)
+            .map_err(|error| self.memory_access_error(error, Memory::from(0), address, offset))?;
         self.set_register(result, loaded_value);
         Ok(())
     }
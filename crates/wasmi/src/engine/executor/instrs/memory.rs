@@ -1,4 +1,4 @@
-use super::{Executor, InstructionPtr};
+use super::{fault::memory_out_of_bounds, Executor, InstructionPtr};
 use crate::{
     core::TrapCode,
     engine::utils::unreachable_unchecked,
@@ -248,29 +248,36 @@ impl Executor<'_> {
         src_index: u32,
         len: u32,
     ) -> Result<(), Error> {
-        let dst_memory = self.fetch_memory_index(1);
-        let src_memory = self.fetch_memory_index(2);
+        let dst_memory_index = self.fetch_memory_index(1);
+        let src_memory_index = self.fetch_memory_index(2);
+        let src = src_index;
+        let dst = dst_index;
         let src_index = src_index as usize;
         let dst_index = dst_index as usize;
-        if src_memory == dst_memory {
-            return self
-                .execute_memory_copy_within_impl(store, src_memory, dst_index, src_index, len);
+        if src_memory_index == dst_memory_index {
+            return self.execute_memory_copy_within_impl(
+                store,
+                src_memory_index,
+                dst_index,
+                src_index,
+                len,
+            );
         }
         let (src_memory, dst_memory, fuel) = store.resolve_memory_pair_and_fuel(
-            &self.get_memory(src_memory),
-            &self.get_memory(dst_memory),
+            &self.get_memory(src_memory_index),
+            &self.get_memory(dst_memory_index),
         );
         // These accesses just perform the bounds checks required by the Wasm spec.
         let src_bytes = src_memory
             .data()
             .get(src_index..)
             .and_then(|memory| memory.get(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+            .ok_or_else(|| memory_out_of_bounds(src_memory_index, src, 0, len))?;
         let dst_bytes = dst_memory
             .data_mut()
             .get_mut(dst_index..)
             .and_then(|memory| memory.get_mut(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+            .ok_or_else(|| memory_out_of_bounds(dst_memory_index, dst, 0, len))?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
         dst_bytes.copy_from_slice(src_bytes);
         self.try_next_instr_at(3)
@@ -285,6 +292,7 @@ impl Executor<'_> {
         src_index: usize,
         len: u32,
     ) -> Result<(), Error> {
+        let memory_index = memory;
         let memory = self.get_memory(memory);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
         let bytes = memory.data_mut();
@@ -292,11 +300,11 @@ impl Executor<'_> {
         bytes
             .get(src_index..)
             .and_then(|memory| memory.get(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+            .ok_or_else(|| memory_out_of_bounds(memory_index, src_index as u32, 0, len))?;
         bytes
             .get(dst_index..)
             .and_then(|memory| memory.get(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+            .ok_or_else(|| memory_out_of_bounds(memory_index, dst_index as u32, 0, len))?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
         bytes.copy_within(src_index..src_index.wrapping_add(len as usize), dst_index);
         self.try_next_instr_at(3)
@@ -419,17 +427,15 @@ impl Executor<'_> {
         value: u8,
         len: u32,
     ) -> Result<(), Error> {
-        let memory = self.fetch_memory_index(1);
-        let dst = dst as usize;
-        let len = len as usize;
-        let memory = self.get_memory(memory);
+        let memory_index = self.fetch_memory_index(1);
+        let memory = self.get_memory(memory_index);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
         let slice = memory
             .data_mut()
-            .get_mut(dst..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+            .get_mut(dst as usize..)
+            .and_then(|memory| memory.get_mut(..len as usize))
+            .ok_or_else(|| memory_out_of_bounds(memory_index, dst, 0, len))?;
+        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
         slice.fill(value);
         self.try_next_instr_at(2)
     }
@@ -568,7 +574,7 @@ impl Executor<'_> {
             .data_mut()
             .get_mut(dst_index..)
             .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+            .ok_or_else(|| memory_out_of_bounds(memory_index, dst, 0, len as u32))?;
         let data = data
             .bytes()
             .get(src_index..)
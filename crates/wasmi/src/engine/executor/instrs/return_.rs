@@ -13,6 +13,7 @@ impl Executor<'_> {
     /// Any return values are expected to already have been transferred
     /// from the returning callee to the caller.
     fn return_impl(&mut self, store: &mut StoreInner) -> ControlFlow {
+        store.profile_return();
         let (returned, popped_instance) = self
             .stack
             .calls
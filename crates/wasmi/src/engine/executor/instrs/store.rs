@@ -113,8 +113,9 @@ impl Executor<'_> {
         value: UntypedVal,
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        store_wrap(memory, address, offset, value)?;
+        let bytes = self.fetch_memory_bytes_mut(memory, store);
+        store_wrap(bytes, address, offset, value)
+            .map_err(|error| self.memory_access_error(error, memory, address, offset))?;
         Ok(())
     }
 
@@ -136,7 +137,8 @@ impl Executor<'_> {
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
         let memory = self.fetch_default_memory_bytes_mut();
-        store_wrap(memory, address, offset, value)?;
+        store_wrap(memory, address, offset, value)
+            .map_err(|error| self.memory_access_error(error, Memory::from(0), address, offset))?;
         Ok(())
     }
 
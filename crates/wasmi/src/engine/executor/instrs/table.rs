@@ -1,6 +1,5 @@
-use super::{Executor, InstructionPtr};
+use super::{fault::table_out_of_bounds, Executor, InstructionPtr};
 use crate::{
-    core::TrapCode,
     engine::utils::unreachable_unchecked,
     error::EntityGrowError,
     ir::{
@@ -79,11 +78,10 @@ impl Executor<'_> {
         index: u32,
     ) -> Result<(), Error> {
         let table_index = self.fetch_table_index(1);
-        let table = self.get_table(table_index);
-        let value = store
-            .resolve_table(&table)
+        let table = store.resolve_table(&self.get_table(table_index));
+        let value = table
             .get_untyped(index)
-            .ok_or(TrapCode::TableOutOfBounds)?;
+            .ok_or_else(|| table_out_of_bounds(table_index, index, table.size()))?;
         self.set_register(result, value);
         self.try_next_instr_at(2)
     }
@@ -130,12 +128,11 @@ impl Executor<'_> {
         value: Reg,
     ) -> Result<(), Error> {
         let table_index = self.fetch_table_index(1);
-        let table = self.get_table(table_index);
+        let table = store.resolve_table_mut(&self.get_table(table_index));
         let value = self.get_register(value);
-        store
-            .resolve_table_mut(&table)
+        table
             .set_untyped(index, value)
-            .map_err(|_| TrapCode::TableOutOfBounds)?;
+            .map_err(|_| table_out_of_bounds(table_index, index, table.size()))?;
         self.try_next_instr_at(2)
     }
 
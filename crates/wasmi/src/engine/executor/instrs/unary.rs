@@ -27,18 +27,39 @@ impl Executor<'_> {
 
         (Instruction::F32Abs, execute_f32_abs, UntypedVal::f32_abs),
         (Instruction::F32Neg, execute_f32_neg, UntypedVal::f32_neg),
-        (Instruction::F32Ceil, execute_f32_ceil, UntypedVal::f32_ceil),
-        (Instruction::F32Floor, execute_f32_floor, UntypedVal::f32_floor),
-        (Instruction::F32Trunc, execute_f32_trunc, UntypedVal::f32_trunc),
-        (Instruction::F32Nearest, execute_f32_nearest, UntypedVal::f32_nearest),
-        (Instruction::F32Sqrt, execute_f32_sqrt, UntypedVal::f32_sqrt),
 
         (Instruction::F64Abs, execute_f64_abs, UntypedVal::f64_abs),
         (Instruction::F64Neg, execute_f64_neg, UntypedVal::f64_neg),
-        (Instruction::F64Ceil, execute_f64_ceil, UntypedVal::f64_ceil),
-        (Instruction::F64Floor, execute_f64_floor, UntypedVal::f64_floor),
-        (Instruction::F64Trunc, execute_f64_trunc, UntypedVal::f64_trunc),
-        (Instruction::F64Nearest, execute_f64_nearest, UntypedVal::f64_nearest),
-        (Instruction::F64Sqrt, execute_f64_sqrt, UntypedVal::f64_sqrt),
+    }
+}
+
+macro_rules! impl_unary_float_impls {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr, $canonicalize:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            pub fn $fn_name(&mut self, result: Reg, input: Reg) {
+                if self.canonicalize_nans {
+                    self.execute_unary(result, input, |input| $canonicalize($op(input)))
+                } else {
+                    self.execute_unary(result, input, $op)
+                }
+            }
+        )*
+    };
+}
+
+impl Executor<'_> {
+    impl_unary_float_impls! {
+        (Instruction::F32Ceil, execute_f32_ceil, UntypedVal::f32_ceil, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Floor, execute_f32_floor, UntypedVal::f32_floor, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Trunc, execute_f32_trunc, UntypedVal::f32_trunc, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Nearest, execute_f32_nearest, UntypedVal::f32_nearest, UntypedVal::f32_canonicalize_nan),
+        (Instruction::F32Sqrt, execute_f32_sqrt, UntypedVal::f32_sqrt, UntypedVal::f32_canonicalize_nan),
+
+        (Instruction::F64Ceil, execute_f64_ceil, UntypedVal::f64_ceil, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Floor, execute_f64_floor, UntypedVal::f64_floor, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Trunc, execute_f64_trunc, UntypedVal::f64_trunc, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Nearest, execute_f64_nearest, UntypedVal::f64_nearest, UntypedVal::f64_canonicalize_nan),
+        (Instruction::F64Sqrt, execute_f64_sqrt, UntypedVal::f64_sqrt, UntypedVal::f64_canonicalize_nan),
     }
 }
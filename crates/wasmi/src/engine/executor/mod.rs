@@ -1,10 +1,15 @@
 pub use self::instrs::ResumableHostError;
-pub(crate) use self::stack::Stack;
+#[cfg(feature = "threaded-dispatch")]
+pub(crate) use self::instrs::{decode_handlers, Handler};
 use self::{
     instr_ptr::InstructionPtr,
-    instrs::{dispatch_host_func, execute_instrs},
+    instrs::{consume_host_func_fuel, dispatch_host_func, execute_instrs},
     stack::CallFrame,
 };
+pub(crate) use self::{
+    instrs::UntypedValueExt,
+    stack::{FrameRegisters, Stack},
+};
 use crate::{
     engine::{CallParams, CallResults, EngineInner, ResumableCallBase, ResumableInvocation},
     func::HostFuncEntity,
@@ -52,6 +57,7 @@ impl EngineInner {
                 Ok(error) => error.into_error(),
                 Err(error) => error,
             });
+        ctx.store.inner.record_stack_usage(&stack);
         self.stacks.lock().recycle(stack);
         results
     }
@@ -77,6 +83,7 @@ impl EngineInner {
         let mut stack = self.stacks.lock().reuse_or_new();
         let results = EngineExecutor::new(&self.code_map, &mut stack)
             .execute_root_func(store, func, params, results);
+        store.inner.record_stack_usage(&stack);
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(stack);
@@ -130,6 +137,7 @@ impl EngineInner {
             caller_results,
             results,
         );
+        ctx.store.inner.record_stack_usage(&invocation.stack);
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(invocation.take_stack());
@@ -210,13 +218,16 @@ impl<'engine> EngineExecutor<'engine> {
                 uninit_params.init_zeroes();
                 self.stack.calls.push(
                     CallFrame::new(
-                        InstructionPtr::new(compiled_func.instrs().as_ptr()),
+                        engine_func,
+                        InstructionPtr::new(compiled_func),
                         offsets,
                         RegSpan::new(Reg::from(0)),
+                        compiled_func,
                     ),
                     Some(instance),
                 )?;
                 store.invoke_call_hook(CallHook::CallingWasm)?;
+                store.inner.profile_call(engine_func);
                 self.execute_func(store)?;
                 store.invoke_call_hook(CallHook::ReturningFromWasm)?;
             }
@@ -236,6 +247,7 @@ impl<'engine> EngineExecutor<'engine> {
                     uninit.write(param);
                 }
                 let host_func = *host_func;
+                consume_host_func_fuel(store, &self.stack.values, &host_func)?;
                 self.dispatch_host_func(store, host_func)?;
             }
         };
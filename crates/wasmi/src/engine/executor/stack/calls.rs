@@ -2,22 +2,18 @@ use super::{err_stack_overflow, BaseValueStackOffset, FrameValueStackOffset};
 use crate::{
     collections::HeadVec,
     core::TrapCode,
-    engine::executor::InstructionPtr,
+    engine::{
+        code_map::{CompiledFuncRef, Hotness},
+        executor::InstructionPtr,
+        EngineFunc,
+    },
     ir::RegSpan,
     Instance,
 };
 use alloc::vec::Vec;
 
 #[cfg(doc)]
-use crate::{
-    engine::executor::stack::ValueStack,
-    engine::EngineFunc,
-    ir::Instruction,
-    ir::Reg,
-    Global,
-    Memory,
-    Table,
-};
+use crate::{engine::executor::stack::ValueStack, ir::Instruction, ir::Reg, Global, Memory, Table};
 
 /// The stack of nested function calls.
 #[derive(Debug, Default)]
@@ -32,6 +28,8 @@ pub struct CallStack {
     ///
     /// A [`TrapCode::StackOverflow`] is raised if the recursion limit is exceeded.
     recursion_limit: usize,
+    /// The maximum number of [`CallFrame`]s on the [`CallStack`] since the last reset.
+    high_water: usize,
 }
 
 impl CallStack {
@@ -41,6 +39,7 @@ impl CallStack {
             frames: Vec::new(),
             instances: HeadVec::default(),
             recursion_limit,
+            high_water: 0,
         }
     }
 
@@ -56,11 +55,17 @@ impl CallStack {
     pub fn reset(&mut self) {
         self.frames.clear();
         self.instances.clear();
+        self.high_water = 0;
+    }
+
+    /// Returns the maximum number of [`CallFrame`]s on the [`CallStack`] since the last reset.
+    pub fn high_water(&self) -> usize {
+        self.high_water
     }
 
     /// Returns the number of [`CallFrame`]s on the [`CallStack`].
     #[inline(always)]
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.frames.len()
     }
 
@@ -70,6 +75,26 @@ impl CallStack {
         self.len() == 0
     }
 
+    /// Returns the [`CallFrame`]s on the [`CallStack`] from the root frame to the top frame.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    /// Returns the [`CallFrame`]s on the [`CallStack`] with the [`Instance`] they are using.
+    ///
+    /// The [`CallFrame`]s are yielded from the root frame to the top frame.
+    pub fn frames_with_instances(&self) -> impl Iterator<Item = (&CallFrame, &Instance)> {
+        let mut instances = self.instances.iter();
+        let mut instance = None;
+        self.frames.iter().map(move |frame| {
+            if frame.changed_instance {
+                instance = instances.next();
+            }
+            let instance = instance.expect("the root frame must have an instance");
+            (frame, instance)
+        })
+    }
+
     /// Returns the currently used [`Instance`].
     #[inline(always)]
     pub fn instance(&self) -> Option<&Instance> {
@@ -107,6 +132,7 @@ impl CallStack {
             call.changed_instance = self.push_instance(instance);
         }
         self.frames.push(call);
+        self.high_water = self.high_water.max(self.frames.len());
         Ok(())
     }
 
@@ -194,12 +220,21 @@ impl StackOffsets {
 /// A single frame of a called [`EngineFunc`].
 #[derive(Debug, Copy, Clone)]
 pub struct CallFrame {
+    /// The called [`EngineFunc`].
+    func: EngineFunc,
     /// The pointer to the [`Instruction`] that is executed next.
     instr_ptr: InstructionPtr,
     /// Offsets of the [`CallFrame`] into the [`ValueStack`].
     offsets: StackOffsets,
     /// Span of registers were the caller expects them in its [`CallFrame`].
     results: RegSpan,
+    /// The compiled code of the called [`EngineFunc`] executed by the [`CallFrame`].
+    ///
+    /// # Note
+    ///
+    /// This is the retired baseline tier of the [`EngineFunc`] if it has been
+    /// tiered up while the [`CallFrame`] was executing.
+    code: CompiledFuncRef<'static>,
     /// Is `true` if this [`CallFrame`] changed the currently used [`Instance`].
     ///
     /// - This flag is an optimization to reduce the amount of accesses on the
@@ -212,11 +247,22 @@ pub struct CallFrame {
 
 impl CallFrame {
     /// Creates a new [`CallFrame`].
-    pub fn new(instr_ptr: InstructionPtr, offsets: StackOffsets, results: RegSpan) -> Self {
+    pub fn new(
+        func: EngineFunc,
+        instr_ptr: InstructionPtr,
+        offsets: StackOffsets,
+        results: RegSpan,
+        code: CompiledFuncRef,
+    ) -> Self {
         Self {
+            func,
             instr_ptr,
             offsets,
             results,
+            // Safety: the `CodeMap` keeps all compiled functions alive, including the
+            //         ones retired by tiering, for as long as its `Engine` exists which
+            //         outlives all call stacks executing its functions.
+            code: unsafe { code.into_static() },
             changed_instance: false,
         }
     }
@@ -239,6 +285,48 @@ impl CallFrame {
         self.instr_ptr = new_instr_ptr;
     }
 
+    /// Returns the called [`EngineFunc`] of the [`CallFrame`].
+    pub fn func(&self) -> EngineFunc {
+        self.func
+    }
+
+    /// Returns the hotness counter of the called [`EngineFunc`] if it can be tiered up.
+    pub fn hotness(&self) -> Option<Hotness> {
+        self.code.hotness()
+    }
+
+    /// Returns the compiled code of the called [`EngineFunc`] executed by the [`CallFrame`].
+    pub fn code(&self) -> CompiledFuncRef<'static> {
+        self.code
+    }
+
+    /// Returns the function index and the Wasm offset of the Wasm operator executed by the [`CallFrame`].
+    ///
+    /// Returns `None` if the executed code has no Wasm offsets or the
+    /// [`InstructionPtr`] of the [`CallFrame`] does not point into it.
+    /// See [`CallFrame::instr_position`] for the meaning of `is_caller`.
+    pub fn symbolicate(&self, is_caller: bool) -> Option<(u32, u32)> {
+        let offsets = self.code.offsets()?;
+        let pos = self.instr_position(is_caller)?;
+        Some((offsets.func_index(), offsets.offset(pos)?))
+    }
+
+    /// Returns the position of the instruction executed by the [`CallFrame`] within its code.
+    ///
+    /// Returns `None` if the [`InstructionPtr`] of the [`CallFrame`] does not point into its code.
+    ///
+    /// # Note
+    ///
+    /// The [`InstructionPtr`] of callers points behind their call instruction
+    /// which is why the preceding instruction is returned if `is_caller` is `true`.
+    pub fn instr_position(&self, is_caller: bool) -> Option<usize> {
+        let pos = self.code.position(self.instr_ptr.as_ptr())?;
+        match is_caller {
+            true => pos.checked_sub(1),
+            false => Some(pos),
+        }
+    }
+
     /// Returns the [`InstructionPtr`] of the [`CallFrame`].
     pub fn instr_ptr(&self) -> InstructionPtr {
         self.instr_ptr
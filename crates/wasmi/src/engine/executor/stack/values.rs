@@ -23,6 +23,8 @@ pub struct ValueStack {
     values: Vec<UntypedVal>,
     /// Maximal possible `sp` value.
     max_len: usize,
+    /// The maximum number of values on the [`ValueStack`] since the last reset.
+    high_water: usize,
 }
 
 impl ValueStack {
@@ -37,6 +39,7 @@ impl Debug for ValueStack {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ValueStack")
             .field("max_len", &self.max_len)
+            .field("high_water", &self.high_water)
             .field("entries", &&self.values[..])
             .finish()
     }
@@ -81,6 +84,7 @@ impl ValueStack {
         Self {
             values: Vec::with_capacity(initial_len),
             max_len: maximum_len,
+            high_water: 0,
         }
     }
 
@@ -94,6 +98,7 @@ impl ValueStack {
         Self {
             values: Vec::new(),
             max_len: 0,
+            high_water: 0,
         }
     }
 
@@ -107,6 +112,12 @@ impl ValueStack {
     /// provide a clean slate for all executions.
     pub fn reset(&mut self) {
         self.values.clear();
+        self.high_water = 0;
+    }
+
+    /// Returns the maximum number of values on the [`ValueStack`] since the last reset.
+    pub fn high_water(&self) -> usize {
+        self.high_water
     }
 
     /// Returns the root [`FrameRegisters`] pointing to the first value on the [`ValueStack`].
@@ -149,6 +160,7 @@ impl ValueStack {
         }
         let spare = self.values.spare_capacity_mut().as_mut_ptr();
         unsafe { self.values.set_len(self.values.len() + additional) };
+        self.high_water = self.high_water.max(self.values.len());
         Ok(unsafe { slice::from_raw_parts_mut(spare, additional) })
     }
 
@@ -421,6 +433,12 @@ impl FrameRegisters {
         ptr::write(self.register_offset(register), value)
     }
 
+    /// Returns the underlying raw pointer to the registers of the [`CallFrame`].
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    pub fn as_ptr(&self) -> *mut UntypedVal {
+        self.ptr
+    }
+
     /// Returns the underlying pointer offset by the [`Reg`] index.
     unsafe fn register_offset(&self, register: Reg) -> *mut UntypedVal {
         unsafe { self.ptr.offset(isize::from(i16::from(register))) }
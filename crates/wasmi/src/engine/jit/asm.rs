@@ -0,0 +1,375 @@
+//! A minimal x86_64 assembler for the machine code generated by the JIT.
+//!
+//! # Note
+//!
+//! Only the instruction forms required by the JIT are supported. Memory operands
+//! are restricted to `[base + disp32]` and `[base + index]` where the `base`
+//! register is neither `rsp`, `rbp`, `r12` nor `r13` which would require special
+//! encodings.
+
+use alloc::vec::Vec;
+
+/// A general purpose x86_64 register.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Gpr {
+    Rax = 0,
+    Rcx = 1,
+    Rdx = 2,
+    Rdi = 7,
+    R8 = 8,
+    R9 = 9,
+    R10 = 10,
+    R11 = 11,
+}
+
+impl Gpr {
+    /// Returns the lower 3 bits of the register encoding.
+    fn low(self) -> u8 {
+        self as u8 & 0b111
+    }
+
+    /// Returns `1` if the register requires a REX extension bit.
+    fn ext(self) -> u8 {
+        (self as u8 >> 3) & 1
+    }
+}
+
+/// The operand width of an x86_64 instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Width {
+    /// 32-bit operands which zero the upper 32 bits of destination registers.
+    W32,
+    /// 64-bit operands.
+    W64,
+}
+
+/// A memory operand of an x86_64 instruction.
+#[derive(Debug, Copy, Clone)]
+pub enum Mem {
+    /// The address `base + disp`.
+    BaseDisp(Gpr, i32),
+    /// The address `base + index`.
+    BaseIndex(Gpr, Gpr),
+}
+
+impl Mem {
+    /// Returns the register used as `index` if any.
+    fn index(self) -> Option<Gpr> {
+        match self {
+            Self::BaseDisp(..) => None,
+            Self::BaseIndex(_, index) => Some(index),
+        }
+    }
+
+    /// Returns the register used as `base`.
+    fn base(self) -> Gpr {
+        match self {
+            Self::BaseDisp(base, _) | Self::BaseIndex(base, _) => base,
+        }
+    }
+}
+
+/// The arithmetic and logic operations sharing the same x86_64 encoding scheme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AluOp {
+    Add = 0,
+    Or = 1,
+    And = 4,
+    Sub = 5,
+    Xor = 6,
+    Cmp = 7,
+}
+
+/// The shift and rotate operations sharing the same x86_64 encoding scheme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ShiftOp {
+    Rol = 0,
+    Ror = 1,
+    Shl = 4,
+    Shr = 5,
+    Sar = 7,
+}
+
+/// An x86_64 condition code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Cond {
+    /// Unsigned `<` or carry.
+    B = 0x2,
+    /// `==` or zero.
+    E = 0x4,
+    /// `!=` or non-zero.
+    Ne = 0x5,
+    /// Unsigned `<=`.
+    Be = 0x6,
+    /// Unsigned `>`.
+    A = 0x7,
+    /// Signed `<`.
+    L = 0xC,
+    /// Signed `<=`.
+    Le = 0xE,
+}
+
+/// The position of a 32-bit relative jump displacement that is patched later.
+#[derive(Debug, Copy, Clone)]
+pub struct Fixup(usize);
+
+/// Assembles x86_64 machine code into a byte buffer.
+#[derive(Debug, Default)]
+pub struct Assembler {
+    /// The assembled machine code.
+    code: Vec<u8>,
+}
+
+impl Assembler {
+    /// Returns the current position within the assembled machine code.
+    pub fn pos(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Returns the assembled machine code.
+    pub fn finish(self) -> Vec<u8> {
+        self.code
+    }
+
+    /// Emits a single byte.
+    fn byte(&mut self, byte: u8) {
+        self.code.push(byte);
+    }
+
+    /// Emits a 32-bit little-endian immediate.
+    fn imm32(&mut self, imm: i32) {
+        self.code.extend_from_slice(&imm.to_le_bytes());
+    }
+
+    /// Emits a REX prefix if required.
+    ///
+    /// The REX prefix is always emitted if `force` is `true`.
+    fn rex(&mut self, width: Width, reg: u8, index: u8, base: u8, force: bool) {
+        let w = u8::from(width == Width::W64);
+        let rex = 0x40 | (w << 3) | (reg << 2) | (index << 1) | base;
+        if rex != 0x40 || force {
+            self.byte(rex);
+        }
+    }
+
+    /// Emits the REX prefix, `opcode` and ModRM byte for a register-register operation.
+    fn op_rr(&mut self, width: Width, opcode: &[u8], reg: u8, rm: Gpr) {
+        self.rex(width, (reg >> 3) & 1, 0, rm.ext(), false);
+        self.code.extend_from_slice(opcode);
+        self.byte(0xC0 | ((reg & 0b111) << 3) | rm.low());
+    }
+
+    /// Emits the REX prefix, `opcode` and memory operand encoding for a register-memory operation.
+    fn op_rm(&mut self, width: Width, opcode: &[u8], reg: Gpr, mem: Mem) {
+        let index = mem.index().map(Gpr::ext).unwrap_or(0);
+        self.rex(width, reg.ext(), index, mem.base().ext(), false);
+        self.code.extend_from_slice(opcode);
+        match mem {
+            Mem::BaseDisp(base, disp) => {
+                self.byte(0x80 | (reg.low() << 3) | base.low());
+                self.imm32(disp);
+            }
+            Mem::BaseIndex(base, index) => {
+                self.byte((reg.low() << 3) | 0b100);
+                self.byte((index.low() << 3) | base.low());
+            }
+        }
+    }
+
+    /// Emits `mov dst, [mem]`.
+    pub fn load(&mut self, width: Width, dst: Gpr, mem: Mem) {
+        self.op_rm(width, &[0x8B], dst, mem);
+    }
+
+    /// Emits `movzx dst, byte [mem]`.
+    pub fn load_u8(&mut self, dst: Gpr, mem: Mem) {
+        self.op_rm(Width::W32, &[0x0F, 0xB6], dst, mem);
+    }
+
+    /// Emits `movzx dst, word [mem]`.
+    pub fn load_u16(&mut self, dst: Gpr, mem: Mem) {
+        self.op_rm(Width::W32, &[0x0F, 0xB7], dst, mem);
+    }
+
+    /// Emits `movsx dst, byte [mem]`.
+    pub fn load_i8(&mut self, width: Width, dst: Gpr, mem: Mem) {
+        self.op_rm(width, &[0x0F, 0xBE], dst, mem);
+    }
+
+    /// Emits `movsx dst, word [mem]`.
+    pub fn load_i16(&mut self, width: Width, dst: Gpr, mem: Mem) {
+        self.op_rm(width, &[0x0F, 0xBF], dst, mem);
+    }
+
+    /// Emits `movsxd dst, dword [mem]`.
+    pub fn load_i32(&mut self, dst: Gpr, mem: Mem) {
+        self.op_rm(Width::W64, &[0x63], dst, mem);
+    }
+
+    /// Emits `mov [mem], src`.
+    pub fn store(&mut self, width: Width, mem: Mem, src: Gpr) {
+        self.op_rm(width, &[0x89], src, mem);
+    }
+
+    /// Emits `mov byte [mem], src`.
+    pub fn store8(&mut self, mem: Mem, src: Gpr) {
+        self.op_rm(Width::W32, &[0x88], src, mem);
+    }
+
+    /// Emits `mov word [mem], src`.
+    pub fn store16(&mut self, mem: Mem, src: Gpr) {
+        self.byte(0x66);
+        self.op_rm(Width::W32, &[0x89], src, mem);
+    }
+
+    /// Emits `mov dst, src`.
+    pub fn mov(&mut self, width: Width, dst: Gpr, src: Gpr) {
+        self.op_rr(width, &[0x89], src as u8, dst);
+    }
+
+    /// Emits a `mov dst, imm` that zero extends the 32-bit `imm` to 64 bits.
+    pub fn mov_imm32(&mut self, dst: Gpr, imm: u32) {
+        self.rex(Width::W32, 0, 0, dst.ext(), false);
+        self.byte(0xB8 | dst.low());
+        self.imm32(imm as i32);
+    }
+
+    /// Emits a `mov dst, imm` for the 64-bit `imm`.
+    pub fn mov_imm64(&mut self, dst: Gpr, imm: u64) {
+        if let Ok(imm) = i32::try_from(imm as i64) {
+            // Note: sign extends the 32-bit immediate to 64 bits.
+            self.op_rr(Width::W64, &[0xC7], 0, dst);
+            self.imm32(imm);
+            return;
+        }
+        self.rex(Width::W64, 0, 0, dst.ext(), false);
+        self.byte(0xB8 | dst.low());
+        self.code.extend_from_slice(&imm.to_le_bytes());
+    }
+
+    /// Emits `op dst, src`.
+    pub fn alu(&mut self, op: AluOp, width: Width, dst: Gpr, src: Gpr) {
+        let opcode = ((op as u8) << 3) | 0x01;
+        self.op_rr(width, &[opcode], src as u8, dst);
+    }
+
+    /// Emits `op dst, imm`.
+    ///
+    /// For [`Width::W64`] the 32-bit `imm` is sign extended to 64 bits.
+    pub fn alu_imm(&mut self, op: AluOp, width: Width, dst: Gpr, imm: i32) {
+        self.op_rr(width, &[0x81], op as u8, dst);
+        self.imm32(imm);
+    }
+
+    /// Emits `test lhs, rhs`.
+    pub fn test(&mut self, width: Width, lhs: Gpr, rhs: Gpr) {
+        self.op_rr(width, &[0x85], rhs as u8, lhs);
+    }
+
+    /// Emits `imul dst, src`.
+    pub fn imul(&mut self, width: Width, dst: Gpr, src: Gpr) {
+        self.op_rr(width, &[0x0F, 0xAF], dst as u8, src);
+    }
+
+    /// Emits `imul dst, dst, imm`.
+    pub fn imul_imm(&mut self, width: Width, dst: Gpr, imm: i32) {
+        self.op_rr(width, &[0x69], dst as u8, dst);
+        self.imm32(imm);
+    }
+
+    /// Emits `op dst, cl`.
+    pub fn shift(&mut self, op: ShiftOp, width: Width, dst: Gpr) {
+        self.op_rr(width, &[0xD3], op as u8, dst);
+    }
+
+    /// Emits `op dst, imm`.
+    pub fn shift_imm(&mut self, op: ShiftOp, width: Width, dst: Gpr, imm: u8) {
+        self.op_rr(width, &[0xC1], op as u8, dst);
+        self.byte(imm);
+    }
+
+    /// Emits `cdq` or `cqo` sign extending `rax` into `rdx`.
+    pub fn sign_extend_rax(&mut self, width: Width) {
+        self.rex(width, 0, 0, 0, false);
+        self.byte(0x99);
+    }
+
+    /// Emits `idiv src` for signed division of `rdx:rax` by `src`.
+    pub fn idiv(&mut self, width: Width, src: Gpr) {
+        self.op_rr(width, &[0xF7], 7, src);
+    }
+
+    /// Emits `div src` for unsigned division of `rdx:rax` by `src`.
+    pub fn div(&mut self, width: Width, src: Gpr) {
+        self.op_rr(width, &[0xF7], 6, src);
+    }
+
+    /// Emits `movsx dst, src` for the lower 8 bits of `src`.
+    pub fn sign_extend8(&mut self, width: Width, dst: Gpr, src: Gpr) {
+        // Note: the REX prefix selects `sil` and `dil` instead of `dh` and `bh`.
+        self.rex(width, dst.ext(), 0, src.ext(), true);
+        self.code.extend_from_slice(&[0x0F, 0xBE]);
+        self.byte(0xC0 | (dst.low() << 3) | src.low());
+    }
+
+    /// Emits `movsx dst, src` for the lower 16 bits of `src`.
+    pub fn sign_extend16(&mut self, width: Width, dst: Gpr, src: Gpr) {
+        self.op_rr(width, &[0x0F, 0xBF], dst as u8, src);
+    }
+
+    /// Emits `movsxd dst, src` for the lower 32 bits of `src`.
+    pub fn sign_extend32(&mut self, dst: Gpr, src: Gpr) {
+        self.op_rr(Width::W64, &[0x63], dst as u8, src);
+    }
+
+    /// Emits `setcc dst` followed by `movzx dst, dst` to materialize `cond` as `0` or `1`.
+    pub fn set_cond(&mut self, cond: Cond, dst: Gpr) {
+        self.rex(Width::W32, 0, 0, dst.ext(), true);
+        self.code.extend_from_slice(&[0x0F, 0x90 | cond as u8]);
+        self.byte(0xC0 | dst.low());
+        self.rex(Width::W32, dst.ext(), 0, dst.ext(), true);
+        self.code.extend_from_slice(&[0x0F, 0xB6]);
+        self.byte(0xC0 | (dst.low() << 3) | dst.low());
+    }
+
+    /// Emits `jcc rel32` and returns the [`Fixup`] of its displacement.
+    pub fn jcc(&mut self, cond: Cond) -> Fixup {
+        self.code.extend_from_slice(&[0x0F, 0x80 | cond as u8]);
+        self.rel32()
+    }
+
+    /// Emits `jmp rel32` and returns the [`Fixup`] of its displacement.
+    pub fn jmp(&mut self) -> Fixup {
+        self.byte(0xE9);
+        self.rel32()
+    }
+
+    /// Emits a zeroed 32-bit displacement and returns its [`Fixup`].
+    fn rel32(&mut self) -> Fixup {
+        let fixup = Fixup(self.pos());
+        self.imm32(0);
+        fixup
+    }
+
+    /// Patches the displacement at `fixup` to jump to the position `target`.
+    pub fn patch(&mut self, fixup: Fixup, target: usize) {
+        let Fixup(pos) = fixup;
+        let rel = target as isize - (pos as isize + 4);
+        let rel = i32::try_from(rel).unwrap_or_else(|_| panic!("out of bounds jump: {rel}"));
+        self.code[pos..pos + 4].copy_from_slice(&rel.to_le_bytes());
+    }
+
+    /// Emits `ret`.
+    pub fn ret(&mut self) {
+        self.byte(0xC3);
+    }
+
+    /// Emits `ud2` which raises an invalid opcode exception.
+    pub fn ud2(&mut self) {
+        self.code.extend_from_slice(&[0x0F, 0x0B]);
+    }
+}
@@ -0,0 +1,1223 @@
+//! Translates Wasmi bytecode into x86_64 machine code.
+//!
+//! # Note
+//!
+//! The generated machine code operates directly on the registers of the executed
+//! call frame and keeps no state of its own in between instructions. Therefore
+//! execution can leave the machine code before any instruction and continue
+//! via the interpreter at the same instruction.
+//!
+//! This is used for instructions that are not supported by the JIT as well as for
+//! the slow paths of supported instructions, for example traps, running out of fuel
+//! or out of bounds memory accesses. Since the machine code exits before the
+//! instruction has any side effects the interpreter re-executes it with its usual
+//! semantics.
+
+use super::{
+    asm::{AluOp, Assembler, Cond, Fixup, Gpr, Mem, ShiftOp, Width},
+    JitContext,
+};
+use crate::ir::{BranchOffset, BranchOffset16, Instruction, Reg};
+use alloc::vec::Vec;
+use core::{
+    mem::offset_of,
+    num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64},
+};
+
+/// Holds the pointer to the registers of the executed call frame.
+const REGS: Gpr = Gpr::R8;
+/// Holds the pointer to the default linear memory.
+const MEM: Gpr = Gpr::R9;
+/// Holds the length of the default linear memory in bytes.
+const MEM_LEN: Gpr = Gpr::R10;
+/// Holds the pointer to the remaining fuel.
+const FUEL: Gpr = Gpr::R11;
+
+/// Translates the Wasmi bytecode `instrs` of a function into x86_64 machine code.
+///
+/// Returns `None` if the first instruction of `instrs` is not supported by the JIT.
+///
+/// # Note
+///
+/// The machine code follows the System V calling convention and takes a
+/// pointer to a [`JitContext`] as its only parameter. Upon exit it returns
+/// the index of the instruction at which the interpreter resumes execution.
+pub fn compile(instrs: &[Instruction]) -> Option<Vec<u8>> {
+    let mut codegen = Codegen::default();
+    codegen.prologue();
+    for (index, instr) in instrs.iter().enumerate() {
+        codegen.labels.push(codegen.asm.pos());
+        let index = index as u32;
+        if !codegen.instr(index, instr) {
+            if index == 0 {
+                return None;
+            }
+            codegen.exit(index);
+        }
+    }
+    Some(codegen.finish())
+}
+
+/// The state of the translation of Wasmi bytecode into x86_64 machine code.
+#[derive(Debug, Default)]
+struct Codegen {
+    /// The assembler of the machine code.
+    asm: Assembler,
+    /// The positions of the machine code of all translated instructions.
+    labels: Vec<usize>,
+    /// The branches to patch with the positions of their target instructions.
+    branches: Vec<(Fixup, usize)>,
+    /// The slow path exits to patch with the exit code of their instruction index.
+    exits: Vec<(Fixup, u32)>,
+}
+
+impl Codegen {
+    /// Loads the [`JitContext`] into the registers used by the machine code.
+    fn prologue(&mut self) {
+        let ctx = |offset: usize| Mem::BaseDisp(Gpr::Rdi, offset as i32);
+        self.asm
+            .load(Width::W64, REGS, ctx(offset_of!(JitContext, regs)));
+        self.asm
+            .load(Width::W64, FUEL, ctx(offset_of!(JitContext, fuel)));
+        self.asm
+            .load(Width::W64, MEM, ctx(offset_of!(JitContext, memory)));
+        self.asm
+            .load(Width::W64, MEM_LEN, ctx(offset_of!(JitContext, memory_len)));
+    }
+
+    /// Patches all branches and emits the slow path exits.
+    fn finish(mut self) -> Vec<u8> {
+        // Note: valid Wasmi bytecode never falls through its last instruction.
+        self.asm.ud2();
+        for (fixup, index) in core::mem::take(&mut self.exits) {
+            let pos = self.asm.pos();
+            self.asm.patch(fixup, pos);
+            self.exit(index);
+        }
+        for (fixup, target) in core::mem::take(&mut self.branches) {
+            self.asm.patch(fixup, self.labels[target]);
+        }
+        self.asm.finish()
+    }
+
+    /// Emits an exit to the interpreter resuming at the instruction at `index`.
+    fn exit(&mut self, index: u32) {
+        self.asm.mov_imm32(Gpr::Rax, index);
+        self.asm.ret();
+    }
+
+    /// Emits a conditional exit to the interpreter resuming at the instruction at `index`.
+    fn exit_if(&mut self, cond: Cond, index: u32) {
+        let fixup = self.asm.jcc(cond);
+        self.exits.push((fixup, index));
+    }
+
+    /// Emits a conditional branch from the instruction at `index` by `offset`.
+    fn branch_if(&mut self, cond: Cond, index: u32, offset: BranchOffset16) {
+        let fixup = self.asm.jcc(cond);
+        self.push_branch(fixup, index, BranchOffset::from(offset));
+    }
+
+    /// Registers the branch at `fixup` from the instruction at `index` by `offset`.
+    fn push_branch(&mut self, fixup: Fixup, index: u32, offset: BranchOffset) {
+        let target = (index as i32 + offset.to_i32()) as usize;
+        self.branches.push((fixup, target));
+    }
+
+    /// Loads the value of `reg` into `dst`.
+    fn load(&mut self, dst: Gpr, reg: Reg) {
+        self.asm.load(Width::W64, dst, reg_mem(reg));
+    }
+
+    /// Stores the value of `src` into `reg`.
+    fn store(&mut self, reg: Reg, src: Gpr) {
+        self.asm.store(Width::W64, reg_mem(reg), src);
+    }
+
+    /// Loads the immediate `value` into `dst`.
+    ///
+    /// For [`Width::W32`] `value` is truncated to 32 bits.
+    fn load_imm(&mut self, width: Width, dst: Gpr, value: i64) {
+        match width {
+            Width::W32 => self.asm.mov_imm32(dst, value as u32),
+            Width::W64 => self.asm.mov_imm64(dst, value as u64),
+        }
+    }
+
+    /// Emits `result = lhs <op> rhs`.
+    fn alu_rr(&mut self, op: AluOp, width: Width, result: Reg, lhs: Reg, rhs: Reg) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(op, width, Gpr::Rax, Gpr::Rcx);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <op> rhs` with an immediate `rhs`.
+    fn alu_ri(&mut self, op: AluOp, width: Width, result: Reg, lhs: Reg, rhs: i32) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.alu_imm(op, width, Gpr::Rax, rhs);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <op> rhs` with an immediate `lhs`.
+    fn alu_ir(&mut self, op: AluOp, width: Width, result: Reg, lhs: i64, rhs: Reg) {
+        self.load_imm(width, Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(op, width, Gpr::Rax, Gpr::Rcx);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs * rhs`.
+    fn mul_rr(&mut self, width: Width, result: Reg, lhs: Reg, rhs: Reg) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.imul(width, Gpr::Rax, Gpr::Rcx);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs * rhs` with an immediate `rhs`.
+    fn mul_ri(&mut self, width: Width, result: Reg, lhs: Reg, rhs: i32) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.imul_imm(width, Gpr::Rax, rhs);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = (lhs <op> rhs) == 0`.
+    fn alu_eqz_rr(&mut self, op: AluOp, result: Reg, lhs: Reg, rhs: Reg) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(op, Width::W32, Gpr::Rax, Gpr::Rcx);
+        self.asm.set_cond(Cond::E, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = (lhs <op> rhs) == 0` with an immediate `rhs`.
+    fn alu_eqz_ri(&mut self, op: AluOp, result: Reg, lhs: Reg, rhs: i32) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.alu_imm(op, Width::W32, Gpr::Rax, rhs);
+        self.asm.set_cond(Cond::E, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <op> rhs` for shift and rotate operations.
+    fn shift_rr(&mut self, op: ShiftOp, width: Width, result: Reg, lhs: Reg, rhs: Reg) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.shift(op, width, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <op> rhs` for shift and rotate operations with an immediate `rhs`.
+    fn shift_ri(&mut self, op: ShiftOp, width: Width, result: Reg, lhs: Reg, rhs: i64) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.shift_imm(op, width, Gpr::Rax, rhs as u8);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <op> rhs` for shift and rotate operations with an immediate `lhs`.
+    fn shift_ir(&mut self, op: ShiftOp, width: Width, result: Reg, lhs: i64, rhs: Reg) {
+        self.load_imm(width, Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.shift(op, width, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <cond> rhs`.
+    fn cmp_rr(&mut self, cond: Cond, width: Width, result: Reg, lhs: Reg, rhs: Reg) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(AluOp::Cmp, width, Gpr::Rax, Gpr::Rcx);
+        self.asm.set_cond(cond, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <cond> rhs` with an immediate `rhs`.
+    fn cmp_ri(&mut self, cond: Cond, width: Width, result: Reg, lhs: Reg, rhs: i32) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.alu_imm(AluOp::Cmp, width, Gpr::Rax, rhs);
+        self.asm.set_cond(cond, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits `result = lhs <cond> rhs` with an immediate `lhs`.
+    fn cmp_ir(&mut self, cond: Cond, width: Width, result: Reg, lhs: i64, rhs: Reg) {
+        self.load_imm(width, Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(AluOp::Cmp, width, Gpr::Rax, Gpr::Rcx);
+        self.asm.set_cond(cond, Gpr::Rax);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Emits a branch by `offset` if `lhs <cond> rhs`.
+    fn branch_cmp_rr(
+        &mut self,
+        index: u32,
+        cond: Cond,
+        width: Width,
+        lhs: Reg,
+        rhs: Reg,
+        offset: BranchOffset16,
+    ) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(AluOp::Cmp, width, Gpr::Rax, Gpr::Rcx);
+        self.branch_if(cond, index, offset);
+    }
+
+    /// Emits a branch by `offset` if `lhs <cond> rhs` with an immediate `rhs`.
+    fn branch_cmp_ri(
+        &mut self,
+        index: u32,
+        cond: Cond,
+        width: Width,
+        lhs: Reg,
+        rhs: i32,
+        offset: BranchOffset16,
+    ) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.alu_imm(AluOp::Cmp, width, Gpr::Rax, rhs);
+        self.branch_if(cond, index, offset);
+    }
+
+    /// Emits a branch by `offset` if `lhs <cond> rhs` with an immediate `lhs`.
+    fn branch_cmp_ir(
+        &mut self,
+        index: u32,
+        cond: Cond,
+        width: Width,
+        lhs: i64,
+        rhs: Reg,
+        offset: BranchOffset16,
+    ) {
+        self.load_imm(width, Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(AluOp::Cmp, width, Gpr::Rax, Gpr::Rcx);
+        self.branch_if(cond, index, offset);
+    }
+
+    /// Emits a branch by `offset` if `lhs <op> rhs` is non-zero for [`Cond::Ne`] or zero for [`Cond::E`].
+    fn branch_alu_rr(
+        &mut self,
+        index: u32,
+        op: AluOp,
+        cond: Cond,
+        lhs: Reg,
+        rhs: Reg,
+        offset: BranchOffset16,
+    ) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.asm.alu(op, Width::W32, Gpr::Rax, Gpr::Rcx);
+        self.branch_if(cond, index, offset);
+    }
+
+    /// Emits a branch by `offset` if `lhs <op> rhs` is non-zero for [`Cond::Ne`] or zero for [`Cond::E`].
+    ///
+    /// The `rhs` operand is an immediate value.
+    fn branch_alu_ri(
+        &mut self,
+        index: u32,
+        op: AluOp,
+        cond: Cond,
+        lhs: Reg,
+        rhs: i32,
+        offset: BranchOffset16,
+    ) {
+        self.load(Gpr::Rax, lhs);
+        self.asm.alu_imm(op, Width::W32, Gpr::Rax, rhs);
+        self.branch_if(cond, index, offset);
+    }
+
+    /// Emits a division or remainder of `rax` by `rcx` storing the result into `result`.
+    ///
+    /// Exits to the interpreter at `index` if `rcx` is zero or, for signed operations,
+    /// if `rcx` is `-1` since the interpreter handles the overflow of `MIN / -1`.
+    fn div(
+        &mut self,
+        index: u32,
+        width: Width,
+        signed: bool,
+        rem: bool,
+        result: Reg,
+        check_rhs: bool,
+    ) {
+        if check_rhs {
+            self.asm.test(width, Gpr::Rcx, Gpr::Rcx);
+            self.exit_if(Cond::E, index);
+            if signed {
+                self.asm.alu_imm(AluOp::Cmp, width, Gpr::Rcx, -1);
+                self.exit_if(Cond::E, index);
+            }
+        }
+        match signed {
+            true => {
+                self.asm.sign_extend_rax(width);
+                self.asm.idiv(width, Gpr::Rcx);
+            }
+            false => {
+                self.asm.alu(AluOp::Xor, Width::W32, Gpr::Rdx, Gpr::Rdx);
+                self.asm.div(width, Gpr::Rcx);
+            }
+        }
+        self.store(result, if rem { Gpr::Rdx } else { Gpr::Rax });
+    }
+
+    /// Emits `result = lhs / rhs` or `result = lhs % rhs` if `rem` is `true`.
+    #[allow(clippy::too_many_arguments)]
+    fn div_rr(
+        &mut self,
+        index: u32,
+        width: Width,
+        signed: bool,
+        rem: bool,
+        result: Reg,
+        lhs: Reg,
+        rhs: Reg,
+    ) {
+        self.load(Gpr::Rax, lhs);
+        self.load(Gpr::Rcx, rhs);
+        self.div(index, width, signed, rem, result, true);
+    }
+
+    /// Emits `result = lhs / rhs` or `result = lhs % rhs` with a non-zero immediate `rhs`.
+    ///
+    /// Returns `false` if `rhs` is `-1` for signed operations which is left to the interpreter.
+    #[allow(clippy::too_many_arguments)]
+    fn div_ri(
+        &mut self,
+        index: u32,
+        width: Width,
+        signed: bool,
+        rem: bool,
+        result: Reg,
+        lhs: Reg,
+        rhs: i64,
+    ) -> bool {
+        if signed && rhs == -1 {
+            return false;
+        }
+        self.load(Gpr::Rax, lhs);
+        self.load_imm(width, Gpr::Rcx, rhs);
+        self.div(index, width, signed, rem, result, false);
+        true
+    }
+
+    /// Emits `result = lhs / rhs` or `result = lhs % rhs` with an immediate `lhs`.
+    ///
+    /// # Note
+    ///
+    /// Checking `rhs` for `-1` is superfluous since a 16-bit `lhs` never overflows.
+    #[allow(clippy::too_many_arguments)]
+    fn div_ir(
+        &mut self,
+        index: u32,
+        width: Width,
+        signed: bool,
+        rem: bool,
+        result: Reg,
+        lhs: i64,
+        rhs: Reg,
+    ) {
+        self.load(Gpr::Rcx, rhs);
+        self.asm.test(width, Gpr::Rcx, Gpr::Rcx);
+        self.exit_if(Cond::E, index);
+        self.load_imm(width, Gpr::Rax, lhs);
+        self.div(index, width, signed, rem, result, false);
+    }
+
+    /// Computes the effective address of a `size` bytes memory access into `rax`.
+    ///
+    /// Exits to the interpreter at `index` if the access is out of bounds.
+    fn effective_address(&mut self, index: u32, ptr: Reg, offset: u32, size: i32) -> Mem {
+        // Note: the 32-bit load zero extends the `i32` pointer to 64 bits.
+        self.asm.load(Width::W32, Gpr::Rax, reg_mem(ptr));
+        if offset != 0 {
+            self.asm
+                .alu_imm(AluOp::Add, Width::W64, Gpr::Rax, offset as i32);
+        }
+        self.asm.mov(Width::W64, Gpr::Rcx, Gpr::Rax);
+        self.asm.alu_imm(AluOp::Add, Width::W64, Gpr::Rcx, size);
+        self.asm.alu(AluOp::Cmp, Width::W64, Gpr::Rcx, MEM_LEN);
+        self.exit_if(Cond::A, index);
+        Mem::BaseIndex(MEM, Gpr::Rax)
+    }
+
+    /// Emits a `size` bytes load from `ptr + offset` into `result` using `load`.
+    fn load_mem(
+        &mut self,
+        index: u32,
+        result: Reg,
+        ptr: Reg,
+        offset: u32,
+        size: i32,
+        load: fn(&mut Assembler, Mem),
+    ) {
+        let mem = self.effective_address(index, ptr, offset, size);
+        load(&mut self.asm, mem);
+        self.store(result, Gpr::Rcx);
+    }
+
+    /// Emits a `size` bytes store of `value` to `ptr + offset` using `store`.
+    #[allow(clippy::too_many_arguments)]
+    fn store_mem(
+        &mut self,
+        index: u32,
+        ptr: Reg,
+        offset: u32,
+        size: i32,
+        value: Result<Reg, i64>,
+        store: fn(&mut Assembler, Mem),
+    ) {
+        let mem = self.effective_address(index, ptr, offset, size);
+        match value {
+            Ok(value) => self.load(Gpr::Rcx, value),
+            Err(value) => self.load_imm(Width::W64, Gpr::Rcx, value),
+        }
+        store(&mut self.asm, mem);
+    }
+
+    /// Emits `result = extend(input)` using `extend`.
+    fn extend(&mut self, result: Reg, input: Reg, extend: fn(&mut Assembler)) {
+        self.load(Gpr::Rax, input);
+        extend(&mut self.asm);
+        self.store(result, Gpr::Rax);
+    }
+
+    /// Translates the `instr` at `index` into machine code.
+    ///
+    /// Returns `false` without emitting machine code if `instr` is not supported.
+    fn instr(&mut self, index: u32, instr: &Instruction) -> bool {
+        use AluOp as Op;
+        use Cond as C;
+        use Instruction as Instr;
+        use ShiftOp as Sh;
+        use Width::{W32, W64};
+        match *instr {
+            Instr::ConsumeFuel { block_fuel } => {
+                let fuel = block_fuel.to_u64();
+                let remaining = Mem::BaseDisp(FUEL, 0);
+                self.asm.load(W64, Gpr::Rax, remaining);
+                match i32::try_from(fuel) {
+                    Ok(fuel) => self.asm.alu_imm(Op::Sub, W64, Gpr::Rax, fuel),
+                    Err(_) => {
+                        self.asm.mov_imm64(Gpr::Rcx, fuel);
+                        self.asm.alu(Op::Sub, W64, Gpr::Rax, Gpr::Rcx);
+                    }
+                }
+                self.exit_if(C::B, index);
+                self.asm.store(W64, remaining, Gpr::Rax);
+            }
+            Instr::Branch { offset } => {
+                let fixup = self.asm.jmp();
+                self.push_branch(fixup, index, offset);
+            }
+            Instr::Copy { result, value } => {
+                self.load(Gpr::Rax, value);
+                self.store(result, Gpr::Rax);
+            }
+            Instr::Copy2 { results, values } => {
+                let result0 = results.span().head();
+                self.load(Gpr::Rax, values[0]);
+                self.load(Gpr::Rcx, values[1]);
+                self.store(result0, Gpr::Rax);
+                self.store(result0.next(), Gpr::Rcx);
+            }
+            Instr::CopyImm32 { result, value } => {
+                self.asm.mov_imm32(Gpr::Rax, u32::from(value));
+                self.store(result, Gpr::Rax);
+            }
+            Instr::CopyI64Imm32 { result, value } => {
+                self.asm.mov_imm64(Gpr::Rax, i64::from(value) as u64);
+                self.store(result, Gpr::Rax);
+            }
+            Instr::CopyF64Imm32 { result, value } => {
+                self.asm.mov_imm64(Gpr::Rax, f64::from(value).to_bits());
+                self.store(result, Gpr::Rax);
+            }
+
+            // Fused compare and branch instructions
+            Instr::BranchI32And { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::And, C::Ne, lhs, rhs, offset)
+            }
+            Instr::BranchI32AndImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::And, C::Ne, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32Or { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::Or, C::Ne, lhs, rhs, offset)
+            }
+            Instr::BranchI32OrImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::Or, C::Ne, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32Xor { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::Xor, C::Ne, lhs, rhs, offset)
+            }
+            Instr::BranchI32XorImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::Xor, C::Ne, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32AndEqz { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::And, C::E, lhs, rhs, offset)
+            }
+            Instr::BranchI32AndEqzImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::And, C::E, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32OrEqz { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::Or, C::E, lhs, rhs, offset)
+            }
+            Instr::BranchI32OrEqzImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::Or, C::E, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32XorEqz { lhs, rhs, offset } => {
+                self.branch_alu_rr(index, Op::Xor, C::E, lhs, rhs, offset)
+            }
+            Instr::BranchI32XorEqzImm16 { lhs, rhs, offset } => {
+                self.branch_alu_ri(index, Op::Xor, C::E, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32Eq { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::E, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32EqImm16 { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::E, W32, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32Ne { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Ne, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32NeImm16 { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Ne, W32, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32LtS { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::L, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32LtSImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::L, W32, i32::from(lhs).into(), rhs, offset)
+            }
+            Instr::BranchI32LtSImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::L, W32, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32LtU { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::B, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32LtUImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::B, W32, u32::from(lhs).into(), rhs, offset)
+            }
+            Instr::BranchI32LtUImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::B, W32, lhs, u32::from(rhs) as i32, offset)
+            }
+            Instr::BranchI32LeS { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Le, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32LeSImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::Le, W32, i32::from(lhs).into(), rhs, offset)
+            }
+            Instr::BranchI32LeSImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Le, W32, lhs, rhs.into(), offset)
+            }
+            Instr::BranchI32LeU { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Be, W32, lhs, rhs, offset)
+            }
+            Instr::BranchI32LeUImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::Be, W32, u32::from(lhs).into(), rhs, offset)
+            }
+            Instr::BranchI32LeUImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Be, W32, lhs, u32::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64Eq { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::E, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64EqImm16 { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::E, W64, lhs, i64::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64Ne { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Ne, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64NeImm16 { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Ne, W64, lhs, i64::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64LtS { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::L, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64LtSImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::L, W64, lhs.into(), rhs, offset)
+            }
+            Instr::BranchI64LtSImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::L, W64, lhs, i64::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64LtU { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::B, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64LtUImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::B, W64, u64::from(lhs) as i64, rhs, offset)
+            }
+            Instr::BranchI64LtUImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::B, W64, lhs, u64::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64LeS { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Le, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64LeSImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::Le, W64, lhs.into(), rhs, offset)
+            }
+            Instr::BranchI64LeSImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Le, W64, lhs, i64::from(rhs) as i32, offset)
+            }
+            Instr::BranchI64LeU { lhs, rhs, offset } => {
+                self.branch_cmp_rr(index, C::Be, W64, lhs, rhs, offset)
+            }
+            Instr::BranchI64LeUImm16Lhs { lhs, rhs, offset } => {
+                self.branch_cmp_ir(index, C::Be, W64, u64::from(lhs) as i64, rhs, offset)
+            }
+            Instr::BranchI64LeUImm16Rhs { lhs, rhs, offset } => {
+                self.branch_cmp_ri(index, C::Be, W64, lhs, u64::from(rhs) as i32, offset)
+            }
+
+            // Comparison instructions
+            Instr::I32Eq { result, lhs, rhs } => self.cmp_rr(C::E, W32, result, lhs, rhs),
+            Instr::I32EqImm16 { result, lhs, rhs } => {
+                self.cmp_ri(C::E, W32, result, lhs, rhs.into())
+            }
+            Instr::I32Ne { result, lhs, rhs } => self.cmp_rr(C::Ne, W32, result, lhs, rhs),
+            Instr::I32NeImm16 { result, lhs, rhs } => {
+                self.cmp_ri(C::Ne, W32, result, lhs, rhs.into())
+            }
+            Instr::I32LtS { result, lhs, rhs } => self.cmp_rr(C::L, W32, result, lhs, rhs),
+            Instr::I32LtSImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::L, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32LtSImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::L, W32, result, lhs, rhs.into())
+            }
+            Instr::I32LtU { result, lhs, rhs } => self.cmp_rr(C::B, W32, result, lhs, rhs),
+            Instr::I32LtUImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::B, W32, result, u32::from(lhs).into(), rhs)
+            }
+            Instr::I32LtUImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::B, W32, result, lhs, u32::from(rhs) as i32)
+            }
+            Instr::I32LeS { result, lhs, rhs } => self.cmp_rr(C::Le, W32, result, lhs, rhs),
+            Instr::I32LeSImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::Le, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32LeSImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::Le, W32, result, lhs, rhs.into())
+            }
+            Instr::I32LeU { result, lhs, rhs } => self.cmp_rr(C::Be, W32, result, lhs, rhs),
+            Instr::I32LeUImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::Be, W32, result, u32::from(lhs).into(), rhs)
+            }
+            Instr::I32LeUImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::Be, W32, result, lhs, u32::from(rhs) as i32)
+            }
+            Instr::I64Eq { result, lhs, rhs } => self.cmp_rr(C::E, W64, result, lhs, rhs),
+            Instr::I64EqImm16 { result, lhs, rhs } => {
+                self.cmp_ri(C::E, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64Ne { result, lhs, rhs } => self.cmp_rr(C::Ne, W64, result, lhs, rhs),
+            Instr::I64NeImm16 { result, lhs, rhs } => {
+                self.cmp_ri(C::Ne, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64LtS { result, lhs, rhs } => self.cmp_rr(C::L, W64, result, lhs, rhs),
+            Instr::I64LtSImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::L, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64LtSImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::L, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64LtU { result, lhs, rhs } => self.cmp_rr(C::B, W64, result, lhs, rhs),
+            Instr::I64LtUImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::B, W64, result, u64::from(lhs) as i64, rhs)
+            }
+            Instr::I64LtUImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::B, W64, result, lhs, u64::from(rhs) as i32)
+            }
+            Instr::I64LeS { result, lhs, rhs } => self.cmp_rr(C::Le, W64, result, lhs, rhs),
+            Instr::I64LeSImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::Le, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64LeSImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::Le, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64LeU { result, lhs, rhs } => self.cmp_rr(C::Be, W64, result, lhs, rhs),
+            Instr::I64LeUImm16Lhs { result, lhs, rhs } => {
+                self.cmp_ir(C::Be, W64, result, u64::from(lhs) as i64, rhs)
+            }
+            Instr::I64LeUImm16Rhs { result, lhs, rhs } => {
+                self.cmp_ri(C::Be, W64, result, lhs, u64::from(rhs) as i32)
+            }
+
+            // Integer arithmetic instructions
+            Instr::I32Add { result, lhs, rhs } => self.alu_rr(Op::Add, W32, result, lhs, rhs),
+            Instr::I32AddImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Add, W32, result, lhs, rhs.into())
+            }
+            Instr::I32Sub { result, lhs, rhs } => self.alu_rr(Op::Sub, W32, result, lhs, rhs),
+            Instr::I32SubImm16Lhs { result, lhs, rhs } => {
+                self.alu_ir(Op::Sub, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32Mul { result, lhs, rhs } => self.mul_rr(W32, result, lhs, rhs),
+            Instr::I32MulImm16 { result, lhs, rhs } => self.mul_ri(W32, result, lhs, rhs.into()),
+            Instr::I32And { result, lhs, rhs } => self.alu_rr(Op::And, W32, result, lhs, rhs),
+            Instr::I32AndImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::And, W32, result, lhs, rhs.into())
+            }
+            Instr::I32Or { result, lhs, rhs } => self.alu_rr(Op::Or, W32, result, lhs, rhs),
+            Instr::I32OrImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Or, W32, result, lhs, rhs.into())
+            }
+            Instr::I32Xor { result, lhs, rhs } => self.alu_rr(Op::Xor, W32, result, lhs, rhs),
+            Instr::I32XorImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Xor, W32, result, lhs, rhs.into())
+            }
+            Instr::I32AndEqz { result, lhs, rhs } => self.alu_eqz_rr(Op::And, result, lhs, rhs),
+            Instr::I32AndEqzImm16 { result, lhs, rhs } => {
+                self.alu_eqz_ri(Op::And, result, lhs, rhs.into())
+            }
+            Instr::I32OrEqz { result, lhs, rhs } => self.alu_eqz_rr(Op::Or, result, lhs, rhs),
+            Instr::I32OrEqzImm16 { result, lhs, rhs } => {
+                self.alu_eqz_ri(Op::Or, result, lhs, rhs.into())
+            }
+            Instr::I32XorEqz { result, lhs, rhs } => self.alu_eqz_rr(Op::Xor, result, lhs, rhs),
+            Instr::I32XorEqzImm16 { result, lhs, rhs } => {
+                self.alu_eqz_ri(Op::Xor, result, lhs, rhs.into())
+            }
+            Instr::I64Add { result, lhs, rhs } => self.alu_rr(Op::Add, W64, result, lhs, rhs),
+            Instr::I64AddImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Add, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64Sub { result, lhs, rhs } => self.alu_rr(Op::Sub, W64, result, lhs, rhs),
+            Instr::I64SubImm16Lhs { result, lhs, rhs } => {
+                self.alu_ir(Op::Sub, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64Mul { result, lhs, rhs } => self.mul_rr(W64, result, lhs, rhs),
+            Instr::I64MulImm16 { result, lhs, rhs } => {
+                self.mul_ri(W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64And { result, lhs, rhs } => self.alu_rr(Op::And, W64, result, lhs, rhs),
+            Instr::I64AndImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::And, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64Or { result, lhs, rhs } => self.alu_rr(Op::Or, W64, result, lhs, rhs),
+            Instr::I64OrImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Or, W64, result, lhs, i64::from(rhs) as i32)
+            }
+            Instr::I64Xor { result, lhs, rhs } => self.alu_rr(Op::Xor, W64, result, lhs, rhs),
+            Instr::I64XorImm16 { result, lhs, rhs } => {
+                self.alu_ri(Op::Xor, W64, result, lhs, i64::from(rhs) as i32)
+            }
+
+            // Integer division and remainder instructions
+            Instr::I32DivS { result, lhs, rhs } => {
+                self.div_rr(index, W32, true, false, result, lhs, rhs)
+            }
+            Instr::I32DivSImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W32,
+                    true,
+                    false,
+                    result,
+                    lhs,
+                    NonZeroI32::from(rhs).get().into(),
+                )
+            }
+            Instr::I32DivSImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W32, true, false, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32DivU { result, lhs, rhs } => {
+                self.div_rr(index, W32, false, false, result, lhs, rhs)
+            }
+            Instr::I32DivUImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W32,
+                    false,
+                    false,
+                    result,
+                    lhs,
+                    NonZeroU32::from(rhs).get().into(),
+                )
+            }
+            Instr::I32DivUImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W32, false, false, result, u32::from(lhs).into(), rhs)
+            }
+            Instr::I32RemS { result, lhs, rhs } => {
+                self.div_rr(index, W32, true, true, result, lhs, rhs)
+            }
+            Instr::I32RemSImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W32,
+                    true,
+                    true,
+                    result,
+                    lhs,
+                    NonZeroI32::from(rhs).get().into(),
+                )
+            }
+            Instr::I32RemSImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W32, true, true, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32RemU { result, lhs, rhs } => {
+                self.div_rr(index, W32, false, true, result, lhs, rhs)
+            }
+            Instr::I32RemUImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W32,
+                    false,
+                    true,
+                    result,
+                    lhs,
+                    NonZeroU32::from(rhs).get().into(),
+                )
+            }
+            Instr::I32RemUImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W32, false, true, result, u32::from(lhs).into(), rhs)
+            }
+            Instr::I64DivS { result, lhs, rhs } => {
+                self.div_rr(index, W64, true, false, result, lhs, rhs)
+            }
+            Instr::I64DivSImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W64,
+                    true,
+                    false,
+                    result,
+                    lhs,
+                    NonZeroI64::from(rhs).get(),
+                )
+            }
+            Instr::I64DivSImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W64, true, false, result, lhs.into(), rhs)
+            }
+            Instr::I64DivU { result, lhs, rhs } => {
+                self.div_rr(index, W64, false, false, result, lhs, rhs)
+            }
+            Instr::I64DivUImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W64,
+                    false,
+                    false,
+                    result,
+                    lhs,
+                    NonZeroU64::from(rhs).get() as i64,
+                )
+            }
+            Instr::I64DivUImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W64, false, false, result, u64::from(lhs) as i64, rhs)
+            }
+            Instr::I64RemS { result, lhs, rhs } => {
+                self.div_rr(index, W64, true, true, result, lhs, rhs)
+            }
+            Instr::I64RemSImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W64,
+                    true,
+                    true,
+                    result,
+                    lhs,
+                    NonZeroI64::from(rhs).get(),
+                )
+            }
+            Instr::I64RemSImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W64, true, true, result, lhs.into(), rhs)
+            }
+            Instr::I64RemU { result, lhs, rhs } => {
+                self.div_rr(index, W64, false, true, result, lhs, rhs)
+            }
+            Instr::I64RemUImm16Rhs { result, lhs, rhs } => {
+                return self.div_ri(
+                    index,
+                    W64,
+                    false,
+                    true,
+                    result,
+                    lhs,
+                    NonZeroU64::from(rhs).get() as i64,
+                )
+            }
+            Instr::I64RemUImm16Lhs { result, lhs, rhs } => {
+                self.div_ir(index, W64, false, true, result, u64::from(lhs) as i64, rhs)
+            }
+
+            // Shift and rotate instructions
+            Instr::I32Shl { result, lhs, rhs } => self.shift_rr(Sh::Shl, W32, result, lhs, rhs),
+            Instr::I32ShlBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Shl, W32, result, lhs, i32::from(rhs).into())
+            }
+            Instr::I32ShlImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Shl, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32ShrU { result, lhs, rhs } => self.shift_rr(Sh::Shr, W32, result, lhs, rhs),
+            Instr::I32ShrUBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Shr, W32, result, lhs, i32::from(rhs).into())
+            }
+            Instr::I32ShrUImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Shr, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32ShrS { result, lhs, rhs } => self.shift_rr(Sh::Sar, W32, result, lhs, rhs),
+            Instr::I32ShrSBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Sar, W32, result, lhs, i32::from(rhs).into())
+            }
+            Instr::I32ShrSImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Sar, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32Rotl { result, lhs, rhs } => self.shift_rr(Sh::Rol, W32, result, lhs, rhs),
+            Instr::I32RotlBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Rol, W32, result, lhs, i32::from(rhs).into())
+            }
+            Instr::I32RotlImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Rol, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I32Rotr { result, lhs, rhs } => self.shift_rr(Sh::Ror, W32, result, lhs, rhs),
+            Instr::I32RotrBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Ror, W32, result, lhs, i32::from(rhs).into())
+            }
+            Instr::I32RotrImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Ror, W32, result, i32::from(lhs).into(), rhs)
+            }
+            Instr::I64Shl { result, lhs, rhs } => self.shift_rr(Sh::Shl, W64, result, lhs, rhs),
+            Instr::I64ShlBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Shl, W64, result, lhs, rhs.into())
+            }
+            Instr::I64ShlImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Shl, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64ShrU { result, lhs, rhs } => self.shift_rr(Sh::Shr, W64, result, lhs, rhs),
+            Instr::I64ShrUBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Shr, W64, result, lhs, rhs.into())
+            }
+            Instr::I64ShrUImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Shr, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64ShrS { result, lhs, rhs } => self.shift_rr(Sh::Sar, W64, result, lhs, rhs),
+            Instr::I64ShrSBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Sar, W64, result, lhs, rhs.into())
+            }
+            Instr::I64ShrSImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Sar, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64Rotl { result, lhs, rhs } => self.shift_rr(Sh::Rol, W64, result, lhs, rhs),
+            Instr::I64RotlBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Rol, W64, result, lhs, rhs.into())
+            }
+            Instr::I64RotlImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Rol, W64, result, lhs.into(), rhs)
+            }
+            Instr::I64Rotr { result, lhs, rhs } => self.shift_rr(Sh::Ror, W64, result, lhs, rhs),
+            Instr::I64RotrBy { result, lhs, rhs } => {
+                self.shift_ri(Sh::Ror, W64, result, lhs, rhs.into())
+            }
+            Instr::I64RotrImm16 { result, lhs, rhs } => {
+                self.shift_ir(Sh::Ror, W64, result, lhs.into(), rhs)
+            }
+
+            // Conversion instructions
+            Instr::I32WrapI64 { result, input } => {
+                self.extend(result, input, |asm| asm.mov(W32, Gpr::Rax, Gpr::Rax))
+            }
+            Instr::I32Extend8S { result, input } => self.extend(result, input, |asm| {
+                asm.sign_extend8(W32, Gpr::Rax, Gpr::Rax)
+            }),
+            Instr::I32Extend16S { result, input } => self.extend(result, input, |asm| {
+                asm.sign_extend16(W32, Gpr::Rax, Gpr::Rax)
+            }),
+            Instr::I64Extend8S { result, input } => self.extend(result, input, |asm| {
+                asm.sign_extend8(W64, Gpr::Rax, Gpr::Rax)
+            }),
+            Instr::I64Extend16S { result, input } => self.extend(result, input, |asm| {
+                asm.sign_extend16(W64, Gpr::Rax, Gpr::Rax)
+            }),
+            Instr::I64Extend32S { result, input } => {
+                self.extend(result, input, |asm| asm.sign_extend32(Gpr::Rax, Gpr::Rax))
+            }
+
+            // Load instructions of the default linear memory
+            Instr::Load32Offset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 4, |asm, mem| {
+                asm.load(W32, Gpr::Rcx, mem)
+            }),
+            Instr::Load64Offset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 8, |asm, mem| {
+                asm.load(W64, Gpr::Rcx, mem)
+            }),
+            Instr::I32Load8sOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 1, |asm, mem| {
+                asm.load_i8(W32, Gpr::Rcx, mem)
+            }),
+            Instr::I32Load8uOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 1, |asm, mem| {
+                asm.load_u8(Gpr::Rcx, mem)
+            }),
+            Instr::I32Load16sOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 2, |asm, mem| {
+                asm.load_i16(W32, Gpr::Rcx, mem)
+            }),
+            Instr::I32Load16uOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 2, |asm, mem| {
+                asm.load_u16(Gpr::Rcx, mem)
+            }),
+            Instr::I64Load8sOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 1, |asm, mem| {
+                asm.load_i8(W64, Gpr::Rcx, mem)
+            }),
+            Instr::I64Load8uOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 1, |asm, mem| {
+                asm.load_u8(Gpr::Rcx, mem)
+            }),
+            Instr::I64Load16sOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 2, |asm, mem| {
+                asm.load_i16(W64, Gpr::Rcx, mem)
+            }),
+            Instr::I64Load16uOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 2, |asm, mem| {
+                asm.load_u16(Gpr::Rcx, mem)
+            }),
+            Instr::I64Load32sOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 4, |asm, mem| {
+                asm.load_i32(Gpr::Rcx, mem)
+            }),
+            Instr::I64Load32uOffset16 {
+                result,
+                ptr,
+                offset,
+            } => self.load_mem(index, result, ptr, offset.into(), 4, |asm, mem| {
+                asm.load(W32, Gpr::Rcx, mem)
+            }),
+
+            // Store instructions of the default linear memory
+            Instr::Store32Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 4, Ok(value), |asm, mem| {
+                    asm.store(W32, mem, Gpr::Rcx)
+                })
+            }
+            Instr::Store64Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 8, Ok(value), |asm, mem| {
+                    asm.store(W64, mem, Gpr::Rcx)
+                })
+            }
+            Instr::I32StoreOffset16Imm16 { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                4,
+                Err(i32::from(value).into()),
+                |asm, mem| asm.store(W32, mem, Gpr::Rcx),
+            ),
+            Instr::I32Store8Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 1, Ok(value), |asm, mem| {
+                    asm.store8(mem, Gpr::Rcx)
+                })
+            }
+            Instr::I32Store8Offset16Imm { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                1,
+                Err(value.into()),
+                |asm, mem| asm.store8(mem, Gpr::Rcx),
+            ),
+            Instr::I32Store16Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 2, Ok(value), |asm, mem| {
+                    asm.store16(mem, Gpr::Rcx)
+                })
+            }
+            Instr::I32Store16Offset16Imm { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                2,
+                Err(value.into()),
+                |asm, mem| asm.store16(mem, Gpr::Rcx),
+            ),
+            Instr::I64StoreOffset16Imm16 { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                8,
+                Err(value.into()),
+                |asm, mem| asm.store(W64, mem, Gpr::Rcx),
+            ),
+            Instr::I64Store8Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 1, Ok(value), |asm, mem| {
+                    asm.store8(mem, Gpr::Rcx)
+                })
+            }
+            Instr::I64Store8Offset16Imm { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                1,
+                Err(value.into()),
+                |asm, mem| asm.store8(mem, Gpr::Rcx),
+            ),
+            Instr::I64Store16Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 2, Ok(value), |asm, mem| {
+                    asm.store16(mem, Gpr::Rcx)
+                })
+            }
+            Instr::I64Store16Offset16Imm { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                2,
+                Err(value.into()),
+                |asm, mem| asm.store16(mem, Gpr::Rcx),
+            ),
+            Instr::I64Store32Offset16 { ptr, offset, value } => {
+                self.store_mem(index, ptr, offset.into(), 4, Ok(value), |asm, mem| {
+                    asm.store(W32, mem, Gpr::Rcx)
+                })
+            }
+            Instr::I64Store32Offset16Imm16 { ptr, offset, value } => self.store_mem(
+                index,
+                ptr,
+                offset.into(),
+                4,
+                Err(i32::from(value).into()),
+                |asm, mem| asm.store(W32, mem, Gpr::Rcx),
+            ),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Returns the memory operand of the register `reg` of the executed call frame.
+fn reg_mem(reg: Reg) -> Mem {
+    Mem::BaseDisp(REGS, i32::from(i16::from(reg)) * 8)
+}
@@ -0,0 +1,148 @@
+//! An experimental baseline JIT compiling Wasmi bytecode into x86_64 machine code.
+//!
+//! The JIT translates the Wasmi bytecode of hot functions instruction by instruction
+//! and leaves everything it does not support to the interpreter. The generated
+//! machine code operates on the same registers, fuel and linear memory as the
+//! interpreter and returns the index of the instruction at which the interpreter
+//! shall resume execution.
+
+mod asm;
+mod codegen;
+#[cfg(test)]
+mod tests;
+
+use crate::core::UntypedVal;
+use core::{fmt, ptr::NonNull};
+
+/// The state shared between the interpreter and the machine code generated by the JIT.
+///
+/// # Note
+///
+/// The layout of this type is part of the ABI of the generated machine code.
+#[derive(Debug)]
+#[repr(C)]
+pub struct JitContext {
+    /// The registers of the executed call frame.
+    pub regs: *mut UntypedVal,
+    /// The remaining fuel of the [`Store`](crate::Store).
+    ///
+    /// Left untouched by the generated machine code if fuel metering is disabled
+    /// since it does not contain [`Instruction::ConsumeFuel`](crate::ir::Instruction::ConsumeFuel) then.
+    pub fuel: *mut u64,
+    /// The bytes of the default linear memory or dangling if there is none.
+    pub memory: *mut u8,
+    /// The length of the default linear memory in bytes.
+    pub memory_len: usize,
+}
+
+/// The signature of the machine code generated by the JIT.
+type JitFn = unsafe extern "sysv64" fn(ctx: *mut JitContext) -> u32;
+
+/// A Wasmi bytecode function compiled to x86_64 machine code.
+///
+/// Owns the executable memory of its machine code.
+pub struct JitFunc {
+    /// The start of the executable memory.
+    code: NonNull<u8>,
+    /// The length of the executable memory in bytes.
+    len: usize,
+}
+
+// Safety: the executable memory is immutable and exclusively owned by the `JitFunc`.
+unsafe impl Send for JitFunc {}
+// Safety: the executable memory is immutable and exclusively owned by the `JitFunc`.
+unsafe impl Sync for JitFunc {}
+
+impl fmt::Debug for JitFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JitFunc")
+            .field("code", &self.code)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl JitFunc {
+    /// Compiles the Wasmi bytecode `instrs` of a function into machine code.
+    ///
+    /// Returns `None` if the JIT does not support the first instruction of `instrs`
+    /// since the machine code would immediately exit to the interpreter, or if
+    /// allocating executable memory failed.
+    pub fn compile(instrs: &[crate::ir::Instruction]) -> Option<Self> {
+        let code = codegen::compile(instrs)?;
+        Self::new(&code)
+    }
+
+    /// Copies the machine `code` into newly allocated executable memory.
+    ///
+    /// Returns `None` if allocating executable memory failed.
+    fn new(code: &[u8]) -> Option<Self> {
+        let len = code.len();
+        // Safety: the requested mapping is anonymous and private which
+        //         does not alias any existing memory.
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return None;
+        }
+        let this = Self {
+            code: NonNull::new(ptr.cast::<u8>())?,
+            len,
+        };
+        // Safety: the mapping is writable and spans `len` bytes.
+        unsafe { core::ptr::copy_nonoverlapping(code.as_ptr(), this.code.as_ptr(), len) };
+        // Safety: the mapping is owned by `this` and no longer written to.
+        let result = unsafe { libc::mprotect(ptr, len, libc::PROT_READ | libc::PROT_EXEC) };
+        if result != 0 {
+            return None;
+        }
+        Some(this)
+    }
+
+    /// Returns the [`JitEntry`] of the machine code.
+    ///
+    /// # Note
+    ///
+    /// The returned [`JitEntry`] must not be used after `self` has been dropped.
+    pub fn entry(&self) -> JitEntry {
+        // Safety: `code` points to machine code generated for the `JitFn` signature.
+        let func = unsafe { core::mem::transmute::<*mut u8, JitFn>(self.code.as_ptr()) };
+        JitEntry { func }
+    }
+}
+
+impl Drop for JitFunc {
+    fn drop(&mut self) {
+        // Safety: the mapping is owned by `self` and no longer executed.
+        unsafe { libc::munmap(self.code.as_ptr().cast(), self.len) };
+    }
+}
+
+/// The entry point of the machine code of a [`JitFunc`].
+#[derive(Debug, Copy, Clone)]
+pub struct JitEntry {
+    /// The machine code of the [`JitFunc`].
+    func: JitFn,
+}
+
+impl JitEntry {
+    /// Executes the machine code using `ctx`.
+    ///
+    /// Returns the index of the instruction at which the interpreter resumes execution.
+    ///
+    /// # Safety
+    ///
+    /// - The [`JitFunc`] of `self` must still be alive.
+    /// - All pointers of `ctx` must be valid for the executed function.
+    pub unsafe fn execute(&self, ctx: &mut JitContext) -> u32 {
+        unsafe { (self.func)(ctx) }
+    }
+}
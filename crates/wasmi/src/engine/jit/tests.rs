@@ -0,0 +1,227 @@
+use super::{
+    asm::{AluOp, Assembler, Cond, Gpr, Mem, ShiftOp, Width},
+    JitContext,
+    JitFunc,
+};
+use crate::{
+    core::UntypedVal,
+    ir::{BlockFuel, BranchOffset, BranchOffset16, Instruction, Reg},
+};
+use std::{vec, vec::Vec};
+
+/// Asserts that assembling via `f` results in the `expected` machine code.
+fn assert_asm(f: impl FnOnce(&mut Assembler), expected: &[u8]) {
+    let mut asm = Assembler::default();
+    f(&mut asm);
+    assert_eq!(asm.finish(), expected);
+}
+
+#[test]
+fn encode_loads_and_stores() {
+    assert_asm(
+        |asm| asm.load(Width::W64, Gpr::Rax, Mem::BaseDisp(Gpr::R8, 8)),
+        &[0x49, 0x8B, 0x80, 0x08, 0x00, 0x00, 0x00],
+    );
+    assert_asm(
+        |asm| asm.load(Width::W32, Gpr::Rcx, Mem::BaseDisp(Gpr::R8, -8)),
+        &[0x41, 0x8B, 0x88, 0xF8, 0xFF, 0xFF, 0xFF],
+    );
+    assert_asm(
+        |asm| asm.load_u8(Gpr::Rcx, Mem::BaseIndex(Gpr::R9, Gpr::Rax)),
+        &[0x41, 0x0F, 0xB6, 0x0C, 0x01],
+    );
+    assert_asm(
+        |asm| asm.store8(Mem::BaseIndex(Gpr::R9, Gpr::Rax), Gpr::Rcx),
+        &[0x41, 0x88, 0x0C, 0x01],
+    );
+    assert_asm(
+        |asm| asm.store16(Mem::BaseIndex(Gpr::R9, Gpr::Rax), Gpr::Rcx),
+        &[0x66, 0x41, 0x89, 0x0C, 0x01],
+    );
+}
+
+#[test]
+fn encode_immediates() {
+    assert_asm(
+        |asm| asm.mov_imm32(Gpr::Rax, 42),
+        &[0xB8, 0x2A, 0x00, 0x00, 0x00],
+    );
+    assert_asm(
+        |asm| asm.mov_imm64(Gpr::Rcx, u64::MAX),
+        &[0x48, 0xC7, 0xC1, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_asm(
+        |asm| asm.mov_imm64(Gpr::Rax, 1 << 32),
+        &[0x48, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00],
+    );
+}
+
+#[test]
+fn encode_arithmetic() {
+    assert_asm(
+        |asm| asm.alu(AluOp::Add, Width::W32, Gpr::Rax, Gpr::Rcx),
+        &[0x01, 0xC8],
+    );
+    assert_asm(
+        |asm| asm.alu(AluOp::Cmp, Width::W64, Gpr::Rcx, Gpr::R10),
+        &[0x4C, 0x39, 0xD1],
+    );
+    assert_asm(
+        |asm| asm.alu_imm(AluOp::Sub, Width::W64, Gpr::Rax, 1),
+        &[0x48, 0x81, 0xE8, 0x01, 0x00, 0x00, 0x00],
+    );
+    assert_asm(
+        |asm| asm.imul(Width::W64, Gpr::Rax, Gpr::Rcx),
+        &[0x48, 0x0F, 0xAF, 0xC1],
+    );
+    assert_asm(
+        |asm| asm.shift(ShiftOp::Shl, Width::W32, Gpr::Rax),
+        &[0xD3, 0xE0],
+    );
+    assert_asm(|asm| asm.sign_extend_rax(Width::W64), &[0x48, 0x99]);
+    assert_asm(|asm| asm.idiv(Width::W32, Gpr::Rcx), &[0xF7, 0xF9]);
+    assert_asm(
+        |asm| asm.set_cond(Cond::L, Gpr::Rax),
+        &[0x40, 0x0F, 0x9C, 0xC0, 0x40, 0x0F, 0xB6, 0xC0],
+    );
+}
+
+#[test]
+fn encode_jumps() {
+    assert_asm(
+        |asm| {
+            let fixup = asm.jmp();
+            asm.ret();
+            asm.patch(fixup, asm.pos());
+        },
+        &[0xE9, 0x01, 0x00, 0x00, 0x00, 0xC3],
+    );
+    assert_asm(
+        |asm| {
+            let start = asm.pos();
+            let fixup = asm.jcc(Cond::Ne);
+            asm.patch(fixup, start);
+        },
+        &[0x0F, 0x85, 0xFA, 0xFF, 0xFF, 0xFF],
+    );
+}
+
+/// The state of an execution of machine code generated by the JIT.
+struct Execution {
+    /// The registers of the executed call frame.
+    regs: Vec<UntypedVal>,
+    /// The remaining fuel.
+    fuel: u64,
+    /// The bytes of the default linear memory.
+    memory: Vec<u8>,
+}
+
+impl Execution {
+    /// Creates a new [`Execution`] with the values of the registers `regs`.
+    fn new(regs: &[u64]) -> Self {
+        Self {
+            regs: regs.iter().copied().map(UntypedVal::from).collect(),
+            fuel: 0,
+            memory: Vec::new(),
+        }
+    }
+
+    /// Executes the machine code of `instrs` and returns the index at which the interpreter resumes.
+    fn run(&mut self, instrs: &[Instruction]) -> u32 {
+        let func = JitFunc::compile(instrs).expect("failed to compile instructions");
+        let mut ctx = JitContext {
+            regs: self.regs.as_mut_ptr(),
+            fuel: &mut self.fuel,
+            memory: self.memory.as_mut_ptr(),
+            memory_len: self.memory.len(),
+        };
+        unsafe { func.entry().execute(&mut ctx) }
+    }
+
+    /// Returns the value of the register at `index`.
+    fn reg(&self, index: usize) -> u64 {
+        u64::from(self.regs[index])
+    }
+}
+
+#[test]
+fn unsupported_entry() {
+    assert!(JitFunc::compile(&[Instruction::Return]).is_none());
+}
+
+#[test]
+fn arithmetic() {
+    let mut exec = Execution::new(&[u64::from(u32::MAX), 2, 0, 0]);
+    let instrs = [
+        Instruction::i32_add(Reg::from(2), Reg::from(0), Reg::from(1)),
+        Instruction::i64_add(Reg::from(3), Reg::from(0), Reg::from(1)),
+        Instruction::return_reg(Reg::from(2)),
+    ];
+    assert_eq!(exec.run(&instrs), 2);
+    assert_eq!(exec.reg(2), 1);
+    assert_eq!(exec.reg(3), u64::from(u32::MAX) + 2);
+}
+
+#[test]
+fn sum_loop() {
+    // Sums up all integers from `n` down to 1 in register 1.
+    let mut exec = Execution::new(&[100, 0]);
+    let instrs = [
+        Instruction::i32_add(Reg::from(1), Reg::from(1), Reg::from(0)),
+        Instruction::i32_add_imm16(Reg::from(0), Reg::from(0), -1_i16),
+        Instruction::branch_i32_ne_imm16(Reg::from(0), 0_i16, BranchOffset16::from(-2)),
+        Instruction::branch(BranchOffset::from(1)),
+        Instruction::return_reg(Reg::from(1)),
+    ];
+    assert_eq!(exec.run(&instrs), 4);
+    assert_eq!(exec.reg(0), 0);
+    assert_eq!(exec.reg(1), 5050);
+}
+
+#[test]
+fn div_exits_on_trap() {
+    let instrs = [
+        Instruction::i32_div_s(Reg::from(2), Reg::from(0), Reg::from(1)),
+        Instruction::return_reg(Reg::from(2)),
+    ];
+    let mut exec = Execution::new(&[u64::from(-7_i32 as u32), 2, 0]);
+    assert_eq!(exec.run(&instrs), 1);
+    assert_eq!(exec.reg(2), u64::from(-3_i32 as u32));
+    let mut exec = Execution::new(&[7, 0, 0]);
+    assert_eq!(exec.run(&instrs), 0);
+    assert_eq!(exec.reg(2), 0);
+    let mut exec = Execution::new(&[u64::from(i32::MIN as u32), u64::from(u32::MAX), 0]);
+    assert_eq!(exec.run(&instrs), 0);
+}
+
+#[test]
+fn consume_fuel() {
+    let instrs = [
+        Instruction::consume_fuel(BlockFuel::from(10)),
+        Instruction::return_reg(Reg::from(0)),
+    ];
+    let mut exec = Execution::new(&[0]);
+    exec.fuel = 15;
+    assert_eq!(exec.run(&instrs), 1);
+    assert_eq!(exec.fuel, 5);
+    assert_eq!(exec.run(&instrs), 0);
+    assert_eq!(exec.fuel, 5);
+}
+
+#[test]
+fn memory_access() {
+    let instrs = [
+        Instruction::store32_offset16(Reg::from(0), 4_u16, Reg::from(1)),
+        Instruction::i32_load8_u_offset16(Reg::from(2), Reg::from(0), 5_u16),
+        Instruction::return_reg(Reg::from(2)),
+    ];
+    let mut exec = Execution::new(&[0, 0x1234_5678, 0]);
+    exec.memory = vec![0; 8];
+    assert_eq!(exec.run(&instrs), 2);
+    assert_eq!(exec.memory, [0, 0, 0, 0, 0x78, 0x56, 0x34, 0x12]);
+    assert_eq!(exec.reg(2), 0x56);
+    let mut exec = Execution::new(&[1, 0x1234_5678, 0]);
+    exec.memory = vec![0; 8];
+    assert_eq!(exec.run(&instrs), 0);
+    assert_eq!(exec.memory, [0; 8]);
+}
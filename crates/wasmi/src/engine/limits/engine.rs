@@ -1,7 +1,7 @@
 use core::fmt::{self, Display};
 
 /// An error that can occur upon parsing or compiling a Wasm module when [`EnforcedLimits`] are set.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum EnforcedLimitsError {
     /// When a Wasm module exceeds the global variable limit.
     TooManyGlobals { limit: u32 },
@@ -1,12 +1,19 @@
 //! The Wasmi interpreter.
 
+#[cfg(feature = "std")]
+mod background;
 mod block_type;
 mod code_map;
 mod config;
+mod disassemble;
 mod executor;
 mod func_args;
 mod func_types;
+#[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+mod jit;
 mod limits;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod resumable;
 mod traits;
 mod translator;
@@ -15,10 +22,15 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "std")]
+use self::background::BackgroundCompiler;
+#[cfg(feature = "parallel")]
+pub(crate) use self::parallel::FuncToTranslate;
 pub(crate) use self::{
     block_type::BlockType,
+    code_map::{CodeMap, InlinableFuncRef, ModuleSymbols},
     config::FuelCosts,
-    executor::Stack,
+    executor::{FrameRegisters, Stack},
     func_args::{FuncFinished, FuncParams, FuncResults},
     func_types::DedupFuncType,
     translator::{
@@ -31,13 +43,13 @@ pub(crate) use self::{
     },
 };
 use self::{
-    code_map::{CodeMap, CompiledFuncEntity},
+    code_map::CompiledFuncEntity,
     func_types::FuncTypeRegistry,
     resumable::ResumableCallBase,
 };
 pub use self::{
     code_map::{EngineFunc, EngineFuncSpan, EngineFuncSpanIter},
-    config::{CompilationMode, Config},
+    config::{CompilationMode, Config, ConfigError, ModuleCacheEviction, OptimizationLevel},
     executor::ResumableHostError,
     limits::{EnforcedLimits, EnforcedLimitsError, StackLimits},
     resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
@@ -46,13 +58,16 @@ pub use self::{
 };
 use crate::{
     collections::arena::{ArenaIndex, GuardedEntity},
-    module::{FuncIdx, ModuleHeader},
+    module::{FuncIdx, ModuleCache, ModuleHeader},
+    stats::{EngineStats, Stopwatch},
     Error,
     Func,
     FuncType,
+    Module,
     StoreContextMut,
 };
 use alloc::{
+    boxed::Box,
     sync::{Arc, Weak},
     vec::Vec,
 };
@@ -155,6 +170,18 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the [`EngineStats`] of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This is cheap to call since the [`EngineStats`] are updated upon compilation.
+    pub fn stats(&self) -> EngineStats {
+        let mut stats = self.inner.code_map.stats();
+        stats.set_cached_stacks(self.inner.stacks.lock().len());
+        stats.set_cached_modules(self.inner.modules.lock().len());
+        stats
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
@@ -212,14 +239,114 @@ impl Engine {
         module: ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) -> Result<(), Error> {
-        self.inner.translate_func(
+        let stopwatch = Stopwatch::start();
+        let result = self.inner.translate_func(
             func_index,
             engine_func,
             offset,
             bytes,
             module,
             func_to_validate,
-        )
+        );
+        self.inner
+            .code_map
+            .add_translation_time(stopwatch.elapsed());
+        result
+    }
+
+    /// Translates all `funcs` of the Wasm `module` in parallel.
+    ///
+    /// # Note
+    ///
+    /// This is only used with [`CompilationMode::Eager`] and yields the same
+    /// results as translating all `funcs` one after another via [`Engine::translate_func`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the index within `funcs` and error of the first function body
+    /// that failed to translate or validate.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn translate_funcs(
+        &self,
+        module: &ModuleHeader,
+        funcs: Vec<FuncToTranslate>,
+    ) -> Result<(), (usize, Error)> {
+        debug_assert!(matches!(
+            self.config().get_compilation_mode(),
+            CompilationMode::Eager
+        ));
+        self.inner.translate_funcs(module, funcs)
+    }
+
+    /// Compiles the uncompiled `funcs` on a background thread if enabled by the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// - All [`Module`]s of the [`Engine`] share a single background thread.
+    /// - The `funcs` are compiled on demand as usual if no thread can be spawned.
+    #[cfg(feature = "std")]
+    pub(crate) fn compile_in_background(&self, funcs: EngineFuncSpan) {
+        let config = self.config();
+        if !config.get_background_compilation()
+            || matches!(config.get_compilation_mode(), CompilationMode::Eager)
+            || funcs.is_empty()
+        {
+            return;
+        }
+        self.inner.background.lock().compile(self, funcs);
+    }
+
+    /// Returns the cached [`Module`] compiled from `wasm` if any.
+    ///
+    /// Only returns a [`Module`] with validated Wasm bytecode if `validated` is `true`.
+    pub(crate) fn get_cached_module(&self, wasm: &[u8], validated: bool) -> Option<Module> {
+        let mut modules = self.inner.modules.lock();
+        if !modules.is_enabled() {
+            return None;
+        }
+        modules.get(self, wasm, validated)
+    }
+
+    /// Caches the `module` compiled from `wasm` if the [`Engine`] caches modules.
+    ///
+    /// `validated` is `true` if `wasm` has been validated.
+    pub(crate) fn cache_module(&self, wasm: &[u8], validated: bool, module: &Module) {
+        self.inner.modules.lock().insert(wasm, validated, module)
+    }
+
+    /// Returns the names of `func` and of the Wasm module that defines it if any.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn func_names(&self, func: EngineFunc) -> (Option<Box<str>>, Option<Box<str>>) {
+        self.inner.code_map.func_names(func)
+    }
+
+    /// Registers the [`ModuleSymbols`] of the Wasm module that defines the `funcs`.
+    pub(crate) fn register_symbols(&self, funcs: EngineFuncSpan, symbols: ModuleSymbols) {
+        self.inner.code_map.register_symbols(funcs, symbols)
+    }
+
+    /// Returns the Wasm offsets of the basic blocks of the compiled `func` indexed by their coverage counter.
+    ///
+    /// Returns `None` if `func` has not been compiled with code coverage enabled.
+    pub(crate) fn coverage_offsets(&self, func: EngineFunc) -> Option<Box<[u32]>> {
+        self.inner.code_map.coverage(func)
+    }
+
+    /// Returns the [`InlinableFuncRef`] of `func` if it has been compiled and can be inlined.
+    pub(crate) fn get_inlinable(&self, func: EngineFunc) -> Option<InlinableFuncRef<'_>> {
+        self.inner.code_map.get_inlinable(func)
+    }
+
+    /// Returns the [`InlinableFuncRef`] of `func` once it has been initialized.
+    ///
+    /// For more information read [`CodeMap::wait_for_inlinable`].
+    pub(crate) fn wait_for_inlinable(&self, func: EngineFunc) -> Option<InlinableFuncRef<'_>> {
+        self.inner.code_map.wait_for_inlinable(func)
+    }
+
+    /// Accounts for `amount` calls inlined into their callers in the [`EngineStats`].
+    pub(crate) fn add_inlined_calls(&self, amount: usize) {
+        self.inner.code_map.add_inlined_calls(amount)
     }
 
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
@@ -261,12 +388,13 @@ impl Engine {
         &self,
         func_idx: FuncIdx,
         func: EngineFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
         self.inner
-            .init_lazy_func(func_idx, func, bytes, module, func_to_validate)
+            .init_lazy_func(func_idx, func, offset, bytes, module, func_to_validate)
     }
 
     /// Executes the given [`Func`] with parameters `params`.
@@ -299,7 +427,9 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner.execute_func(ctx, func, params, results)
+        in_call_span(ctx, func, |ctx| {
+            self.inner.execute_func(ctx, func, params, results)
+        })
     }
 
     /// Executes the given [`Func`] resumably with parameters `params` and returns.
@@ -335,8 +465,10 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner
-            .execute_func_resumable(ctx, func, params, results)
+        in_call_span(ctx, func, |ctx| {
+            self.inner
+                .execute_func_resumable(ctx, func, params, results)
+        })
     }
 
     /// Resumes the given `invocation` given the `params`.
@@ -381,6 +513,36 @@ impl Engine {
     }
 }
 
+/// Executes `call` within the `tracing` span of a call to `func`.
+#[cfg(feature = "tracing")]
+fn in_call_span<T, R>(
+    ctx: StoreContextMut<T>,
+    func: &Func,
+    call: impl FnOnce(StoreContextMut<T>) -> Result<R, Error>,
+) -> Result<R, Error> {
+    let store = ctx.store;
+    let span = crate::instrument::call_span(store, func);
+    let fuel = store.get_fuel().ok();
+    let result = span.in_scope(|| call(StoreContextMut { store: &mut *store }));
+    crate::instrument::finish_call(&span, store, fuel, result.as_ref().err());
+    result
+}
+
+/// Executes `call`.
+///
+/// # Note
+///
+/// Calls are only wrapped in `tracing` spans if the `tracing` crate feature is enabled.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+fn in_call_span<T, R>(
+    ctx: StoreContextMut<T>,
+    _func: &Func,
+    call: impl FnOnce(StoreContextMut<T>) -> Result<R, Error>,
+) -> Result<R, Error> {
+    call(ctx)
+}
+
 /// The internal state of the Wasmi [`Engine`].
 #[derive(Debug)]
 pub struct EngineInner {
@@ -403,6 +565,11 @@ pub struct EngineInner {
     /// operate on. Therefore a Wasm engine is required to provide stacks and
     /// ideally recycles old ones since creation of a new stack is rather expensive.
     stacks: Mutex<EngineStacks>,
+    /// The compiled Wasm modules cached for reuse.
+    modules: Mutex<ModuleCache>,
+    /// The thread compiling lazily compiled functions in the background.
+    #[cfg(feature = "std")]
+    background: Mutex<BackgroundCompiler>,
 }
 
 /// Stacks to hold and distribute reusable allocations.
@@ -498,6 +665,11 @@ impl EngineStacks {
         }
     }
 
+    /// Returns the number of [`Stack`]s kept for reuse.
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
     /// Disose and recycle the `stack`.
     pub fn recycle(&mut self, stack: Stack) {
         if stack.capacity() > 0 && self.stacks.len() < self.keep {
@@ -516,6 +688,9 @@ impl EngineInner {
             func_types: RwLock::new(FuncTypeRegistry::new(engine_idx)),
             allocs: Mutex::new(ReusableAllocationStack::default()),
             stacks: Mutex::new(EngineStacks::new(config)),
+            modules: Mutex::new(ModuleCache::new(config)),
+            #[cfg(feature = "std")]
+            background: Mutex::new(BackgroundCompiler::default()),
         }
     }
 
@@ -561,6 +736,8 @@ impl EngineInner {
         module: ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::instrument::translate_span(func_index, bytes.len(), false).entered();
         let features = self.config().wasm_features();
         match (self.config.get_compilation_mode(), func_to_validate) {
             (CompilationMode::Eager, Some(func_to_validate)) => {
@@ -581,8 +758,13 @@ impl EngineInner {
             }
             (CompilationMode::LazyTranslation, Some(func_to_validate)) => {
                 let allocs = self.get_validation_allocs();
-                let translator =
-                    LazyFuncTranslator::new_unchecked(func_index, engine_func, module, features);
+                let translator = LazyFuncTranslator::new_unchecked(
+                    func_index,
+                    engine_func,
+                    offset,
+                    module,
+                    features,
+                );
                 let validator = func_to_validate.into_validator(allocs);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
                 let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
@@ -591,12 +773,20 @@ impl EngineInner {
             }
             (CompilationMode::Lazy | CompilationMode::LazyTranslation, func_to_validate) => {
                 let translator = match func_to_validate {
-                    Some(func_to_validate) => {
-                        LazyFuncTranslator::new(func_index, engine_func, module, func_to_validate)
-                    }
-                    None => {
-                        LazyFuncTranslator::new_unchecked(func_index, engine_func, module, features)
-                    }
+                    Some(func_to_validate) => LazyFuncTranslator::new(
+                        func_index,
+                        engine_func,
+                        offset,
+                        module,
+                        func_to_validate,
+                    ),
+                    None => LazyFuncTranslator::new_unchecked(
+                        func_index,
+                        engine_func,
+                        offset,
+                        module,
+                        features,
+                    ),
                 };
                 FuncTranslationDriver::new(offset, bytes, translator)?
                     .translate(|func_entity| self.init_func(engine_func, func_entity))?;
@@ -686,12 +876,19 @@ impl EngineInner {
         &self,
         func_idx: FuncIdx,
         func: EngineFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
-        self.code_map
-            .init_func_as_uncompiled(func, func_idx, bytes, module, func_to_validate)
+        self.code_map.init_func_as_uncompiled(
+            func,
+            func_idx,
+            offset,
+            bytes,
+            module,
+            func_to_validate,
+        )
     }
 
     /// Recycles the given [`Stack`].
@@ -0,0 +1,200 @@
+//! Parallel eager translation of Wasm function bodies.
+//!
+//! # Note
+//!
+//! Function bodies are handed out to the worker threads in the order of their
+//! function indices. Each worker reuses its own [`FuncTranslatorAllocations`]
+//! and [`FuncValidatorAllocations`] for all of its function bodies.
+//!
+//! Results are deterministic since every function body is translated independently
+//! and inlining only considers functions with lower indices which are waited for.
+//! Upon failure the error of the failing function body with the lowest index is returned.
+
+use super::{
+    CodeMap,
+    EngineFunc,
+    EngineInner,
+    FuncTranslationDriver,
+    FuncTranslator,
+    FuncTranslatorAllocations,
+    ValidatingFuncTranslator,
+};
+use crate::{
+    module::{FuncIdx, ModuleHeader},
+    stats::Stopwatch,
+    Error,
+};
+use alloc::vec::Vec;
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use spin::Mutex;
+use std::thread;
+use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
+
+/// A Wasm function body to be translated by [`EngineInner::translate_funcs`].
+pub struct FuncToTranslate<'a> {
+    /// The index of the function within its Wasm module.
+    pub func_index: FuncIdx,
+    /// The [`EngineFunc`] to initialize with the translated function.
+    pub engine_func: EngineFunc,
+    /// The offset of the function body within the Wasm binary.
+    pub offset: usize,
+    /// The bytes of the function body.
+    pub bytes: &'a [u8],
+    /// The validator of the function body if validation is enabled.
+    pub func_to_validate: Option<FuncToValidate<ValidatorResources>>,
+}
+
+/// The reusable allocations of a translation worker.
+type WorkerAllocations = (FuncTranslatorAllocations, FuncValidatorAllocations);
+
+/// The shared state of the translation workers.
+struct Workers<'a> {
+    /// The function bodies that still need to be translated in the order of their indices.
+    queue: Mutex<core::iter::Enumerate<alloc::vec::IntoIter<FuncToTranslate<'a>>>>,
+    /// The lowest index of all function bodies that failed to translate so far.
+    ///
+    /// Function bodies with higher indices are skipped.
+    failed: AtomicUsize,
+    /// The errors of all function bodies that failed to translate.
+    errors: Mutex<Vec<(usize, Error)>>,
+}
+
+/// Initializes the function translated by a worker as failed if the worker panics.
+struct FailOnUnwind<'a> {
+    /// The [`CodeMap`] holding the translated function.
+    code_map: &'a CodeMap,
+    /// The function that is being translated.
+    func: EngineFunc,
+}
+
+impl Drop for FailOnUnwind<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.code_map.init_func_as_failed(self.func);
+        }
+    }
+}
+
+impl EngineInner {
+    /// Translates all `funcs` of the Wasm `module` in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns the index within `funcs` and error of the first function body
+    /// that failed to translate or validate.
+    pub(super) fn translate_funcs(
+        &self,
+        module: &ModuleHeader,
+        funcs: Vec<FuncToTranslate>,
+    ) -> Result<(), (usize, Error)> {
+        let len_workers = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(funcs.len());
+        let workers = Workers {
+            queue: Mutex::new(funcs.into_iter().enumerate()),
+            failed: AtomicUsize::new(usize::MAX),
+            errors: Mutex::new(Vec::new()),
+        };
+        // Note: workers report to the `tracing` subscriber and span of the calling thread.
+        #[cfg(feature = "tracing")]
+        let (dispatch, span) = (
+            tracing::dispatcher::get_default(Clone::clone),
+            tracing::Span::current(),
+        );
+        thread::scope(|scope| {
+            for _ in 0..len_workers {
+                scope.spawn(|| {
+                    #[cfg(feature = "tracing")]
+                    let _guard = tracing::dispatcher::set_default(&dispatch);
+                    #[cfg(feature = "tracing")]
+                    let _span = span.enter();
+                    self.translate_funcs_worker(module, &workers)
+                });
+            }
+        });
+        match workers
+            .errors
+            .into_inner()
+            .into_iter()
+            .min_by_key(|(index, _)| *index)
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Translates the function bodies of the `workers` queue until it is empty.
+    fn translate_funcs_worker(&self, module: &ModuleHeader, workers: &Workers) {
+        let mut allocs = self.get_allocs();
+        loop {
+            let Some((index, func)) = workers.queue.lock().next() else {
+                break;
+            };
+            let engine_func = func.engine_func;
+            if index > workers.failed.load(Ordering::Acquire) {
+                // Note: skipped functions must not be waited for by inlining callers.
+                self.code_map.init_func_as_failed(engine_func);
+                continue;
+            }
+            // Note: waiting callers must not block forever if this worker panics.
+            let _guard = FailOnUnwind {
+                code_map: &self.code_map,
+                func: engine_func,
+            };
+            let stopwatch = Stopwatch::start();
+            let result = self.translate_func_with(module, func, allocs);
+            self.code_map.add_translation_time(stopwatch.elapsed());
+            allocs = match result {
+                Ok(allocs) => allocs,
+                Err(error) => {
+                    self.code_map.init_func_as_failed(engine_func);
+                    workers.failed.fetch_min(index, Ordering::AcqRel);
+                    workers.errors.lock().push((index, error));
+                    self.get_allocs()
+                }
+            };
+        }
+        self.recycle_allocs(allocs.0, allocs.1);
+    }
+
+    /// Eagerly translates `func` of the Wasm `module` reusing `allocs`.
+    ///
+    /// # Errors
+    ///
+    /// If translation or validation of `func` failed.
+    fn translate_func_with(
+        &self,
+        module: &ModuleHeader,
+        func: FuncToTranslate,
+        allocs: WorkerAllocations,
+    ) -> Result<WorkerAllocations, Error> {
+        let FuncToTranslate {
+            func_index,
+            engine_func,
+            offset,
+            bytes,
+            func_to_validate,
+        } = func;
+        #[cfg(feature = "tracing")]
+        let _span = crate::instrument::translate_span(func_index, bytes.len(), false).entered();
+        let (translation_allocs, validation_allocs) = allocs;
+        let translator = FuncTranslator::new(func_index, module.clone(), translation_allocs)?;
+        match func_to_validate {
+            Some(func_to_validate) => {
+                let validator = func_to_validate.into_validator(validation_allocs);
+                let translator = ValidatingFuncTranslator::new(validator, translator)?;
+                let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
+                    .translate(|func_entity| self.init_func(engine_func, func_entity))?;
+                Ok((allocs.translation, allocs.validation))
+            }
+            None => {
+                let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
+                    .translate(|func_entity| self.init_func(engine_func, func_entity))?;
+                Ok((allocs, validation_allocs))
+            }
+        }
+    }
+}
@@ -40,6 +40,8 @@ where
         mut self,
         finalize: impl FnOnce(CompiledFuncEntity),
     ) -> Result<T::Allocations, Error> {
+        // Note: informs the translator about the position of the function body.
+        self.translator.update_pos(self.func_body.range().start);
         if self.translator.setup(self.bytes)? {
             let allocations = self.translator.finish(finalize)?;
             return Ok(allocations);
@@ -82,6 +84,7 @@ where
         while !reader.eof() {
             let pos = reader.original_position();
             self.translator.update_pos(pos);
+            self.translator.before_visit_operator(None)?;
             reader.visit_operator(&mut self.translator)??;
         }
         reader.ensure_end()?;
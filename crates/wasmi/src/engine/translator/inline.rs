@@ -0,0 +1,449 @@
+//! Inlining of small leaf Wasm functions into their callers.
+//!
+//! # Note
+//!
+//! Inlining operates on the already compiled Wasmi bytecode of the called function.
+//! The callee's instructions are spliced into the caller with all of their registers
+//! moved into a frame of registers above the caller's dynamic registers.
+//! Returns of the callee are replaced by branches to epilogues that copy the
+//! returned values into the result registers of the call.
+//!
+//! The callee's fuel instructions are kept as is so that fuel consumption is unaffected.
+
+use super::{FuncTranslator, TypedProvider, ValueStack};
+use crate::{
+    core::UntypedVal,
+    engine::{EngineFunc, InlinableFuncRef},
+    ir::{BranchOffset, BranchOffset16, Instruction, Reg, RegSpan, VisitRegs},
+    Error,
+};
+use alloc::vec::Vec;
+
+/// The maximum number of parameters of inlined calls.
+///
+/// # Note
+///
+/// `local.set` does not relink the result of calls with more parameters when preserving
+/// local variables. Inlining them would therefore alter fuel consumption.
+const MAX_INLINED_PARAMS: usize = 6;
+
+/// Returns `true` if the compiled `instrs` of a function can be inlined into its callers.
+///
+/// # Note
+///
+/// - `len_locals` is the number of local variables excluding parameters of the function
+///   which need to be zero-initialized whenever the function is inlined.
+/// - Only leaf functions that fit into the `budget` are inlinable.
+pub fn is_inlinable(instrs: &[Instruction], len_locals: u16, budget: u32) -> bool {
+    use Instruction as I;
+    let size = instrs.len().saturating_add(usize::from(len_locals));
+    if size > budget as usize {
+        return false;
+    }
+    instrs.iter().all(|instr| {
+        !matches!(
+            instr,
+            I::CallInternal0 { .. }
+                | I::CallInternal { .. }
+                | I::CallImported0 { .. }
+                | I::CallImported { .. }
+                | I::CallIndirect0 { .. }
+                | I::CallIndirect0Imm16 { .. }
+                | I::CallIndirect { .. }
+                | I::CallIndirectImm16 { .. }
+                | I::ReturnCallInternal0 { .. }
+                | I::ReturnCallInternal { .. }
+                | I::ReturnCallImported0 { .. }
+                | I::ReturnCallImported { .. }
+                | I::ReturnCallIndirect0 { .. }
+                | I::ReturnCallIndirect0Imm16 { .. }
+                | I::ReturnCallIndirect { .. }
+                | I::ReturnCallIndirectImm16 { .. }
+                | I::ReturnMany { .. }
+                | I::ReturnNezMany { .. }
+                | I::BranchTableMany { .. }
+                | I::BranchCmpFallback { .. }
+                | I::DebugStep { .. }
+                | I::CoverageCounter { .. }
+        )
+    })
+}
+
+/// Splits a return [`Instruction`] into its unconditional return and its condition if any.
+///
+/// Returns `None` if `instr` is not a return [`Instruction`].
+fn split_return(instr: Instruction) -> Option<(Instruction, Option<Reg>)> {
+    use Instruction as I;
+    let split = match instr {
+        I::Return
+        | I::ReturnReg { .. }
+        | I::ReturnReg2 { .. }
+        | I::ReturnReg3 { .. }
+        | I::ReturnImm32 { .. }
+        | I::ReturnI64Imm32 { .. }
+        | I::ReturnF64Imm32 { .. }
+        | I::ReturnSpan { .. } => (instr, None),
+        I::ReturnNez { condition } => (I::Return, Some(condition)),
+        I::ReturnNezReg { condition, value } => (I::return_reg(value), Some(condition)),
+        I::ReturnNezReg2 { condition, values } => (I::return_reg2(values), Some(condition)),
+        I::ReturnNezImm32 { condition, value } => (I::return_imm32(value), Some(condition)),
+        I::ReturnNezI64Imm32 { condition, value } => (I::return_i64imm32(value), Some(condition)),
+        I::ReturnNezF64Imm32 { condition, value } => (I::return_f64imm32(value), Some(condition)),
+        I::ReturnNezSpan { condition, values } => (I::return_span(values), Some(condition)),
+        _ => return None,
+    };
+    Some(split)
+}
+
+/// Returns the number of copy [`Instruction`]s required to write the values of `ret` to the results.
+fn len_return_copies(ret: Instruction) -> u32 {
+    match ret {
+        Instruction::Return => 0,
+        Instruction::ReturnReg3 { .. } => 2,
+        _ => 1,
+    }
+}
+
+/// Returns `true` if `ret` returns a single value.
+fn is_single_value_return(ret: Instruction) -> bool {
+    matches!(
+        ret,
+        Instruction::ReturnReg { .. }
+            | Instruction::ReturnImm32 { .. }
+            | Instruction::ReturnI64Imm32 { .. }
+            | Instruction::ReturnF64Imm32 { .. }
+    )
+}
+
+/// Encodes the copy [`Instruction`]s that write the values of `ret` to the `results`.
+fn encode_return_copies(ret: Instruction, results: RegSpan, instrs: &mut Vec<Instruction>) {
+    use Instruction as I;
+    let result = results.head();
+    match ret {
+        I::Return => {}
+        I::ReturnReg { value } => instrs.push(I::copy(result, value)),
+        I::ReturnReg2 { values: [v0, v1] } => instrs.push(I::copy2_ext(results, v0, v1)),
+        I::ReturnReg3 {
+            values: [v0, v1, v2],
+        } => {
+            instrs.push(I::copy2_ext(results, v0, v1));
+            instrs.push(I::copy(result.next_n(2), v2));
+        }
+        I::ReturnImm32 { value } => instrs.push(I::copy_imm32(result, value)),
+        I::ReturnI64Imm32 { value } => instrs.push(I::copy_i64imm32(result, value)),
+        I::ReturnF64Imm32 { value } => instrs.push(I::copy_f64imm32(result, value)),
+        I::ReturnSpan { values } => instrs.push(I::copy_span_non_overlapping(
+            results,
+            values.span(),
+            values.len(),
+        )),
+        unexpected => {
+            panic!("expected an unconditional return instruction but found: {unexpected:?}")
+        }
+    }
+}
+
+/// The layout of an inlined function body with its return epilogues.
+#[derive(Debug, Default)]
+struct InlineLayout {
+    /// The distinct unconditional returns of the inlined function with values.
+    ///
+    /// Each of them has an epilogue copying its values to the results of the call.
+    epilogues: Vec<Epilogue>,
+    /// The number of instructions of the inlined function body that are kept.
+    len_body: u32,
+    /// The index of the first instruction after the inlined function body and its epilogues.
+    end: u32,
+    /// Is `true` if the epilogues write the single result to a temporary register instead.
+    ///
+    /// # Note
+    ///
+    /// In this case the temporary register is copied to the result at `end` so that
+    /// the single result of the inlined call is always written by its final instruction.
+    merge_results: bool,
+}
+
+/// The epilogue of an inlined function for one of its returns.
+#[derive(Debug, Copy, Clone)]
+struct Epilogue {
+    /// The unconditional return replaced by the epilogue.
+    ret: Instruction,
+    /// The index of the first instruction of the epilogue relative to the inlined function body.
+    start: u32,
+}
+
+impl InlineLayout {
+    /// Computes the [`InlineLayout`] for the inlined `instrs`.
+    ///
+    /// Returns `None` if the branches to the epilogues cannot be encoded.
+    fn new(instrs: &[Instruction]) -> Option<Self> {
+        let mut epilogues = Vec::new();
+        let last = instrs.len().checked_sub(1)?;
+        // Note: the epilogue of the final return is placed first so that
+        //       the final return can be dropped in favor of falling through.
+        let final_ret = split_return(instrs[last]).filter(|(_, condition)| condition.is_none());
+        for (ret, _) in instrs.iter().rev().filter_map(|instr| split_return(*instr)) {
+            if ret != Instruction::Return && !epilogues.iter().any(|e: &Epilogue| e.ret == ret) {
+                epilogues.push(Epilogue { ret, start: 0 });
+            }
+        }
+        // Note: dropping the final return is only possible if it is no branch table target
+        //       and if execution falls through to its epilogue or the end afterwards.
+        let drop_final = final_ret.is_some_and(|(ret, _)| {
+            !is_branch_table_target(instrs, last)
+                && (ret != Instruction::Return || epilogues.is_empty())
+        });
+        let len_body = u32::try_from(instrs.len() - usize::from(drop_final)).ok()?;
+        let mut pos = len_body;
+        let len_epilogues = epilogues.len();
+        for (n, epilogue) in epilogues.iter_mut().enumerate() {
+            epilogue.start = pos;
+            pos += len_return_copies(epilogue.ret);
+            if n + 1 != len_epilogues {
+                // Note: all but the last epilogue branch to the end of the inlined function.
+                pos += 1;
+            }
+        }
+        let merge_results =
+            epilogues.len() > 1 && epilogues.iter().all(|e| is_single_value_return(e.ret));
+        let layout = Self {
+            epilogues,
+            len_body,
+            end: pos,
+            merge_results,
+        };
+        // Note: conditional returns are replaced with 16-bit encoded branches.
+        for (n, instr) in instrs[..len_body as usize].iter().enumerate() {
+            if let Some((ret, Some(_))) = split_return(*instr) {
+                let offset = layout.offset(n as u32, ret);
+                BranchOffset16::try_from(offset).ok()?;
+            }
+        }
+        Some(layout)
+    }
+
+    /// Returns `true` if the final instruction of the inlined function writes its single result.
+    fn has_single_result(&self) -> bool {
+        match self.epilogues.as_slice() {
+            [epilogue] => is_single_value_return(epilogue.ret),
+            _ => self.merge_results,
+        }
+    }
+
+    /// Returns the start of the epilogue of the unconditional `ret`.
+    fn target(&self, ret: Instruction) -> u32 {
+        self.epilogues
+            .iter()
+            .find(|epilogue| epilogue.ret == ret)
+            .map(|epilogue| epilogue.start)
+            .unwrap_or(self.end)
+    }
+
+    /// Returns the [`BranchOffset`] from the instruction at `src` to the epilogue of `ret`.
+    fn offset(&self, src: u32, ret: Instruction) -> BranchOffset {
+        let dst = self.target(ret);
+        BranchOffset::from(dst as i32 - src as i32)
+    }
+}
+
+/// Returns `true` if the instruction at `index` of `instrs` is a branch table target.
+fn is_branch_table_target(instrs: &[Instruction], index: usize) -> bool {
+    use Instruction as I;
+    instrs[..index].iter().enumerate().any(|(n, instr)| {
+        let (len_params, len_targets) = match *instr {
+            I::BranchTable0 { len_targets, .. } => (0, len_targets),
+            I::BranchTable1 { len_targets, .. }
+            | I::BranchTable2 { len_targets, .. }
+            | I::BranchTable3 { len_targets, .. }
+            | I::BranchTableSpan { len_targets, .. } => (1, len_targets),
+            _ => return false,
+        };
+        let first = n + 1 + len_params;
+        (first..first + len_targets as usize).contains(&index)
+    })
+}
+
+/// Moves the [`Reg`]s of an inlined function into the register space of its caller.
+struct InlinedRegs<'a> {
+    /// The first register of the inlined function's frame within its caller.
+    frame: Reg,
+    /// The function local constant values of the inlined function.
+    consts: &'a [UntypedVal],
+    /// The value stack of the caller to allocate function local constant values.
+    stack: &'a mut ValueStack,
+    /// The first error that occurred while allocating function local constant values if any.
+    error: Option<Error>,
+}
+
+impl InlinedRegs<'_> {
+    /// Returns the [`Reg`] of the caller for the [`Reg`] of the inlined function.
+    fn rename(&mut self, reg: Reg) -> Reg {
+        let index = i16::from(reg);
+        if !reg.is_const() {
+            return self.frame.next_n(index as u16);
+        }
+        // Note: constant registers index the function local constants in reverse order.
+        let value = self.consts[self.consts.len() - usize::from(index.unsigned_abs())];
+        match self.stack.alloc_inlined_const(value) {
+            Ok(reg) => reg,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                reg
+            }
+        }
+    }
+}
+
+impl VisitRegs for InlinedRegs<'_> {
+    fn visit_result_reg(&mut self, reg: &mut Reg) {
+        *reg = self.rename(*reg);
+    }
+
+    fn visit_result_regs(&mut self, regs: &mut RegSpan, _len: Option<u16>) {
+        *regs.head_mut() = self.rename(regs.head());
+    }
+
+    fn visit_input_reg(&mut self, reg: &mut Reg) {
+        *reg = self.rename(*reg);
+    }
+
+    fn visit_input_regs(&mut self, regs: &mut RegSpan, _len: Option<u16>) {
+        *regs.head_mut() = self.rename(regs.head());
+    }
+}
+
+impl FuncTranslator {
+    /// Inlines the call to the internal `func` with its `results` if possible.
+    ///
+    /// Returns `true` if the call has been inlined.
+    ///
+    /// # Note
+    ///
+    /// The parameters of the call are expected in the providers buffer.
+    pub(super) fn try_inline_call(
+        &mut self,
+        func: EngineFunc,
+        results: RegSpan,
+    ) -> Result<bool, Error> {
+        if !self.inlining {
+            return Ok(false);
+        }
+        let engine = self.engine.clone();
+        let callee = match self.ordered_inlining {
+            true => match self.module.get_engine_func(self.func) {
+                Some(caller) if func < caller => engine.wait_for_inlinable(func),
+                _ => None,
+            },
+            false => engine.get_inlinable(func),
+        };
+        let Some(callee) = callee else {
+            return Ok(false);
+        };
+        let len_params = self.alloc.buffer.providers.len();
+        if len_params > MAX_INLINED_PARAMS {
+            return Ok(false);
+        }
+        if self.alloc.stack.remaining_consts() <= callee.consts().len() + len_params {
+            return Ok(false);
+        }
+        let Some(layout) = InlineLayout::new(callee.instrs()) else {
+            return Ok(false);
+        };
+        // Note: the frame is placed above the popped parameters so that
+        //       copying the parameters into the frame never overwrites them.
+        let Ok(skip) = u16::try_from(len_params) else {
+            return Ok(false);
+        };
+        let len_frame = callee.len_frame_registers() + u16::from(layout.merge_results);
+        let Some(frame) = self.alloc.stack.peek_inlined_frame(skip, len_frame) else {
+            return Ok(false);
+        };
+        self.encode_inlined_call(callee, &layout, frame.head(), results)?;
+        self.inlined_calls += 1;
+        Ok(true)
+    }
+
+    /// Encodes the inlined `callee` with its `frame` and `results` following its `layout`.
+    fn encode_inlined_call(
+        &mut self,
+        callee: InlinableFuncRef,
+        layout: &InlineLayout,
+        frame: Reg,
+        results: RegSpan,
+    ) -> Result<(), Error> {
+        let mut instrs = Vec::new();
+        // Step 1: copy the parameters into the frame and zero-initialize the locals.
+        //
+        // Note: constant parameters are allocated the same way as for calls.
+        let len_params = self.alloc.buffer.providers.len() as u16;
+        for (n, param) in self.alloc.buffer.providers.iter().enumerate() {
+            let value = match param {
+                TypedProvider::Register(reg) => *reg,
+                TypedProvider::Const(_) => self.alloc.stack.provider2reg(param)?,
+            };
+            instrs.push(Instruction::copy(frame.next_n(n as u16), value));
+        }
+        for n in len_params..callee.len_locals() {
+            instrs.push(Instruction::copy_imm32(frame.next_n(n), 0_i32));
+        }
+        // Step 2: splice in the body with returns replaced by branches to the epilogues.
+        let mut regs = InlinedRegs {
+            frame,
+            consts: callee.consts(),
+            stack: &mut self.alloc.stack,
+            error: None,
+        };
+        for (n, instr) in callee.instrs()[..layout.len_body as usize]
+            .iter()
+            .enumerate()
+        {
+            // Note: the epilogues are identified by the returns of the callee before renaming.
+            let instr = match split_return(*instr) {
+                None => {
+                    let mut instr = *instr;
+                    instr.visit_regs(&mut regs);
+                    instr
+                }
+                Some((ret, None)) => Instruction::branch(layout.offset(n as u32, ret)),
+                Some((ret, Some(condition))) => {
+                    let offset = BranchOffset16::try_from(layout.offset(n as u32, ret))
+                        .unwrap_or_else(|_| panic!("checked 16-bit branch offset: {instr:?}"));
+                    Instruction::branch_i32_ne_imm16(regs.rename(condition), 0_i16, offset)
+                }
+            };
+            instrs.push(instr);
+        }
+        // Step 3: encode the epilogues copying the returned values to the results.
+        let epilogue_results = match layout.merge_results {
+            true => RegSpan::new(frame.next_n(callee.len_frame_registers())),
+            false => results,
+        };
+        let len_epilogues = layout.epilogues.len();
+        for (n, epilogue) in layout.epilogues.iter().enumerate() {
+            let mut ret = epilogue.ret;
+            ret.visit_regs(&mut regs);
+            encode_return_copies(ret, epilogue_results, &mut instrs);
+            if n + 1 != len_epilogues {
+                let src = epilogue.start + len_return_copies(ret);
+                let offset = BranchOffset::from(layout.end as i32 - src as i32);
+                instrs.push(Instruction::branch(offset));
+            }
+        }
+        if layout.merge_results {
+            instrs.push(Instruction::copy(results.head(), epilogue_results.head()));
+        }
+        if let Some(error) = regs.error {
+            return Err(error);
+        }
+        self.alloc.instr_encoder.notify_inlining();
+        let mut last_instr = None;
+        for instr in instrs {
+            last_instr = Some(self.alloc.instr_encoder.append_instr(instr)?);
+        }
+        // Note: the inlined instructions must not be altered by the caller's peephole optimizations
+        //       except for relinking the single result the same way as for non-inlined calls.
+        let result = last_instr.filter(|_| layout.has_single_result());
+        self.alloc.instr_encoder.notify_inlined_call(result);
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 use super::{
+    inline,
+    optimizer,
     relink_result::RelinkResult as _,
     utils::FromProviders as _,
     visit_register::VisitInputRegisters as _,
@@ -17,6 +19,7 @@ use crate::{
     engine::{
         translator::{stack::RegisterSpace, ValueStack},
         FuelCosts,
+        OptimizationLevel,
     },
     ir::{
         BoundedRegSpan,
@@ -91,6 +94,14 @@ pub struct InstrEncoder {
     labels: LabelRegistry,
     /// The last [`Instruction`] created via [`InstrEncoder::push_instr`].
     last_instr: Option<Instr>,
+    /// The [`Instruction`] writing the single result of the last inlined call if any.
+    ///
+    /// # Note
+    ///
+    /// This allows `local.set` and `local.tee` to relink the result of inlined calls
+    /// the same way as for non-inlined calls without exposing the inlined instructions
+    /// to other optimizations that make use of the `last_instr` information.
+    inlined_result: Option<Instr>,
     /// The first encoded [`Instr`] that is affected by a `local.set` preservation.
     ///
     /// # Note
@@ -99,6 +110,14 @@ pub struct InstrEncoder {
     /// defragmentation of the register space due to `local.set` register
     /// preservations.
     notified_preservation: Option<Instr>,
+    /// The position of the first [`Instruction`] charged to an [`Instruction::ConsumeFuel`]
+    /// together with the position of the latter.
+    ///
+    /// # Note
+    ///
+    /// This allows the optimizer to stop charging fuel for the instructions that it removes.
+    /// The position of the [`Instruction::ConsumeFuel`] is `u32::MAX` for inlined instructions.
+    fuel_blocks: Vec<(u32, u32)>,
 }
 
 /// The sequence of encoded [`Instruction`].
@@ -182,6 +201,11 @@ impl InstrSequence {
         self.instrs.drain(..)
     }
 
+    /// Shortens the sequence of [`Instruction`] to `len` instructions.
+    pub fn truncate(&mut self, len: usize) {
+        self.instrs.truncate(len);
+    }
+
     /// Returns a slice to the sequence of [`Instruction`] starting at `start`.
     ///
     /// # Panics
@@ -208,6 +232,7 @@ impl InstrEncoder {
         self.labels.reset();
         self.reset_last_instr();
         self.notified_preservation = None;
+        self.fuel_blocks.clear();
     }
 
     /// Resets the [`Instr`] last created via [`InstrEncoder::push_instr`].
@@ -223,6 +248,25 @@ impl InstrEncoder {
     /// not invalidly optimize across control flow boundaries.
     pub fn reset_last_instr(&mut self) {
         self.last_instr = None;
+        self.inlined_result = None;
+    }
+
+    /// Notifies the [`InstrEncoder`] that the next [`Instruction`]s are spliced in by inlining.
+    ///
+    /// # Note
+    ///
+    /// The inlined instructions are charged to the fuel instructions of the inlined function.
+    /// Therefore the optimizer keeps charging fuel for inlined instructions that it removes.
+    pub fn notify_inlining(&mut self) {
+        self.push_fuel_block(u32::MAX);
+    }
+
+    /// Notifies the [`InstrEncoder`] that a call has just been inlined.
+    ///
+    /// The inlined call writes its single result via the `result` [`Instr`] if any.
+    pub fn notify_inlined_call(&mut self, result: Option<Instr>) {
+        self.last_instr = None;
+        self.inlined_result = result;
     }
 
     /// Return an iterator over the sequence of generated [`Instruction`].
@@ -234,6 +278,11 @@ impl InstrEncoder {
         self.instrs.drain()
     }
 
+    /// Returns the [`Instr`] of the next pushed [`Instruction`].
+    pub fn next_instr(&self) -> Instr {
+        self.instrs.next_instr()
+    }
+
     /// Creates a new unresolved label and returns its [`LabelRef`].
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -308,10 +357,49 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Optimizes the encoded [`Instruction`]s inplace with `level`.
+    ///
+    /// Returns the new positions of the [`Instruction`]s if the optimizer removed any of them.
+    ///
+    /// # Note
+    ///
+    /// - This must be used after all branch offsets have been updated.
+    /// - The function local constant values `consts` are indexed by their constant register.
+    /// - The `fuel_costs` are `Some` if fuel metering is enabled.
+    pub fn optimize(
+        &mut self,
+        consts: &[UntypedVal],
+        level: OptimizationLevel,
+        fuel_costs: Option<&FuelCosts>,
+    ) -> Option<optimizer::Positions> {
+        let instrs = self.instrs.get_slice_at_mut(Instr::from_u32(0));
+        optimizer::optimize(instrs, consts, level);
+        let fuel = fuel_costs.map(|costs| optimizer::Fuel {
+            base: costs.base(),
+            blocks: &self.fuel_blocks,
+        });
+        let positions = optimizer::compact(instrs, fuel)?;
+        self.instrs.truncate(positions.len_instrs());
+        self.reset_last_instr();
+        Some(positions)
+    }
+
+    /// Returns `true` if the encoded function can be inlined into its callers.
+    ///
+    /// # Note
+    ///
+    /// The function has `len_locals` local variables excluding its parameters
+    /// and must fit into the inlining `budget`.
+    pub fn is_inlinable(&mut self, len_locals: u16, budget: u32) -> bool {
+        let instrs = self.instrs.get_slice_at_mut(Instr::from_u32(0));
+        inline::is_inlinable(instrs, len_locals, budget)
+    }
+
     /// Push the [`Instruction`] to the [`InstrEncoder`].
     pub fn push_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
         let last_instr = self.instrs.push(instr)?;
         self.last_instr = Some(last_instr);
+        self.inlined_result = None;
         Ok(last_instr)
     }
 
@@ -578,9 +666,21 @@ impl InstrEncoder {
         self.instrs
             .get_mut(instr)
             .bump_fuel_consumption(fuel_consumed)?;
+        // Note: fuel is charged right before the charged instructions are encoded.
+        self.push_fuel_block(instr.into_u32());
         Ok(())
     }
 
+    /// Registers that the next encoded [`Instruction`]s are charged to the `fuel_instr`.
+    fn push_fuel_block(&mut self, fuel_instr: u32) {
+        let next = self.instrs.next_instr().into_u32();
+        match self.fuel_blocks.last_mut() {
+            Some(last) if last.1 == fuel_instr => {}
+            Some(last) if last.0 == next => last.1 = fuel_instr,
+            _ => self.fuel_blocks.push((next, fuel_instr)),
+        }
+    }
+
     /// Encodes an unconditional `return` instruction.
     pub fn encode_return(
         &mut self,
@@ -809,7 +909,10 @@ impl InstrEncoder {
             // is _NOT_ itself a local register due to observable behavior or already preserved.
             return fallback_case(self, stack, local, value, preserved, fuel_info);
         }
-        let Some(last_instr) = self.last_instr else {
+        let inlined_result = self
+            .inlined_result
+            .filter(|result| result.distance(self.instrs.next_instr()) == 1);
+        let Some(last_instr) = self.last_instr.or(inlined_result) else {
             // Can only apply the optimization if there is a previous instruction
             // to replace its result register instead of emitting a copy.
             return fallback_case(self, stack, local, value, preserved, fuel_info);
@@ -855,7 +958,10 @@ impl InstrEncoder {
                 .instrs
                 .push_before(last_instr, Instruction::copy(preserved, local))?;
             self.notify_preserved_register(last_instr);
-            self.last_instr = Some(shifted_last_instr);
+            match self.last_instr {
+                Some(_) => self.last_instr = Some(shifted_last_instr),
+                None => self.inlined_result = Some(shifted_last_instr),
+            }
         }
         Ok(())
     }
@@ -5,8 +5,10 @@ mod control_frame;
 mod control_stack;
 mod driver;
 mod error;
+mod inline;
 mod instr_encoder;
 mod labels;
+mod optimizer;
 mod provider;
 mod relink_result;
 mod stack;
@@ -40,10 +42,12 @@ pub use self::{
     instr_encoder::{Instr, InstrEncoder},
     stack::TypedProvider,
 };
-use super::code_map::CompiledFuncEntity;
+use super::code_map::{CompiledFuncEntity, UncompiledFuncEntity};
 use crate::{
+    backtrace::FuncOffsetsBuilder,
     core::{TrapCode, Typed, TypedVal, UntypedVal, ValType},
-    engine::{config::FuelCosts, BlockType, EngineFunc},
+    debugger::{DebugOperand, FuncDebugInfoBuilder, MemoryAccess},
+    engine::{config::FuelCosts, BlockType, CompilationMode, EngineFunc, OptimizationLevel},
     ir::{
         index,
         AnyConst16,
@@ -58,7 +62,7 @@ use crate::{
         ShiftAmount,
         Sign,
     },
-    module::{FuncIdx, FuncTypeIdx, ModuleHeader},
+    module::{FuncIdx, FuncTypeIdx, ModuleHeader, WasmiValueType},
     Engine,
     Error,
     ExternRef,
@@ -117,6 +121,12 @@ pub struct FuncTranslatorAllocations {
     control_stack: ControlStack,
     /// Some reusable buffers for translation purposes.
     buffer: TranslationBuffers,
+    /// The debug information of the translated function.
+    debug_info: FuncDebugInfoBuilder,
+    /// The Wasm offsets of the basic blocks with coverage counters.
+    coverage: Vec<u32>,
+    /// The Wasm offsets of the translated Wasm operators for symbolication.
+    offsets: FuncOffsetsBuilder,
 }
 
 /// Reusable allocations for utility buffers.
@@ -160,6 +170,9 @@ impl FuncTranslatorAllocations {
         self.instr_encoder.reset();
         self.control_stack.reset();
         self.buffer.reset();
+        self.debug_info.reset();
+        self.coverage.clear();
+        self.offsets.reset();
     }
 }
 
@@ -241,6 +254,17 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     /// This information is mainly required for properly locating translation errors.
     fn update_pos(&mut self, pos: usize);
 
+    /// Informs the [`WasmTranslator`] that the Wasm operator at the current position is translated next.
+    ///
+    /// The `validator` provides the types of the Wasm operand stack if the Wasm function is validated.
+    ///
+    /// # Note
+    ///
+    /// This is used to generate debugging information if [`Config::debugging`] is enabled.
+    ///
+    /// [`Config::debugging`]: crate::Config::debugging
+    fn before_visit_operator(&mut self, validator: Option<&FuncValidator>) -> Result<(), Error>;
+
     /// Finishes constructing the Wasm function translation.
     ///
     /// # Note
@@ -316,6 +340,11 @@ where
 
     fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
+    }
+
+    fn before_visit_operator(&mut self, _validator: Option<&FuncValidator>) -> Result<(), Error> {
+        self.translator.before_visit_operator(Some(&self.validator))
     }
 
     fn finish(
@@ -403,6 +432,8 @@ pub struct LazyFuncTranslator {
     func_idx: FuncIdx,
     /// The identifier of the to be compiled function.
     engine_func: EngineFunc,
+    /// The byte offset of the function body within the Wasm binary.
+    offset: usize,
     /// The Wasm module header information used for translation.
     module: ModuleHeader,
     /// Information about Wasm validation during lazy translation.
@@ -459,12 +490,14 @@ impl LazyFuncTranslator {
     pub fn new(
         func_idx: FuncIdx,
         engine_func: EngineFunc,
+        offset: usize,
         module: ModuleHeader,
         func_to_validate: FuncToValidate<ValidatorResources>,
     ) -> Self {
         Self {
             func_idx,
             engine_func,
+            offset,
             module,
             validation: Validation::Checked(func_to_validate),
         }
@@ -474,12 +507,14 @@ impl LazyFuncTranslator {
     pub fn new_unchecked(
         func_idx: FuncIdx,
         engine_func: EngineFunc,
+        offset: usize,
         module: ModuleHeader,
         features: WasmFeatures,
     ) -> Self {
         Self {
             func_idx,
             engine_func,
+            offset,
             module,
             validation: Validation::Unchecked(features),
         }
@@ -502,6 +537,7 @@ impl WasmTranslator<'_> for LazyFuncTranslator {
             .init_lazy_func(
                 self.func_idx,
                 self.engine_func,
+                self.offset,
                 bytes,
                 &self.module,
                 self.validation.take_func_to_validate(),
@@ -531,6 +567,11 @@ impl WasmTranslator<'_> for LazyFuncTranslator {
     #[inline]
     fn update_pos(&mut self, _pos: usize) {}
 
+    #[inline]
+    fn before_visit_operator(&mut self, _validator: Option<&FuncValidator>) -> Result<(), Error> {
+        Ok(())
+    }
+
     #[inline]
     fn finish(
         self,
@@ -585,6 +626,47 @@ pub struct FuncTranslator {
     ///
     /// `None` if fuel metering is disabled.
     fuel_costs: Option<FuelCosts>,
+    /// Is `true` if debugging information is generated.
+    debugging: bool,
+    /// Is `true` if an [`Instruction::DebugStep`] is translated for every Wasm operator.
+    debug_steps: bool,
+    /// Is `true` if the Wasm offsets of the translated Wasm operators are recorded.
+    symbolication: bool,
+    /// Is `true` if code coverage counters are generated.
+    coverage: bool,
+    /// Is `true` if the next Wasm operator starts a new basic block.
+    ///
+    /// # Note
+    ///
+    /// This is used to place code coverage counters.
+    new_basic_block: bool,
+    /// Is `true` if tier-up counters are generated for tiering.
+    tiering: bool,
+    /// The optimizations applied to the translated function.
+    ///
+    /// # Note
+    ///
+    /// This is [`OptimizationLevel::Full`] when re-translating a hot function for tiering.
+    optimization_level: OptimizationLevel,
+    /// The Wasm function body to re-translate once the function is hot.
+    ///
+    /// This is `Some` if tier-up counters are generated.
+    tier_up: Option<UncompiledFuncEntity>,
+    /// Is `true` if calls to small leaf functions are inlined and the function may be inlined.
+    inlining: bool,
+    /// Is `true` if only functions with lower indices than the translated function are inlined.
+    ///
+    /// # Note
+    ///
+    /// This is the case for eager translation which might happen in parallel and
+    /// keeps the inlining decisions independent from the order of translation.
+    ordered_inlining: bool,
+    /// The maximum number of instructions of an inlined function.
+    inlining_budget: u32,
+    /// The number of calls inlined into the translated function.
+    inlined_calls: usize,
+    /// The current position within the Wasm binary while parsing operators.
+    pos: usize,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
@@ -592,7 +674,20 @@ pub struct FuncTranslator {
 impl WasmTranslator<'_> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
-    fn setup(&mut self, _bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
+        if self.debugging {
+            // Note: the driver informs about the position of the function body before setup.
+            self.alloc.debug_info.set_body_offset(self.pos as u32);
+        }
+        if self.tiering {
+            self.tier_up = Some(UncompiledFuncEntity::new(
+                self.func,
+                self.pos,
+                bytes,
+                self.module.clone(),
+                None,
+            ));
+        }
         Ok(false)
     }
 
@@ -604,9 +699,14 @@ impl WasmTranslator<'_> for FuncTranslator {
     fn translate_locals(
         &mut self,
         amount: u32,
-        _value_type: wasmparser::ValType,
+        value_type: wasmparser::ValType,
     ) -> Result<(), Error> {
-        self.alloc.stack.register_locals(amount)
+        self.alloc.stack.register_locals(amount)?;
+        if self.debugging {
+            let ty = WasmiValueType::from(value_type).into_inner();
+            self.alloc.debug_info.push_locals(amount, ty);
+        }
+        Ok(())
     }
 
     fn finish_translate_locals(&mut self) -> Result<(), Error> {
@@ -614,7 +714,28 @@ impl WasmTranslator<'_> for FuncTranslator {
         Ok(())
     }
 
-    fn update_pos(&mut self, _pos: usize) {}
+    fn update_pos(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    fn before_visit_operator(&mut self, validator: Option<&FuncValidator>) -> Result<(), Error> {
+        let new_basic_block = mem::take(&mut self.new_basic_block);
+        if !self.is_reachable() {
+            return Ok(());
+        }
+        if self.symbolication {
+            // Note: Wasmi does not support Wasm binaries larger than 4 GiB.
+            let pos = self.alloc.instr_encoder.next_instr().into_u32();
+            self.alloc.offsets.push(pos, self.pos as u32);
+        }
+        if self.coverage && new_basic_block {
+            self.translate_coverage_counter()?;
+        }
+        if self.debugging {
+            self.record_debug_point(validator)?;
+        }
+        Ok(())
+    }
 
     fn finish(
         mut self,
@@ -634,17 +755,67 @@ impl WasmTranslator<'_> for FuncTranslator {
             //       the compiled function.
             // Note: The function enclosing block fuel instruction is always
             //       the instruction at the 0th index if fuel metering is enabled.
+            // Note: The registers of inlined functions are excluded since their
+            //       own fuel instructions already account for them.
             let fuel_instr = Instr::from_u32(0);
             let fuel_info = FuelInfo::some(*fuel_costs, fuel_instr);
+            let len_registers = self.alloc.stack.len_registers_uninlined();
             self.alloc
                 .instr_encoder
                 .bump_fuel_consumption(fuel_info, |costs| {
                     costs.fuel_for_copies(u64::from(len_registers))
                 })?;
         }
+        if self.optimization_level != OptimizationLevel::None {
+            // Note: constant registers index the function local constants in reverse order.
+            let consts = Vec::from_iter(self.alloc.stack.func_local_consts().rev());
+            let fuel_costs = self.fuel_costs().copied();
+            let positions = self.alloc.instr_encoder.optimize(
+                &consts,
+                self.optimization_level,
+                fuel_costs.as_ref(),
+            );
+            if let Some(positions) = positions {
+                self.alloc.offsets.remap(|pos| positions.get(pos));
+                self.alloc.debug_info.remap(|pos| positions.get(pos));
+            }
+        }
+        let debug_info = self.debugging.then(|| {
+            let stack = &mut self.alloc.stack;
+            self.alloc
+                .debug_info
+                .finish(self.func.into_u32(), |reg| stack.defrag_register(reg))
+        });
+        let len_params = self.func_type_of(self.func).params().len() as u16;
+        let len_locals = self.alloc.stack.len_locals();
+        let inlinable = self.inlining
+            && self
+                .alloc
+                .instr_encoder
+                .is_inlinable(len_locals - len_params, self.inlining_budget);
+        if self.inlined_calls != 0 {
+            self.engine.add_inlined_calls(self.inlined_calls);
+        }
         let func_consts = self.alloc.stack.func_local_consts();
         let instrs = self.alloc.instr_encoder.drain_instrs();
-        finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
+        let mut func_entity = CompiledFuncEntity::new(len_registers, instrs, func_consts);
+        if let Some(debug_info) = debug_info {
+            func_entity = func_entity.with_debug_info(debug_info);
+        }
+        if self.coverage {
+            func_entity = func_entity.with_coverage(&self.alloc.coverage);
+        }
+        if self.symbolication {
+            let offsets = self.alloc.offsets.finish(self.func.into_u32());
+            func_entity = func_entity.with_offsets(offsets);
+        }
+        if let Some(tier_up) = self.tier_up.take() {
+            func_entity = func_entity.with_tier_up(tier_up);
+        }
+        if inlinable {
+            func_entity = func_entity.with_inlining(len_locals);
+        }
+        finalize(func_entity);
         Ok(self.into_allocations())
     }
 }
@@ -694,6 +865,31 @@ impl FuncTranslator {
         func: FuncIdx,
         res: ModuleHeader,
         alloc: FuncTranslatorAllocations,
+    ) -> Result<Self, Error> {
+        Self::new_impl(func, res, alloc, None)
+    }
+
+    /// Creates a new [`FuncTranslator`] that optimizes the translated function.
+    ///
+    /// # Note
+    ///
+    /// Used to re-translate hot functions for tiering.
+    pub fn new_optimizing(
+        func: FuncIdx,
+        res: ModuleHeader,
+        alloc: FuncTranslatorAllocations,
+    ) -> Result<Self, Error> {
+        Self::new_impl(func, res, alloc, Some(OptimizationLevel::Full))
+    }
+
+    /// Creates a new [`FuncTranslator`] that optimizes the translated function with `level`.
+    ///
+    /// Uses the [`OptimizationLevel`] of the [`Config`] if `level` is `None`.
+    fn new_impl(
+        func: FuncIdx,
+        res: ModuleHeader,
+        alloc: FuncTranslatorAllocations,
+        level: Option<OptimizationLevel>,
     ) -> Result<Self, Error> {
         let Some(engine) = res.engine().upgrade() else {
             panic!(
@@ -706,12 +902,50 @@ impl FuncTranslator {
             .get_consume_fuel()
             .then(|| config.fuel_costs())
             .copied();
+        // Note: core dumps are captured from the debugging information but
+        //       only the debugger is notified about every executed Wasm operator.
+        let debugging = config.get_debugging() || config.get_coredump_on_trap();
+        let debug_steps = config.get_debugging();
+        // Note: backtraces, trap offsets and the trace hook resolve the Wasm offsets
+        //       of executed instructions from the position of the instructions.
+        let symbolication = cfg!(feature = "trace") || debugging || config.get_wasm_backtrace();
+        let coverage = config.get_coverage();
+        // Note: functions with debugging information are never tiered up since their
+        //       debugging information refers to the instructions of the baseline tier.
+        let tiering = config.get_tiering() && !debugging;
+        // Note: functions with debugging information are never optimized since their
+        //       debugging information refers to the registers of the unoptimized code.
+        let optimization_level = match debugging {
+            true => OptimizationLevel::None,
+            false => level.unwrap_or_else(|| config.get_optimization_level()),
+        };
+        // Note: tier-up counters are useless if the function is already fully optimized.
+        let tiering = tiering && optimization_level != OptimizationLevel::Full;
+        // Note: functions with debugging information or coverage counters are neither
+        //       inlined nor inline other functions since both refer to single frames.
+        let inlining = config.get_inlining() && !debugging && !coverage;
+        let ordered_inlining =
+            level.is_none() && matches!(config.get_compilation_mode(), CompilationMode::Eager);
+        let inlining_budget = config.get_inlining_budget();
         Self {
             func,
             engine,
             module: res,
             reachable: true,
             fuel_costs,
+            debugging,
+            debug_steps,
+            symbolication,
+            coverage,
+            new_basic_block: true,
+            tiering,
+            optimization_level,
+            tier_up: None,
+            inlining,
+            ordered_inlining,
+            inlining_budget,
+            inlined_calls: 0,
+            pos: 0,
             alloc,
         }
         .init()
@@ -736,6 +970,7 @@ impl FuncTranslator {
         let block_type = BlockType::func_type(func_type);
         let end_label = self.alloc.instr_encoder.new_label();
         let consume_fuel = self.make_fuel_instr()?;
+        self.translate_tier_up_counter()?;
         // Note: we use a dummy `RegSpan` as placeholder.
         //
         // We can do this since the branch parameters of the function enclosing block
@@ -754,12 +989,87 @@ impl FuncTranslator {
 
     /// Registers the function parameters in the emulated value stack.
     fn init_func_params(&mut self) -> Result<(), Error> {
-        for _param_type in self.func_type().params() {
+        for param_type in self.func_type().params() {
             self.alloc.stack.register_locals(1)?;
+            if self.debugging {
+                self.alloc.debug_info.push_locals(1, *param_type);
+            }
         }
         Ok(())
     }
 
+    /// Records the current Wasm operand stack of the Wasm operator at the current position.
+    ///
+    /// Also translates an [`Instruction::DebugStep`] if the [`Debugger`] is notified about it.
+    /// The types of the operands are taken from the `validator` if any.
+    ///
+    /// [`Debugger`]: crate::Debugger
+    fn record_debug_point(&mut self, validator: Option<&FuncValidator>) -> Result<(), Error> {
+        // Note: Wasmi does not support Wasm binaries larger than 4 GiB.
+        let offset = self.pos as u32;
+        let instr = self.alloc.instr_encoder.next_instr().into_u32();
+        let height = self.alloc.stack.height();
+        self.alloc
+            .stack
+            .peek_n(height, &mut self.alloc.buffer.providers);
+        let operands = self
+            .alloc
+            .buffer
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                let operand = match *provider {
+                    TypedProvider::Register(reg) => DebugOperand::Register(reg),
+                    TypedProvider::Const(value) => DebugOperand::Const(UntypedVal::from(value)),
+                };
+                let ty = validator
+                    .and_then(|validator| validator.get_operand_type(height - 1 - index))
+                    .flatten()
+                    .map(|ty| WasmiValueType::from(ty).into_inner());
+                (operand, ty)
+            });
+        self.alloc.debug_info.push_point(offset, instr, operands);
+        if self.debug_steps {
+            self.alloc
+                .instr_encoder
+                .push_instr(Instruction::debug_step(offset))?;
+        }
+        Ok(())
+    }
+
+    /// Records the linear memory `access` of the translated Wasm operator for watchpoints.
+    ///
+    /// Does nothing unless debugging information is generated.
+    fn record_memory_access(&mut self, access: MemoryAccess) {
+        if self.debugging {
+            self.alloc.debug_info.push_access(access);
+        }
+    }
+
+    /// Translates an [`Instruction::CoverageCounter`] for the basic block starting at the current position.
+    fn translate_coverage_counter(&mut self) -> Result<(), Error> {
+        // Note: Wasmi does not support Wasm binaries larger than 4 GiB.
+        let offset = self.pos as u32;
+        let index = self.alloc.coverage.len() as u32;
+        self.alloc.coverage.push(offset);
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::coverage_counter(index))?;
+        Ok(())
+    }
+
+    /// Translates an [`Instruction::TierUpCounter`] if tiering is enabled.
+    fn translate_tier_up_counter(&mut self) -> Result<(), Error> {
+        if !self.tiering {
+            return Ok(());
+        }
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::TierUpCounter)?;
+        Ok(())
+    }
+
     /// Consumes `self` and returns the underlying reusable [`FuncTranslatorAllocations`].
     fn into_allocations(self) -> FuncTranslatorAllocations {
         self.alloc
@@ -1512,6 +1822,9 @@ impl FuncTranslator {
         T: WasmFloat,
     {
         bail_unreachable!(self);
+        if self.engine().config().get_deterministic() {
+            return self.translate_fbinary_canonicalized(make_instr, consteval);
+        }
         match self.alloc.stack.pop2() {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
@@ -1550,6 +1863,57 @@ impl FuncTranslator {
         }
     }
 
+    /// Translate a binary Wasmi float instruction with deterministic NaN results.
+    ///
+    /// # Note
+    ///
+    /// - Used by [`Self::translate_fbinary`] and [`Self::translate_fbinary_commutative`]
+    ///   if deterministic execution is enabled.
+    /// - Custom optimizations and non-canonicalized NaN propagation are not applied
+    ///   since they may forward NaN values that otherwise would be canonicalized.
+    /// - Applies constant evaluation if both operands are constant values.
+    fn translate_fbinary_canonicalized(
+        &mut self,
+        make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
+        consteval: fn(TypedVal, TypedVal) -> TypedVal,
+    ) -> Result<(), Error> {
+        match self.alloc.stack.pop2() {
+            (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
+                self.push_binary_instr(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Register(lhs), TypedProvider::Const(rhs)) => {
+                self.push_binary_instr_imm(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Const(lhs), TypedProvider::Register(rhs)) => {
+                self.push_binary_instr_imm_rev(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Const(lhs), TypedProvider::Const(rhs)) => {
+                self.push_binary_consteval(lhs, rhs, consteval)?;
+                self.canonicalize_nan_const();
+                Ok(())
+            }
+        }
+    }
+
+    /// Canonicalizes the constant float NaN on top of the stack if deterministic execution is enabled.
+    ///
+    /// Does nothing if the top-most value on the stack is not a constant float NaN.
+    fn canonicalize_nan_const(&mut self) {
+        if !self.engine().config().get_deterministic() {
+            return;
+        }
+        let TypedProvider::Const(value) = self.alloc.stack.peek() else {
+            return;
+        };
+        let canonicalized = match value.ty() {
+            ValType::F32 => value.f32_canonicalize_nan(),
+            ValType::F64 => value.f64_canonicalize_nan(),
+            _ => return,
+        };
+        self.alloc.stack.drop();
+        self.alloc.stack.push_const(canonicalized);
+    }
+
     /// Translate Wasmi float `{f32,f64}.copysign` instructions.
     ///
     /// # Note
@@ -1681,6 +2045,9 @@ impl FuncTranslator {
         T: WasmFloat,
     {
         bail_unreachable!(self);
+        if self.engine().config().get_deterministic() {
+            return self.translate_fbinary_canonicalized(make_instr, consteval);
+        }
         match self.alloc.stack.pop2() {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
@@ -1871,6 +2238,23 @@ impl FuncTranslator {
         }
     }
 
+    /// Translates a unary Wasm float instruction to Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// Same as [`Self::translate_unary`] but canonicalizes constant evaluated
+    /// NaN results if deterministic execution is enabled.
+    fn translate_funary(
+        &mut self,
+        make_instr: fn(result: Reg, input: Reg) -> Instruction,
+        consteval: fn(input: TypedVal) -> TypedVal,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        self.translate_unary(make_instr, consteval)?;
+        self.canonicalize_nan_const();
+        Ok(())
+    }
+
     /// Translates a fallible unary Wasm instruction to Wasmi bytecode.
     fn translate_unary_fallible(
         &mut self,
@@ -1937,6 +2321,7 @@ impl FuncTranslator {
         make_instr_at: fn(result: Reg, address: u32) -> Instruction,
     ) -> Result<(), Error> {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::load(memarg));
         let (memory, offset) = Self::decode_memarg(memarg);
         let ptr = self.alloc.stack.pop();
         let ptr = match ptr {
@@ -2028,6 +2413,7 @@ impl FuncTranslator {
         Field: TryFrom<Wrapped> + Into<AnyConst16>,
     {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::store(memarg));
         let (memory, offset) = Self::decode_memarg(memarg);
         let (ptr, value) = self.alloc.stack.pop2();
         let ptr = match ptr {
@@ -2180,6 +2566,7 @@ impl FuncTranslator {
         make_instr_at: fn(value: Reg, address: u32) -> Instruction,
     ) -> Result<(), Error> {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::store(memarg));
         let (memory, offset) = Self::decode_memarg(memarg);
         let (ptr, value) = self.alloc.stack.pop2();
         let ptr = match ptr {
@@ -0,0 +1,85 @@
+use crate::ir::{BranchOffset, BranchOffset16, Instruction, VisitBranchOffsets};
+
+/// The maximum number of unconditional branches followed to find a branch destination.
+///
+/// # Note
+///
+/// This guards against cycles of unconditional branches which represent infinite loops.
+const MAX_THREADED_BRANCHES: usize = 16;
+
+/// Retargets branches to unconditional branches to the final destination of the latter.
+///
+/// # Note
+///
+/// This removes the indirection of branching out of nested Wasm blocks, for example
+/// a `br_if` to a block that ends with a `br` to one of its parents.
+/// Branches with 16-bit offsets are left untouched if their new offset does not fit.
+pub fn thread_branches(instrs: &mut [Instruction]) {
+    for pos in 0..instrs.len() {
+        let mut instr = instrs[pos];
+        let mut threader = BranchThreader {
+            instrs,
+            pos,
+            changed: false,
+        };
+        instr.visit_branch_offsets(&mut threader);
+        if threader.changed {
+            instrs[pos] = instr;
+        }
+    }
+}
+
+/// Visitor that threads the branch offsets of the [`Instruction`] at `pos`.
+struct BranchThreader<'a> {
+    /// The instructions of the optimized function.
+    instrs: &'a [Instruction],
+    /// The position of the visited [`Instruction`] within `instrs`.
+    pos: usize,
+    /// Is `true` if any branch offset of the visited [`Instruction`] has been changed.
+    changed: bool,
+}
+
+impl BranchThreader<'_> {
+    /// Returns the offset from `pos` to the final destination of a branch with `offset`.
+    ///
+    /// Returns `None` if the branch cannot be threaded.
+    fn thread(&self, offset: i32) -> Option<i32> {
+        let pos = self.pos as i64;
+        let mut dst = pos + i64::from(offset);
+        for _ in 0..MAX_THREADED_BRANCHES {
+            let Some(Instruction::Branch { offset }) = usize::try_from(dst)
+                .ok()
+                .and_then(|dst| self.instrs.get(dst))
+            else {
+                break;
+            };
+            dst += i64::from(offset.to_i32());
+        }
+        let threaded = i32::try_from(dst - pos).ok()?;
+        if threaded == offset || threaded == 0 {
+            return None;
+        }
+        Some(threaded)
+    }
+}
+
+impl VisitBranchOffsets for BranchThreader<'_> {
+    fn visit_branch_offset(&mut self, offset: &mut BranchOffset) {
+        let Some(threaded) = self.thread(offset.to_i32()) else {
+            return;
+        };
+        *offset = BranchOffset::from(threaded);
+        self.changed = true;
+    }
+
+    fn visit_branch_offset16(&mut self, offset: &mut BranchOffset16) {
+        let Some(threaded) = self.thread(i32::from(offset.to_i16())) else {
+            return;
+        };
+        let Ok(threaded) = BranchOffset16::try_from(BranchOffset::from(threaded)) else {
+            return;
+        };
+        *offset = threaded;
+        self.changed = true;
+    }
+}
@@ -0,0 +1,144 @@
+use super::graph;
+use crate::ir::{BlockFuel, BranchOffset, BranchOffset16, Instruction, VisitBranchOffsets};
+use alloc::{vec, vec::Vec};
+
+/// The fuel metering information of the compacted function.
+#[derive(Debug, Copy, Clone)]
+pub struct Fuel<'a> {
+    /// The base fuel costs charged for every removed instruction.
+    pub base: u64,
+    /// The position of the first instruction charged to an [`Instruction::ConsumeFuel`]
+    /// together with the position of the latter, in the order of their instructions.
+    pub blocks: &'a [(u32, u32)],
+}
+
+impl Fuel<'_> {
+    /// Returns the position of the [`Instruction::ConsumeFuel`] charging the instruction at `pos`.
+    fn block_of(&self, pos: usize) -> Option<usize> {
+        let index = self
+            .blocks
+            .partition_point(|&(first, _)| first as usize <= pos)
+            .checked_sub(1)?;
+        Some(self.blocks[index].1 as usize)
+    }
+}
+
+/// Maps the positions of instructions before compaction to their positions afterwards.
+#[derive(Debug)]
+pub struct Positions {
+    /// The new position of every instruction before compaction followed by the new length.
+    positions: Vec<u32>,
+}
+
+impl Positions {
+    /// Returns the new position of the instruction at `pos` before compaction.
+    ///
+    /// Removed instructions are mapped to the new position of the next remaining instruction.
+    pub fn get(&self, pos: u32) -> u32 {
+        self.positions[pos as usize]
+    }
+
+    /// Returns the number of instructions after compaction.
+    pub fn len_instrs(&self) -> usize {
+        self.positions.last().copied().unwrap_or(0) as usize
+    }
+}
+
+/// Removes the instructions that have been replaced by [`nop`](super::nop) from `instrs`.
+///
+/// The remaining instructions are moved to the front of `instrs` and keep their order.
+/// Their branch offsets are adjusted and the fuel of removed instructions is no longer
+/// charged by their [`Instruction::ConsumeFuel`] if `fuel` metering is enabled.
+///
+/// Returns `None` if no instruction has been removed.
+/// This is also the case for functions that use [`Instruction::BranchCmpFallback`]
+/// since the latter stores its branch offset in a function local constant.
+pub fn compact(instrs: &mut [Instruction], fuel: Option<Fuel>) -> Option<Positions> {
+    let mut removed = vec![false; instrs.len()];
+    let mut pos = 0;
+    while pos < instrs.len() {
+        // Note: only whole nodes are removed since for example
+        //       the targets of branch tables are branches, too.
+        let end = graph::node_end(instrs, pos)?;
+        removed[pos] = end == pos + 1 && is_nop(&instrs[pos]);
+        pos = end;
+    }
+    if !removed.contains(&true) {
+        return None;
+    }
+    let mut positions = Vec::with_capacity(instrs.len() + 1);
+    let mut len_instrs = 0;
+    for is_removed in &removed {
+        positions.push(len_instrs);
+        if !is_removed {
+            len_instrs += 1;
+        }
+    }
+    positions.push(len_instrs);
+    let positions = Positions { positions };
+    if let Some(fuel) = fuel {
+        for pos in (0..instrs.len()).filter(|pos| removed[*pos]) {
+            let Some(Instruction::ConsumeFuel { block_fuel }) =
+                fuel.block_of(pos).and_then(|block| instrs.get_mut(block))
+            else {
+                continue;
+            };
+            if let Ok(reduced) = BlockFuel::try_from(block_fuel.to_u64().saturating_sub(fuel.base))
+            {
+                *block_fuel = reduced;
+            }
+        }
+    }
+    for pos in (0..instrs.len()).filter(|pos| !removed[*pos]) {
+        let mut instr = instrs[pos];
+        instr.visit_branch_offsets(&mut BranchRemapper {
+            pos,
+            positions: &positions,
+        });
+        instrs[positions.get(pos as u32) as usize] = instr;
+    }
+    Some(positions)
+}
+
+/// Returns `true` if `instr` is a branch to its next instruction.
+fn is_nop(instr: &Instruction) -> bool {
+    matches!(instr, Instruction::Branch { offset } if offset.to_i32() == 1)
+}
+
+/// Visitor that adjusts the branch offsets of the [`Instruction`] at `pos` to the compacted positions.
+struct BranchRemapper<'a> {
+    /// The position of the visited [`Instruction`] before compaction.
+    pos: usize,
+    /// The positions of all instructions after compaction.
+    positions: &'a Positions,
+}
+
+impl BranchRemapper<'_> {
+    /// Returns the compacted offset of a branch with `offset`.
+    ///
+    /// Returns `None` if the branch target is out of bounds.
+    fn remap(&self, offset: i32) -> Option<i32> {
+        let target = usize::try_from(self.pos as i64 + i64::from(offset)).ok()?;
+        let target = *self.positions.positions.get(target)?;
+        let pos = self.positions.positions[self.pos];
+        i32::try_from(i64::from(target) - i64::from(pos)).ok()
+    }
+}
+
+impl VisitBranchOffsets for BranchRemapper<'_> {
+    fn visit_branch_offset(&mut self, offset: &mut BranchOffset) {
+        if let Some(remapped) = self.remap(offset.to_i32()) {
+            *offset = BranchOffset::from(remapped);
+        }
+    }
+
+    fn visit_branch_offset16(&mut self, offset: &mut BranchOffset16) {
+        // Note: compaction never increases the distance of a branch.
+        if let Some(remapped) = self
+            .remap(i32::from(offset.to_i16()))
+            .and_then(|remapped| i16::try_from(remapped).ok())
+        {
+            *offset = BranchOffset16::from(remapped);
+        }
+    }
+}
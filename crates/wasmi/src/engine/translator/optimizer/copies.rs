@@ -0,0 +1,158 @@
+use super::{
+    graph::{Effects, Graph, Node},
+    nop,
+};
+use crate::ir::{Instruction, Reg};
+use alloc::{vec, vec::Vec};
+
+/// Eliminates redundant [`Instruction::Copy`] instructions.
+///
+/// # Note
+///
+/// - Copies of a register to itself or to a register that already holds
+///   the copied value are removed.
+/// - Reads of a copied register are replaced with reads of the register it
+///   was copied from while both registers hold the same value. This makes
+///   the copy itself dead unless its result is read by a span of registers.
+pub fn eliminate_copies(instrs: &mut [Instruction]) {
+    let Some(graph) = Graph::new(instrs) else {
+        return;
+    };
+    let mut copies = Copies::new(&graph);
+    copies.solve(instrs);
+    copies.rewrite(instrs);
+}
+
+/// The available copies of a basic block.
+///
+/// Maps each register to the register it has been copied from if any.
+type State = Vec<Option<Reg>>;
+
+/// Available copies analysis of a single function.
+struct Copies<'a> {
+    /// The [`Graph`] of the function.
+    graph: &'a Graph,
+    /// The states at the start of all basic blocks.
+    ///
+    /// A basic block that has not yet been visited has no state.
+    states: Vec<Option<State>>,
+    /// Reusable buffer for the [`Effects`] of nodes.
+    effects: Effects,
+}
+
+impl<'a> Copies<'a> {
+    /// Creates a new [`Copies`] for `graph`.
+    fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            states: vec![None; graph.len_blocks()],
+            effects: Effects::default(),
+        }
+    }
+
+    /// Computes the available copies at the start of all basic blocks.
+    fn solve(&mut self, instrs: &[Instruction]) {
+        self.states[0] = Some(vec![None; self.graph.len_regs()]);
+        let mut worklist = vec![0];
+        let mut in_worklist = vec![false; self.graph.len_blocks()];
+        while let Some(block) = worklist.pop() {
+            in_worklist[block] = false;
+            let Some(mut state) = self.states[block].clone() else {
+                continue;
+            };
+            for node in self.graph.nodes(block) {
+                self.transfer(instrs, node, &mut state);
+            }
+            for succ in self.graph.succs(block) {
+                let changed = match &mut self.states[*succ] {
+                    Some(succ_state) => {
+                        let mut changed = false;
+                        for (copy, new_copy) in succ_state.iter_mut().zip(&state) {
+                            if copy.is_some() && copy != new_copy {
+                                *copy = None;
+                                changed = true;
+                            }
+                        }
+                        changed
+                    }
+                    succ_state @ None => {
+                        *succ_state = Some(state.clone());
+                        true
+                    }
+                };
+                if changed && !in_worklist[*succ] {
+                    in_worklist[*succ] = true;
+                    worklist.push(*succ);
+                }
+            }
+        }
+    }
+
+    /// Rewrites the instructions of all visited basic blocks given their available copies.
+    fn rewrite(&mut self, instrs: &mut [Instruction]) {
+        for block in 0..self.graph.len_blocks() {
+            let Some(mut state) = self.states[block].take() else {
+                continue;
+            };
+            for node in self.graph.nodes(block) {
+                self.graph
+                    .rewrite_inputs(instrs, node, |reg| self.source(&state, reg));
+                let is_redundant = match instrs[node.start] {
+                    Instruction::Copy { result, value } => {
+                        let value = self.root(&state, value);
+                        result == value || self.source(&state, result) == Some(value)
+                    }
+                    _ => false,
+                };
+                self.transfer(instrs, node, &mut state);
+                if is_redundant {
+                    instrs[node.start] = nop();
+                }
+            }
+        }
+    }
+
+    /// Updates `state` with the effects of `node`.
+    fn transfer(&mut self, instrs: &[Instruction], node: &Node, state: &mut State) {
+        self.graph.effects(instrs, node, &mut self.effects);
+        let copy = match instrs[node.start] {
+            Instruction::Copy { result, value } => Some((result, self.root(state, value))),
+            _ => None,
+        };
+        for def in &self.effects.defs {
+            if let Some(index) = self.graph.reg_index(*def) {
+                kill(state, index, *def);
+            }
+        }
+        for index in self.graph.regs_from(self.effects.defs_from) {
+            kill(state, index, Reg::from(index as i16));
+        }
+        if let Some((result, value)) = copy {
+            if let Some(index) = self.graph.reg_index(result) {
+                if result != value {
+                    state[index] = Some(value);
+                }
+            }
+        }
+    }
+
+    /// Returns the register `reg` has been copied from if any.
+    fn source(&self, state: &State, reg: Reg) -> Option<Reg> {
+        self.graph.reg_index(reg).and_then(|index| state[index])
+    }
+
+    /// Returns the register `reg` has been copied from or `reg` itself.
+    fn root(&self, state: &State, reg: Reg) -> Reg {
+        self.source(state, reg).unwrap_or(reg)
+    }
+}
+
+/// Removes all copies from and to `reg` at `index` from `state`.
+fn kill(state: &mut State, index: usize, reg: Reg) {
+    state[index] = None;
+    for copy in state.iter_mut() {
+        if *copy == Some(reg) {
+            *copy = None;
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::{
+    eval,
+    graph::{Effects, Graph, Node},
+    nop,
+};
+use crate::ir::Instruction;
+use alloc::{vec, vec::Vec};
+
+/// Removes instructions writing registers that are never read afterwards.
+///
+/// # Note
+///
+/// Only instructions without effects besides writing their results are removed.
+/// Instructions that may trap, such as loads or integer divisions, are kept.
+pub fn eliminate_dead_stores(instrs: &mut [Instruction]) {
+    let Some(graph) = Graph::new(instrs) else {
+        return;
+    };
+    let mut liveness = Liveness::new(&graph);
+    liveness.solve(instrs);
+    liveness.rewrite(instrs);
+}
+
+/// The live registers at a program point.
+type State = Vec<bool>;
+
+/// Register liveness analysis of a single function.
+struct Liveness<'a> {
+    /// The [`Graph`] of the function.
+    graph: &'a Graph,
+    /// The live registers at the start of all basic blocks.
+    live_in: Vec<State>,
+    /// Reusable buffer for the [`Effects`] of nodes.
+    effects: Effects,
+}
+
+impl<'a> Liveness<'a> {
+    /// Creates a new [`Liveness`] for `graph`.
+    fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            live_in: vec![vec![false; graph.len_regs()]; graph.len_blocks()],
+            effects: Effects::default(),
+        }
+    }
+
+    /// Computes the live registers at the start of all basic blocks.
+    fn solve(&mut self, instrs: &[Instruction]) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in (0..self.graph.len_blocks()).rev() {
+                let mut state = self.live_out(block);
+                for node in self.graph.nodes(block).iter().rev() {
+                    self.transfer(instrs, node, &mut state);
+                }
+                if state != self.live_in[block] {
+                    self.live_in[block] = state;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    /// Removes the dead stores of all basic blocks given their live registers.
+    fn rewrite(&mut self, instrs: &mut [Instruction]) {
+        for block in 0..self.graph.len_blocks() {
+            let mut state = self.live_out(block);
+            for node in self.graph.nodes(block).iter().rev() {
+                if self.is_dead(instrs, node, &state) {
+                    instrs[node.start] = nop();
+                    continue;
+                }
+                self.transfer(instrs, node, &mut state);
+            }
+        }
+    }
+
+    /// Returns the live registers at the end of `block`.
+    fn live_out(&self, block: usize) -> State {
+        let mut state = vec![false; self.graph.len_regs()];
+        for succ in self.graph.succs(block) {
+            for (live, live_in) in state.iter_mut().zip(&self.live_in[*succ]) {
+                *live |= *live_in;
+            }
+        }
+        state
+    }
+
+    /// Returns `true` if `node` can be removed given the live registers after it.
+    fn is_dead(&mut self, instrs: &[Instruction], node: &Node, state: &State) -> bool {
+        if !node.is_single() || !eval::is_pure(&instrs[node.start]) {
+            return false;
+        }
+        self.graph.effects(instrs, node, &mut self.effects);
+        self.effects.defs_from.is_none()
+            && self.effects.defs.iter().all(|def| {
+                // Note: constant registers are never written.
+                self.graph
+                    .reg_index(*def)
+                    .is_some_and(|index| !state[index])
+            })
+    }
+
+    /// Updates the live registers `state` before `node` given the live registers after it.
+    fn transfer(&mut self, instrs: &[Instruction], node: &Node, state: &mut State) {
+        self.graph.effects(instrs, node, &mut self.effects);
+        // Note: registers that may be written by `node` stay live.
+        for def in &self.effects.defs {
+            if let Some(index) = self.graph.reg_index(*def) {
+                state[index] = false;
+            }
+        }
+        for use_ in &self.effects.uses {
+            if let Some(index) = self.graph.reg_index(*use_) {
+                state[index] = true;
+            }
+        }
+        for index in self.graph.regs_from(self.effects.uses_from) {
+            state[index] = true;
+        }
+    }
+}
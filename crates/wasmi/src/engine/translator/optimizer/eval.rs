@@ -0,0 +1,690 @@
+use crate::{
+    core::UntypedVal,
+    engine::executor::UntypedValueExt as _,
+    ir::{BranchOffset16, Instruction, Reg},
+};
+
+/// The abstract value of a register during constant propagation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// The register holds the same value on all execution paths.
+    Const(UntypedVal),
+    /// The register may hold different values.
+    Varying,
+}
+
+impl Value {
+    /// Creates a [`Value::Const`] from `value`.
+    fn imm<T>(value: T) -> Self
+    where
+        UntypedVal: From<T>,
+    {
+        Self::Const(UntypedVal::from(value))
+    }
+
+    /// Returns the [`Value`] that is valid on both execution paths of `self` and `other`.
+    pub fn join(self, other: Self) -> Self {
+        if self == other {
+            return self;
+        }
+        Self::Varying
+    }
+}
+
+/// The type of the result of an evaluated [`Instruction`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// The result is an `i32` value.
+    I32,
+    /// The result is an `i64` value.
+    I64,
+}
+
+/// The result of an [`Instruction`] evaluated by [`evaluate`].
+#[derive(Debug, Copy, Clone)]
+pub struct Evaluated {
+    /// The register written by the evaluated [`Instruction`].
+    pub result: Reg,
+    /// The value written to `result`.
+    pub value: Value,
+    /// The type of `value` if the evaluated [`Instruction`] is an operator.
+    ///
+    /// This is `None` for copy instructions.
+    pub ty: Option<Type>,
+}
+
+/// A unary operator of the Wasmi bytecode.
+type UnaryOp = fn(UntypedVal) -> UntypedVal;
+
+/// A binary operator of the Wasmi bytecode.
+type BinaryOp = fn(UntypedVal, UntypedVal) -> UntypedVal;
+
+/// Evaluates `instr` given the abstract values of its input registers returned by `get`.
+///
+/// Returns `None` if `instr` is not supported by the evaluator.
+///
+/// # Note
+///
+/// All supported instructions write a single register, cannot trap and
+/// have no other side effects. Floating point arithmetic is not supported
+/// since its results depend on the NaN canonicalization of the executor.
+pub fn evaluate(instr: &Instruction, get: impl Fn(Reg) -> Value) -> Option<Evaluated> {
+    let (result, value, ty) = match *instr {
+        Instruction::Copy { result, value } => (result, get(value), None),
+        Instruction::CopyImm32 { result, value } => (result, Value::imm(u32::from(value)), None),
+        Instruction::CopyI64Imm32 { result, value } => (result, Value::imm(i64::from(value)), None),
+        Instruction::CopyF64Imm32 { result, value } => (result, Value::imm(f64::from(value)), None),
+        _ => {
+            if let Some((result, input, op, ty)) = unary(instr, &get) {
+                let value = match input {
+                    Value::Const(input) => Value::Const(op(input)),
+                    Value::Varying => Value::Varying,
+                };
+                (result, value, Some(ty))
+            } else {
+                let (result, lhs, rhs, op, ty) = binary(instr, &get)?;
+                (result, apply(lhs, rhs, op), Some(ty))
+            }
+        }
+    };
+    Some(Evaluated { result, value, ty })
+}
+
+/// Returns `true` if `instr` writes its results and has no other effects.
+pub fn is_pure(instr: &Instruction) -> bool {
+    matches!(instr, Instruction::Copy2 { .. }) || evaluate(instr, |_| Value::Varying).is_some()
+}
+
+/// Evaluates the condition of the conditional branch `instr`.
+///
+/// Returns the branch offset of `instr` and whether the branch is taken if known.
+/// Returns `None` if `instr` is not a supported conditional branch.
+pub fn condition(
+    instr: &Instruction,
+    get: impl Fn(Reg) -> Value,
+) -> Option<(BranchOffset16, Option<bool>)> {
+    let (lhs, rhs, op, offset) = branch(instr, &get)?;
+    let taken = match apply(lhs, rhs, op) {
+        Value::Const(value) => Some(u64::from(value) != 0),
+        Value::Varying => None,
+    };
+    Some((offset, taken))
+}
+
+/// Applies the binary `op` to `lhs` and `rhs`.
+fn apply(lhs: Value, rhs: Value, op: BinaryOp) -> Value {
+    match (lhs, rhs) {
+        (Value::Const(lhs), Value::Const(rhs)) => Value::Const(op(lhs, rhs)),
+        _ => Value::Varying,
+    }
+}
+
+/// Decomposes the unary operator `instr`.
+fn unary(instr: &Instruction, get: impl Fn(Reg) -> Value) -> Option<(Reg, Value, UnaryOp, Type)> {
+    use Instruction as I;
+    use Type::{I32, I64};
+    use UntypedVal as V;
+    let decomposed: (Reg, Reg, UnaryOp, Type) = match *instr {
+        I::I32Clz { result, input } => (result, input, V::i32_clz, I32),
+        I::I32Ctz { result, input } => (result, input, V::i32_ctz, I32),
+        I::I32Popcnt { result, input } => (result, input, V::i32_popcnt, I32),
+        I::I64Clz { result, input } => (result, input, V::i64_clz, I64),
+        I::I64Ctz { result, input } => (result, input, V::i64_ctz, I64),
+        I::I64Popcnt { result, input } => (result, input, V::i64_popcnt, I64),
+        I::I32WrapI64 { result, input } => (result, input, V::i32_wrap_i64, I32),
+        I::I32Extend8S { result, input } => (result, input, V::i32_extend8_s, I32),
+        I::I32Extend16S { result, input } => (result, input, V::i32_extend16_s, I32),
+        I::I64Extend8S { result, input } => (result, input, V::i64_extend8_s, I64),
+        I::I64Extend16S { result, input } => (result, input, V::i64_extend16_s, I64),
+        I::I64Extend32S { result, input } => (result, input, V::i64_extend32_s, I64),
+        _ => return None,
+    };
+    let (result, input, op, ty) = decomposed;
+    Some((result, get(input), op, ty))
+}
+
+/// Decomposes the binary operator `instr`.
+fn binary(
+    instr: &Instruction,
+    get: impl Fn(Reg) -> Value,
+) -> Option<(Reg, Value, Value, BinaryOp, Type)> {
+    use Instruction as I;
+    use Type::{I32, I64};
+    use UntypedVal as V;
+    let decomposed: (Reg, Value, Value, BinaryOp, Type) = match *instr {
+        // Note: binary operators with register operands.
+        I::I32Add { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_add, I32),
+        I::I32Sub { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_sub, I32),
+        I::I32Mul { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_mul, I32),
+        I::I32And { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_and, I32),
+        I::I32AndEqz { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_and_eqz, I32),
+        I::I32Or { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_or, I32),
+        I::I32OrEqz { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_or_eqz, I32),
+        I::I32Xor { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_xor, I32),
+        I::I32XorEqz { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_xor_eqz, I32),
+        I::I32Shl { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_shl, I32),
+        I::I32ShrU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_shr_u, I32),
+        I::I32ShrS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_shr_s, I32),
+        I::I32Rotl { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_rotl, I32),
+        I::I32Rotr { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_rotr, I32),
+        I::I64Add { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_add, I64),
+        I::I64Sub { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_sub, I64),
+        I::I64Mul { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_mul, I64),
+        I::I64And { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_and, I64),
+        I::I64Or { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_or, I64),
+        I::I64Xor { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_xor, I64),
+        I::I64Shl { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_shl, I64),
+        I::I64ShrU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_shr_u, I64),
+        I::I64ShrS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_shr_s, I64),
+        I::I64Rotl { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_rotl, I64),
+        I::I64Rotr { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_rotr, I64),
+        // Note: comparison operators with register operands.
+        I::I32Eq { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_eq, I32),
+        I::I32Ne { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_ne, I32),
+        I::I32LtS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_lt_s, I32),
+        I::I32LtU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_lt_u, I32),
+        I::I32LeS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_le_s, I32),
+        I::I32LeU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i32_le_u, I32),
+        I::I64Eq { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_eq, I32),
+        I::I64Ne { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_ne, I32),
+        I::I64LtS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_lt_s, I32),
+        I::I64LtU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_lt_u, I32),
+        I::I64LeS { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_le_s, I32),
+        I::I64LeU { result, lhs, rhs } => (result, get(lhs), get(rhs), V::i64_le_u, I32),
+        I::F32Eq { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f32_eq, I32),
+        I::F32Ne { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f32_ne, I32),
+        I::F32Lt { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f32_lt, I32),
+        I::F32Le { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f32_le, I32),
+        I::F64Eq { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f64_eq, I32),
+        I::F64Ne { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f64_ne, I32),
+        I::F64Lt { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f64_lt, I32),
+        I::F64Le { result, lhs, rhs } => (result, get(lhs), get(rhs), V::f64_le, I32),
+        // Note: binary operators with an immediate `rhs` operand.
+        I::I32AddImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_add,
+            I32,
+        ),
+        I::I32MulImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_mul,
+            I32,
+        ),
+        I::I32AndImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_and,
+            I32,
+        ),
+        I::I32AndEqzImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_and_eqz,
+            I32,
+        ),
+        I::I32OrImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i32::from(rhs)), V::i32_or, I32)
+        }
+        I::I32OrEqzImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_or_eqz,
+            I32,
+        ),
+        I::I32XorImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_xor,
+            I32,
+        ),
+        I::I32XorEqzImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_xor_eqz,
+            I32,
+        ),
+        I::I64AddImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_add,
+            I64,
+        ),
+        I::I64MulImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_mul,
+            I64,
+        ),
+        I::I64AndImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_and,
+            I64,
+        ),
+        I::I64OrImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i64::from(rhs)), V::i64_or, I64)
+        }
+        I::I64XorImm16 { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_xor,
+            I64,
+        ),
+        I::I32ShlBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_shl,
+            I32,
+        ),
+        I::I32ShrUBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_shr_u,
+            I32,
+        ),
+        I::I32ShrSBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_shr_s,
+            I32,
+        ),
+        I::I32RotlBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_rotl,
+            I32,
+        ),
+        I::I32RotrBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_rotr,
+            I32,
+        ),
+        I::I64ShlBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_shl,
+            I64,
+        ),
+        I::I64ShrUBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_shr_u,
+            I64,
+        ),
+        I::I64ShrSBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_shr_s,
+            I64,
+        ),
+        I::I64RotlBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_rotl,
+            I64,
+        ),
+        I::I64RotrBy { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_rotr,
+            I64,
+        ),
+        I::I32EqImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i32::from(rhs)), V::i32_eq, I32)
+        }
+        I::I32NeImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i32::from(rhs)), V::i32_ne, I32)
+        }
+        I::I32LtSImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_lt_s,
+            I32,
+        ),
+        I::I32LtUImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(u32::from(rhs)),
+            V::i32_lt_u,
+            I32,
+        ),
+        I::I32LeSImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i32::from(rhs)),
+            V::i32_le_s,
+            I32,
+        ),
+        I::I32LeUImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(u32::from(rhs)),
+            V::i32_le_u,
+            I32,
+        ),
+        I::I64EqImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i64::from(rhs)), V::i64_eq, I32)
+        }
+        I::I64NeImm16 { result, lhs, rhs } => {
+            (result, get(lhs), Value::imm(i64::from(rhs)), V::i64_ne, I32)
+        }
+        I::I64LtSImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_lt_s,
+            I32,
+        ),
+        I::I64LtUImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(u64::from(rhs)),
+            V::i64_lt_u,
+            I32,
+        ),
+        I::I64LeSImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(i64::from(rhs)),
+            V::i64_le_s,
+            I32,
+        ),
+        I::I64LeUImm16Rhs { result, lhs, rhs } => (
+            result,
+            get(lhs),
+            Value::imm(u64::from(rhs)),
+            V::i64_le_u,
+            I32,
+        ),
+        // Note: binary operators with an immediate `lhs` operand.
+        I::I32SubImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_sub,
+            I32,
+        ),
+        I::I64SubImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_sub,
+            I64,
+        ),
+        I::I32ShlImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_shl,
+            I32,
+        ),
+        I::I32ShrUImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_shr_u,
+            I32,
+        ),
+        I::I32ShrSImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_shr_s,
+            I32,
+        ),
+        I::I32RotlImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_rotl,
+            I32,
+        ),
+        I::I32RotrImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_rotr,
+            I32,
+        ),
+        I::I64ShlImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_shl,
+            I64,
+        ),
+        I::I64ShrUImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_shr_u,
+            I64,
+        ),
+        I::I64ShrSImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_shr_s,
+            I64,
+        ),
+        I::I64RotlImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_rotl,
+            I64,
+        ),
+        I::I64RotrImm16 { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_rotr,
+            I64,
+        ),
+        I::I32LtSImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_lt_s,
+            I32,
+        ),
+        I::I32LtUImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(u32::from(lhs)),
+            get(rhs),
+            V::i32_lt_u,
+            I32,
+        ),
+        I::I32LeSImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i32::from(lhs)),
+            get(rhs),
+            V::i32_le_s,
+            I32,
+        ),
+        I::I32LeUImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(u32::from(lhs)),
+            get(rhs),
+            V::i32_le_u,
+            I32,
+        ),
+        I::I64LtSImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_lt_s,
+            I32,
+        ),
+        I::I64LtUImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(u64::from(lhs)),
+            get(rhs),
+            V::i64_lt_u,
+            I32,
+        ),
+        I::I64LeSImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(i64::from(lhs)),
+            get(rhs),
+            V::i64_le_s,
+            I32,
+        ),
+        I::I64LeUImm16Lhs { result, lhs, rhs } => (
+            result,
+            Value::imm(u64::from(lhs)),
+            get(rhs),
+            V::i64_le_u,
+            I32,
+        ),
+        _ => return None,
+    };
+    Some(decomposed)
+}
+
+/// Decomposes the conditional branch `instr` into its comparison and branch offset.
+///
+/// The branch is taken if the comparison yields a non-zero value.
+fn branch(
+    instr: &Instruction,
+    get: impl Fn(Reg) -> Value,
+) -> Option<(Value, Value, BinaryOp, BranchOffset16)> {
+    use Instruction as I;
+    use UntypedVal as V;
+    let decomposed: (Value, Value, BinaryOp, BranchOffset16) = match *instr {
+        // Note: conditional branches with register operands.
+        I::BranchI32And { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_and, offset),
+        I::BranchI32Or { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_or, offset),
+        I::BranchI32Xor { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_xor, offset),
+        I::BranchI32AndEqz { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_and_eqz, offset),
+        I::BranchI32OrEqz { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_or_eqz, offset),
+        I::BranchI32XorEqz { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_xor_eqz, offset),
+        I::BranchI32Eq { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_eq, offset),
+        I::BranchI32Ne { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_ne, offset),
+        I::BranchI32LtS { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_lt_s, offset),
+        I::BranchI32LtU { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_lt_u, offset),
+        I::BranchI32LeS { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_le_s, offset),
+        I::BranchI32LeU { lhs, rhs, offset } => (get(lhs), get(rhs), V::i32_le_u, offset),
+        I::BranchI64Eq { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_eq, offset),
+        I::BranchI64Ne { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_ne, offset),
+        I::BranchI64LtS { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_lt_s, offset),
+        I::BranchI64LtU { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_lt_u, offset),
+        I::BranchI64LeS { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_le_s, offset),
+        I::BranchI64LeU { lhs, rhs, offset } => (get(lhs), get(rhs), V::i64_le_u, offset),
+        I::BranchF32Eq { lhs, rhs, offset } => (get(lhs), get(rhs), V::f32_eq, offset),
+        I::BranchF32Ne { lhs, rhs, offset } => (get(lhs), get(rhs), V::f32_ne, offset),
+        I::BranchF32Lt { lhs, rhs, offset } => (get(lhs), get(rhs), V::f32_lt, offset),
+        I::BranchF32Le { lhs, rhs, offset } => (get(lhs), get(rhs), V::f32_le, offset),
+        I::BranchF64Eq { lhs, rhs, offset } => (get(lhs), get(rhs), V::f64_eq, offset),
+        I::BranchF64Ne { lhs, rhs, offset } => (get(lhs), get(rhs), V::f64_ne, offset),
+        I::BranchF64Lt { lhs, rhs, offset } => (get(lhs), get(rhs), V::f64_lt, offset),
+        I::BranchF64Le { lhs, rhs, offset } => (get(lhs), get(rhs), V::f64_le, offset),
+        // Note: conditional branches with an immediate `rhs` operand.
+        I::BranchI32AndImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_and, offset)
+        }
+        I::BranchI32OrImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_or, offset)
+        }
+        I::BranchI32XorImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_xor, offset)
+        }
+        I::BranchI32AndEqzImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_and_eqz, offset)
+        }
+        I::BranchI32OrEqzImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_or_eqz, offset)
+        }
+        I::BranchI32XorEqzImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_xor_eqz, offset)
+        }
+        I::BranchI32EqImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_eq, offset)
+        }
+        I::BranchI32NeImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_ne, offset)
+        }
+        I::BranchI32LtSImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_lt_s, offset)
+        }
+        I::BranchI32LtUImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(u32::from(rhs)), V::i32_lt_u, offset)
+        }
+        I::BranchI32LeSImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i32::from(rhs)), V::i32_le_s, offset)
+        }
+        I::BranchI32LeUImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(u32::from(rhs)), V::i32_le_u, offset)
+        }
+        I::BranchI64EqImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i64::from(rhs)), V::i64_eq, offset)
+        }
+        I::BranchI64NeImm16 { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i64::from(rhs)), V::i64_ne, offset)
+        }
+        I::BranchI64LtSImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i64::from(rhs)), V::i64_lt_s, offset)
+        }
+        I::BranchI64LtUImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(u64::from(rhs)), V::i64_lt_u, offset)
+        }
+        I::BranchI64LeSImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(i64::from(rhs)), V::i64_le_s, offset)
+        }
+        I::BranchI64LeUImm16Rhs { lhs, rhs, offset } => {
+            (get(lhs), Value::imm(u64::from(rhs)), V::i64_le_u, offset)
+        }
+        // Note: conditional branches with an immediate `lhs` operand.
+        I::BranchI32LtSImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(i32::from(lhs)), get(rhs), V::i32_lt_s, offset)
+        }
+        I::BranchI32LtUImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(u32::from(lhs)), get(rhs), V::i32_lt_u, offset)
+        }
+        I::BranchI32LeSImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(i32::from(lhs)), get(rhs), V::i32_le_s, offset)
+        }
+        I::BranchI32LeUImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(u32::from(lhs)), get(rhs), V::i32_le_u, offset)
+        }
+        I::BranchI64LtSImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(i64::from(lhs)), get(rhs), V::i64_lt_s, offset)
+        }
+        I::BranchI64LtUImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(u64::from(lhs)), get(rhs), V::i64_lt_u, offset)
+        }
+        I::BranchI64LeSImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(i64::from(lhs)), get(rhs), V::i64_le_s, offset)
+        }
+        I::BranchI64LeUImm16Lhs { lhs, rhs, offset } => {
+            (Value::imm(u64::from(lhs)), get(rhs), V::i64_le_u, offset)
+        }
+        _ => return None,
+    };
+    Some(decomposed)
+}
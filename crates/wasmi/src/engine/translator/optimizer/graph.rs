@@ -0,0 +1,454 @@
+use crate::ir::{
+    BranchOffset,
+    BranchOffset16,
+    Instruction,
+    Reg,
+    RegSpan,
+    VisitBranchOffsets,
+    VisitRegs,
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The maximum number of nodes times registers of a function that is analyzed.
+///
+/// # Note
+///
+/// The analyses of the optimizer store register states per basic block.
+/// Functions exceeding this limit are not analyzed to bound their memory usage.
+const MAX_ANALYSIS_SIZE: usize = 1 << 20;
+
+/// The control flow graph of the Wasmi bytecode of a function.
+#[derive(Debug)]
+pub struct Graph {
+    /// The nodes of the function in the order of their instructions.
+    nodes: Vec<Node>,
+    /// The basic blocks of the function in the order of their instructions.
+    blocks: Vec<Block>,
+    /// The successors of all basic blocks referenced by [`Block::succs`].
+    succs: Vec<usize>,
+    /// The number of non-constant registers referenced by the function.
+    len_regs: usize,
+}
+
+/// A node of the [`Graph`]: an [`Instruction`] together with its parameter instructions.
+///
+/// # Note
+///
+/// A branch table and all of its targets form a single node.
+#[derive(Debug, Copy, Clone)]
+pub struct Node {
+    /// The position of the first [`Instruction`] of the node.
+    pub start: usize,
+    /// The position after the last [`Instruction`] of the node.
+    pub end: usize,
+}
+
+impl Node {
+    /// Returns `true` if the node consists of a single [`Instruction`].
+    pub fn is_single(&self) -> bool {
+        self.end - self.start == 1
+    }
+}
+
+/// A basic block of the [`Graph`].
+#[derive(Debug, Clone)]
+struct Block {
+    /// The nodes of the basic block.
+    nodes: Range<usize>,
+    /// The successors of the basic block within [`Graph::succs`].
+    succs: Range<usize>,
+}
+
+/// The registers read and written by a [`Node`].
+#[derive(Debug, Default)]
+pub struct Effects {
+    /// The registers read by the node.
+    pub uses: Vec<Reg>,
+    /// The registers written by the node.
+    pub defs: Vec<Reg>,
+    /// If `Some` the node may read all registers starting at this register.
+    pub uses_from: Option<Reg>,
+    /// If `Some` the node may write all registers starting at this register.
+    pub defs_from: Option<Reg>,
+}
+
+impl Effects {
+    /// Resets `self` for reuse.
+    fn reset(&mut self) {
+        self.uses.clear();
+        self.defs.clear();
+        self.uses_from = None;
+        self.defs_from = None;
+    }
+}
+
+impl VisitRegs for Effects {
+    fn visit_result_reg(&mut self, reg: &mut Reg) {
+        self.defs.push(*reg);
+    }
+
+    fn visit_result_regs(&mut self, regs: &mut RegSpan, len: Option<u16>) {
+        match len {
+            Some(len) => self.defs.extend(regs.iter(len)),
+            None => self.defs_from = min_reg(self.defs_from, regs.head()),
+        }
+    }
+
+    fn visit_input_reg(&mut self, reg: &mut Reg) {
+        self.uses.push(*reg);
+    }
+
+    fn visit_input_regs(&mut self, regs: &mut RegSpan, len: Option<u16>) {
+        match len {
+            Some(len) => self.uses.extend(regs.iter(len)),
+            None => self.uses_from = min_reg(self.uses_from, regs.head()),
+        }
+    }
+}
+
+/// Returns the lower of `reg` and the optional `lhs` register.
+fn min_reg(lhs: Option<Reg>, reg: Reg) -> Option<Reg> {
+    match lhs {
+        Some(lhs) if i16::from(lhs) < i16::from(reg) => Some(lhs),
+        _ => Some(reg),
+    }
+}
+
+impl Graph {
+    /// Creates the [`Graph`] of the Wasmi bytecode `instrs`.
+    ///
+    /// Returns `None` if `instrs` cannot be analyzed.
+    /// This is the case for functions that are too large or use [`Instruction::BranchCmpFallback`]
+    /// since the latter stores its branch offset in a function local constant.
+    pub fn new(instrs: &[Instruction]) -> Option<Self> {
+        let mut nodes = Vec::new();
+        let mut pos = 0;
+        while pos < instrs.len() {
+            let end = node_end(instrs, pos)?;
+            nodes.push(Node { start: pos, end });
+            pos = end;
+        }
+        let node_at = |pos: usize| nodes.binary_search_by_key(&pos, |node| node.start).ok();
+        let mut targets = Vec::new();
+        let mut exits = Vec::with_capacity(nodes.len());
+        let mut is_leader = Vec::from_iter(nodes.iter().map(|_| false));
+        let mut len_regs = 0;
+        let mut effects = Effects::default();
+        for node in &nodes {
+            let first = targets.len();
+            for (pos, instr) in instrs.iter().enumerate().take(node.end).skip(node.start) {
+                let mut instr = *instr;
+                instr.visit_branch_offsets(&mut BranchTargets {
+                    pos,
+                    targets: &mut targets,
+                });
+            }
+            for target in &mut targets[first..] {
+                *target = node_at(*target)?;
+                is_leader[*target] = true;
+            }
+            let falls_through = !is_terminator(&instrs[node.start]);
+            exits.push((first..targets.len(), falls_through));
+            effects.reset();
+            for instr in &instrs[node.start..node.end] {
+                let mut instr = *instr;
+                instr.visit_regs(&mut effects);
+            }
+            let max_reg = effects
+                .uses
+                .iter()
+                .chain(&effects.defs)
+                .chain(&effects.uses_from)
+                .chain(&effects.defs_from)
+                .map(|reg| i16::from(*reg))
+                .max();
+            if let Some(max_reg) = max_reg.and_then(|reg| usize::try_from(reg).ok()) {
+                len_regs = len_regs.max(max_reg + 1);
+            }
+        }
+        if nodes.len().saturating_mul(len_regs) > MAX_ANALYSIS_SIZE {
+            return None;
+        }
+        if let Some(first) = is_leader.first_mut() {
+            *first = true;
+        }
+        for (index, (node_targets, falls_through)) in exits.iter().enumerate() {
+            if !node_targets.is_empty() || !falls_through {
+                if let Some(next) = is_leader.get_mut(index + 1) {
+                    *next = true;
+                }
+            }
+        }
+        let mut block_at = Vec::with_capacity(nodes.len());
+        let mut len_blocks = 0;
+        for starts_block in &is_leader {
+            if *starts_block {
+                len_blocks += 1;
+            }
+            block_at.push(len_blocks - 1);
+        }
+        let mut blocks = Vec::<Block>::with_capacity(len_blocks);
+        let mut succs = Vec::new();
+        for (index, starts_block) in is_leader.iter().enumerate() {
+            if *starts_block {
+                blocks.push(Block {
+                    nodes: index..index,
+                    succs: 0..0,
+                });
+            }
+            let block = blocks.last_mut()?;
+            block.nodes.end = index + 1;
+            let is_last = is_leader.get(index + 1).copied().unwrap_or(true);
+            if !is_last {
+                continue;
+            }
+            let (node_targets, falls_through) = &exits[index];
+            let first = succs.len();
+            if *falls_through {
+                // Note: all Wasmi functions end with a return instruction.
+                succs.push(*block_at.get(index + 1)?);
+            }
+            succs.extend(
+                targets[node_targets.clone()]
+                    .iter()
+                    .map(|node| block_at[*node]),
+            );
+            block.succs = first..succs.len();
+        }
+        Some(Self {
+            nodes,
+            blocks,
+            succs,
+            len_regs,
+        })
+    }
+
+    /// Returns the number of basic blocks of the [`Graph`].
+    pub fn len_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the number of non-constant registers referenced by the function.
+    pub fn len_regs(&self) -> usize {
+        self.len_regs
+    }
+
+    /// Returns the index of `reg` if it is a non-constant register of the function.
+    pub fn reg_index(&self, reg: Reg) -> Option<usize> {
+        usize::try_from(i16::from(reg))
+            .ok()
+            .filter(|index| *index < self.len_regs)
+    }
+
+    /// Returns the indices of all registers starting at the optional `reg`.
+    pub fn regs_from(&self, reg: Option<Reg>) -> Range<usize> {
+        match reg {
+            Some(reg) => {
+                let start = usize::try_from(i16::from(reg)).unwrap_or(0);
+                start.min(self.len_regs)..self.len_regs
+            }
+            None => 0..0,
+        }
+    }
+
+    /// Returns the nodes of the basic `block`.
+    pub fn nodes(&self, block: usize) -> &[Node] {
+        &self.nodes[self.blocks[block].nodes.clone()]
+    }
+
+    /// Returns the successors of the basic `block`.
+    pub fn succs(&self, block: usize) -> &[usize] {
+        &self.succs[self.blocks[block].succs.clone()]
+    }
+
+    /// Returns the basic block starting at the instruction at `pos` if any.
+    pub fn block_at(&self, pos: usize) -> Option<usize> {
+        self.blocks
+            .binary_search_by_key(&pos, |block| self.nodes[block.nodes.start].start)
+            .ok()
+    }
+
+    /// Writes the [`Effects`] of `node` into `effects`.
+    pub fn effects(&self, instrs: &[Instruction], node: &Node, effects: &mut Effects) {
+        effects.reset();
+        if is_branch_table(&instrs[node.start]) {
+            // Note: branch tables copy values to the results of their targets
+            //       which are not encoded as results of their instructions.
+            effects.uses_from = Some(Reg::from(0));
+            effects.defs_from = Some(Reg::from(0));
+            return;
+        }
+        for instr in &instrs[node.start..node.end] {
+            let mut instr = *instr;
+            instr.visit_regs(effects);
+        }
+    }
+
+    /// Replaces the input registers of `node` for which `f` returns `Some`.
+    ///
+    /// Returns `true` if any input register has been replaced.
+    pub fn rewrite_inputs(
+        &self,
+        instrs: &mut [Instruction],
+        node: &Node,
+        f: impl FnMut(Reg) -> Option<Reg>,
+    ) -> bool {
+        if is_branch_table(&instrs[node.start]) {
+            return false;
+        }
+        let mut rewriter = InputRewriter { f, changed: false };
+        for instr in &mut instrs[node.start..node.end] {
+            instr.visit_regs(&mut rewriter);
+        }
+        rewriter.changed
+    }
+}
+
+/// Returns the position after the node starting at `pos`.
+///
+/// Returns `None` if the node cannot be analyzed.
+pub fn node_end(instrs: &[Instruction], pos: usize) -> Option<usize> {
+    let end = match instrs[pos] {
+        Instruction::BranchCmpFallback { .. } => return None,
+        Instruction::BranchTable0 { len_targets, .. } => pos + 1 + len_targets as usize,
+        Instruction::BranchTable1 { len_targets, .. }
+        | Instruction::BranchTable2 { len_targets, .. }
+        | Instruction::BranchTable3 { len_targets, .. }
+        | Instruction::BranchTableSpan { len_targets, .. } => pos + 2 + len_targets as usize,
+        Instruction::BranchTableMany { len_targets, .. } => {
+            let mut end = pos + 1;
+            while let Some(Instruction::RegisterList { .. }) = instrs.get(end) {
+                end += 1;
+            }
+            end + 1 + len_targets as usize
+        }
+        _ => {
+            let mut end = pos + 1;
+            while instrs.get(end).is_some_and(is_parameter) {
+                end += 1;
+            }
+            end
+        }
+    };
+    (end <= instrs.len()).then_some(end)
+}
+
+/// Returns `true` if `instr` is a branch table [`Instruction`].
+fn is_branch_table(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::BranchTable0 { .. }
+            | Instruction::BranchTable1 { .. }
+            | Instruction::BranchTable2 { .. }
+            | Instruction::BranchTable3 { .. }
+            | Instruction::BranchTableSpan { .. }
+            | Instruction::BranchTableMany { .. }
+    )
+}
+
+/// Returns `true` if `instr` is a parameter of its preceding [`Instruction`].
+fn is_parameter(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::TableIndex { .. }
+            | Instruction::MemoryIndex { .. }
+            | Instruction::DataIndex { .. }
+            | Instruction::ElemIndex { .. }
+            | Instruction::Const32 { .. }
+            | Instruction::I64Const32 { .. }
+            | Instruction::F64Const32 { .. }
+            | Instruction::BranchTableTarget { .. }
+            | Instruction::BranchTableTargetNonOverlapping { .. }
+            | Instruction::Imm16AndImm32 { .. }
+            | Instruction::RegisterAndImm32 { .. }
+            | Instruction::RegisterSpan { .. }
+            | Instruction::Register { .. }
+            | Instruction::Register2 { .. }
+            | Instruction::Register3 { .. }
+            | Instruction::RegisterList { .. }
+            | Instruction::CallIndirectParams { .. }
+            | Instruction::CallIndirectParamsImm16 { .. }
+    )
+}
+
+/// Returns `true` if execution never continues with the [`Instruction`] after `instr`.
+fn is_terminator(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::Trap { .. }
+            | Instruction::Return
+            | Instruction::ReturnReg { .. }
+            | Instruction::ReturnReg2 { .. }
+            | Instruction::ReturnReg3 { .. }
+            | Instruction::ReturnImm32 { .. }
+            | Instruction::ReturnI64Imm32 { .. }
+            | Instruction::ReturnF64Imm32 { .. }
+            | Instruction::ReturnSpan { .. }
+            | Instruction::ReturnMany { .. }
+            | Instruction::ReturnCallInternal0 { .. }
+            | Instruction::ReturnCallInternal { .. }
+            | Instruction::ReturnCallImported0 { .. }
+            | Instruction::ReturnCallImported { .. }
+            | Instruction::ReturnCallIndirect0 { .. }
+            | Instruction::ReturnCallIndirect0Imm16 { .. }
+            | Instruction::ReturnCallIndirect { .. }
+            | Instruction::ReturnCallIndirectImm16 { .. }
+            | Instruction::Branch { .. }
+    ) || is_branch_table(instr)
+}
+
+/// Visitor collecting the absolute branch targets of an [`Instruction`] at `pos`.
+struct BranchTargets<'a> {
+    /// The position of the visited [`Instruction`].
+    pos: usize,
+    /// The collected branch targets.
+    targets: &'a mut Vec<usize>,
+}
+
+impl BranchTargets<'_> {
+    /// Pushes the target of a branch with `offset`.
+    fn push(&mut self, offset: i32) {
+        // Note: invalid targets are rejected by [`Graph::new`].
+        let target = usize::try_from(self.pos as i64 + i64::from(offset)).unwrap_or(usize::MAX);
+        self.targets.push(target);
+    }
+}
+
+impl VisitBranchOffsets for BranchTargets<'_> {
+    fn visit_branch_offset(&mut self, offset: &mut BranchOffset) {
+        self.push(offset.to_i32());
+    }
+
+    fn visit_branch_offset16(&mut self, offset: &mut BranchOffset16) {
+        self.push(i32::from(offset.to_i16()));
+    }
+}
+
+/// Visitor replacing the input registers of an [`Instruction`].
+struct InputRewriter<F> {
+    /// Returns the replacement of an input register if any.
+    f: F,
+    /// Is `true` if any input register has been replaced.
+    changed: bool,
+}
+
+impl<F> VisitRegs for InputRewriter<F>
+where
+    F: FnMut(Reg) -> Option<Reg>,
+{
+    fn visit_result_reg(&mut self, _reg: &mut Reg) {}
+
+    fn visit_result_regs(&mut self, _regs: &mut RegSpan, _len: Option<u16>) {}
+
+    fn visit_input_reg(&mut self, reg: &mut Reg) {
+        if let Some(new_reg) = (self.f)(*reg) {
+            if new_reg != *reg {
+                *reg = new_reg;
+                self.changed = true;
+            }
+        }
+    }
+
+    fn visit_input_regs(&mut self, _regs: &mut RegSpan, _len: Option<u16>) {}
+}
@@ -0,0 +1,53 @@
+//! The optimizing pipeline for the Wasmi bytecode of translated Wasm functions.
+//!
+//! # Note
+//!
+//! The optimizer runs on the finished Wasmi bytecode of a function after
+//! its register defragmentation and its branch offsets have been resolved.
+//! The optimizations never alter the number or positions of instructions
+//! so that branch offsets stay valid while they run. Instead removed
+//! instructions are replaced with branches to their next instruction which
+//! are skipped by branch threading and finally removed via [`compact`].
+
+mod branch_threading;
+mod compaction;
+mod copies;
+mod dead_stores;
+mod eval;
+mod graph;
+mod sccp;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::compaction::{compact, Fuel, Positions};
+
+use crate::{
+    core::UntypedVal,
+    engine::OptimizationLevel,
+    ir::{BranchOffset, Instruction},
+};
+
+/// Optimizes the Wasmi bytecode `instrs` of a single function with `level`.
+///
+/// The function local constant values `consts` are indexed by their constant register
+/// where the constant register `-1` refers to the first value.
+pub fn optimize(instrs: &mut [Instruction], consts: &[UntypedVal], level: OptimizationLevel) {
+    match level {
+        OptimizationLevel::None => return,
+        OptimizationLevel::Basic => {
+            copies::eliminate_copies(instrs);
+        }
+        OptimizationLevel::Full => {
+            sccp::propagate_constants(instrs, consts);
+            copies::eliminate_copies(instrs);
+            dead_stores::eliminate_dead_stores(instrs);
+        }
+    }
+    branch_threading::thread_branches(instrs);
+}
+
+/// Returns the [`Instruction`] that replaces removed instructions.
+fn nop() -> Instruction {
+    Instruction::branch(BranchOffset::from(1))
+}
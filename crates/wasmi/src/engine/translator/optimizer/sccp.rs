@@ -0,0 +1,263 @@
+use super::{
+    eval::{self, Type, Value},
+    graph::{Effects, Graph, Node},
+    nop,
+};
+use crate::{
+    core::UntypedVal,
+    ir::{AnyConst32, BranchOffset, Const32, Instruction, Reg},
+};
+use alloc::{vec, vec::Vec};
+
+/// Propagates constant register values across basic blocks.
+///
+/// # Note
+///
+/// This is a sparse conditional constant propagation over the basic blocks of the function:
+///
+/// - Operators with constant inputs are folded into immediate copy instructions.
+/// - Conditional branches with constant conditions become unconditional or are removed.
+/// - Instructions writing a value that their result register already holds are removed.
+/// - Inputs with constant values read function local constants if possible.
+///
+/// Basic blocks that are found to be unreachable are left untouched.
+pub fn propagate_constants(instrs: &mut [Instruction], consts: &[UntypedVal]) {
+    let Some(graph) = Graph::new(instrs) else {
+        return;
+    };
+    let mut sccp = Sccp::new(&graph, consts);
+    sccp.solve(instrs);
+    sccp.rewrite(instrs);
+}
+
+/// The abstract register values of a basic block.
+type State = Vec<Value>;
+
+/// Sparse conditional constant propagation of a single function.
+struct Sccp<'a> {
+    /// The [`Graph`] of the function.
+    graph: &'a Graph,
+    /// The function local constant values indexed by their constant register.
+    consts: &'a [UntypedVal],
+    /// The function local constant registers sorted by their values.
+    const_regs: Vec<(UntypedVal, Reg)>,
+    /// The states at the start of all basic blocks.
+    ///
+    /// A basic block that has not yet been found reachable has no state.
+    states: Vec<Option<State>>,
+    /// Reusable buffer for the [`Effects`] of nodes.
+    effects: Effects,
+}
+
+impl<'a> Sccp<'a> {
+    /// Creates a new [`Sccp`] for `graph` with function local `consts`.
+    fn new(graph: &'a Graph, consts: &'a [UntypedVal]) -> Self {
+        let mut const_regs = consts
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let index = i16::try_from(index).ok()?;
+                Some((*value, Reg::from(-1 - index)))
+            })
+            .collect::<Vec<_>>();
+        const_regs.sort_by_key(|(value, _)| *value);
+        Self {
+            graph,
+            consts,
+            const_regs,
+            states: vec![None; graph.len_blocks()],
+            effects: Effects::default(),
+        }
+    }
+
+    /// Computes the states at the start of all reachable basic blocks.
+    fn solve(&mut self, instrs: &[Instruction]) {
+        // Note: the function parameters and locals are unknown at function entry.
+        self.states[0] = Some(vec![Value::Varying; self.graph.len_regs()]);
+        let mut worklist = vec![0];
+        let mut in_worklist = vec![false; self.graph.len_blocks()];
+        let mut succs = Vec::new();
+        while let Some(block) = worklist.pop() {
+            in_worklist[block] = false;
+            let Some(mut state) = self.states[block].clone() else {
+                continue;
+            };
+            for node in self.graph.nodes(block) {
+                self.transfer(instrs, node, &mut state);
+            }
+            succs.clear();
+            self.reachable_succs(instrs, block, &state, &mut succs);
+            for succ in &succs {
+                let changed = match &mut self.states[*succ] {
+                    Some(succ_state) => {
+                        let mut changed = false;
+                        for (value, new_value) in succ_state.iter_mut().zip(&state) {
+                            let joined = value.join(*new_value);
+                            changed |= joined != *value;
+                            *value = joined;
+                        }
+                        changed
+                    }
+                    succ_state @ None => {
+                        *succ_state = Some(state.clone());
+                        true
+                    }
+                };
+                if changed && !in_worklist[*succ] {
+                    in_worklist[*succ] = true;
+                    worklist.push(*succ);
+                }
+            }
+        }
+    }
+
+    /// Rewrites the instructions of all reachable basic blocks given their solved states.
+    fn rewrite(&mut self, instrs: &mut [Instruction]) {
+        for block in 0..self.graph.len_blocks() {
+            let Some(mut state) = self.states[block].take() else {
+                continue;
+            };
+            for node in self.graph.nodes(block) {
+                let replacement = self.replacement(instrs, node, &state);
+                if replacement.is_none() {
+                    self.graph
+                        .rewrite_inputs(instrs, node, |reg| match self.get(&state, reg) {
+                            Value::Const(value) if !reg.is_const() => self.const_reg(value),
+                            _ => None,
+                        });
+                }
+                self.transfer(instrs, node, &mut state);
+                if let Some(replacement) = replacement {
+                    instrs[node.start] = replacement;
+                }
+            }
+        }
+    }
+
+    /// Returns the [`Instruction`] replacing `node` given the `state` before it if any.
+    fn replacement(
+        &self,
+        instrs: &[Instruction],
+        node: &Node,
+        state: &State,
+    ) -> Option<Instruction> {
+        if !node.is_single() {
+            return None;
+        }
+        let instr = &instrs[node.start];
+        if let Some((offset, taken)) = eval::condition(instr, |reg| self.get(state, reg)) {
+            return match taken? {
+                true => Some(Instruction::branch(BranchOffset::from(i32::from(
+                    offset.to_i16(),
+                )))),
+                false => Some(nop()),
+            };
+        }
+        let evaluated = eval::evaluate(instr, |reg| self.get(state, reg))?;
+        let Value::Const(value) = evaluated.value else {
+            return None;
+        };
+        if self.get(state, evaluated.result) == evaluated.value {
+            return Some(nop());
+        }
+        let result = evaluated.result;
+        match evaluated.ty? {
+            Type::I32 => Some(Instruction::CopyImm32 {
+                result,
+                value: AnyConst32::from(u32::from(value)),
+            }),
+            Type::I64 => {
+                let value = Const32::<i64>::try_from(i64::from(value)).ok()?;
+                Some(Instruction::CopyI64Imm32 { result, value })
+            }
+        }
+    }
+
+    /// Updates `state` with the effects of `node`.
+    fn transfer(&mut self, instrs: &[Instruction], node: &Node, state: &mut State) {
+        let instr = &instrs[node.start];
+        if node.is_single() {
+            if let Some(evaluated) = eval::evaluate(instr, |reg| self.get(state, reg)) {
+                self.set(state, evaluated.result, evaluated.value);
+                return;
+            }
+            if let Instruction::Copy2 { results, values } = *instr {
+                let values = values.map(|reg| self.get(state, reg));
+                let result0 = results.span().head();
+                self.set(state, result0, values[0]);
+                self.set(state, result0.next(), values[1]);
+                return;
+            }
+        }
+        self.graph.effects(instrs, node, &mut self.effects);
+        for def in &self.effects.defs {
+            if let Some(index) = self.graph.reg_index(*def) {
+                state[index] = Value::Varying;
+            }
+        }
+        for index in self.graph.regs_from(self.effects.defs_from) {
+            state[index] = Value::Varying;
+        }
+    }
+
+    /// Pushes the successors of `block` that are reachable given its final `state`.
+    fn reachable_succs(
+        &self,
+        instrs: &[Instruction],
+        block: usize,
+        state: &State,
+        succs: &mut Vec<usize>,
+    ) {
+        let all_succs = self.graph.succs(block);
+        let Some(last) = self.graph.nodes(block).last() else {
+            return;
+        };
+        let instr = &instrs[last.start];
+        let Some((offset, Some(taken))) = eval::condition(instr, |reg| self.get(state, reg)) else {
+            succs.extend_from_slice(all_succs);
+            return;
+        };
+        let pos = match taken {
+            true => last.start as isize + isize::from(offset.to_i16()),
+            false => last.end as isize,
+        };
+        let succ = usize::try_from(pos)
+            .ok()
+            .and_then(|pos| self.graph.block_at(pos));
+        match succ {
+            Some(succ) => succs.push(succ),
+            None => succs.extend_from_slice(all_succs),
+        }
+    }
+
+    /// Returns the [`Value`] of `reg` in `state`.
+    fn get(&self, state: &State, reg: Reg) -> Value {
+        if reg.is_const() {
+            let index = usize::from(i16::from(reg).wrapping_add(1).unsigned_abs());
+            return match self.consts.get(index) {
+                Some(value) => Value::Const(*value),
+                None => Value::Varying,
+            };
+        }
+        match self.graph.reg_index(reg) {
+            Some(index) => state[index],
+            None => Value::Varying,
+        }
+    }
+
+    /// Sets the [`Value`] of `reg` in `state`.
+    fn set(&self, state: &mut State, reg: Reg, value: Value) {
+        if let Some(index) = self.graph.reg_index(reg) {
+            state[index] = value;
+        }
+    }
+
+    /// Returns a function local constant register holding `value` if any.
+    fn const_reg(&self, value: UntypedVal) -> Option<Reg> {
+        let index = self
+            .const_regs
+            .binary_search_by_key(&value, |(value, _)| *value)
+            .ok()?;
+        Some(self.const_regs[index].1)
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{
+    core::UntypedVal,
+    engine::OptimizationLevel,
+    ir::{AnyConst32, BranchOffset, BranchOffset16, Const16, Instruction, Reg},
+};
+use std::{vec, vec::Vec};
+
+/// Optimizes `instrs` with [`OptimizationLevel::Full`] and without function local constants.
+fn optimize(instrs: &mut [Instruction]) {
+    super::optimize(instrs, &[], OptimizationLevel::Full)
+}
+
+/// Returns a `br_if` [`Instruction`] with a 16-bit branch `offset`.
+fn br_if(offset: i16) -> Instruction {
+    br_if_reg(0, offset)
+}
+
+/// Returns a `br_if` [`Instruction`] on `reg` with a 16-bit branch `offset`.
+fn br_if_reg(reg: i16, offset: i16) -> Instruction {
+    Instruction::branch_i32_ne_imm16(
+        Reg::from(reg),
+        Const16::<i32>::from(0),
+        BranchOffset16::from(offset),
+    )
+}
+
+/// Returns an [`Instruction::Copy`] from `value` to `result`.
+fn copy(result: i16, value: i16) -> Instruction {
+    Instruction::Copy {
+        result: Reg::from(result),
+        value: Reg::from(value),
+    }
+}
+
+/// Returns an [`Instruction::CopyImm32`] of `value` to `result`.
+fn copy_imm32(result: i16, value: i32) -> Instruction {
+    Instruction::CopyImm32 {
+        result: Reg::from(result),
+        value: AnyConst32::from(value),
+    }
+}
+
+/// Returns an [`Instruction::ReturnReg`] returning `value`.
+fn return_reg(value: i16) -> Instruction {
+    Instruction::ReturnReg {
+        value: Reg::from(value),
+    }
+}
+
+/// Returns an [`Instruction::ReturnImm32`] returning `value`.
+fn return_imm32(value: i32) -> Instruction {
+    Instruction::ReturnImm32 {
+        value: AnyConst32::from(value),
+    }
+}
+
+/// Returns an [`Instruction::I32Add`] of `lhs` and `rhs` to `result`.
+fn i32_add(result: i16, lhs: i16, rhs: i16) -> Instruction {
+    Instruction::I32Add {
+        result: Reg::from(result),
+        lhs: Reg::from(lhs),
+        rhs: Reg::from(rhs),
+    }
+}
+
+/// Returns a `br` [`Instruction`] with a branch `offset`.
+fn br(offset: i32) -> Instruction {
+    Instruction::branch(BranchOffset::from(offset))
+}
+
+/// Optimizes `instrs` and asserts that the result equals `expected`.
+fn assert_optimized<const N: usize>(instrs: [Instruction; N], expected: [Instruction; N]) {
+    assert_optimized_with(OptimizationLevel::Full, &[], instrs, expected)
+}
+
+/// Optimizes `instrs` with `level` and `consts` and asserts that the result equals `expected`.
+fn assert_optimized_with<const N: usize>(
+    level: OptimizationLevel,
+    consts: &[UntypedVal],
+    mut instrs: [Instruction; N],
+    expected: [Instruction; N],
+) {
+    super::optimize(&mut instrs, consts, level);
+    assert_eq!(instrs, expected);
+}
+
+#[test]
+fn thread_branch_chain() {
+    assert_optimized(
+        [br_if(2), br(3), br(2), Instruction::Return, br(-1)],
+        [br_if(3), br(2), br(1), Instruction::Return, br(-1)],
+    );
+}
+
+#[test]
+fn thread_backward_branch() {
+    assert_optimized(
+        [Instruction::Return, br(-1), br_if(-1)],
+        [Instruction::Return, br(-1), br_if(-2)],
+    );
+}
+
+#[test]
+fn keep_branch_cycles() {
+    assert_optimized([br(1), br(-1), br_if(-1)], [br(1), br(-1), br_if(-1)]);
+    assert_optimized([br(0), br_if(-1)], [br(0), br_if(-1)]);
+}
+
+#[test]
+fn keep_offset16_overflow() {
+    let mut instrs = vec![Instruction::Return; 40_000];
+    instrs[0] = br_if(1);
+    instrs[1] = br(i32::from(i16::MAX));
+    let mut expected = instrs.clone();
+    optimize(&mut instrs);
+    assert_eq!(instrs, expected);
+    instrs[1] = br(100);
+    expected[0] = br_if(101);
+    expected[1] = br(100);
+    optimize(&mut instrs);
+    assert_eq!(instrs, expected);
+}
+
+#[test]
+fn no_optimization() {
+    let instrs = [br_if(1), br(1), Instruction::Return];
+    assert_optimized_with(OptimizationLevel::None, &[], instrs, instrs);
+}
+
+#[test]
+fn fold_constants() {
+    assert_optimized(
+        [
+            copy_imm32(0, 5),
+            Instruction::i32_add_imm16(Reg::from(1), Reg::from(0), Const16::<i32>::from(3)),
+            return_reg(1),
+        ],
+        [br(1), copy_imm32(1, 8), return_reg(1)],
+    );
+}
+
+#[test]
+fn fold_constants_to_function_local_constants() {
+    assert_optimized_with(
+        OptimizationLevel::Full,
+        &[UntypedVal::from(10_i32), UntypedVal::from(20_i32)],
+        [i32_add(0, -1, -1), return_reg(0)],
+        [br(1), return_reg(-2)],
+    );
+}
+
+#[test]
+fn fold_constant_branches() {
+    assert_optimized(
+        [copy_imm32(0, 1), br_if(2), return_imm32(0), return_imm32(1)],
+        [br(3), br(2), return_imm32(0), return_imm32(1)],
+    );
+    assert_optimized(
+        [copy_imm32(0, 0), br_if(2), return_imm32(0), return_imm32(1)],
+        [br(2), br(1), return_imm32(0), return_imm32(1)],
+    );
+}
+
+#[test]
+fn keep_varying_across_blocks() {
+    // Note: `r1` holds different constants depending on the branch taken.
+    let instrs = [copy_imm32(1, 1), br_if(2), copy_imm32(1, 2), return_reg(1)];
+    assert_optimized(instrs, instrs);
+}
+
+#[test]
+fn remove_redundant_copies() {
+    assert_optimized_with(
+        OptimizationLevel::Basic,
+        &[],
+        [copy(1, 0), br_if_reg(2, 2), copy(1, 0), return_reg(1)],
+        [copy(1, 0), br_if_reg(2, 2), br(1), return_reg(0)],
+    );
+    assert_optimized_with(
+        OptimizationLevel::Basic,
+        &[],
+        [copy(0, 0), return_reg(0)],
+        [br(1), return_reg(0)],
+    );
+}
+
+#[test]
+fn propagate_copies() {
+    let instrs = [copy(1, 0), i32_add(2, 1, 1), return_reg(2)];
+    assert_optimized_with(
+        OptimizationLevel::Basic,
+        &[],
+        instrs,
+        [copy(1, 0), i32_add(2, 0, 0), return_reg(2)],
+    );
+    assert_optimized(instrs, [br(1), i32_add(2, 0, 0), return_reg(2)]);
+}
+
+#[test]
+fn keep_copies_of_overwritten_registers() {
+    let instrs = [
+        copy(1, 0),
+        copy_imm32(0, 1),
+        i32_add(2, 1, 0),
+        return_reg(2),
+    ];
+    assert_optimized_with(OptimizationLevel::Basic, &[], instrs, instrs);
+}
+
+#[test]
+fn remove_dead_stores() {
+    assert_optimized(
+        [i32_add(1, 0, 0), i32_add(1, 0, 2), return_reg(1)],
+        [br(1), i32_add(1, 0, 2), return_reg(1)],
+    );
+}
+
+#[test]
+fn keep_trapping_dead_stores() {
+    let instrs = [
+        Instruction::i32_div_s(Reg::from(1), Reg::from(0), Reg::from(0)),
+        return_reg(0),
+    ];
+    assert_optimized(instrs, instrs);
+}
+
+/// Compacts `instrs` with `fuel` and asserts that the result equals `expected`.
+///
+/// Returns the new positions of all instructions of `instrs`.
+fn assert_compacted(
+    mut instrs: Vec<Instruction>,
+    fuel: Option<super::Fuel>,
+    expected: &[Instruction],
+) -> Vec<u32> {
+    let len_instrs = instrs.len() as u32;
+    let positions = super::compact(&mut instrs, fuel).unwrap();
+    instrs.truncate(positions.len_instrs());
+    assert_eq!(instrs, expected);
+    (0..=len_instrs).map(|pos| positions.get(pos)).collect()
+}
+
+#[test]
+fn compact_removed_instructions() {
+    let positions = assert_compacted(
+        vec![
+            br_if(3),
+            br(1),
+            copy_imm32(0, 1),
+            br(-2),
+            br(1),
+            return_reg(0),
+        ],
+        None,
+        &[br_if(2), copy_imm32(0, 1), br(-1), return_reg(0)],
+    );
+    assert_eq!(positions, [0, 1, 1, 2, 3, 3, 4]);
+}
+
+#[test]
+fn compact_nothing() {
+    let mut instrs = [br_if(2), br(2), return_reg(0), return_reg(1)];
+    assert!(super::compact(&mut instrs, None).is_none());
+}
+
+#[test]
+fn keep_branch_table_targets() {
+    let branch_table = Instruction::BranchTable0 {
+        index: Reg::from(0),
+        len_targets: 2,
+    };
+    assert_compacted(
+        vec![br(1), branch_table, br(1), br(1), Instruction::Return],
+        None,
+        &[branch_table, br(1), br(1), Instruction::Return],
+    );
+}
+
+#[test]
+fn compact_fuel() {
+    let blocks = [(1, 0), (3, 2), (5, 0)];
+    let fuel = super::Fuel {
+        base: 1,
+        blocks: &blocks,
+    };
+    assert_compacted(
+        vec![
+            Instruction::consume_fuel(5),
+            br_if(4),
+            Instruction::consume_fuel(3),
+            br(1),
+            copy_imm32(0, 1),
+            br(1),
+            return_reg(0),
+        ],
+        Some(fuel),
+        &[
+            Instruction::consume_fuel(4),
+            br_if(3),
+            Instruction::consume_fuel(2),
+            copy_imm32(0, 1),
+            return_reg(0),
+        ],
+    );
+}
@@ -1,7 +1,7 @@
 use super::Reg;
 use crate::{core::UntypedVal, engine::TranslationError, Error};
 use alloc::{
-    collections::{btree_map, BTreeMap},
+    collections::{btree_map, BTreeMap, BTreeSet},
     vec::Vec,
 };
 use core::{iter::Rev, slice::Iter as SliceIter};
@@ -22,6 +22,8 @@ pub struct FuncLocalConsts {
     idx2const: Vec<UntypedVal>,
     /// The [`Reg`] index for the next allocated function local constant value.
     next_idx: i16,
+    /// The [`Reg`] indices of function local constant values only used by inlined functions.
+    inlined: BTreeSet<Reg>,
 }
 
 impl FuncLocalConsts {
@@ -30,6 +32,7 @@ impl FuncLocalConsts {
         self.const2idx.clear();
         self.idx2const.clear();
         self.next_idx = Self::first_index();
+        self.inlined.clear();
     }
 
     /// The maximum index for [`Reg`] referring to function local constant values.
@@ -57,6 +60,17 @@ impl FuncLocalConsts {
         self.next_idx.abs_diff(Self::first_index())
     }
 
+    /// Returns the number of function local constant values not only used by inlined functions.
+    pub fn len_consts_uninlined(&self) -> u16 {
+        // Note: the inlined constant values are a subset of all constant values.
+        self.len_consts() - self.inlined.len() as u16
+    }
+
+    /// Returns the number of function local constant values that can still be allocated.
+    pub fn remaining(&self) -> usize {
+        usize::from(self.next_idx.abs_diff(Self::last_index()))
+    }
+
     /// Allocates a new constant `value` on the [`FuncLocalConsts`] and returns its identifier.
     ///
     /// # Note
@@ -68,6 +82,37 @@ impl FuncLocalConsts {
     ///
     /// If too many constant values have been allocated for this [`FuncLocalConsts`].
     pub fn alloc(&mut self, value: UntypedVal) -> Result<Reg, Error> {
+        let register = self.alloc_impl(value)?;
+        self.inlined.remove(&register);
+        Ok(register)
+    }
+
+    /// Allocates a new constant `value` used by an inlined function and returns its identifier.
+    ///
+    /// # Note
+    ///
+    /// Same as [`FuncLocalConsts::alloc`] but a newly allocated constant `value` is not
+    /// accounted for by [`FuncLocalConsts::len_consts_uninlined`] unless it is
+    /// allocated via [`FuncLocalConsts::alloc`] later.
+    ///
+    /// # Errors
+    ///
+    /// If too many constant values have been allocated for this [`FuncLocalConsts`].
+    pub fn alloc_inlined(&mut self, value: UntypedVal) -> Result<Reg, Error> {
+        let len_consts = self.len_consts();
+        let register = self.alloc_impl(value)?;
+        if self.len_consts() != len_consts {
+            self.inlined.insert(register);
+        }
+        Ok(register)
+    }
+
+    /// Allocates a new constant `value` on the [`FuncLocalConsts`] and returns its identifier.
+    ///
+    /// # Errors
+    ///
+    /// If too many constant values have been allocated for this [`FuncLocalConsts`].
+    fn alloc_impl(&mut self, value: UntypedVal) -> Result<Reg, Error> {
         if self.next_idx == Self::last_index() {
             return Err(Error::from(TranslationError::TooManyFuncLocalConstValues));
         }
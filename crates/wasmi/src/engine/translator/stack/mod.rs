@@ -125,6 +125,22 @@ impl ValueStack {
         self.consts.len_consts() + self.reg_alloc.len_registers()
     }
 
+    /// Returns the number of function parameters and local variables.
+    pub fn len_locals(&self) -> u16 {
+        self.reg_alloc.len_locals()
+    }
+
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding inlined functions.
+    ///
+    /// # Note
+    ///
+    /// This excludes the frames of inlined functions as well as the
+    /// function local constant values only used by inlined functions.
+    pub fn len_registers_uninlined(&self) -> u16 {
+        // The addition won't overflow since both operands are in the range of `0..i16::MAX`.
+        self.consts.len_consts_uninlined() + self.reg_alloc.len_registers_uninlined()
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
@@ -162,6 +178,20 @@ impl ValueStack {
         self.consts.alloc(value.into())
     }
 
+    /// Allocates a new function local constant value used by an inlined function and returns its [`Reg`].
+    ///
+    /// # Note
+    ///
+    /// Constant values allocated this way are deduplicated and return shared [`Reg`].
+    pub fn alloc_inlined_const(&mut self, value: UntypedVal) -> Result<Reg, Error> {
+        self.consts.alloc_inlined(value)
+    }
+
+    /// Returns the number of function local constant values that can still be allocated.
+    pub fn remaining_consts(&self) -> usize {
+        self.consts.remaining()
+    }
+
     /// Converts a [`TypedProvider`] into a [`Reg`].
     ///
     /// This allocates constant values for [`TypedProvider::Const`].
@@ -385,6 +415,17 @@ impl ValueStack {
         Ok(registers)
     }
 
+    /// Returns a [`RegSpan`] of `n` registers for the frame of an inlined function if possible.
+    ///
+    /// # Note
+    ///
+    /// - The registers are located `skip` registers above the dynamic registers of the [`ValueStack`].
+    /// - This procedure does not push anything onto the [`ValueStack`].
+    /// - Returns `None` if this procedure would allocate more registers than are available.
+    pub fn peek_inlined_frame(&mut self, skip: u16, n: u16) -> Option<RegSpan> {
+        self.reg_alloc.peek_inlined_frame(skip, n)
+    }
+
     /// Finalizes register allocation and allows to defragment the register space.
     pub fn finalize_alloc(&mut self) {
         self.reg_alloc.finalize_alloc()
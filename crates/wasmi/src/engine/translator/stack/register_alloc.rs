@@ -78,6 +78,8 @@ pub struct RegisterAlloc {
     next_dynamic: i16,
     /// The maximum index registered for a dynamically allocated register.
     max_dynamic: i16,
+    /// The maximum index registered for a dynamically allocated register outside of inlined function frames.
+    max_uninlined: i16,
     /// The minimum index registered for a preservation allocated register.
     min_preserve: i16,
     /// The offset for the defragmentation register index.
@@ -138,6 +140,7 @@ impl RegisterAlloc {
         self.len_locals = 0;
         self.next_dynamic = 0;
         self.max_dynamic = 0;
+        self.max_uninlined = 0;
         self.min_preserve = Self::INITIAL_PRESERVATION_INDEX;
         self.defrag_offset = 0;
     }
@@ -188,6 +191,11 @@ impl RegisterAlloc {
         Self::MAX_LEN_LOCALS - self.max_dynamic.abs_diff(self.min_preserve)
     }
 
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding inlined function frames.
+    pub fn len_registers_uninlined(&self) -> u16 {
+        Self::MAX_LEN_LOCALS - self.max_uninlined.abs_diff(self.min_preserve)
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
@@ -213,6 +221,7 @@ impl RegisterAlloc {
         // We can convert `len_locals` to `i16` because it is always without bounds of `0..i16::MAX`.
         self.next_dynamic = self.len_locals as i16;
         self.max_dynamic = self.len_locals as i16;
+        self.max_uninlined = self.len_locals as i16;
         Ok(())
     }
 
@@ -251,6 +260,7 @@ impl RegisterAlloc {
         let reg = Reg::from(self.next_dynamic);
         self.next_dynamic += 1;
         self.max_dynamic = max(self.max_dynamic, self.next_dynamic);
+        self.max_uninlined = max(self.max_uninlined, self.next_dynamic);
         Ok(reg)
     }
 
@@ -273,6 +283,7 @@ impl RegisterAlloc {
             let register = RegSpan::new(Reg::from(this.next_dynamic));
             this.next_dynamic += n;
             this.max_dynamic = max(this.max_dynamic, this.next_dynamic);
+            this.max_uninlined = max(this.max_uninlined, this.next_dynamic);
             Some(register)
         }
         self.assert_alloc_phase();
@@ -280,6 +291,27 @@ impl RegisterAlloc {
             .ok_or_else(|| Error::from(TranslationError::AllocatedTooManyRegisters))
     }
 
+    /// Returns a [`RegSpan`] of `n` registers for the frame of an inlined function if possible.
+    ///
+    /// # Note
+    ///
+    /// - The registers are located `skip` registers above the dynamic allocation stack but not pushed onto it.
+    /// - Returns `None` if not enough registers are available.
+    ///
+    /// # Panics
+    ///
+    /// If the current [`AllocPhase`] is not [`AllocPhase::Alloc`].
+    pub fn peek_inlined_frame(&mut self, skip: u16, n: u16) -> Option<RegSpan> {
+        self.assert_alloc_phase();
+        let start = self.next_dynamic.checked_add_unsigned(skip)?;
+        let end = start.checked_add_unsigned(n)?;
+        if end >= self.min_preserve {
+            return None;
+        }
+        self.max_dynamic = max(self.max_dynamic, end);
+        Some(RegSpan::new(Reg::from(start)))
+    }
+
     /// Pops the top-most dynamically allocated [`Reg`] from the allocation stack.
     ///
     /// # Panics
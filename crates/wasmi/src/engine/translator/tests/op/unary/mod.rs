@@ -5,6 +5,8 @@ mod op;
 
 use super::*;
 use crate::core::{TrapCode, UntypedVal};
+#[cfg(feature = "soft-float")]
+use crate::{Engine, Module};
 use std::fmt::Display;
 use wasm_type::WasmTy;
 
@@ -94,6 +96,44 @@ where
     conversion_imm::<T, T>(wasm_op, input, eval)
 }
 
+/// Asserts that the unary Wasm operator `wasm_op` applied to `input` translates to a NaN constant.
+///
+/// # Note
+///
+/// Used where the sign and payload of the resulting NaN are not specified.
+#[cfg(feature = "soft-float")]
+fn unary_imm_nan<T>(wasm_op: &str, input: T, is_nan: fn(T) -> bool)
+where
+    T: WasmTy + From<UntypedVal>,
+    DisplayWasm<T>: Display,
+{
+    let ty = <T as WasmTy>::NAME;
+    let wasm_input = DisplayWasm::from(input);
+    let wasm = format!(
+        r#"
+        (module
+            (func (result {ty})
+                {ty}.const {wasm_input}
+                {ty}.{wasm_op}
+            )
+        )
+    "#,
+    );
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm).unwrap();
+    let (_, func) = module.internal_funcs().next().unwrap();
+    let result = match engine.resolve_instr(func, 0).unwrap().unwrap() {
+        Instruction::ReturnImm32 { value } => UntypedVal::from(u32::from(value)),
+        Instruction::ReturnF64Imm32 { value } => UntypedVal::from(f64::from(value)),
+        Instruction::ReturnReg { value } if value.is_const() => {
+            engine.get_func_const(func, 0).unwrap().unwrap()
+        }
+        instr => panic!("expected a constant return instruction but found: {instr:?}"),
+    };
+    assert!(is_nan(T::from(result)));
+    assert!(engine.resolve_instr(func, 1).unwrap().is_none());
+}
+
 /// Asserts that the unary Wasm operator `wasm_op` translates properly to a unary Wasmi instruction.
 fn fallible_conversion_imm_err<I, O>(wasm_op: &str, input: I, eval: fn(input: I) -> TrapCode)
 where
@@ -16,6 +16,7 @@ use super::{
 };
 use crate::{
     core::{TrapCode, ValType, F32, F64},
+    debugger::MemoryAccess,
     engine::{
         translator::{AcquiredTarget, Provider},
         BlockType,
@@ -140,6 +141,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_loop(&mut self, block_type: wasmparser::BlockType) -> Self::Output {
+        self.new_basic_block = true;
         let block_type = BlockType::new(block_type, &self.module);
         if !self.is_reachable() {
             // See `visit_block` for rational of tracking unreachable control flow.
@@ -181,6 +183,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         // Note: The fuel instruction for the loop must be encoded after the loop header is
         //       pinned so that loop iterations will properly consume fuel per iteration.
         let consume_fuel = self.make_fuel_instr()?;
+        self.translate_tier_up_counter()?;
         // Finally create the loop control frame.
         self.alloc.control_stack.push_frame(LoopControlFrame::new(
             block_type,
@@ -193,6 +196,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_if(&mut self, block_type: wasmparser::BlockType) -> Self::Output {
+        self.new_basic_block = true;
         let block_type = BlockType::new(block_type, &self.module);
         if !self.is_reachable() {
             // We keep track of unreachable control flow frames so that we
@@ -288,6 +292,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_else(&mut self) -> Self::Output {
+        self.new_basic_block = true;
         let mut frame = match self.alloc.control_stack.pop_frame() {
             ControlFrame::If(frame) => frame,
             ControlFrame::Unreachable(frame) if matches!(frame.kind(), ControlFrameKind::If) => {
@@ -371,6 +376,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_end(&mut self) -> Self::Output {
+        self.new_basic_block = true;
         match self.alloc.control_stack.pop_frame() {
             ControlFrame::Block(frame) => self.translate_end_block(frame),
             ControlFrame::Loop(frame) => self.translate_end_loop(frame),
@@ -388,6 +394,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_br_if(&mut self, relative_depth: u32) -> Self::Output {
         bail_unreachable!(self);
+        self.new_basic_block = true;
         let engine = self.engine().clone();
         let condition = match self.alloc.stack.pop() {
             Provider::Const(condition) => {
@@ -488,6 +495,10 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let results = self.alloc.stack.push_dynamic_n(results.len())?;
         let instr = match self.module.get_engine_func(func_idx) {
             Some(engine_func) => {
+                if self.try_inline_call(engine_func, results)? {
+                    // Case: We inlined the called internal function.
+                    return Ok(());
+                }
                 // Case: We are calling an internal function and can optimize
                 //       this case by using the special instruction for it.
                 match params.len() {
@@ -507,7 +518,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_register_list(&mut self.alloc.stack, &self.alloc.buffer.providers)?;
         Ok(())
     }
 
@@ -2653,23 +2664,23 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_f32_ceil(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_ceil, TypedVal::f32_ceil)
+        self.translate_funary(Instruction::f32_ceil, TypedVal::f32_ceil)
     }
 
     fn visit_f32_floor(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_floor, TypedVal::f32_floor)
+        self.translate_funary(Instruction::f32_floor, TypedVal::f32_floor)
     }
 
     fn visit_f32_trunc(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_trunc, TypedVal::f32_trunc)
+        self.translate_funary(Instruction::f32_trunc, TypedVal::f32_trunc)
     }
 
     fn visit_f32_nearest(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_nearest, TypedVal::f32_nearest)
+        self.translate_funary(Instruction::f32_nearest, TypedVal::f32_nearest)
     }
 
     fn visit_f32_sqrt(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_sqrt, TypedVal::f32_sqrt)
+        self.translate_funary(Instruction::f32_sqrt, TypedVal::f32_sqrt)
     }
 
     fn visit_f32_add(&mut self) -> Self::Output {
@@ -2765,23 +2776,23 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_f64_ceil(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_ceil, TypedVal::f64_ceil)
+        self.translate_funary(Instruction::f64_ceil, TypedVal::f64_ceil)
     }
 
     fn visit_f64_floor(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_floor, TypedVal::f64_floor)
+        self.translate_funary(Instruction::f64_floor, TypedVal::f64_floor)
     }
 
     fn visit_f64_trunc(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_trunc, TypedVal::f64_trunc)
+        self.translate_funary(Instruction::f64_trunc, TypedVal::f64_trunc)
     }
 
     fn visit_f64_nearest(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_nearest, TypedVal::f64_nearest)
+        self.translate_funary(Instruction::f64_nearest, TypedVal::f64_nearest)
     }
 
     fn visit_f64_sqrt(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_sqrt, TypedVal::f64_sqrt)
+        self.translate_funary(Instruction::f64_sqrt, TypedVal::f64_sqrt)
     }
 
     fn visit_f64_add(&mut self) -> Self::Output {
@@ -2929,7 +2940,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_f32_demote_f64(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f32_demote_f64, TypedVal::f32_demote_f64)
+        self.translate_funary(Instruction::f32_demote_f64, TypedVal::f32_demote_f64)
     }
 
     fn visit_f64_convert_i32_s(&mut self) -> Self::Output {
@@ -2949,7 +2960,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_f64_promote_f32(&mut self) -> Self::Output {
-        self.translate_unary(Instruction::f64_promote_f32, TypedVal::f64_promote_f32)
+        self.translate_funary(Instruction::f64_promote_f32, TypedVal::f64_promote_f32)
     }
 
     fn visit_i32_reinterpret_f32(&mut self) -> Self::Output {
@@ -3046,6 +3057,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_memory_init(&mut self, data_index: u32, mem: u32) -> Self::Output {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::Init { memory: mem });
         let memory = index::Memory::from(mem);
         let (dst, src, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3095,6 +3107,10 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_memory_copy(&mut self, dst_mem: u32, src_mem: u32) -> Self::Output {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::Copy {
+            dst: dst_mem,
+            src: src_mem,
+        });
         let dst_memory = index::Memory::from(dst_mem);
         let src_memory = index::Memory::from(src_mem);
         let (dst, src, len) = self.alloc.stack.pop3();
@@ -3139,6 +3155,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_memory_fill(&mut self, mem: u32) -> Self::Output {
         bail_unreachable!(self);
+        self.record_memory_access(MemoryAccess::Fill { memory: mem });
         let memory = index::Memory::from(mem);
         let (dst, value, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -1,4 +1,5 @@
 use super::errors::{
+    ConfigError,
     EnforcedLimitsError,
     FuelError,
     FuncError,
@@ -12,7 +13,10 @@ use super::errors::{
 use crate::{
     core::{HostError, TrapCode},
     engine::{ResumableHostError, TranslationError},
-    module::ReadError,
+    module::{ModuleError, ReadError},
+    TrapDetails,
+    WasmBacktrace,
+    WasmCoreDump,
 };
 use alloc::{boxed::Box, string::String};
 use core::{fmt, fmt::Display};
@@ -24,8 +28,23 @@ use wat::Error as WatError;
 /// The generic Wasmi root error type.
 #[derive(Debug)]
 pub struct Error {
+    /// The underlying kind of the error and its attached information.
+    inner: Box<ErrorInner>,
+}
+
+/// The heap allocated contents of an [`Error`].
+#[derive(Debug)]
+struct ErrorInner {
     /// The underlying kind of the error and its specific information.
-    kind: Box<ErrorKind>,
+    kind: ErrorKind,
+    /// The [`WasmCoreDump`] captured when the error trapped a Wasm execution if any.
+    coredump: Option<Box<WasmCoreDump>>,
+    /// The [`WasmBacktrace`] captured when the error trapped a Wasm execution if any.
+    backtrace: Option<Box<WasmBacktrace>>,
+    /// The [`TrapDetails`] of the fault that caused the trap if any.
+    trap_details: Option<Box<TrapDetails>>,
+    /// The [`ModuleError`] if the error occurred while compiling a Wasm module.
+    module_error: Option<Box<ModuleError>>,
 }
 
 #[test]
@@ -38,7 +57,13 @@ impl Error {
     /// Creates a new [`Error`] from the [`ErrorKind`].
     fn from_kind(kind: ErrorKind) -> Self {
         Self {
-            kind: Box::new(kind),
+            inner: Box::new(ErrorInner {
+                kind,
+                coredump: None,
+                backtrace: None,
+                trap_details: None,
+                module_error: None,
+            }),
         }
     }
 
@@ -75,7 +100,73 @@ impl Error {
 
     /// Returns the [`ErrorKind`] of the [`Error`].
     pub fn kind(&self) -> &ErrorKind {
-        &self.kind
+        &self.inner.kind
+    }
+
+    /// Returns the [`WasmCoreDump`] captured when the [`Error`] trapped a Wasm execution if any.
+    ///
+    /// # Note
+    ///
+    /// Core dumps are only captured if [`Config::coredump_on_trap`] is enabled.
+    ///
+    /// [`Config::coredump_on_trap`]: crate::Config::coredump_on_trap
+    pub fn coredump(&self) -> Option<&WasmCoreDump> {
+        self.inner.coredump.as_deref()
+    }
+
+    /// Attaches the `coredump` of the trapped Wasm execution to the [`Error`].
+    pub(crate) fn set_coredump(&mut self, coredump: WasmCoreDump) {
+        self.inner.coredump = Some(Box::new(coredump));
+    }
+
+    /// Returns the [`WasmBacktrace`] captured when the [`Error`] trapped a Wasm execution if any.
+    ///
+    /// # Note
+    ///
+    /// Backtraces are only captured if [`Config::wasm_backtrace`] is enabled.
+    ///
+    /// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+    pub fn backtrace(&self) -> Option<&WasmBacktrace> {
+        self.inner.backtrace.as_deref()
+    }
+
+    /// Attaches the `backtrace` of the trapped Wasm execution to the [`Error`].
+    pub(crate) fn set_backtrace(&mut self, backtrace: WasmBacktrace) {
+        self.inner.backtrace = Some(Box::new(backtrace));
+    }
+
+    /// Returns the [`TrapDetails`] of the fault that caused the trap if any.
+    ///
+    /// # Note
+    ///
+    /// Wasmi attaches [`TrapDetails`] to traps caused by out of bounds linear memory
+    /// accesses, out of bounds `table.get` or `table.set` accesses and failed indirect calls.
+    pub fn trap_details(&self) -> Option<&TrapDetails> {
+        self.inner.trap_details.as_deref()
+    }
+
+    /// Returns an exclusive reference to the [`TrapDetails`] of the trap if any.
+    pub(crate) fn trap_details_mut(&mut self) -> Option<&mut TrapDetails> {
+        self.inner.trap_details.as_deref_mut()
+    }
+
+    /// Creates a new [`Error`] for the trap described by `details`.
+    #[cold]
+    pub(crate) fn from_trap_details(details: TrapDetails) -> Self {
+        let mut error = Self::from(details.trap_code());
+        error.inner.trap_details = Some(Box::new(details));
+        error
+    }
+
+    /// Returns the [`ModuleError`] if the error occurred while parsing,
+    /// validating or translating a Wasm module.
+    pub fn module_error(&self) -> Option<&ModuleError> {
+        self.inner.module_error.as_deref()
+    }
+
+    /// Attaches the [`ModuleError`] to the [`Error`].
+    pub(crate) fn set_module_error(&mut self, module_error: ModuleError) {
+        self.inner.module_error = Some(Box::new(module_error));
     }
 
     /// Returns a reference to [`TrapCode`] if [`Error`] is a [`TrapCode`].
@@ -98,7 +189,8 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -111,7 +203,8 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -124,15 +217,16 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
     }
 
     pub(crate) fn into_resumable(self) -> Result<ResumableHostError, Error> {
-        if matches!(&*self.kind, ErrorKind::ResumableHost(_)) {
-            let ErrorKind::ResumableHost(error) = *self.kind else {
+        if matches!(&self.inner.kind, ErrorKind::ResumableHost(_)) {
+            let ErrorKind::ResumableHost(error) = self.inner.kind else {
                 unreachable!("asserted that host error is resumable")
             };
             return Ok(error);
@@ -146,7 +240,14 @@ impl std::error::Error for Error {}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.kind, f)
+        Display::fmt(&self.inner.kind, f)?;
+        if let Some(details) = self.trap_details() {
+            write!(f, " ({details})")?;
+        }
+        if let Some(backtrace) = self.backtrace() {
+            write!(f, "\n{backtrace}")?;
+        }
+        Ok(())
     }
 }
 
@@ -195,6 +296,8 @@ pub enum ErrorKind {
     Limits(EnforcedLimitsError),
     /// Encountered for Wasmi bytecode related errors.
     Ir(IrError),
+    /// Encountered when the [`Config`](crate::Config) has contradicting settings.
+    Config(ConfigError),
     /// Encountered an error from the `wat` crate.
     #[cfg(feature = "wat")]
     Wat(WatError),
@@ -265,6 +368,7 @@ impl Display for ErrorKind {
             Self::Limits(error) => Display::fmt(error, f),
             Self::ResumableHost(error) => Display::fmt(error, f),
             Self::Ir(error) => Display::fmt(error, f),
+            Self::Config(error) => Display::fmt(error, f),
             #[cfg(feature = "wat")]
             Self::Wat(error) => Display::fmt(error, f),
         }
@@ -299,6 +403,7 @@ impl_from! {
     impl From<EnforcedLimitsError> for Error::Limits;
     impl From<ResumableHostError> for Error::ResumableHost;
     impl From<IrError> for Error::Ir;
+    impl From<ConfigError> for Error::Config;
 }
 #[cfg(feature = "wat")]
 impl_from! {
@@ -306,7 +411,7 @@ impl_from! {
 }
 
 /// An error that can occur upon `memory.grow` or `table.grow`.
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub enum EntityGrowError {
     /// Usually a [`TrapCode::OutOfFuel`] trap.
     TrapCode(TrapCode),
@@ -0,0 +1,94 @@
+use crate::core::UntypedVal;
+use alloc::sync::Arc;
+use core::{fmt, fmt::Debug};
+
+/// The fuel costs charged for every call to a host function.
+///
+/// Host functions are associated with a [`HostFuncCost`] upon their definition via
+/// [`Linker::func_wrap_with_cost`], [`Linker::func_new_with_cost`],
+/// [`Func::wrap_with_cost`] or [`Func::new_with_cost`].
+///
+/// # Note
+///
+/// - The fuel is charged right before the host function is invoked.
+///   If the [`Store`] does not hold enough fuel the execution traps with
+///   [`TrapCode::OutOfFuel`] and the host function is not invoked at all.
+/// - Nothing is charged if fuel metering is disabled for the [`Engine`].
+///
+/// [`Linker::func_wrap_with_cost`]: crate::Linker::func_wrap_with_cost
+/// [`Linker::func_new_with_cost`]: crate::Linker::func_new_with_cost
+/// [`Func::wrap_with_cost`]: crate::Func::wrap_with_cost
+/// [`Func::new_with_cost`]: crate::Func::new_with_cost
+/// [`Store`]: crate::Store
+/// [`Engine`]: crate::Engine
+/// [`TrapCode::OutOfFuel`]: crate::core::TrapCode::OutOfFuel
+#[derive(Clone)]
+pub struct HostFuncCost {
+    kind: HostFuncCostKind,
+}
+
+/// The underlying kind of a [`HostFuncCost`].
+#[derive(Clone)]
+enum HostFuncCostKind {
+    /// The same amount of fuel is charged for every call.
+    Fixed(u64),
+    /// The fuel charged is computed from the parameters of every call.
+    Dynamic(Arc<HostFuncCostFn>),
+}
+
+/// Computes the fuel costs of a host function call from its parameters.
+type HostFuncCostFn = dyn Fn(&[UntypedVal]) -> u64 + Send + Sync + 'static;
+
+impl Debug for HostFuncCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HostFuncCostKind::Fixed(fuel) => f.debug_tuple("Fixed").field(fuel).finish(),
+            HostFuncCostKind::Dynamic(_) => f.debug_tuple("Dynamic").finish(),
+        }
+    }
+}
+
+impl From<u64> for HostFuncCost {
+    fn from(fuel: u64) -> Self {
+        Self::fixed(fuel)
+    }
+}
+
+impl HostFuncCost {
+    /// Creates a [`HostFuncCost`] that charges `fuel` for every call.
+    pub fn fixed(fuel: u64) -> Self {
+        Self {
+            kind: HostFuncCostKind::Fixed(fuel),
+        }
+    }
+
+    /// Creates a [`HostFuncCost`] that computes the charged fuel from the call parameters.
+    ///
+    /// The closure receives the untyped parameters of the host function call in order.
+    /// This allows to charge costs proportional to the call's inputs, for example
+    /// the length of a byte buffer in linear memory that is passed to the host.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::HostFuncCost;
+    /// // Charges 1 fuel per 64 bytes for a host function with `(ptr: i32, len: i32)` parameters.
+    /// let cost = HostFuncCost::dynamic(|params| u64::from(u32::from(params[1])) / 64);
+    /// ```
+    pub fn dynamic<F>(f: F) -> Self
+    where
+        F: Fn(&[UntypedVal]) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            kind: HostFuncCostKind::Dynamic(Arc::new(f)),
+        }
+    }
+
+    /// Returns the fuel charged for a host function call with `params`.
+    pub(crate) fn fuel_for(&self, params: &[UntypedVal]) -> u64 {
+        match &self.kind {
+            HostFuncCostKind::Fixed(fuel) => *fuel,
+            HostFuncCostKind::Dynamic(f) => f(params),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod caller;
+mod cost;
 mod error;
 mod func_type;
 mod funcref;
@@ -8,6 +9,7 @@ mod typed_func;
 pub(crate) use self::typed_func::CallResultsTuple;
 pub use self::{
     caller::Caller,
+    cost::HostFuncCost,
     error::FuncError,
     func_type::FuncType,
     funcref::FuncRef,
@@ -252,6 +254,12 @@ impl<T> HostFuncTrampolineEntity<T> {
         Self { ty, trampoline }
     }
 
+    /// Attaches the fuel `cost` charged for every call to the host function.
+    pub fn with_cost(mut self, cost: HostFuncCost) -> Self {
+        self.trampoline = self.trampoline.with_cost(cost);
+        self
+    }
+
     /// Returns the [`FuncType`] of the host function.
     pub fn func_type(&self) -> &FuncType {
         &self.ty
@@ -268,6 +276,8 @@ type TrampolineFn<T> =
 
 pub struct TrampolineEntity<T> {
     closure: Arc<TrampolineFn<T>>,
+    /// The fuel charged for every call to the host function if any.
+    cost: Option<HostFuncCost>,
 }
 
 impl<T> Debug for TrampolineEntity<T> {
@@ -284,9 +294,21 @@ impl<T> TrampolineEntity<T> {
     {
         Self {
             closure: Arc::new(trampoline),
+            cost: None,
         }
     }
 
+    /// Attaches the fuel `cost` charged for every call to the host function.
+    pub fn with_cost(mut self, cost: HostFuncCost) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Returns the fuel [`HostFuncCost`] of the host function if any.
+    pub fn cost(&self) -> Option<&HostFuncCost> {
+        self.cost.as_ref()
+    }
+
     /// Calls the host function trampoline with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
@@ -305,6 +327,7 @@ impl<T> Clone for TrampolineEntity<T> {
     fn clone(&self) -> Self {
         Self {
             closure: self.closure.clone(),
+            cost: self.cost.clone(),
         }
     }
 }
@@ -349,26 +372,53 @@ impl Func {
     ///   created using this constructor have runtime overhead for every invocation that
     ///   can be avoided by using [`Func::wrap`].
     pub fn new<T>(
-        mut ctx: impl AsContextMut<Data = T>,
+        ctx: impl AsContextMut<Data = T>,
         ty: FuncType,
         func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync + 'static,
     ) -> Self {
-        let host_func = HostFuncTrampolineEntity::new(ty.clone(), func);
-        let trampoline = host_func.trampoline().clone();
-        let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
-        let host_func = HostFuncEntity::new(ctx.as_context().engine(), &ty, func);
-        ctx.as_context_mut()
-            .store
-            .inner
-            .alloc_func(host_func.into())
+        let host_func = HostFuncTrampolineEntity::new(ty, func);
+        Self::alloc_host_func(ctx, host_func)
+    }
+
+    /// Creates a new [`Func`] with the given arguments that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Func::new`] and [`HostFuncCost`].
+    pub fn new_with_cost<T>(
+        ctx: impl AsContextMut<Data = T>,
+        ty: FuncType,
+        cost: impl Into<HostFuncCost>,
+        func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        let host_func = HostFuncTrampolineEntity::new(ty, func).with_cost(cost.into());
+        Self::alloc_host_func(ctx, host_func)
     }
 
     /// Creates a new host function from the given closure.
     pub fn wrap<T, Params, Results>(
-        mut ctx: impl AsContextMut<Data = T>,
+        ctx: impl AsContextMut<Data = T>,
         func: impl IntoFunc<T, Params, Results>,
     ) -> Self {
         let host_func = HostFuncTrampolineEntity::wrap(func);
+        Self::alloc_host_func(ctx, host_func)
+    }
+
+    /// Creates a new host function from the given closure that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Func::wrap`] and [`HostFuncCost`].
+    pub fn wrap_with_cost<T, Params, Results>(
+        ctx: impl AsContextMut<Data = T>,
+        cost: impl Into<HostFuncCost>,
+        func: impl IntoFunc<T, Params, Results>,
+    ) -> Self {
+        let host_func = HostFuncTrampolineEntity::wrap(func).with_cost(cost.into());
+        Self::alloc_host_func(ctx, host_func)
+    }
+
+    /// Allocates the host function trampoline `host_func` in the store as new [`Func`].
+    fn alloc_host_func<T>(
+        mut ctx: impl AsContextMut<Data = T>,
+        host_func: HostFuncTrampolineEntity<T>,
+    ) -> Self {
         let ty = host_func.func_type();
         let trampoline = host_func.trampoline().clone();
         let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
@@ -0,0 +1,92 @@
+//! Spans and events emitted via the `tracing` crate.
+//!
+//! # Note
+//!
+//! This only exists if the `tracing` crate feature is enabled.
+
+use crate::{func::FuncEntity, module::FuncIdx, Error, Func, Module, Store};
+use tracing::{field, Span};
+
+/// Returns the [`Span`] of the compilation of a Wasm module.
+///
+/// The `module` and `funcs` fields are recorded via [`record_module`].
+pub fn module_span() -> Span {
+    tracing::info_span!(
+        "compile_module",
+        module = field::Empty,
+        funcs = field::Empty
+    )
+}
+
+/// Records the name and number of functions of the compiled `module` in `span`.
+pub fn record_module(span: &Span, module: &Module) {
+    if let Some(name) = module.name() {
+        span.record("module", name);
+    }
+    span.record("funcs", module.len_funcs());
+}
+
+/// Returns the [`Span`] of the translation of the function at `func_index` with `size` bytes.
+pub fn translate_span(func_index: FuncIdx, size: usize, lazy: bool) -> Span {
+    tracing::debug_span!(
+        "translate_func",
+        func_index = func_index.into_u32(),
+        size,
+        lazy
+    )
+}
+
+/// Returns the [`Span`] of the instantiation of `module`.
+pub fn instantiate_span(module: &Module) -> Span {
+    tracing::info_span!("instantiate", module = module.name())
+}
+
+/// Returns the [`Span`] of a call to `func`.
+///
+/// The `fuel_consumed` field is recorded via [`finish_call`].
+pub fn call_span<T>(store: &Store<T>, func: &Func) -> Span {
+    let span = tracing::info_span!(
+        "call",
+        module = field::Empty,
+        func = field::Empty,
+        fuel_consumed = field::Empty
+    );
+    if span.is_disabled() {
+        return span;
+    }
+    match store.inner.resolve_func(func) {
+        FuncEntity::Wasm(wasm_func) => {
+            let (module, name) = store.engine().func_names(wasm_func.func_body());
+            if let Some(module) = module {
+                span.record("module", &*module);
+            }
+            if let Some(name) = name {
+                span.record("func", &*name);
+            }
+        }
+        FuncEntity::Host(_) => {
+            span.record("func", "<host>");
+        }
+    }
+    span
+}
+
+/// Records the fuel consumed by a call in `span` and emits an event if it trapped.
+///
+/// The `fuel` is the remaining fuel of the `store` before the call if fuel metering is enabled.
+pub fn finish_call<T>(span: &Span, store: &Store<T>, fuel: Option<u64>, error: Option<&Error>) {
+    if span.is_disabled() {
+        return;
+    }
+    if let (Some(before), Ok(after)) = (fuel, store.get_fuel()) {
+        span.record("fuel_consumed", before.saturating_sub(after));
+    }
+    if let Some(error) = error {
+        tracing::debug!(parent: span, %error, "call trapped");
+    }
+}
+
+/// Returns the [`Span`] of a call to a host function with `params` and `results`.
+pub fn host_call_span(params: u16, results: u16) -> Span {
+    tracing::debug_span!("host_call", params, results)
+}
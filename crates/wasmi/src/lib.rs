@@ -79,18 +79,31 @@ extern crate std;
 #[macro_use]
 mod foreach_tuple;
 
+mod backtrace;
+mod coredump;
+mod coverage;
+mod debugger;
+#[cfg(feature = "dwarf")]
+mod dwarf;
 mod engine;
 mod error;
 mod externref;
 mod func;
 mod global;
 mod instance;
+#[cfg(feature = "tracing")]
+mod instrument;
 mod limits;
 mod linker;
 mod memory;
 mod module;
+mod profiler;
+mod stats;
 mod store;
 mod table;
+#[cfg(feature = "trace")]
+mod trace;
+mod trap_details;
 mod value;
 
 /// Definitions from the `wasmi_core` crate.
@@ -108,26 +121,44 @@ use wasmi_ir as ir;
 /// Defines some errors that may occur upon interaction with Wasmi.
 pub mod errors {
     pub use super::{
-        engine::EnforcedLimitsError,
+        engine::{ConfigError, EnforcedLimitsError},
         error::ErrorKind,
         func::FuncError,
         global::GlobalError,
         ir::Error as IrError,
         linker::LinkerError,
         memory::MemoryError,
-        module::{InstantiationError, ReadError},
+        module::{InstantiationError, ModuleError, ModuleErrorKind, ModuleSection, ReadError},
         store::FuelError,
         table::TableError,
     };
 }
 
+#[cfg(feature = "trace")]
+pub use self::trace::{TraceEvent, TraceInstr, TraceRegisters};
 pub use self::{
+    backtrace::{BacktraceFrame, SourceFrame, SourceLocation, WasmBacktrace},
+    coredump::{CoreDumpFrame, WasmCoreDump},
+    coverage::{BlockCoverage, Coverage, FuncCoverage},
+    debugger::{
+        Breakpoints,
+        DebugAction,
+        DebugContext,
+        DebugFrame,
+        DebugLocation,
+        Debugger,
+        WatchKind,
+        WatchpointHit,
+        Watchpoints,
+    },
     engine::{
         CompilationMode,
         Config,
         EnforcedLimits,
         Engine,
         EngineWeak,
+        ModuleCacheEviction,
+        OptimizationLevel,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
@@ -141,6 +172,7 @@ pub use self::{
         Func,
         FuncRef,
         FuncType,
+        HostFuncCost,
         IntoFunc,
         TypedFunc,
         WasmParams,
@@ -165,8 +197,11 @@ pub use self::{
         ModuleImportsIter,
         Read,
     },
+    profiler::{FuncStats, Profile, ProfileFunc, ProfileStack},
+    stats::{EngineStats, StoreStats},
     store::{AsContext, AsContextMut, CallHook, Store, StoreContext, StoreContextMut},
     table::{Table, TableType},
+    trap_details::{TrapDetails, TrapFault},
     value::Val,
 };
 use self::{
@@ -15,6 +15,7 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    HostFuncCost,
     Instance,
     InstancePre,
     IntoFunc,
@@ -477,6 +478,49 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_new`] and [`HostFuncCost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_new_with_cost(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        cost: impl Into<HostFuncCost>,
+        func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        self.ensure_undefined(module, name)?;
+        let func = HostFuncTrampolineEntity::new(ty, func).with_cost(cost.into());
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
+    /// Creates a new named [`Func::wrap`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_wrap`] and [`HostFuncCost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_wrap_with_cost<Params, Args>(
+        &mut self,
+        module: &str,
+        name: &str,
+        cost: impl Into<HostFuncCost>,
+        func: impl IntoFunc<T, Params, Args>,
+    ) -> Result<&mut Self, LinkerError> {
+        self.ensure_undefined(module, name)?;
+        let func = HostFuncTrampolineEntity::wrap(func).with_cost(cost.into());
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Looks up a defined [`Extern`] by name in this [`Linker`].
     ///
     /// - Returns `None` if this name was not previously defined in this [`Linker`].
@@ -585,6 +629,8 @@ impl<T> Linker<T> {
         module: &Module,
     ) -> Result<InstancePre, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
+        #[cfg(feature = "tracing")]
+        let _span = crate::instrument::instantiate_span(module).entered();
         // TODO: possibly add further resource limtation here on number of externals.
         // Not clear that user can't import the same external lots of times to inflate this.
         let externals = module
@@ -800,6 +846,53 @@ impl<T> LinkerBuilder<state::Constructing, T> {
         self.inner_mut().func_wrap(module, name, func)?;
         Ok(self)
     }
+
+    /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_new_with_cost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`LinkerBuilder`] has already created a [`Linker`] using [`LinkerBuilder::finish`].
+    pub fn func_new_with_cost(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        cost: impl Into<HostFuncCost>,
+        func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        self.inner_mut()
+            .func_new_with_cost(module, name, ty, cost, func)?;
+        Ok(self)
+    }
+
+    /// Creates a new named [`Func::wrap`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_wrap_with_cost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`LinkerBuilder`] has already created a [`Linker`] using [`LinkerBuilder::finish`].
+    pub fn func_wrap_with_cost<Params, Args>(
+        &mut self,
+        module: &str,
+        name: &str,
+        cost: impl Into<HostFuncCost>,
+        func: impl IntoFunc<T, Params, Args>,
+    ) -> Result<&mut Self, LinkerError> {
+        self.inner_mut()
+            .func_wrap_with_cost(module, name, cost, func)?;
+        Ok(self)
+    }
 }
 
 /// Internal [`Linker`] implementation.
@@ -972,6 +1065,47 @@ impl<T> LinkerInner<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_new_with_cost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_new_with_cost(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        cost: impl Into<HostFuncCost>,
+        func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        let func = HostFuncTrampolineEntity::new(ty, func).with_cost(cost.into());
+        let key = self.new_import_key(module, name);
+        self.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
+    /// Creates a new named [`Func::wrap`]-style host [`Func`] for this [`Linker`] that charges `cost` fuel per call.
+    ///
+    /// For more information see [`Linker::func_wrap_with_cost`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_wrap_with_cost<Params, Args>(
+        &mut self,
+        module: &str,
+        name: &str,
+        cost: impl Into<HostFuncCost>,
+        func: impl IntoFunc<T, Params, Args>,
+    ) -> Result<&mut Self, LinkerError> {
+        let func = HostFuncTrampolineEntity::wrap(func).with_cost(cost.into());
+        let key = self.new_import_key(module, name);
+        self.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Looks up a [`Definition`] by name in this [`Linker`].
     ///
     /// Returns `None` if this name was not previously defined in this [`Linker`].
@@ -253,6 +253,8 @@ impl MemoryEntity {
             if let Some(limiter) = limiter.as_resource_limiter() {
                 limiter.memory_grow_failed(&MemoryError::OutOfBoundsGrowth)
             }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(error = ?err, "memory growth failed");
             Err(err)
         }
 
@@ -307,6 +309,12 @@ impl MemoryEntity {
         // 3. There is enough fuel for the operation.
         self.bytes.grow(new_size);
         self.current_pages = new_pages;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            old_pages = u32::from(current_pages),
+            new_pages = u32::from(new_pages),
+            "memory grown"
+        );
         Ok(u32::from(current_pages))
     }
 
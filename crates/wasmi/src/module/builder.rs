@@ -17,10 +17,11 @@ use super::{
     ModuleHeaderInner,
     ModuleImports,
     ModuleInner,
+    ModuleNames,
 };
 use crate::{
     collections::Map,
-    engine::{DedupFuncType, EngineFuncSpan},
+    engine::{DedupFuncType, EngineFuncSpan, ModuleSymbols},
     Engine,
     Error,
     FuncType,
@@ -30,6 +31,9 @@ use crate::{
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 
+#[cfg(feature = "dwarf")]
+use crate::dwarf::DwarfInfo;
+
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
 pub struct ModuleBuilder {
@@ -53,6 +57,8 @@ pub struct ModuleHeaderBuilder {
     pub start: Option<FuncIdx>,
     pub engine_funcs: EngineFuncSpan,
     pub element_segments: Box<[ElementSegment]>,
+    #[cfg(feature = "dwarf")]
+    pub code_offset: u32,
 }
 
 impl ModuleHeaderBuilder {
@@ -71,6 +77,8 @@ impl ModuleHeaderBuilder {
             start: None,
             engine_funcs: EngineFuncSpan::default(),
             element_segments: Box::from([]),
+            #[cfg(feature = "dwarf")]
+            code_offset: 0,
         }
     }
 
@@ -90,6 +98,8 @@ impl ModuleHeaderBuilder {
                 start: self.start,
                 engine_funcs: self.engine_funcs,
                 element_segments: self.element_segments,
+                #[cfg(feature = "dwarf")]
+                code_offset: self.code_offset,
             }),
         }
     }
@@ -409,12 +419,34 @@ impl ModuleBuilder {
 
     /// Finishes construction of the WebAssembly [`Module`].
     pub fn finish(self, engine: &Engine) -> Module {
+        let custom_sections = self.custom_sections.finish();
+        let names = Arc::new(ModuleNames::new(&custom_sections));
+        #[cfg(feature = "dwarf")]
+        let dwarf = DwarfInfo::new(self.header.inner.code_offset, &custom_sections).map(Arc::new);
+        #[cfg(feature = "tracing")]
+        let len_func_imports = u32::try_from(self.header.inner.imports.len_funcs())
+            .unwrap_or_else(|_| panic!("too many imported functions"));
+        let symbols = ModuleSymbols {
+            names: names.clone(),
+            #[cfg(feature = "tracing")]
+            len_func_imports,
+            #[cfg(feature = "dwarf")]
+            dwarf: dwarf.clone(),
+        };
+        if symbols.has_symbols() {
+            engine.register_symbols(self.header.inner.engine_funcs, symbols);
+        }
+        #[cfg(feature = "std")]
+        engine.compile_in_background(self.header.inner.engine_funcs);
         Module {
+            engine: engine.clone(),
             inner: Arc::new(ModuleInner {
-                engine: engine.clone(),
                 header: self.header,
                 data_segments: self.data_segments.finish(),
-                custom_sections: self.custom_sections.finish(),
+                custom_sections,
+                names,
+                #[cfg(feature = "dwarf")]
+                dwarf,
             }),
         }
     }
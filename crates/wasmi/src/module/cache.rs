@@ -0,0 +1,147 @@
+use super::{Module, ModuleInner};
+use crate::{engine::ModuleCacheEviction, Config, Engine};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+/// A cache of compiled Wasm [`Module`]s of an [`Engine`] keyed by their Wasm bytecode.
+///
+/// # Note
+///
+/// - Cached [`Module`]s are looked up by a hash of their Wasm bytecode which is
+///   then compared byte by byte. Therefore hash collisions never yield wrong [`Module`]s.
+/// - The [`Config`] of an [`Engine`] never changes. Therefore all cached [`Module`]s
+///   have been compiled with the same [`Config`] options.
+#[derive(Debug)]
+pub struct ModuleCache {
+    /// The cached [`Module`]s.
+    entries: Vec<CacheEntry>,
+    /// The maximum number of cached [`Module`]s.
+    capacity: usize,
+    /// The policy to evict a cached [`Module`] once the [`ModuleCache`] is full.
+    eviction: ModuleCacheEviction,
+    /// A logical clock ordering insertions and uses of cached [`Module`]s.
+    clock: u64,
+}
+
+/// A [`Module`] cached by the [`ModuleCache`].
+#[derive(Debug)]
+struct CacheEntry {
+    /// The hash of the Wasm bytecode of the [`Module`].
+    hash: u64,
+    /// The Wasm bytecode of the [`Module`].
+    wasm: Box<[u8]>,
+    /// Is `true` if the Wasm bytecode has been validated.
+    validated: bool,
+    /// The cached [`Module`] without its [`Engine`].
+    module: Arc<ModuleInner>,
+    /// The time the [`Module`] was cached.
+    inserted: u64,
+    /// The time the [`Module`] was last used.
+    used: u64,
+}
+
+impl ModuleCache {
+    /// Creates a new [`ModuleCache`] configured by `config`.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity: config.cached_modules(),
+            eviction: config.get_module_cache_eviction(),
+            clock: 0,
+        }
+    }
+
+    /// Returns `true` if the [`ModuleCache`] caches [`Module`]s.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Returns the number of cached [`Module`]s.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the next time of the logical clock.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Returns the index of the entry caching `wasm` if any.
+    ///
+    /// Only considers entries with validated Wasm bytecode if `validated` is `true`.
+    fn find(&self, hash: u64, wasm: &[u8], validated: bool) -> Option<usize> {
+        self.entries.iter().position(|entry| {
+            entry.hash == hash && (entry.validated || !validated) && *entry.wasm == *wasm
+        })
+    }
+
+    /// Returns the cached [`Module`] of `engine` compiled from `wasm` if any.
+    ///
+    /// Only returns [`Module`]s with validated Wasm bytecode if `validated` is `true`.
+    pub fn get(&mut self, engine: &Engine, wasm: &[u8], validated: bool) -> Option<Module> {
+        let index = self.find(hash(wasm), wasm, validated)?;
+        let used = self.tick();
+        let entry = &mut self.entries[index];
+        entry.used = used;
+        Some(Module {
+            engine: engine.clone(),
+            inner: entry.module.clone(),
+        })
+    }
+
+    /// Caches the `module` compiled from `wasm`.
+    ///
+    /// Evicts a cached [`Module`] according to the [`ModuleCacheEviction`] policy if the [`ModuleCache`] is full.
+    pub fn insert(&mut self, wasm: &[u8], validated: bool, module: &Module) {
+        if !self.is_enabled() {
+            return;
+        }
+        let hash = hash(wasm);
+        if let Some(index) = self.find(hash, wasm, false) {
+            if self.entries[index].validated || !validated {
+                // Note: another thread compiled and cached the same Wasm bytecode concurrently.
+                return;
+            }
+            // Note: the validated `module` supersedes the cached unvalidated one.
+            self.entries.swap_remove(index);
+        }
+        if self.entries.len() >= self.capacity {
+            self.evict();
+        }
+        let time = self.tick();
+        self.entries.push(CacheEntry {
+            hash,
+            wasm: wasm.into(),
+            validated,
+            module: module.inner.clone(),
+            inserted: time,
+            used: time,
+        });
+    }
+
+    /// Evicts a cached [`Module`] according to the [`ModuleCacheEviction`] policy.
+    fn evict(&mut self) {
+        let eviction = self.eviction;
+        let oldest = self
+            .entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| match eviction {
+                ModuleCacheEviction::LeastRecentlyUsed => entry.used,
+                ModuleCacheEviction::FirstInFirstOut => entry.inserted,
+            })
+            .map(|(index, _)| index);
+        if let Some(index) = oldest {
+            self.entries.swap_remove(index);
+        }
+    }
+}
+
+/// Returns the 64-bit FNV-1a hash of the Wasm bytecode `wasm`.
+fn hash(wasm: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01B3;
+    wasm.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
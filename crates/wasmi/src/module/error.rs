@@ -0,0 +1,261 @@
+use crate::{engine::EnforcedLimitsError, errors::ErrorKind, Error};
+use core::fmt::{self, Display};
+use wasmparser::Payload;
+
+/// Structured information about an error that occurred while parsing,
+/// validating or translating a Wasm module.
+///
+/// Accessible via [`Error::module_error`] for errors returned by
+/// [`Module::new`] and [`Module::validate`] and their variants.
+///
+/// [`Error::module_error`]: crate::Error::module_error
+/// [`Module::new`]: crate::Module::new
+/// [`Module::validate`]: crate::Module::validate
+#[derive(Debug, Clone)]
+pub struct ModuleError {
+    /// The kind of the error.
+    kind: ModuleErrorKind,
+    /// The Wasm section in which the error occurred if known.
+    section: Option<ModuleSection>,
+    /// The byte offset of the error within the Wasm binary if known.
+    offset: Option<usize>,
+    /// The index of the function in which the error occurred if any.
+    func_index: Option<u32>,
+}
+
+/// The kind of a [`ModuleError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ModuleErrorKind {
+    /// The Wasm byte stream could not be read.
+    Read,
+    /// The Wasm binary is malformed or failed to validate.
+    Wasm,
+    /// The Wasm binary could not be translated to Wasmi bytecode.
+    Translation,
+    /// The Wasm binary exceeds an enforced limit.
+    Limits(EnforcedLimitsError),
+}
+
+/// A section of a Wasm binary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ModuleSection {
+    /// A custom section.
+    Custom,
+    /// The type section.
+    Type,
+    /// The import section.
+    Import,
+    /// The function section.
+    Function,
+    /// The table section.
+    Table,
+    /// The memory section.
+    Memory,
+    /// The global section.
+    Global,
+    /// The export section.
+    Export,
+    /// The start section.
+    Start,
+    /// The element section.
+    Element,
+    /// The data count section.
+    DataCount,
+    /// The code section.
+    Code,
+    /// The data section.
+    Data,
+}
+
+impl ModuleError {
+    /// Returns the kind of the [`ModuleError`].
+    pub fn kind(&self) -> ModuleErrorKind {
+        self.kind
+    }
+
+    /// Returns the Wasm section in which the error occurred if known.
+    pub fn section(&self) -> Option<ModuleSection> {
+        self.section
+    }
+
+    /// Returns the byte offset of the error within the Wasm binary if known.
+    ///
+    /// # Note
+    ///
+    /// If the exact location of the error is unknown this is the offset of
+    /// the function body or section in which the error occurred.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Returns the index of the function in which the error occurred if any.
+    ///
+    /// # Note
+    ///
+    /// The function index space includes imported functions.
+    pub fn func_index(&self) -> Option<u32> {
+        self.func_index
+    }
+}
+
+impl Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        if let Some(section) = self.section {
+            write!(f, "{section} section")?;
+            sep = ", ";
+        }
+        if let Some(func_index) = self.func_index {
+            write!(f, "{sep}func {func_index}")?;
+            sep = ", ";
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "{sep}offset {offset:#x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ModuleErrorKind {
+    /// Returns the [`ModuleErrorKind`] for `kind` if it belongs to a Wasm module.
+    fn from_error_kind(kind: &ErrorKind) -> Option<Self> {
+        let kind = match kind {
+            ErrorKind::Read(_) => Self::Read,
+            ErrorKind::Wasm(_) => Self::Wasm,
+            ErrorKind::Translation(_) | ErrorKind::Ir(_) => Self::Translation,
+            ErrorKind::Limits(error) => Self::Limits(*error),
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+impl ModuleSection {
+    /// Returns the [`ModuleSection`] of `payload` if any.
+    fn from_payload(payload: &Payload) -> Option<Self> {
+        let section = match payload {
+            Payload::CustomSection(_) => Self::Custom,
+            Payload::TypeSection(_) => Self::Type,
+            Payload::ImportSection(_) => Self::Import,
+            Payload::FunctionSection(_) => Self::Function,
+            Payload::TableSection(_) => Self::Table,
+            Payload::MemorySection(_) => Self::Memory,
+            Payload::GlobalSection(_) => Self::Global,
+            Payload::ExportSection(_) => Self::Export,
+            Payload::StartSection { .. } => Self::Start,
+            Payload::ElementSection(_) => Self::Element,
+            Payload::DataCountSection { .. } => Self::DataCount,
+            Payload::CodeSectionStart { .. } | Payload::CodeSectionEntry(_) => Self::Code,
+            Payload::DataSection(_) => Self::Data,
+            _ => return None,
+        };
+        Some(section)
+    }
+}
+
+impl Display for ModuleSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Custom => "custom",
+            Self::Type => "type",
+            Self::Import => "import",
+            Self::Function => "function",
+            Self::Table => "table",
+            Self::Memory => "memory",
+            Self::Global => "global",
+            Self::Export => "export",
+            Self::Start => "start",
+            Self::Element => "element",
+            Self::DataCount => "data count",
+            Self::Code => "code",
+            Self::Data => "data",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The position of a parser within a Wasm binary used to create [`ModuleError`]s.
+#[derive(Debug, Default)]
+pub struct ParsePosition {
+    /// The currently processed Wasm section if any.
+    section: Option<ModuleSection>,
+    /// The offset of the currently processed Wasm section or function body.
+    offset: Option<usize>,
+    /// The index of the currently processed function if any.
+    func_index: Option<u32>,
+    /// The number of remaining code section entries.
+    remaining_funcs: u32,
+}
+
+impl ParsePosition {
+    /// Updates the [`ParsePosition`] before parsing the next `payload`.
+    ///
+    /// # Note
+    ///
+    /// Errors while parsing the next payload belong to the code section
+    /// only if not all of its entries have been parsed, yet.
+    pub fn leave_payload(&mut self) {
+        if self.remaining_funcs == 0 {
+            self.section = None;
+            self.offset = None;
+        }
+        self.func_index = None;
+    }
+
+    /// Updates the [`ParsePosition`] upon processing the parsed `payload`.
+    pub fn enter_payload(&mut self, payload: &Payload) {
+        self.section = ModuleSection::from_payload(payload);
+        self.offset = payload.as_section().map(|(_, range)| range.start);
+        match payload {
+            Payload::CodeSectionStart { count, .. } => {
+                self.remaining_funcs = *count;
+            }
+            Payload::CodeSectionEntry(body) => {
+                self.offset = Some(body.range().start);
+                self.remaining_funcs = self.remaining_funcs.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Updates the [`ParsePosition`] upon processing the function at `func_index`.
+    pub fn enter_func(&mut self, func_index: u32) {
+        self.func_index = Some(func_index);
+    }
+
+    /// Restores the [`ParsePosition`] of the code section entry at `offset` for the function at `func_index`.
+    ///
+    /// # Note
+    ///
+    /// Used to report errors of function bodies that are translated after parsing the code section.
+    #[cfg(feature = "parallel")]
+    pub fn enter_code_entry(&mut self, offset: usize, func_index: u32) {
+        self.section = Some(ModuleSection::Code);
+        self.offset = Some(offset);
+        self.func_index = Some(func_index);
+    }
+
+    /// Attaches a [`ModuleError`] for the [`ParsePosition`] to `error` if it belongs to a Wasm module.
+    #[cold]
+    pub fn error(&self, mut error: Error) -> Error {
+        if error.module_error().is_some() {
+            return error;
+        }
+        let Some(kind) = ModuleErrorKind::from_error_kind(error.kind()) else {
+            return error;
+        };
+        let offset = match error.kind() {
+            ErrorKind::Wasm(error) => Some(error.offset()),
+            _ => self.offset,
+        };
+        error.set_module_error(ModuleError {
+            kind,
+            section: self.section,
+            offset,
+            func_index: self.func_index,
+        });
+        error
+    }
+}
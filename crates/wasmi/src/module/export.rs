@@ -118,6 +118,7 @@ pub struct ModuleExportsIter<'module> {
 pub struct ExportType<'module> {
     name: &'module str,
     ty: ExternType,
+    debug_name: Option<&'module str>,
 }
 
 impl<'module> ExportType<'module> {
@@ -130,6 +131,16 @@ impl<'module> ExportType<'module> {
     pub fn ty(&self) -> &ExternType {
         &self.ty
     }
+
+    /// Returns the name of the exported item in the `name` custom section if any.
+    ///
+    /// # Note
+    ///
+    /// This is the name of the item within the Wasm module, e.g. the name
+    /// of the exported function in its source code, and may differ from [`ExportType::name`].
+    pub fn debug_name(&self) -> Option<&'module str> {
+        self.debug_name
+    }
 }
 
 impl<'module> ModuleExportsIter<'module> {
@@ -148,7 +159,12 @@ impl<'module> Iterator for ModuleExportsIter<'module> {
     fn next(&mut self) -> Option<Self::Item> {
         self.exports.next().map(|(name, idx)| {
             let ty = self.module.get_extern_type(*idx);
-            ExportType { name, ty }
+            let debug_name = self.module.inner.names.extern_item(*idx);
+            ExportType {
+                name,
+                ty,
+                debug_name,
+            }
         })
     }
 }
@@ -42,6 +42,8 @@ impl InstancePre {
     /// If the `start` function is invalid albeit successful validation.
     pub fn start(self, mut context: impl AsContextMut) -> Result<Instance, Error> {
         let opt_start_index = self.start_fn();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("start", start_func = opt_start_index).entered();
         context
             .as_context_mut()
             .store
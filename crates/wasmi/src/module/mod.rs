@@ -1,38 +1,46 @@
 mod builder;
+mod cache;
 mod custom_section;
 mod data;
 mod element;
+mod error;
 mod export;
 mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+mod names;
 mod parser;
 mod read;
 pub(crate) mod utils;
 
 use self::{
     builder::ModuleBuilder,
-    custom_section::{CustomSections, CustomSectionsBuilder},
+    custom_section::CustomSectionsBuilder,
     export::ExternIdx,
     global::Global,
     import::{ExternTypeIdx, Import},
     parser::ModuleParser,
 };
+pub(crate) use self::{
+    cache::ModuleCache,
+    custom_section::CustomSections,
+    data::{DataSegment, DataSegments, InitDataSegment, PassiveDataSegmentBytes},
+    element::{ElementSegment, ElementSegmentKind},
+    error::ParsePosition,
+    init_expr::ConstExpr,
+    names::ModuleNames,
+    utils::WasmiValueType,
+};
 pub use self::{
     custom_section::{CustomSection, CustomSectionsIter},
+    error::{ModuleError, ModuleErrorKind, ModuleSection},
     export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
     read::{Read, ReadError},
 };
-pub(crate) use self::{
-    data::{DataSegment, DataSegments, InitDataSegment, PassiveDataSegmentBytes},
-    element::{ElementSegment, ElementSegmentKind},
-    init_expr::ConstExpr,
-    utils::WasmiValueType,
-};
 use crate::{
     collections::Map,
     engine::{DedupFuncType, EngineFunc, EngineFuncSpan, EngineFuncSpanIter, EngineWeak},
@@ -44,23 +52,37 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, format, string::String, sync::Arc};
 use core::{iter, slice::Iter as SliceIter};
 use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
 
+#[cfg(feature = "dwarf")]
+use crate::{dwarf::DwarfInfo, SourceFrame};
+#[cfg(feature = "dwarf")]
+use alloc::vec::Vec;
+
 /// A parsed and validated WebAssembly module.
 #[derive(Debug, Clone)]
 pub struct Module {
+    engine: Engine,
     inner: Arc<ModuleInner>,
 }
 
 /// The internal data of a [`Module`].
+///
+/// # Note
+///
+/// This does not own its [`Engine`] so that the [`Engine`] can cache it.
 #[derive(Debug)]
 struct ModuleInner {
-    engine: Engine,
     header: ModuleHeader,
     data_segments: DataSegments,
     custom_sections: CustomSections,
+    /// The names of the `name` custom section of the Wasm module.
+    names: Arc<ModuleNames>,
+    /// The DWARF debug information of the Wasm module if any.
+    #[cfg(feature = "dwarf")]
+    dwarf: Option<Arc<DwarfInfo>>,
 }
 
 /// A parsed and validated WebAssembly module header.
@@ -83,6 +105,9 @@ struct ModuleHeaderInner {
     start: Option<FuncIdx>,
     engine_funcs: EngineFuncSpan,
     element_segments: Box<[ElementSegment]>,
+    /// The offset of the contents of the Wasm code section within the Wasm binary.
+    #[cfg(feature = "dwarf")]
+    code_offset: u32,
 }
 
 impl ModuleHeader {
@@ -204,6 +229,8 @@ impl Module {
     /// - This parses, validates and translates the buffered Wasm bytecode.
     /// - The `wasm` may be encoded as WebAssembly binary (`.wasm`) or as
     ///   WebAssembly text format (`.wat`).
+    /// - Returns a cached [`Module`] for the same Wasm bytecode if the `engine`
+    ///   caches modules as configured by [`Config::set_cached_modules`].
     ///
     /// # Errors
     ///
@@ -213,11 +240,17 @@ impl Module {
     /// - If Wasmi cannot translate the Wasm bytecode.
     ///
     /// [`Config`]: crate::Config
+    /// [`Config::set_cached_modules`]: crate::Config::set_cached_modules
     pub fn new(engine: &Engine, wasm: impl AsRef<[u8]>) -> Result<Self, Error> {
         let wasm = wasm.as_ref();
         #[cfg(feature = "wat")]
         let wasm = &wat::parse_bytes(wasm)?[..];
-        ModuleParser::new(engine).parse_buffered(wasm)
+        if let Some(module) = engine.get_cached_module(wasm, true) {
+            return Ok(module);
+        }
+        let module = ModuleParser::new(engine).parse_buffered(wasm)?;
+        engine.cache_module(wasm, true, &module);
+        Ok(module)
     }
 
     /// Creates a new Wasm [`Module`] from the given Wasm bytecode stream.
@@ -242,7 +275,9 @@ impl Module {
     ///
     /// # Note
     ///
-    /// This parses and translates the buffered Wasm bytecode.
+    /// - This parses and translates the buffered Wasm bytecode.
+    /// - Returns a cached [`Module`] for the same Wasm bytecode if the `engine`
+    ///   caches modules as configured by [`Config::set_cached_modules`].
     ///
     /// # Safety
     ///
@@ -258,9 +293,15 @@ impl Module {
     /// - If the Wasm bytecode fails to be compiled by Wasmi.
     ///
     /// [`Config`]: crate::Config
+    /// [`Config::set_cached_modules`]: crate::Config::set_cached_modules
     pub unsafe fn new_unchecked(engine: &Engine, wasm: &[u8]) -> Result<Self, Error> {
+        if let Some(module) = engine.get_cached_module(wasm, false) {
+            return Ok(module);
+        }
         let parser = ModuleParser::new(engine);
-        unsafe { parser.parse_buffered_unchecked(wasm) }
+        let module = unsafe { parser.parse_buffered_unchecked(wasm)? };
+        engine.cache_module(wasm, false, &module);
+        Ok(module)
     }
 
     /// Creates a new Wasm [`Module`] from the given byte stream.
@@ -293,7 +334,7 @@ impl Module {
 
     /// Returns the [`Engine`] used during creation of the [`Module`].
     pub fn engine(&self) -> &Engine {
-        &self.inner.engine
+        &self.engine
     }
 
     /// Returns a shared reference to the [`ModuleHeaderInner`].
@@ -323,19 +364,29 @@ impl Module {
     /// [`Config`]: crate::Config
     pub fn validate(engine: &Engine, wasm: &[u8]) -> Result<(), Error> {
         let mut validator = Validator::new_with_features(engine.config().wasm_features());
+        let mut position = ParsePosition::default();
         for payload in Parser::new(0).parse_all(wasm) {
-            let payload = payload?;
-            if let ValidPayload::Func(func_to_validate, func_body) = validator.payload(&payload)? {
+            position.leave_payload();
+            let payload = payload.map_err(|error| position.error(error.into()))?;
+            position.enter_payload(&payload);
+            let valid_payload = validator
+                .payload(&payload)
+                .map_err(|error| position.error(error.into()))?;
+            if let ValidPayload::Func(func_to_validate, func_body) = valid_payload {
+                position.enter_func(func_to_validate.index);
                 func_to_validate
                     .into_validator(FuncValidatorAllocations::default())
-                    .validate(&func_body)?;
+                    .validate(&func_body)
+                    .map_err(|error| position.error(error.into()))?;
             }
         }
         Ok(())
     }
 
-    /// Returns the number of non-imported functions of the [`Module`].
-    pub(crate) fn len_funcs(&self) -> usize {
+    /// Returns the number of functions of the [`Module`].
+    ///
+    /// The function index space includes imported functions.
+    pub fn len_funcs(&self) -> usize {
         self.module_header().funcs.len()
     }
     /// Returns the number of non-imported tables of the [`Module`].
@@ -351,6 +402,24 @@ impl Module {
         self.module_header().globals.len()
     }
 
+    /// Returns the [`EngineFunc`] of the function at `func_index` if it is defined by the [`Module`].
+    ///
+    /// Returns `None` if `func_index` is out of bounds or refers to an imported function.
+    pub(crate) fn get_engine_func(&self, func_index: u32) -> Option<EngineFunc> {
+        if func_index as usize >= self.len_funcs() {
+            return None;
+        }
+        self.inner.header.get_engine_func(FuncIdx::from(func_index))
+    }
+
+    /// Returns the index of the function for `func` if it is defined by the [`Module`].
+    pub(crate) fn get_func_index(&self, func: EngineFunc) -> Option<u32> {
+        self.inner
+            .header
+            .get_func_index(func)
+            .map(FuncIdx::into_u32)
+    }
+
     /// Returns a slice to the function types of the [`Module`].
     ///
     /// # Note
@@ -367,7 +436,12 @@ impl Module {
         let len_imported_globals = header.imports.len_globals;
         ModuleImportsIter {
             engine: self.engine(),
+            debug_names: &self.inner.names,
             names: header.imports.items.iter(),
+            len_funcs: 0,
+            len_tables: 0,
+            len_memories: 0,
+            len_globals: 0,
             funcs: header.funcs[..len_imported_funcs].iter(),
             tables: header.tables.iter(),
             memories: header.memories.iter(),
@@ -487,13 +561,121 @@ impl Module {
     pub fn custom_sections(&self) -> CustomSectionsIter {
         self.inner.custom_sections.iter()
     }
+
+    /// Returns the source frames of the Wasm operator at `offset` within the Wasm binary.
+    ///
+    /// The source frames are derived from the DWARF debug information of the [`Module`].
+    /// The innermost inlined function comes first and the function that contains the
+    /// Wasm operator comes last.
+    ///
+    /// # Note
+    ///
+    /// Returns an empty `Vec` if the [`Module`] has no DWARF debug information for `offset`.
+    /// DWARF debug information is read from the custom sections of the [`Module`] and
+    /// therefore unavailable if [`Config::ignore_custom_sections`] is enabled.
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    #[cfg(feature = "dwarf")]
+    pub fn source_frames(&self, offset: u32) -> Vec<SourceFrame> {
+        self.inner
+            .dwarf
+            .as_ref()
+            .map(|dwarf| dwarf.source_frames(offset))
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the [`Module`] in the `name` custom section if any.
+    ///
+    /// # Note
+    ///
+    /// Names are read from the custom sections of the [`Module`] and therefore
+    /// unavailable if [`Config::ignore_custom_sections`] is enabled.
+    /// This applies to all name getters of the [`Module`].
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    pub fn name(&self) -> Option<&str> {
+        self.inner.names.module()
+    }
+
+    /// Returns the name of the function at `func_index` in the `name` custom section if any.
+    ///
+    /// The function index space includes imported functions.
+    pub fn func_name(&self, func_index: u32) -> Option<&str> {
+        self.inner.names.func(func_index)
+    }
+
+    /// Returns the name of the local at `local_index` of the function at `func_index` if any.
+    ///
+    /// The local index space of a function includes its parameters.
+    pub fn local_name(&self, func_index: u32, local_index: u32) -> Option<&str> {
+        self.inner.names.local(func_index, local_index)
+    }
+
+    /// Returns the name of the global variable at `global_index` if any.
+    pub fn global_name(&self, global_index: u32) -> Option<&str> {
+        self.inner.names.global(global_index)
+    }
+
+    /// Returns the name of the linear memory at `memory_index` if any.
+    pub fn memory_name(&self, memory_index: u32) -> Option<&str> {
+        self.inner.names.memory(memory_index)
+    }
+
+    /// Returns the name of the table at `table_index` if any.
+    pub fn table_name(&self, table_index: u32) -> Option<&str> {
+        self.inner.names.table(table_index)
+    }
+
+    /// Returns the name of the data segment at `data_index` if any.
+    pub fn data_name(&self, data_index: u32) -> Option<&str> {
+        self.inner.names.data(data_index)
+    }
+
+    /// Returns the name of the element segment at `elem_index` if any.
+    pub fn elem_name(&self, elem_index: u32) -> Option<&str> {
+        self.inner.names.elem(elem_index)
+    }
+
+    /// Returns the disassembled Wasmi bytecode of the function at `func_index` in a human readable text format.
+    ///
+    /// The output lists the register allocation, the names of the function locals
+    /// from the `name` custom section, the function local constant values and the
+    /// translated Wasmi instructions including their branch targets.
+    ///
+    /// # Note
+    ///
+    /// - The function is compiled if it has not yet been compiled, for example due to lazy compilation.
+    /// - The output format is meant for humans and may change between Wasmi versions.
+    ///
+    /// # Errors
+    ///
+    /// - If `func_index` is out of bounds or refers to an imported function.
+    /// - If translation or Wasm validation of the function failed.
+    pub fn disassemble(&self, func_index: u32) -> Result<String, Error> {
+        let Some(func) = self.get_engine_func(func_index) else {
+            return Err(Error::new(format!(
+                "function index {func_index} does not refer to a function defined by the module"
+            )));
+        };
+        let dedup = &self.module_header().funcs[func_index as usize];
+        let len_params = self
+            .engine()
+            .resolve_func_type(dedup, |func_type| func_type.params().len());
+        let local_names = self.inner.names.locals(func_index);
+        self.engine().disassemble(func, len_params, local_names)
+    }
 }
 
 /// An iterator over the imports of a [`Module`].
 #[derive(Debug)]
 pub struct ModuleImportsIter<'a> {
     engine: &'a Engine,
+    debug_names: &'a ModuleNames,
     names: SliceIter<'a, Imported>,
+    len_funcs: u32,
+    len_tables: u32,
+    len_memories: u32,
+    len_globals: u32,
     funcs: SliceIter<'a, DedupFuncType>,
     tables: SliceIter<'a, TableType>,
     memories: SliceIter<'a, MemoryType>,
@@ -512,25 +694,33 @@ impl<'a> Iterator for ModuleImportsIter<'a> {
                         panic!("unexpected missing imported function for {name:?}")
                     });
                     let func_type = self.engine.resolve_func_type(func_type, FuncType::clone);
-                    ImportType::new(name, func_type)
+                    let debug_name = self.debug_names.func(self.len_funcs);
+                    self.len_funcs += 1;
+                    ImportType::new(name, func_type).with_debug_name(debug_name)
                 }
                 Imported::Table(name) => {
                     let table_type = self.tables.next().unwrap_or_else(|| {
                         panic!("unexpected missing imported table for {name:?}")
                     });
-                    ImportType::new(name, *table_type)
+                    let debug_name = self.debug_names.table(self.len_tables);
+                    self.len_tables += 1;
+                    ImportType::new(name, *table_type).with_debug_name(debug_name)
                 }
                 Imported::Memory(name) => {
                     let memory_type = self.memories.next().unwrap_or_else(|| {
                         panic!("unexpected missing imported linear memory for {name:?}")
                     });
-                    ImportType::new(name, *memory_type)
+                    let debug_name = self.debug_names.memory(self.len_memories);
+                    self.len_memories += 1;
+                    ImportType::new(name, *memory_type).with_debug_name(debug_name)
                 }
                 Imported::Global(name) => {
                     let global_type = self.globals.next().unwrap_or_else(|| {
                         panic!("unexpected missing imported global variable for {name:?}")
                     });
-                    ImportType::new(name, *global_type)
+                    let debug_name = self.debug_names.global(self.len_globals);
+                    self.len_globals += 1;
+                    ImportType::new(name, *global_type).with_debug_name(debug_name)
                 }
             },
         };
@@ -559,6 +749,8 @@ pub struct ImportType<'module> {
     name: &'module ImportName,
     /// The external item type.
     ty: ExternType,
+    /// The name of the imported item in the `name` custom section if any.
+    debug_name: Option<&'module str>,
 }
 
 impl<'module> ImportType<'module> {
@@ -570,9 +762,16 @@ impl<'module> ImportType<'module> {
         Self {
             name,
             ty: ty.into(),
+            debug_name: None,
         }
     }
 
+    /// Sets the name of the imported item in the `name` custom section.
+    fn with_debug_name(mut self, debug_name: Option<&'module str>) -> Self {
+        self.debug_name = debug_name;
+        self
+    }
+
     /// Returns the import name.
     pub(crate) fn import_name(&self) -> &ImportName {
         self.name
@@ -592,6 +791,16 @@ impl<'module> ImportType<'module> {
     pub fn ty(&self) -> &ExternType {
         &self.ty
     }
+
+    /// Returns the name of the imported item in the `name` custom section if any.
+    ///
+    /// # Note
+    ///
+    /// This is the name by which the Wasm module refers to the imported item
+    /// and may differ from [`ImportType::name`].
+    pub fn debug_name(&self) -> Option<&'module str> {
+        self.debug_name
+    }
 }
 
 /// An iterator over the internally defined functions of a [`Module`].
@@ -0,0 +1,159 @@
+use super::{export::ExternIdx, CustomSections};
+use alloc::{boxed::Box, collections::BTreeMap};
+use wasmparser::{BinaryReader, Name, NameMap, NameSectionReader};
+
+/// The names of a Wasm module and its items from the `name` custom section.
+///
+/// This includes the subsections of the extended name section proposal.
+///
+/// # Note
+///
+/// A malformed `name` custom section is not an error: all names
+/// that could be read up to the malformed part are used.
+#[derive(Debug, Default)]
+pub struct ModuleNames {
+    /// The name of the Wasm module if any.
+    module: Option<Box<str>>,
+    /// The names of functions by their index.
+    funcs: BTreeMap<u32, Box<str>>,
+    /// The names of function locals by their function and local index.
+    locals: BTreeMap<u32, BTreeMap<u32, Box<str>>>,
+    /// The names of global variables by their index.
+    globals: BTreeMap<u32, Box<str>>,
+    /// The names of linear memories by their index.
+    memories: BTreeMap<u32, Box<str>>,
+    /// The names of tables by their index.
+    tables: BTreeMap<u32, Box<str>>,
+    /// The names of data segments by their index.
+    data: BTreeMap<u32, Box<str>>,
+    /// The names of element segments by their index.
+    elems: BTreeMap<u32, Box<str>>,
+}
+
+impl ModuleNames {
+    /// Reads the [`ModuleNames`] from the `name` section of the `custom_sections` of a Wasm module.
+    pub fn new(custom_sections: &CustomSections) -> Self {
+        let mut names = Self::default();
+        for section in custom_sections.iter() {
+            if section.name() == "name" {
+                let reader = NameSectionReader::new(BinaryReader::new(section.data(), 0));
+                names.read(reader);
+            }
+        }
+        names
+    }
+
+    /// Reads all subsections of the `name` section `reader`.
+    fn read(&mut self, reader: NameSectionReader) {
+        for name in reader {
+            let Ok(name) = name else {
+                return;
+            };
+            match name {
+                Name::Module { name, .. } => self.module = Some(name.into()),
+                Name::Function(map) => read_name_map(map, &mut self.funcs),
+                Name::Local(map) => {
+                    for naming in map {
+                        let Ok(naming) = naming else {
+                            break;
+                        };
+                        let locals = self.locals.entry(naming.index).or_default();
+                        read_name_map(naming.names, locals);
+                    }
+                }
+                Name::Global(map) => read_name_map(map, &mut self.globals),
+                Name::Memory(map) => read_name_map(map, &mut self.memories),
+                Name::Table(map) => read_name_map(map, &mut self.tables),
+                Name::Data(map) => read_name_map(map, &mut self.data),
+                Name::Element(map) => read_name_map(map, &mut self.elems),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns `true` if the [`ModuleNames`] contain no names.
+    pub fn is_empty(&self) -> bool {
+        self.module.is_none()
+            && self.funcs.is_empty()
+            && self.locals.is_empty()
+            && self.globals.is_empty()
+            && self.memories.is_empty()
+            && self.tables.is_empty()
+            && self.data.is_empty()
+            && self.elems.is_empty()
+    }
+
+    /// Returns the name of the Wasm module if any.
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the name of the function at `index` if any.
+    pub fn func(&self, index: u32) -> Option<&str> {
+        self.funcs.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the local at `local_index` of the function at `func_index` if any.
+    pub fn local(&self, func_index: u32, local_index: u32) -> Option<&str> {
+        self.locals
+            .get(&func_index)?
+            .get(&local_index)
+            .map(Box::as_ref)
+    }
+
+    /// Returns the names of the locals of the function at `func_index` ordered by their index.
+    pub fn locals(&self, func_index: u32) -> impl Iterator<Item = (u32, &str)> {
+        self.locals
+            .get(&func_index)
+            .into_iter()
+            .flatten()
+            .map(|(index, name)| (*index, name.as_ref()))
+    }
+
+    /// Returns the name of the global variable at `index` if any.
+    pub fn global(&self, index: u32) -> Option<&str> {
+        self.globals.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the linear memory at `index` if any.
+    pub fn memory(&self, index: u32) -> Option<&str> {
+        self.memories.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the table at `index` if any.
+    pub fn table(&self, index: u32) -> Option<&str> {
+        self.tables.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the data segment at `index` if any.
+    pub fn data(&self, index: u32) -> Option<&str> {
+        self.data.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the element segment at `index` if any.
+    pub fn elem(&self, index: u32) -> Option<&str> {
+        self.elems.get(&index).map(Box::as_ref)
+    }
+
+    /// Returns the name of the item referred to by `idx` if any.
+    pub fn extern_item(&self, idx: ExternIdx) -> Option<&str> {
+        match idx {
+            ExternIdx::Func(index) => self.func(index.into_u32()),
+            ExternIdx::Table(index) => self.table(index.into_u32()),
+            ExternIdx::Memory(index) => self.memory(index.into_u32()),
+            ExternIdx::Global(index) => self.global(index.into_u32()),
+        }
+    }
+}
+
+/// Reads the names of `map` into `names`.
+///
+/// Stops at the first malformed entry of `map`.
+fn read_name_map(map: NameMap, names: &mut BTreeMap<u32, Box<str>>) {
+    for naming in map {
+        let Ok(naming) = naming else {
+            return;
+        };
+        names.insert(naming.index, naming.name.into());
+    }
+}
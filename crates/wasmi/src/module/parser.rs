@@ -8,6 +8,7 @@ use super::{
     FuncIdx,
     ModuleBuilder,
     ModuleHeader,
+    ParsePosition,
 };
 use crate::{
     engine::{EnforcedLimitsError, EngineFunc},
@@ -37,6 +38,9 @@ use wasmparser::{
     Validator,
 };
 
+#[cfg(feature = "parallel")]
+use crate::engine::FuncToTranslate;
+
 #[cfg(doc)]
 use crate::Module;
 
@@ -55,6 +59,10 @@ pub struct ModuleParser {
     engine_funcs: u32,
     /// Flag, `true` when `stream` is at the end.
     eof: bool,
+    /// The position within the Wasm binary used to create [`ModuleError`]s.
+    ///
+    /// [`ModuleError`]: crate::errors::ModuleError
+    position: ParsePosition,
 }
 
 impl ModuleParser {
@@ -67,6 +75,7 @@ impl ModuleParser {
             parser,
             engine_funcs: 0,
             eof: false,
+            position: ParsePosition::default(),
         }
     }
 
@@ -489,6 +498,7 @@ impl ModuleParser {
         header: &ModuleHeader,
     ) -> Result<(), Error> {
         let (func, engine_func) = self.next_func(header);
+        self.position.enter_func(func.into_u32());
         let module = header.clone();
         let offset = func_body.get_binary_reader().original_position();
         let func_to_validate = match &mut self.validator {
@@ -500,6 +510,37 @@ impl ModuleParser {
         Ok(())
     }
 
+    /// Prepares a single module code section entry for parallel translation.
+    ///
+    /// # Note
+    ///
+    /// This validates the code section entry but not its function body.
+    ///
+    /// # Errors
+    ///
+    /// If the code section entry fails to validate.
+    #[cfg(feature = "parallel")]
+    fn prepare_code_entry<'a>(
+        &mut self,
+        func_body: FunctionBody<'a>,
+        header: &ModuleHeader,
+    ) -> Result<FuncToTranslate<'a>, Error> {
+        let (func_index, engine_func) = self.next_func(header);
+        self.position.enter_func(func_index.into_u32());
+        let offset = func_body.get_binary_reader().original_position();
+        let func_to_validate = match &mut self.validator {
+            Some(validator) => Some(validator.code_section_entry(&func_body)?),
+            None => None,
+        };
+        Ok(FuncToTranslate {
+            func_index,
+            engine_func,
+            offset,
+            bytes: func_body.as_bytes(),
+            func_to_validate,
+        })
+    }
+
     /// Process a single Wasm custom section.
     fn process_custom_section(
         &mut self,
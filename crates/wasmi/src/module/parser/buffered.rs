@@ -8,6 +8,11 @@ use super::{
 use crate::{Error, Module};
 use wasmparser::{Chunk, Payload, Validator};
 
+#[cfg(feature = "parallel")]
+use crate::engine::{CompilationMode, FuncToTranslate};
+#[cfg(feature = "parallel")]
+use alloc::vec::Vec;
+
 impl ModuleParser {
     /// Starts parsing and validating the Wasm bytecode stream.
     ///
@@ -57,10 +62,18 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     unsafe fn parse_buffered_impl(mut self, mut buffer: &[u8]) -> Result<Module, Error> {
+        self.engine.config().validate()?;
+        #[cfg(feature = "tracing")]
+        let span = crate::instrument::module_span().entered();
         let mut custom_sections = CustomSectionsBuilder::default();
-        let header = Self::parse_buffered_header(&mut self, &mut buffer, &mut custom_sections)?;
-        let builder = Self::parse_buffered_code(&mut self, &mut buffer, header, custom_sections)?;
-        let module = Self::parse_buffered_data(&mut self, &mut buffer, builder)?;
+        let header = Self::parse_buffered_header(&mut self, &mut buffer, &mut custom_sections)
+            .map_err(|error| self.position.error(error))?;
+        let builder = Self::parse_buffered_code(&mut self, &mut buffer, header, custom_sections)
+            .map_err(|error| self.position.error(error))?;
+        let module = Self::parse_buffered_data(&mut self, &mut buffer, builder)
+            .map_err(|error| self.position.error(error))?;
+        #[cfg(feature = "tracing")]
+        crate::instrument::record_module(&span, &module);
         Ok(module)
     }
 
@@ -70,8 +83,12 @@ impl ModuleParser {
     ///
     /// If the parsed Wasm is malformed.
     fn next_payload<'a>(&mut self, buffer: &mut &'a [u8]) -> Result<(usize, Payload<'a>), Error> {
+        self.position.leave_payload();
         match self.parser.parse(&buffer[..], true)? {
-            Chunk::Parsed { consumed, payload } => Ok((consumed, payload)),
+            Chunk::Parsed { consumed, payload } => {
+                self.position.enter_payload(&payload);
+                Ok((consumed, payload))
+            }
             Chunk::NeedMoreData(_hint) => {
                 // This is not possible since `eof` is always true.
                 unreachable!()
@@ -123,6 +140,10 @@ impl ModuleParser {
                 Payload::ElementSection(section) => self.process_element(section, &mut header),
                 Payload::DataCountSection { count, range } => self.process_data_count(count, range),
                 Payload::CodeSectionStart { count, range, size } => {
+                    #[cfg(feature = "dwarf")]
+                    {
+                        header.code_offset = range.start as u32;
+                    }
                     self.process_code_start(count, range, size)?;
                     Self::consume_buffer(consumed, buffer);
                     break;
@@ -154,6 +175,14 @@ impl ModuleParser {
         header: ModuleHeader,
         custom_sections: CustomSectionsBuilder,
     ) -> Result<ModuleBuilder, Error> {
+        #[cfg(feature = "parallel")]
+        if matches!(
+            self.engine.config().get_compilation_mode(),
+            CompilationMode::Eager
+        ) {
+            self.parse_buffered_code_parallel(buffer, &header)?;
+            return Ok(ModuleBuilder::new(header, custom_sections));
+        }
         loop {
             let (consumed, payload) = self.next_payload(buffer)?;
             match payload {
@@ -172,6 +201,61 @@ impl ModuleParser {
         Ok(ModuleBuilder::new(header, custom_sections))
     }
 
+    /// Parse the Wasm code section entries and translate them in parallel.
+    ///
+    /// # Note
+    ///
+    /// All code section entries are parsed and validated up front before
+    /// their function bodies are translated and validated in parallel.
+    /// The reported error is the same as if the entries were translated one after another.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm bytecode stream fails to parse or validate.
+    #[cfg(feature = "parallel")]
+    fn parse_buffered_code_parallel(
+        &mut self,
+        buffer: &mut &[u8],
+        header: &ModuleHeader,
+    ) -> Result<(), Error> {
+        let mut funcs = Vec::new();
+        let mut entry_error = None;
+        loop {
+            let (consumed, payload) = match self.next_payload(buffer) {
+                Ok(payload) => payload,
+                Err(error) => {
+                    entry_error = Some(self.position.error(error));
+                    break;
+                }
+            };
+            let Payload::CodeSectionEntry(func_body) = payload else {
+                break;
+            };
+            Self::consume_buffer(consumed, buffer);
+            match self.prepare_code_entry(func_body, header) {
+                Ok(func) => funcs.push(func),
+                Err(error) => {
+                    entry_error = Some(self.position.error(error));
+                    break;
+                }
+            }
+        }
+        let positions: Vec<_> = funcs
+            .iter()
+            .map(|func: &FuncToTranslate| (func.offset, func.func_index))
+            .collect();
+        if let Err((index, error)) = self.engine.translate_funcs(header, funcs) {
+            let (offset, func_index) = positions[index];
+            self.position
+                .enter_code_entry(offset, func_index.into_u32());
+            return Err(self.position.error(error));
+        }
+        match entry_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
     /// Parse the Wasm data section and finalize parsing.
     ///
     /// We separate parsing of the Wasm data section since it is the only Wasm
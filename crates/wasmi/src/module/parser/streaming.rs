@@ -112,22 +112,26 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     unsafe fn parse_streaming_impl(mut self, mut stream: impl Read) -> Result<Module, Error> {
+        self.engine.config().validate()?;
+        #[cfg(feature = "tracing")]
+        let span = crate::instrument::module_span().entered();
         let mut custom_sections = CustomSectionsBuilder::default();
         let mut buffer = ParseBuffer::default();
-        let header = Self::parse_streaming_header(
-            &mut self,
-            &mut stream,
-            &mut buffer,
-            &mut custom_sections,
-        )?;
+        let header =
+            Self::parse_streaming_header(&mut self, &mut stream, &mut buffer, &mut custom_sections)
+                .map_err(|error| self.position.error(error))?;
         let builder = Self::parse_streaming_code(
             &mut self,
             &mut stream,
             &mut buffer,
             header,
             custom_sections,
-        )?;
-        let module = Self::parse_streaming_data(&mut self, &mut stream, &mut buffer, builder)?;
+        )
+        .map_err(|error| self.position.error(error))?;
+        let module = Self::parse_streaming_data(&mut self, &mut stream, &mut buffer, builder)
+            .map_err(|error| self.position.error(error))?;
+        #[cfg(feature = "tracing")]
+        crate::instrument::record_module(&span, &module);
         Ok(module)
     }
 
@@ -149,6 +153,7 @@ impl ModuleParser {
     ) -> Result<ModuleHeader, Error> {
         let mut header = ModuleHeaderBuilder::new(&self.engine);
         loop {
+            self.position.leave_payload();
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
@@ -157,6 +162,7 @@ impl ModuleParser {
                     }
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    self.position.enter_payload(&payload);
                     match payload {
                         Payload::Version {
                             num,
@@ -190,6 +196,10 @@ impl ModuleParser {
                             self.process_data_count(count, range)
                         }
                         Payload::CodeSectionStart { count, range, size } => {
+                            #[cfg(feature = "dwarf")]
+                            {
+                                header.code_offset = range.start as u32;
+                            }
                             self.process_code_start(count, range, size)?;
                             ParseBuffer::consume(buffer, consumed);
                             break;
@@ -226,11 +236,13 @@ impl ModuleParser {
         custom_sections: CustomSectionsBuilder,
     ) -> Result<ModuleBuilder, Error> {
         loop {
+            self.position.leave_payload();
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    self.position.enter_payload(&payload);
                     match payload {
                         Payload::CodeSectionEntry(func_body) => {
                             // Note: Unfortunately the `wasmparser` crate is missing an API
@@ -266,11 +278,13 @@ impl ModuleParser {
         mut builder: ModuleBuilder,
     ) -> Result<Module, Error> {
         loop {
+            self.position.leave_payload();
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    self.position.enter_payload(&payload);
                     match payload {
                         Payload::DataSection(section) => {
                             self.process_data(section, &mut builder)?;
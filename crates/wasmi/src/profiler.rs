@@ -0,0 +1,279 @@
+use crate::{engine::EngineFunc, store::Fuel, Module};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+/// A Wasm function recorded in a [`Profile`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProfileFunc(EngineFunc);
+
+impl ProfileFunc {
+    /// Returns the index of the [`ProfileFunc`] within `module`.
+    ///
+    /// Returns `None` if the [`ProfileFunc`] is not defined by `module`.
+    pub fn func_index(&self, module: &Module) -> Option<u32> {
+        module.get_func_index(self.0)
+    }
+}
+
+/// The aggregated statistics of a single [`ProfileFunc`] within a [`Profile`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FuncStats {
+    calls: u64,
+    inclusive: u64,
+    exclusive: u64,
+}
+
+impl FuncStats {
+    /// Returns the number of calls to the function.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the fuel consumed by the function including its callees.
+    ///
+    /// Recursive calls are accounted only once.
+    pub fn inclusive_fuel(&self) -> u64 {
+        self.inclusive
+    }
+
+    /// Returns the fuel consumed by the function excluding its callees.
+    pub fn exclusive_fuel(&self) -> u64 {
+        self.exclusive
+    }
+}
+
+/// A unique call stack within a [`Profile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileStack {
+    funcs: Vec<ProfileFunc>,
+    calls: u64,
+    fuel: u64,
+}
+
+impl ProfileStack {
+    /// Returns the functions of the call stack with the outermost function first.
+    pub fn funcs(&self) -> &[ProfileFunc] {
+        &self.funcs
+    }
+
+    /// Returns how often the innermost function was called with this call stack.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the fuel consumed by the innermost function with this call stack.
+    ///
+    /// This excludes the fuel consumed by callees of the innermost function.
+    pub fn fuel(&self) -> u64 {
+        self.fuel
+    }
+}
+
+/// The call tree recorded while profiling via [`Store::set_profiling`].
+///
+/// # Note
+///
+/// The cost of executing Wasm functions is measured in consumed fuel.
+/// Therefore [`Config::consume_fuel`] must be enabled in order to record
+/// anything besides the number of calls.
+///
+/// [`Store::set_profiling`]: crate::Store::set_profiling
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The nodes of the call tree where the first node is its root.
+    ///
+    /// The root node represents the host and is not associated to any function.
+    nodes: Vec<CallNode>,
+}
+
+/// A node of the call tree of a [`Profile`].
+#[derive(Debug, Clone)]
+struct CallNode {
+    /// The called function or `None` for the root node.
+    func: Option<ProfileFunc>,
+    /// The index of the parent node.
+    parent: usize,
+    /// The indices of the child nodes.
+    children: BTreeMap<ProfileFunc, usize>,
+    /// The number of calls to the function with this call stack.
+    calls: u64,
+    /// The fuel consumed by the function with this call stack excluding its callees.
+    fuel: u64,
+}
+
+impl CallNode {
+    /// Creates a new [`CallNode`] for `func` with `parent` node.
+    fn new(func: Option<ProfileFunc>, parent: usize) -> Self {
+        Self {
+            func,
+            parent,
+            children: BTreeMap::new(),
+            calls: 0,
+            fuel: 0,
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            nodes: vec![CallNode::new(None, 0)],
+        }
+    }
+}
+
+impl Profile {
+    /// Returns `true` if the [`Profile`] did not record any function calls.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 1
+    }
+
+    /// Returns the total fuel consumed by all recorded Wasm functions.
+    pub fn total_fuel(&self) -> u64 {
+        self.nodes.iter().map(|node| node.fuel).sum()
+    }
+
+    /// Returns the aggregated statistics of all recorded functions.
+    pub fn funcs(&self) -> BTreeMap<ProfileFunc, FuncStats> {
+        let mut funcs = BTreeMap::<ProfileFunc, FuncStats>::new();
+        let inclusive = self.inclusive_fuel();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let Some(func) = node.func else { continue };
+            let stats = funcs.entry(func).or_default();
+            stats.calls += node.calls;
+            stats.exclusive += node.fuel;
+            if !self.ancestors(index).any(|ancestor| ancestor == func) {
+                stats.inclusive += inclusive[index];
+            }
+        }
+        funcs
+    }
+
+    /// Returns all unique call stacks that have been recorded.
+    ///
+    /// Call stacks are ordered so that every call stack follows its prefixes.
+    pub fn stacks(&self) -> Vec<ProfileStack> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, node)| {
+                let mut funcs = self.ancestors(index).collect::<Vec<_>>();
+                funcs.reverse();
+                funcs.extend(node.func);
+                ProfileStack {
+                    funcs,
+                    calls: node.calls,
+                    fuel: node.fuel,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the functions of the ancestor nodes of the node at `index`, innermost first.
+    fn ancestors(&self, index: usize) -> impl Iterator<Item = ProfileFunc> + '_ {
+        let mut current = self.nodes[index].parent;
+        core::iter::from_fn(move || {
+            let node = &self.nodes[current];
+            current = node.parent;
+            node.func
+        })
+    }
+
+    /// Returns the fuel consumed by every node of the call tree including its children.
+    fn inclusive_fuel(&self) -> Vec<u64> {
+        let mut inclusive = self.nodes.iter().map(|node| node.fuel).collect::<Vec<_>>();
+        // Children are always pushed after their parents so that a reverse
+        // iteration visits all children before their parents.
+        for index in (1..self.nodes.len()).rev() {
+            let parent = self.nodes[index].parent;
+            inclusive[parent] += inclusive[index];
+        }
+        inclusive
+    }
+
+    /// Returns the index of the child node of `parent` for `func`.
+    ///
+    /// Creates the child node if it does not yet exist.
+    fn child(&mut self, parent: usize, func: ProfileFunc) -> usize {
+        if let Some(&index) = self.nodes[parent].children.get(&func) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.nodes.push(CallNode::new(Some(func), parent));
+        self.nodes[parent].children.insert(func, index);
+        index
+    }
+}
+
+/// The profiling state of a [`Store`].
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Default)]
+pub(crate) struct Profiler {
+    /// Is `true` if profiling is enabled.
+    enabled: bool,
+    /// The recorded [`Profile`].
+    profile: Profile,
+    /// The call tree nodes of the currently executing Wasm functions.
+    stack: Vec<usize>,
+    /// The remaining fuel at the last profiling event.
+    fuel: u64,
+}
+
+impl Profiler {
+    /// Enables or disables profiling.
+    pub fn set_enabled(&mut self, enable: bool) {
+        self.enabled = enable;
+    }
+
+    /// Returns `true` if profiling is enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the recorded [`Profile`].
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns the recorded [`Profile`] and starts a new one.
+    pub fn take_profile(&mut self) -> Profile {
+        core::mem::take(&mut self.profile)
+    }
+
+    /// Attributes the fuel consumed since the last event to the currently executing function.
+    fn charge(&mut self, fuel: &Fuel) {
+        let remaining = fuel.get_fuel().unwrap_or(0);
+        let consumed = self.fuel.saturating_sub(remaining);
+        self.fuel = remaining;
+        let node = self.stack.last().copied().unwrap_or(0);
+        self.profile.nodes[node].fuel += consumed;
+    }
+
+    /// Records a call to `func`.
+    pub fn call(&mut self, func: EngineFunc, fuel: &Fuel) {
+        if self.stack.is_empty() {
+            // The remaining fuel might have been changed by the host in between executions.
+            self.fuel = fuel.get_fuel().unwrap_or(0);
+        }
+        self.charge(fuel);
+        let parent = self.stack.last().copied().unwrap_or(0);
+        let node = self.profile.child(parent, ProfileFunc(func));
+        self.profile.nodes[node].calls += 1;
+        self.stack.push(node);
+    }
+
+    /// Records a return from the currently executing function.
+    pub fn ret(&mut self, fuel: &Fuel) {
+        self.unwind(1, fuel)
+    }
+
+    /// Records that `depth` of the currently executing functions have been unwound.
+    pub fn unwind(&mut self, depth: usize, fuel: &Fuel) {
+        self.charge(fuel);
+        let len = self.stack.len().saturating_sub(depth);
+        self.stack.truncate(len);
+    }
+}
@@ -0,0 +1,225 @@
+//! Resource accounting statistics of [`Engine`] and [`Store`].
+//!
+//! [`Engine`]: crate::Engine
+//! [`Store`]: crate::Store
+
+use core::time::Duration;
+
+/// Statistics about the resources used by an [`Engine`].
+///
+/// Returned by [`Engine::stats`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::stats`]: crate::Engine::stats
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EngineStats {
+    compiled_funcs: usize,
+    uncompiled_funcs: usize,
+    tiered_up_funcs: usize,
+    jit_compiled_funcs: usize,
+    inlined_calls: usize,
+    bytecode_bytes: usize,
+    const_bytes: usize,
+    cached_stacks: usize,
+    cached_modules: usize,
+    translation_time: Duration,
+}
+
+impl EngineStats {
+    /// Returns the number of compiled Wasm functions.
+    pub fn compiled_funcs(&self) -> usize {
+        self.compiled_funcs
+    }
+
+    /// Returns the number of Wasm functions that have not yet been compiled lazily.
+    pub fn uncompiled_funcs(&self) -> usize {
+        self.uncompiled_funcs
+    }
+
+    /// Returns the number of hot Wasm functions that have been re-translated due to [`Config::tiering`].
+    ///
+    /// [`Config::tiering`]: crate::Config::tiering
+    pub fn tiered_up_funcs(&self) -> usize {
+        self.tiered_up_funcs
+    }
+
+    /// Returns the number of tiered up Wasm functions that have been compiled to machine code.
+    ///
+    /// # Note
+    ///
+    /// This is always zero without the `jit` crate feature.
+    pub fn jit_compiled_funcs(&self) -> usize {
+        self.jit_compiled_funcs
+    }
+
+    /// Returns the number of calls that have been inlined into their callers due to [`Config::inlining`].
+    ///
+    /// [`Config::inlining`]: crate::Config::inlining
+    pub fn inlined_calls(&self) -> usize {
+        self.inlined_calls
+    }
+
+    /// Returns the total size of the bytecode of all compiled Wasm functions in bytes.
+    ///
+    /// # Note
+    ///
+    /// - This includes the bytecode of both tiers of tiered up Wasm functions.
+    /// - This includes the pre-decoded instruction handlers with the `threaded-dispatch` crate feature.
+    pub fn bytecode_bytes(&self) -> usize {
+        self.bytecode_bytes
+    }
+
+    /// Returns the total size of the function local constants of all compiled Wasm functions in bytes.
+    pub fn const_bytes(&self) -> usize {
+        self.const_bytes
+    }
+
+    /// Returns the number of stacks cached for reuse by Wasm executions.
+    pub fn cached_stacks(&self) -> usize {
+        self.cached_stacks
+    }
+
+    /// Returns the number of compiled Wasm modules cached due to [`Config::set_cached_modules`].
+    ///
+    /// [`Config::set_cached_modules`]: crate::Config::set_cached_modules
+    pub fn cached_modules(&self) -> usize {
+        self.cached_modules
+    }
+
+    /// Returns the total time spent translating Wasm functions.
+    ///
+    /// # Note
+    ///
+    /// Translation time is only measured if the `std` crate feature is enabled.
+    pub fn translation_time(&self) -> Duration {
+        self.translation_time
+    }
+
+    /// Accounts for a Wasm function compiled with `bytecode_bytes` and `const_bytes`.
+    pub(crate) fn add_compiled(&mut self, bytecode_bytes: usize, const_bytes: usize) {
+        self.compiled_funcs += 1;
+        self.bytecode_bytes += bytecode_bytes;
+        self.const_bytes += const_bytes;
+    }
+
+    /// Accounts for a Wasm function initialized for lazy compilation.
+    pub(crate) fn add_uncompiled(&mut self) {
+        self.uncompiled_funcs += 1;
+    }
+
+    /// Accounts for a lazily compiled Wasm function that is no longer uncompiled.
+    pub(crate) fn remove_uncompiled(&mut self) {
+        self.uncompiled_funcs -= 1;
+    }
+
+    /// Accounts for a Wasm function tiered up with `bytecode_bytes` and `const_bytes`.
+    pub(crate) fn add_tiered_up(&mut self, bytecode_bytes: usize, const_bytes: usize) {
+        self.tiered_up_funcs += 1;
+        self.bytecode_bytes += bytecode_bytes;
+        self.const_bytes += const_bytes;
+    }
+
+    /// Accounts for a tiered up Wasm function compiled to machine code.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    pub(crate) fn add_jit_compiled(&mut self) {
+        self.jit_compiled_funcs += 1;
+    }
+
+    /// Accounts for `amount` calls inlined into their callers.
+    pub(crate) fn add_inlined_calls(&mut self, amount: usize) {
+        self.inlined_calls += amount;
+    }
+
+    /// Accounts for `duration` spent translating a Wasm function.
+    pub(crate) fn add_translation_time(&mut self, duration: Duration) {
+        self.translation_time += duration;
+    }
+
+    /// Sets the number of cached stacks to `cached_stacks`.
+    pub(crate) fn set_cached_stacks(&mut self, cached_stacks: usize) {
+        self.cached_stacks = cached_stacks;
+    }
+
+    /// Sets the number of cached Wasm modules to `cached_modules`.
+    pub(crate) fn set_cached_modules(&mut self, cached_modules: usize) {
+        self.cached_modules = cached_modules;
+    }
+}
+
+/// Statistics about the resources used by a [`Store`].
+///
+/// Returned by [`Store::stats`].
+///
+/// [`Store`]: crate::Store
+/// [`Store::stats`]: crate::Store::stats
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StoreStats {
+    pub(crate) memory_bytes: usize,
+    pub(crate) table_elements: usize,
+    pub(crate) instances: usize,
+    pub(crate) extern_objects: usize,
+    pub(crate) max_value_stack_bytes: usize,
+    pub(crate) max_call_stack_depth: usize,
+}
+
+impl StoreStats {
+    /// Returns the total size of all linear memories in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.memory_bytes
+    }
+
+    /// Returns the total number of elements of all tables.
+    pub fn table_elements(&self) -> usize {
+        self.table_elements
+    }
+
+    /// Returns the number of instances.
+    pub fn instances(&self) -> usize {
+        self.instances
+    }
+
+    /// Returns the number of host objects referenced by [`ExternRef`]s.
+    ///
+    /// [`ExternRef`]: crate::ExternRef
+    pub fn extern_objects(&self) -> usize {
+        self.extern_objects
+    }
+
+    /// Returns the maximum size of the value stack in bytes used by any Wasm execution.
+    pub fn max_value_stack_bytes(&self) -> usize {
+        self.max_value_stack_bytes
+    }
+
+    /// Returns the maximum number of nested calls used by any Wasm execution.
+    pub fn max_call_stack_depth(&self) -> usize {
+        self.max_call_stack_depth
+    }
+}
+
+/// Measures the time spent translating Wasm functions.
+///
+/// # Note
+///
+/// This measures nothing without the `std` crate feature.
+pub(crate) struct Stopwatch {
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    /// Starts a new [`Stopwatch`].
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the time elapsed since the [`Stopwatch`] has been started.
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(feature = "std")]
+        return self.start.elapsed();
+        #[cfg(not(feature = "std"))]
+        return Duration::ZERO;
+    }
+}
@@ -1,15 +1,23 @@
+#[cfg(feature = "trace")]
+use crate::TraceEvent;
 use crate::{
     collections::arena::{Arena, ArenaIndex, GuardedEntity},
-    core::TrapCode,
-    engine::{DedupFuncType, FuelCosts},
+    core::{TrapCode, UntypedVal},
+    coverage::Coverage,
+    debugger::DebugState,
+    engine::{ConfigError, DedupFuncType, EngineFunc, FuelCosts, Stack},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
     func::{Trampoline, TrampolineEntity, TrampolineIdx},
     memory::{DataSegment, MemoryError},
     module::InstantiationError,
+    profiler::Profiler,
+    stats::StoreStats,
     table::TableError,
+    Breakpoints,
     Config,
     DataSegmentEntity,
     DataSegmentIdx,
+    Debugger,
     ElementSegment,
     ElementSegmentEntity,
     ElementSegmentIdx,
@@ -28,14 +36,19 @@ use crate::{
     Memory,
     MemoryEntity,
     MemoryIdx,
+    Profile,
     ResourceLimiter,
     Table,
     TableEntity,
     TableIdx,
+    WatchKind,
+    Watchpoints,
 };
 use alloc::boxed::Box;
 use core::{
     fmt::{self, Debug},
+    mem,
+    ops::Range,
     sync::atomic::{AtomicU32, Ordering},
 };
 
@@ -116,6 +129,21 @@ impl<T> Debug for CallHookWrapper<T> {
     }
 }
 
+/// A wrapper used to store hooks added with [`Store::trace_hook`], containing a
+/// boxed `FnMut(&mut T, &TraceEvent) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[cfg(feature = "trace")]
+#[allow(clippy::type_complexity)]
+struct TraceHookWrapper<T>(Box<dyn FnMut(&mut T, &TraceEvent) -> Result<(), Error> + Send + Sync>);
+#[cfg(feature = "trace")]
+impl<T> Debug for TraceHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TraceHook(...)")
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -136,6 +164,11 @@ pub struct Store<T> {
     /// or a WebAssembly function calls a host function, or these functions
     /// return.
     call_hook: Option<CallHookWrapper<T>>,
+    /// User provided callback called before every executed Wasmi instruction.
+    #[cfg(feature = "trace")]
+    trace_hook: Option<TraceHookWrapper<T>>,
+    /// The debugging state including the user provided [`Debugger`] if any.
+    pub(crate) debug: DebugState,
 }
 
 /// The inner store that owns all data not associated to the host state.
@@ -169,6 +202,14 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// The profiling state of the [`Store`].
+    profiler: Profiler,
+    /// The code coverage counters of the [`Store`].
+    coverage: Coverage,
+    /// The maximum number of values on the value stack used by any Wasm execution.
+    max_stack_values: usize,
+    /// The maximum number of nested calls used by any Wasm execution.
+    max_call_depth: usize,
 }
 
 #[test]
@@ -305,6 +346,16 @@ impl Fuel {
         Ok(self.remaining)
     }
 
+    /// Returns an exclusive reference to the remaining fuel.
+    ///
+    /// # Note
+    ///
+    /// This does not check if fuel metering is enabled.
+    #[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+    pub(crate) fn remaining_mut(&mut self) -> &mut u64 {
+        &mut self.remaining
+    }
+
     /// Synthetically consumes an amount of [`Fuel`] from the [`Store`].
     ///
     /// Returns the remaining amount of [`Fuel`] after this operation.
@@ -381,6 +432,10 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel,
+            profiler: Profiler::default(),
+            coverage: Coverage::default(),
+            max_stack_values: 0,
+            max_call_depth: 0,
         }
     }
 
@@ -394,6 +449,60 @@ impl StoreInner {
         &mut self.fuel
     }
 
+    /// Returns an exclusive reference to the [`Coverage`] counters.
+    pub fn coverage_mut(&mut self) -> &mut Coverage {
+        &mut self.coverage
+    }
+
+    /// Records the maximum value stack and call stack usage of the Wasm execution that used `stack`.
+    pub fn record_stack_usage(&mut self, stack: &Stack) {
+        self.max_stack_values = self.max_stack_values.max(stack.values.high_water());
+        self.max_call_depth = self.max_call_depth.max(stack.calls.high_water());
+    }
+
+    /// Returns the [`StoreStats`] of the [`StoreInner`].
+    pub fn stats(&self) -> StoreStats {
+        StoreStats {
+            memory_bytes: self
+                .memories
+                .iter()
+                .map(|(_, memory)| memory.data().len())
+                .sum(),
+            table_elements: self
+                .tables
+                .iter()
+                .map(|(_, table)| table.size() as usize)
+                .sum(),
+            instances: self.instances.len(),
+            extern_objects: self.extern_objects.len(),
+            max_value_stack_bytes: self.max_stack_values * mem::size_of::<UntypedVal>(),
+            max_call_stack_depth: self.max_call_depth,
+        }
+    }
+
+    /// Notifies the [`Profiler`] that `func` is called if profiling is enabled.
+    #[inline]
+    pub fn profile_call(&mut self, func: EngineFunc) {
+        if self.profiler.is_enabled() {
+            self.profiler.call(func, &self.fuel)
+        }
+    }
+
+    /// Notifies the [`Profiler`] that the current function returns if profiling is enabled.
+    #[inline]
+    pub fn profile_return(&mut self) {
+        if self.profiler.is_enabled() {
+            self.profiler.ret(&self.fuel)
+        }
+    }
+
+    /// Notifies the [`Profiler`] that `depth` functions have been unwound if profiling is enabled.
+    pub fn profile_unwind(&mut self, depth: usize) {
+        if self.profiler.is_enabled() {
+            self.profiler.unwind(depth, &self.fuel)
+        }
+    }
+
     /// Wraps an entity `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -871,6 +980,9 @@ where
             data: T::default(),
             limiter: None,
             call_hook: None,
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+            debug: DebugState::new(engine.config()),
         }
     }
 }
@@ -884,6 +996,9 @@ impl<T> Store<T> {
             data,
             limiter: None,
             call_hook: None,
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+            debug: DebugState::new(engine.config()),
         }
     }
 
@@ -1030,6 +1145,30 @@ impl<T> Store<T> {
             .unwrap_or_else(|| panic!("failed to resolve stored host function: {entity_index:?}"))
     }
 
+    /// Returns the following data:
+    ///
+    /// - A shared reference to the [`TrampolineEntity`] associated to the given [`Trampoline`].
+    /// - An exclusive reference to the [`Fuel`] of the [`StoreInner`].
+    ///
+    /// This method exists to properly handle use cases where
+    /// otherwise the Rust borrow-checker would not accept.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Trampoline`] does not originate from this [`Store`].
+    /// - If the [`Trampoline`] cannot be resolved to its entity.
+    pub(super) fn resolve_trampoline_and_fuel_mut(
+        &mut self,
+        func: &Trampoline,
+    ) -> (&TrampolineEntity<T>, &mut Fuel) {
+        let entity_index = self.inner.unwrap_stored(func.as_inner());
+        let trampoline = self
+            .trampolines
+            .get(entity_index)
+            .unwrap_or_else(|| panic!("failed to resolve stored host function: {entity_index:?}"));
+        (trampoline, self.inner.fuel_mut())
+    }
+
     /// Sets a callback function that is executed whenever a WebAssembly
     /// function is called from the host or a host function is called from
     /// WebAssembly, or these functions return.
@@ -1064,6 +1203,174 @@ impl<T> Store<T> {
         }
     }
 
+    /// Sets a callback function that is executed before every executed Wasmi instruction.
+    ///
+    /// The function is passed a `&mut T` to the underlying store and a [`TraceEvent`]
+    /// describing the executed instruction and the registers of its function frame.
+    ///
+    /// # Note
+    ///
+    /// - Wasmi instructions are the translated Wasmi bytecode and not the original
+    ///   Wasm operators. Use [`TraceEvent::offset`] to map instructions back to the
+    ///   Wasm operator from which they were translated.
+    /// - Instructions of Wasm functions inlined via [`Config::inlining`] are reported
+    ///   as instructions of their caller at the Wasm offset of the inlined call.
+    /// - Returning an error from the callback aborts the execution with that error.
+    /// - Tracing severely slows down execution.
+    #[cfg(feature = "trace")]
+    pub fn trace_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, &TraceEvent) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.trace_hook = Some(TraceHookWrapper(Box::new(hook)));
+    }
+
+    /// Returns `true` if a callback has been set via [`Store::trace_hook`].
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub(crate) fn has_trace_hook(&self) -> bool {
+        self.trace_hook.is_some()
+    }
+
+    /// Executes the callback set by [`Store::trace_hook`] for `event` if any has been set.
+    #[cfg(feature = "trace")]
+    pub(crate) fn invoke_trace_hook(&mut self, event: &TraceEvent) -> Result<(), Error> {
+        match self.trace_hook.as_mut() {
+            None => Ok(()),
+            Some(trace_hook) => trace_hook.0(&mut self.data, event),
+        }
+    }
+
+    /// Installs the [`Debugger`] that is notified during Wasm execution.
+    ///
+    /// Returns the previously installed [`Debugger`] if any.
+    ///
+    /// # Note
+    ///
+    /// The [`Debugger`] is only notified when executing Wasm functions that
+    /// have been compiled with [`Config::debugging`] enabled.
+    pub fn set_debugger(&mut self, debugger: impl Debugger + 'static) -> Option<Box<dyn Debugger>> {
+        self.debug.set_debugger(Box::new(debugger))
+    }
+
+    /// Removes the installed [`Debugger`] if any.
+    pub fn remove_debugger(&mut self) -> Option<Box<dyn Debugger>> {
+        self.debug.take_debugger()
+    }
+
+    /// Returns a shared reference to the [`Breakpoints`] of the [`Store`].
+    pub fn breakpoints(&self) -> &Breakpoints {
+        self.debug.breakpoints()
+    }
+
+    /// Returns an exclusive reference to the [`Breakpoints`] of the [`Store`].
+    pub fn breakpoints_mut(&mut self) -> &mut Breakpoints {
+        self.debug.breakpoints_mut()
+    }
+
+    /// Returns a shared reference to the [`Watchpoints`] of the [`Store`].
+    pub fn watchpoints(&self) -> &Watchpoints {
+        self.debug.watchpoints()
+    }
+
+    /// Returns an exclusive reference to the [`Watchpoints`] of the [`Store`].
+    pub fn watchpoints_mut(&mut self) -> &mut Watchpoints {
+        self.debug.watchpoints_mut()
+    }
+
+    /// Adds a watchpoint observing accesses of `kind` to the byte `range` of `memory`.
+    ///
+    /// The installed [`Debugger`] is notified via [`Debugger::on_watchpoint`] before
+    /// a Wasm operator accesses the watched memory. The notification reports the
+    /// function and Wasm offset of the accessing Wasm operator.
+    ///
+    /// Returns `false` if the same watchpoint already existed.
+    ///
+    /// # Note
+    ///
+    /// Like all [`Debugger`] notifications watchpoints are only hit by Wasm
+    /// functions compiled with [`Config::debugging`] enabled.
+    ///
+    /// # Errors
+    ///
+    /// If [`Config::debugging`] is disabled for the [`Engine`] of the [`Store`].
+    pub fn add_watchpoint(
+        &mut self,
+        memory: Memory,
+        range: Range<u64>,
+        kind: WatchKind,
+    ) -> Result<bool, Error> {
+        if !self.debug.is_debugging_enabled() {
+            return Err(Error::from(ConfigError::DebuggingDisabled));
+        }
+        Ok(self.debug.watchpoints_mut().insert(memory, range, kind))
+    }
+
+    /// Removes the watchpoint observing accesses of `kind` to the byte `range` of `memory`.
+    ///
+    /// Returns `true` if the watchpoint existed.
+    pub fn remove_watchpoint(
+        &mut self,
+        memory: Memory,
+        range: Range<u64>,
+        kind: WatchKind,
+    ) -> bool {
+        self.debug.watchpoints_mut().remove(memory, range, kind)
+    }
+
+    /// Enables or disables single stepping of the installed [`Debugger`].
+    ///
+    /// If enabled the [`Debugger`] is notified via [`Debugger::on_step`] before the
+    /// next Wasm operator is executed.
+    pub fn set_single_stepping(&mut self, enable: bool) {
+        self.debug.set_stepping(enable)
+    }
+
+    /// Enables or disables profiling of Wasm function calls.
+    ///
+    /// While enabled the [`Store`] records the number of calls and the consumed
+    /// fuel of every executed Wasm function into its [`Profile`].
+    ///
+    /// # Note
+    ///
+    /// Fuel is only recorded if [`Config::consume_fuel`] is enabled.
+    pub fn set_profiling(&mut self, enable: bool) {
+        self.inner.profiler.set_enabled(enable)
+    }
+
+    /// Returns the [`Profile`] recorded so far.
+    pub fn profile(&self) -> &Profile {
+        self.inner.profiler.profile()
+    }
+
+    /// Returns the [`Profile`] recorded so far and starts recording a new one.
+    pub fn take_profile(&mut self) -> Profile {
+        self.inner.profiler.take_profile()
+    }
+
+    /// Returns the [`Coverage`] recorded so far.
+    ///
+    /// # Note
+    ///
+    /// Coverage is only recorded for Wasm functions compiled with [`Config::coverage`] enabled.
+    pub fn coverage(&self) -> &Coverage {
+        &self.inner.coverage
+    }
+
+    /// Returns the [`Coverage`] recorded so far and resets all coverage counters.
+    pub fn take_coverage(&mut self) -> Coverage {
+        mem::take(&mut self.inner.coverage)
+    }
+
+    /// Returns the [`StoreStats`] of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is cheap to call since it only sums up the sizes of memories and tables.
+    pub fn stats(&self) -> StoreStats {
+        self.inner.stats()
+    }
+
     /// Utility function to invoke the [`Store::call_hook`] that is asserted to
     /// be available in this case.
     ///
@@ -0,0 +1,172 @@
+use crate::{
+    core::UntypedVal,
+    engine::FrameRegisters,
+    ir::{Instruction, Reg},
+    Instance,
+};
+use core::fmt;
+
+/// An executed Wasmi bytecode instruction reported to the hook set via [`Store::trace_hook`].
+///
+/// [`Store::trace_hook`]: crate::Store::trace_hook
+#[derive(Debug)]
+pub struct TraceEvent<'a> {
+    /// The [`Instance`] of the executed function.
+    pub(crate) instance: Instance,
+    /// The index of the executed function within its Wasm module.
+    pub(crate) func_index: u32,
+    /// The Wasm offset of the Wasm operator from which `instr` was translated if known.
+    pub(crate) offset: Option<u32>,
+    /// The position of `instr` within the compiled function.
+    pub(crate) pos: usize,
+    /// The executed [`Instruction`].
+    pub(crate) instr: &'a Instruction,
+    /// The registers of the executed function frame.
+    pub(crate) registers: TraceRegisters<'a>,
+}
+
+impl<'a> TraceEvent<'a> {
+    /// Returns the [`Instance`] of the executed function.
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the index of the executed function within its Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the byte offset within the Wasm binary of the Wasm operator from which the instruction was translated.
+    ///
+    /// Returns `None` for instructions that do not originate from a Wasm operator.
+    pub fn offset(&self) -> Option<u32> {
+        self.offset
+    }
+
+    /// Returns the position of the instruction within its compiled function.
+    ///
+    /// This is the position used by [`Module::disassemble`].
+    ///
+    /// [`Module::disassemble`]: crate::Module::disassemble
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the executed instruction.
+    pub fn instr(&self) -> TraceInstr<'a> {
+        TraceInstr {
+            instr: self.instr,
+            pos: self.pos,
+        }
+    }
+
+    /// Returns a read-only view of the registers of the executed function frame.
+    pub fn registers(&self) -> &TraceRegisters<'a> {
+        &self.registers
+    }
+}
+
+/// A read-only view of an executed Wasmi bytecode instruction.
+///
+/// # Note
+///
+/// Wasmi bytecode is an implementation detail of Wasmi. Therefore its
+/// instruction names and operands are not covered by semantic versioning.
+#[derive(Debug, Copy, Clone)]
+pub struct TraceInstr<'a> {
+    /// The executed [`Instruction`].
+    instr: &'a Instruction,
+    /// The position of `instr` within the compiled function.
+    pos: usize,
+}
+
+impl TraceInstr<'_> {
+    /// Returns the name of the instruction, e.g. `i32_add`.
+    pub fn name(&self) -> &'static str {
+        self.instr.name()
+    }
+}
+
+impl fmt::Display for TraceInstr<'_> {
+    /// Formats the instruction followed by its operands.
+    ///
+    /// This is the same format as used by [`Module::disassemble`].
+    ///
+    /// [`Module::disassemble`]: crate::Module::disassemble
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.instr.display(self.pos).fmt(f)
+    }
+}
+
+/// A read-only view of the registers of an executed function frame.
+pub struct TraceRegisters<'a> {
+    /// The registers of the executed function frame.
+    regs: &'a FrameRegisters,
+    /// The number of function local constant values.
+    len_consts: u16,
+    /// The number of registers excluding function local constant values.
+    len_registers: u16,
+}
+
+impl fmt::Debug for TraceRegisters<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceRegisters")
+            .field("len_consts", &self.len_consts)
+            .field("len_registers", &self.len_registers)
+            .finish()
+    }
+}
+
+impl<'a> TraceRegisters<'a> {
+    /// Creates a new [`TraceRegisters`] for `regs` of a compiled function.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that all `len_consts` constant values and
+    /// `len_registers` registers of `regs` are valid to read.
+    pub(crate) unsafe fn new(
+        regs: &'a FrameRegisters,
+        len_consts: u16,
+        len_registers: u16,
+    ) -> Self {
+        Self {
+            regs,
+            len_consts,
+            len_registers,
+        }
+    }
+
+    /// Returns the number of registers of the function frame.
+    ///
+    /// # Note
+    ///
+    /// This excludes the registers referring to function local constant values.
+    pub fn len(&self) -> u16 {
+        self.len_registers
+    }
+
+    /// Returns `true` if the function frame has no registers.
+    pub fn is_empty(&self) -> bool {
+        self.len_registers == 0
+    }
+
+    /// Returns the value of the register at `index`.
+    ///
+    /// Negative indices refer to function local constant values.
+    /// Register `rN` of a displayed [`TraceInstr`] is at index `N`.
+    ///
+    /// Returns `None` if `index` is out of bounds for the function frame.
+    pub fn get(&self, index: i16) -> Option<UntypedVal> {
+        let in_bounds = -i32::from(self.len_consts) <= i32::from(index)
+            && i32::from(index) < i32::from(self.len_registers);
+        if !in_bounds {
+            return None;
+        }
+        // Safety: we just checked that `index` is within the bounds of the function frame.
+        Some(unsafe { self.regs.get(Reg::from(index)) })
+    }
+}
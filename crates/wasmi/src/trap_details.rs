@@ -0,0 +1,188 @@
+use crate::{
+    core::{TrapCode, ValType},
+    FuncType,
+};
+use core::fmt;
+
+/// Structured details about the fault that caused a Wasm trap.
+///
+/// Accessible via [`Error::trap_details`] for traps caused by out of bounds
+/// memory or table accesses and failed indirect calls.
+///
+/// [`Error::trap_details`]: crate::Error::trap_details
+#[derive(Debug, Clone)]
+pub struct TrapDetails {
+    /// The fault that caused the trap.
+    fault: TrapFault,
+    /// The offset of the trapping Wasm operator within the Wasm binary if known.
+    offset: Option<u32>,
+}
+
+/// The fault of [`TrapDetails`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrapFault {
+    /// A linear memory access was out of bounds.
+    ///
+    /// Belongs to [`TrapCode::MemoryOutOfBounds`].
+    MemoryOutOfBounds {
+        /// The index of the accessed linear memory within its instance.
+        memory: u32,
+        /// The dynamic address operand of the access.
+        address: u32,
+        /// The static offset of the access.
+        ///
+        /// This is zero for bulk memory operations.
+        offset: u32,
+        /// The number of accessed bytes.
+        len: u32,
+    },
+    /// A table access was out of bounds.
+    ///
+    /// Belongs to [`TrapCode::TableOutOfBounds`].
+    TableOutOfBounds {
+        /// The index of the accessed table within its instance.
+        table: u32,
+        /// The accessed table element index.
+        index: u32,
+        /// The size of the table at the time of the access.
+        size: u32,
+    },
+    /// An indirect call referred to a null function reference.
+    ///
+    /// Belongs to [`TrapCode::IndirectCallToNull`].
+    IndirectCallToNull {
+        /// The index of the table of the indirect call within its instance.
+        table: u32,
+        /// The table element index of the indirect call.
+        index: u32,
+    },
+    /// An indirect call referred to a function with an unexpected signature.
+    ///
+    /// Belongs to [`TrapCode::BadSignature`].
+    BadSignature {
+        /// The index of the table of the indirect call within its instance.
+        table: u32,
+        /// The table element index of the indirect call.
+        index: u32,
+        /// The function type expected by the indirect call.
+        expected: FuncType,
+        /// The function type of the called function.
+        actual: FuncType,
+    },
+}
+
+impl TrapDetails {
+    /// Creates new [`TrapDetails`] for the `fault`.
+    pub(crate) fn new(fault: TrapFault) -> Self {
+        Self {
+            fault,
+            offset: None,
+        }
+    }
+
+    /// Returns the fault that caused the trap.
+    pub fn fault(&self) -> &TrapFault {
+        &self.fault
+    }
+
+    /// Returns the [`TrapCode`] of the trap.
+    pub fn trap_code(&self) -> TrapCode {
+        match self.fault {
+            TrapFault::MemoryOutOfBounds { .. } => TrapCode::MemoryOutOfBounds,
+            TrapFault::TableOutOfBounds { .. } => TrapCode::TableOutOfBounds,
+            TrapFault::IndirectCallToNull { .. } => TrapCode::IndirectCallToNull,
+            TrapFault::BadSignature { .. } => TrapCode::BadSignature,
+        }
+    }
+
+    /// Returns the offset of the trapping Wasm operator within the Wasm binary if known.
+    ///
+    /// # Note
+    ///
+    /// The offset is only known for Wasm functions compiled with the Wasm offsets of
+    /// their Wasm operators, for example if [`Config::wasm_backtrace`] is enabled.
+    ///
+    /// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+    pub fn offset(&self) -> Option<u32> {
+        self.offset
+    }
+
+    /// Sets the `offset` of the trapping Wasm operator.
+    pub(crate) fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+}
+
+impl fmt::Display for TrapDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.fault {
+            TrapFault::MemoryOutOfBounds {
+                memory,
+                address,
+                offset,
+                len,
+            } => write!(
+                f,
+                "memory {memory}: {len} byte access at address {address:#x} with offset {offset:#x}"
+            )?,
+            TrapFault::TableOutOfBounds { table, index, size } => {
+                write!(f, "table {table}: index {index} with table size {size}")?
+            }
+            TrapFault::IndirectCallToNull { table, index } => {
+                write!(f, "table {table}: null function reference at index {index}")?
+            }
+            TrapFault::BadSignature {
+                table,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "table {table}: index {index} expected {} but found {}",
+                DisplayFuncType(expected),
+                DisplayFuncType(actual),
+            )?,
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at wasm offset {offset:#x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays a [`FuncType`] in the Wasm text format.
+struct DisplayFuncType<'a>(&'a FuncType);
+
+impl fmt::Display for DisplayFuncType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(func")?;
+        if !self.0.params().is_empty() {
+            write!(f, " (param")?;
+            for param in self.0.params() {
+                write!(f, " {}", display_val_type(param))?;
+            }
+            write!(f, ")")?;
+        }
+        if !self.0.results().is_empty() {
+            write!(f, " (result")?;
+            for result in self.0.results() {
+                write!(f, " {}", display_val_type(result))?;
+            }
+            write!(f, ")")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Returns the Wasm text format name of `ty`.
+fn display_val_type(ty: &ValType) -> &'static str {
+    match ty {
+        ValType::I32 => "i32",
+        ValType::I64 => "i64",
+        ValType::F32 => "f32",
+        ValType::F64 => "f64",
+        ValType::FuncRef => "funcref",
+        ValType::ExternRef => "externref",
+    }
+}
@@ -0,0 +1,118 @@
+//! Tests for the compilation of lazily compiled Wasm functions via [`Config::background_compilation`].
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+use wasmi::{CompilationMode, Config, Engine, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (func $inc (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $double (param i32) (result i32)
+            (i32.mul (local.get 0) (i32.const 2))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $double (call $inc (local.get 0)))
+        )
+    )
+"#;
+
+/// Returns a [`Config`] that compiles Wasm functions with `mode` on a background thread.
+fn config(mode: CompilationMode) -> Config {
+    let mut config = Config::default();
+    config.compilation_mode(mode).background_compilation(true);
+    config
+}
+
+/// Waits until the [`Engine`] has compiled `expected` Wasm functions.
+///
+/// # Panics
+///
+/// If the [`Engine`] did not compile `expected` Wasm functions within a few seconds.
+fn wait_for_compiled_funcs(engine: &Engine, expected: usize) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while engine.stats().compiled_funcs() < expected {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for background compilation: {:?}",
+            engine.stats()
+        );
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn compiles_all_funcs() {
+    let engine = Engine::new(&config(CompilationMode::LazyTranslation));
+    let module = Module::new(&engine, WAT).unwrap();
+    wait_for_compiled_funcs(&engine, 3);
+    assert_eq!(engine.stats().uncompiled_funcs(), 0);
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 20).unwrap(), 42);
+    assert_eq!(engine.stats().compiled_funcs(), 3);
+}
+
+#[test]
+fn compiles_funcs_of_all_modules() {
+    let engine = Engine::new(&config(CompilationMode::LazyTranslation));
+    let _modules = (0..4)
+        .map(|_| Module::new(&engine, WAT).unwrap())
+        .collect::<Vec<_>>();
+    wait_for_compiled_funcs(&engine, 12);
+    assert_eq!(engine.stats().uncompiled_funcs(), 0);
+}
+
+#[test]
+fn disabled_by_default() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::LazyTranslation);
+    let engine = Engine::new(&config);
+    let _module = Module::new(&engine, WAT).unwrap();
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(engine.stats().compiled_funcs(), 0);
+    assert_eq!(engine.stats().uncompiled_funcs(), 3);
+}
+
+#[test]
+fn lazy_validation_on_demand() {
+    let wat = r#"
+        (module
+            (func (export "valid") (result i32)
+                (i32.const 1)
+            )
+            (func (export "invalid") (result i32)
+                (i64.const 1)
+            )
+        )
+    "#;
+    let mut expected = Config::default();
+    expected.compilation_mode(CompilationMode::Lazy);
+    let expected_engine = Engine::new(&expected);
+    let engine = Engine::new(&config(CompilationMode::Lazy));
+    let call = |engine: &Engine, name: &str| {
+        let module = Module::new(engine, wat).unwrap();
+        let mut store = Store::new(engine, ());
+        let instance = Linker::new(engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        instance
+            .get_typed_func::<(), i32>(&store, name)
+            .unwrap()
+            .call(&mut store, ())
+    };
+    let expected_error = call(&expected_engine, "invalid").unwrap_err();
+    let error = call(&engine, "invalid").unwrap_err();
+    assert_eq!(error.to_string(), expected_error.to_string());
+    assert_eq!(call(&engine, "valid").unwrap(), 1);
+}
@@ -0,0 +1,118 @@
+//! Tests for the Wasm backtraces captured via [`Config::wasm_backtrace`].
+
+use wasmi::{Config, Engine, Error, Linker, Module, OptimizationLevel, Store};
+use wasmparser::{Operator, Parser, Payload};
+
+const WASM: &str = r#"
+    (module
+        (func (export "run") (param i32) (result i32)
+            (i32.add (i32.const 1) (call $inner (local.get 0)))
+        )
+        (func $inner (param i32) (result i32)
+            (i32.div_u (i32.const 10) (local.get 0))
+        )
+    )
+"#;
+
+/// Calls `run` with `0` so that it traps and returns the resulting [`Error`].
+fn run(wasm_backtrace: bool) -> Error {
+    let mut config = Config::default();
+    config.wasm_backtrace(wasm_backtrace);
+    run_with(&Engine::new(&config), 0)
+}
+
+/// Calls `run` `warmup` times with `1` and then with `0` so that it traps.
+///
+/// Returns the resulting [`Error`] of the trapping call.
+fn run_with(engine: &Engine, warmup: usize) -> Error {
+    let module = Module::new(engine, WASM).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    for _ in 0..warmup {
+        assert_eq!(run.call(&mut store, 1).unwrap(), 11);
+    }
+    run.call(&mut store, 0).unwrap_err()
+}
+
+/// Asserts that the `error` has a backtrace of the `run` and `inner` call frames.
+fn assert_frames(error: &Error) {
+    let backtrace = error.backtrace().unwrap();
+    let frames = backtrace.frames();
+    let calls = find_operators(|op| matches!(op, Operator::Call { .. }));
+    let divs = find_operators(|op| matches!(op, Operator::I32DivU));
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 1);
+    assert_eq!(frames[0].offset(), divs[0]);
+    assert_eq!(frames[1].func_index(), 0);
+    assert_eq!(frames[1].offset(), calls[0]);
+    assert!(frames.iter().all(|frame| frame.source_frames().is_empty()));
+}
+
+/// Returns the offsets of all operators of the Wasm module that satisfy `filter`.
+fn find_operators(filter: fn(&Operator) -> bool) -> Vec<u32> {
+    let wasm = wat::parse_str(WASM).unwrap();
+    let mut offsets = Vec::new();
+    for payload in Parser::new(0).parse_all(&wasm) {
+        let Payload::CodeSectionEntry(body) = payload.unwrap() else {
+            continue;
+        };
+        for operator in body
+            .get_operators_reader()
+            .unwrap()
+            .into_iter_with_offsets()
+        {
+            let (operator, offset) = operator.unwrap();
+            if filter(&operator) {
+                offsets.push(offset as u32);
+            }
+        }
+    }
+    offsets
+}
+
+#[test]
+fn disabled() {
+    let error = run(false);
+    assert!(error.backtrace().is_none());
+    assert!(!error.to_string().contains("wasm backtrace:"));
+}
+
+#[test]
+fn frames() {
+    assert_frames(&run(true));
+}
+
+#[test]
+fn optimized_and_tiered_up() {
+    let mut config = Config::default();
+    config
+        .wasm_backtrace(true)
+        .optimization_level(OptimizationLevel::Full);
+    assert_frames(&run_with(&Engine::new(&config), 0));
+    let mut config = Config::default();
+    config
+        .wasm_backtrace(true)
+        .tiering(true)
+        .tier_up_threshold(2);
+    let engine = Engine::new(&config);
+    assert_frames(&run_with(&engine, 5));
+    assert_eq!(engine.stats().tiered_up_funcs(), 2);
+}
+
+#[test]
+fn display() {
+    let error = run(true);
+    let message = error.to_string();
+    let (trap, backtrace) = message.split_once('\n').unwrap();
+    assert!(trap.contains("integer divide by zero"));
+    let lines = backtrace.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "wasm backtrace:");
+    assert!(lines[1].starts_with("    0: 0x") && lines[1].ends_with(" - inner"));
+    assert!(lines[2].starts_with("    1: 0x") && lines[2].ends_with(" - func[0]"));
+}
@@ -0,0 +1,199 @@
+//! Tests for the Wasm core dumps captured via [`Config::coredump_on_trap`].
+
+use wasmi::{Config, Engine, Error, Linker, Module, Store, Val};
+use wasmparser::{Parser, Payload, Validator};
+
+const WASM: &str = r#"
+    (module
+        (memory 1 2)
+        (global (mut i32) (i32.const 42))
+        (data (i32.const 16) "hi")
+        (func (export "run") (param i32) (result i32)
+            (local i64)
+            (local.set 1 (i64.const 7))
+            (i32.add (i32.const 1) (call $inner (local.get 0)))
+        )
+        (func $inner (param i32) (result i32)
+            (i32.div_u (i32.const 10) (local.get 0))
+        )
+    )
+"#;
+
+/// Calls `run` with `0` so that it traps and returns the resulting [`Error`].
+fn run(coredump_on_trap: bool) -> Error {
+    let mut config = Config::default();
+    config.coredump_on_trap(coredump_on_trap);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    run.call(&mut store, 0).unwrap_err()
+}
+
+/// Returns the integer values of `values`.
+fn ints(values: &[Option<Val>]) -> Vec<Option<i64>> {
+    values
+        .iter()
+        .map(|value| match value {
+            Some(Val::I32(value)) => Some(i64::from(*value)),
+            Some(Val::I64(value)) => Some(*value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn disabled() {
+    assert!(run(false).coredump().is_none());
+}
+
+#[test]
+fn frames() {
+    let error = run(true);
+    let coredump = error.coredump().unwrap();
+    let frames = coredump.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 1);
+    assert_eq!(ints(frames[0].locals()), [Some(0)]);
+    assert_eq!(ints(frames[0].stack()), [Some(10), Some(0)]);
+    assert_eq!(frames[1].func_index(), 0);
+    assert_eq!(ints(frames[1].locals()), [Some(0), Some(7)]);
+    assert_eq!(ints(frames[1].stack()), [Some(1), Some(0)]);
+    assert!(frames[1].offset() < frames[0].offset());
+}
+
+#[test]
+fn no_debug_steps() {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    let module = Module::new(&Engine::new(&config), WASM).unwrap();
+    let mut config = Config::default();
+    config.debugging(true);
+    let debug_module = Module::new(&Engine::new(&config), WASM).unwrap();
+    for func_index in 0..2 {
+        let instrs = module.disassemble(func_index).unwrap();
+        assert!(!instrs.contains("debug_step"));
+        assert!(debug_module
+            .disassemble(func_index)
+            .unwrap()
+            .contains("debug_step"));
+    }
+}
+
+#[test]
+fn serialize() {
+    let error = run(true);
+    let bytes = error.coredump().unwrap().serialize("test.wasm");
+    Validator::new().validate_all(&bytes).unwrap();
+    let mut custom_sections = Vec::new();
+    let mut memories = Vec::new();
+    let mut globals = 0;
+    let mut data = Vec::new();
+    for payload in Parser::new(0).parse_all(&bytes) {
+        match payload.unwrap() {
+            Payload::CustomSection(section) => {
+                custom_sections.push(section.name().to_string());
+                if section.name() == "core" {
+                    assert_eq!(section.data(), b"\x00\x09test.wasm");
+                }
+            }
+            Payload::MemorySection(section) => {
+                for memory in section {
+                    let memory = memory.unwrap();
+                    memories.push((memory.initial, memory.maximum));
+                }
+            }
+            Payload::GlobalSection(section) => {
+                for global in section {
+                    assert!(global.unwrap().ty.mutable);
+                    globals += 1;
+                }
+            }
+            Payload::DataSection(section) => {
+                for segment in section {
+                    data.push(segment.unwrap().data.to_vec());
+                }
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(
+        custom_sections,
+        ["core", "coremodules", "coreinstances", "corestack"]
+    );
+    assert_eq!(memories, [(1, Some(2))]);
+    assert_eq!(globals, 1);
+    assert_eq!(data, [b"hi".to_vec()]);
+}
+
+#[test]
+fn multiple_instances() {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    let engine = Engine::new(&config);
+    let callee = Module::new(
+        &engine,
+        r#"
+        (module
+            (memory 1)
+            (func (export "inner") (param i32) (result i32)
+                (i32.div_u (i32.const 10) (local.get 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let caller = Module::new(
+        &engine,
+        r#"
+        (module
+            (import "callee" "inner" (func $inner (param i32) (result i32)))
+            (memory 2 3)
+            (func (export "run") (param i32) (result i32)
+                (i32.add (i32.const 1) (call $inner (local.get 0)))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    let callee = linker
+        .instantiate(&mut store, &callee)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    linker.instance(&mut store, "callee", callee).unwrap();
+    let error = linker
+        .instantiate(&mut store, &caller)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, 0)
+        .unwrap_err();
+    let coredump = error.coredump().unwrap();
+    let frames = coredump.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 0);
+    assert_eq!(ints(frames[0].stack()), [Some(10), Some(0)]);
+    assert_eq!(frames[1].func_index(), 1);
+    assert_eq!(ints(frames[1].stack()), [Some(1), Some(0)]);
+    let bytes = coredump.serialize("test.wasm");
+    let mut memories = Vec::new();
+    for payload in Parser::new(0).parse_all(&bytes) {
+        if let Payload::MemorySection(section) = payload.unwrap() {
+            for memory in section {
+                let memory = memory.unwrap();
+                memories.push((memory.initial, memory.maximum));
+            }
+        }
+    }
+    assert_eq!(memories, [(1, None), (2, Some(3))]);
+}
@@ -0,0 +1,88 @@
+//! Tests for the code coverage API of Wasmi.
+
+use wasmi::{CompilationMode, Config, Engine, FuncCoverage, Linker, Module, Store};
+
+const WASM: &str = r#"
+    (module
+        (func (export "abs") (param i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get 0) (i32.const 0))
+                (then (i32.sub (i32.const 0) (local.get 0)))
+                (else (local.get 0))
+            )
+        )
+        (func (export "sum") (param i32) (result i32)
+            (local i32)
+            (block
+                (loop
+                    (br_if 1 (i32.eqz (local.get 0)))
+                    (local.set 1 (i32.add (local.get 1) (local.get 0)))
+                    (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (local.get 1)
+        )
+        (func (export "unused"))
+    )
+"#;
+
+/// Calls `abs(-1)`, `abs(2)`, `abs(3)` and `sum(3)` and returns the resulting coverage.
+fn coverage(mode: CompilationMode) -> Vec<FuncCoverage> {
+    let mut config = Config::default();
+    config.coverage(true).compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let abs = instance.get_typed_func::<i32, i32>(&store, "abs").unwrap();
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(abs.call(&mut store, -1).unwrap(), 1);
+    assert_eq!(abs.call(&mut store, 2).unwrap(), 2);
+    assert_eq!(abs.call(&mut store, 3).unwrap(), 3);
+    assert_eq!(sum.call(&mut store, 3).unwrap(), 6);
+    let coverage = store.take_coverage().funcs(&module);
+    assert!(store.coverage().is_empty());
+    coverage
+}
+
+/// Returns the hit counts of all basic blocks of `func`.
+fn hits(func: &FuncCoverage) -> Vec<u64> {
+    func.blocks().iter().map(|block| block.hits()).collect()
+}
+
+#[test]
+fn eager() {
+    let funcs = coverage(CompilationMode::Eager);
+    let [abs, sum, unused] = &funcs[..] else {
+        panic!("unexpected coverage: {funcs:?}")
+    };
+    assert_eq!(abs.func_index(), 0);
+    assert_eq!(abs.hits(), 3);
+    // entry, then, else, after if
+    assert_eq!(hits(abs), [3, 1, 2, 3]);
+    assert_eq!(sum.hits(), 1);
+    // entry, loop header, after br_if, after block
+    //
+    // Note: the end of the loop is unreachable and thus has no counter.
+    assert_eq!(hits(sum), [1, 4, 3, 1]);
+    assert_eq!(unused.hits(), 0);
+    assert_eq!(hits(unused), [0]);
+    for func in &funcs {
+        assert!(func
+            .blocks()
+            .windows(2)
+            .all(|w| w[0].offset() < w[1].offset()));
+    }
+}
+
+#[test]
+fn lazy() {
+    let eager = coverage(CompilationMode::Eager);
+    let lazy = coverage(CompilationMode::Lazy);
+    // The `unused` function is never compiled and thus not reported.
+    assert_eq!(lazy, eager[..2]);
+}
@@ -0,0 +1,435 @@
+//! Tests for the [`Debugger`] API of Wasmi.
+
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+use wasmi::{
+    errors::{ConfigError, ErrorKind},
+    CompilationMode,
+    Config,
+    DebugAction,
+    DebugContext,
+    DebugLocation,
+    Debugger,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    WatchKind,
+    WatchpointHit,
+};
+
+/// A single event recorded by the [`TestDebugger`].
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Step {
+        offset: u32,
+    },
+    Breakpoint {
+        func_index: u32,
+        offset: u32,
+        locals: Vec<i32>,
+        operands: Vec<i32>,
+    },
+    Trap {
+        func_index: u32,
+        offset: u32,
+    },
+}
+
+/// A [`Debugger`] that records all its events.
+#[derive(Debug, Default, Clone)]
+struct TestDebugger {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl TestDebugger {
+    fn events(&self) -> Vec<Event> {
+        self.events.lock().unwrap().clone()
+    }
+
+    fn push(&self, event: Event) {
+        self.events.lock().unwrap().push(event)
+    }
+}
+
+impl Debugger for TestDebugger {
+    fn on_breakpoint(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+        let locals = (0..ctx.len_locals() as u32)
+            .map(|index| ctx.local(index).unwrap().i32().unwrap())
+            .collect();
+        let operands = (0..ctx.len_operands())
+            .map(|index| i32::from(ctx.operand(index).unwrap()))
+            .collect();
+        self.push(Event::Breakpoint {
+            func_index: ctx.location().func_index(),
+            offset: ctx.location().offset(),
+            locals,
+            operands,
+        });
+        Ok(DebugAction::Continue)
+    }
+
+    fn on_step(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+        self.push(Event::Step {
+            offset: ctx.location().offset(),
+        });
+        Ok(DebugAction::Step)
+    }
+
+    fn on_trap(&mut self, location: DebugLocation, _error: &Error) {
+        self.push(Event::Trap {
+            func_index: location.func_index(),
+            offset: location.offset(),
+        });
+    }
+}
+
+const WASM: &str = r#"
+    (module
+        (func (export "f") (param i32 i32) (result i32)
+            (local i32)
+            (local.set 2 (i32.add (local.get 0) (local.get 1)))
+            (i32.mul (local.get 2) (i32.const 2))
+        )
+        (func (export "trap")
+            (unreachable)
+        )
+    )
+"#;
+
+fn setup(mode: CompilationMode) -> (Store<()>, Module, Instance, TestDebugger) {
+    let mut config = Config::default();
+    config.debugging(true).compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let debugger = TestDebugger::default();
+    store.set_debugger(debugger.clone());
+    (store, module, instance, debugger)
+}
+
+/// Returns the offsets of all Wasm operators of `f` by single stepping through it.
+fn step_offsets(mode: CompilationMode) -> Vec<u32> {
+    let (mut store, _module, instance, debugger) = setup(mode);
+    let f = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "f")
+        .unwrap();
+    store.set_single_stepping(true);
+    assert_eq!(f.call(&mut store, (3, 4)).unwrap(), 14);
+    debugger
+        .events()
+        .into_iter()
+        .map(|event| match event {
+            Event::Step { offset } => offset,
+            event => panic!("unexpected event: {event:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn single_stepping() {
+    let offsets = step_offsets(CompilationMode::Eager);
+    // local.get, local.get, i32.add, local.set, local.get, i32.const, i32.mul, end
+    assert_eq!(offsets.len(), 8);
+    assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn single_stepping_lazy() {
+    let expected = step_offsets(CompilationMode::Eager);
+    assert_eq!(step_offsets(CompilationMode::Lazy), expected);
+    assert_eq!(step_offsets(CompilationMode::LazyTranslation), expected);
+}
+
+#[test]
+fn breakpoints() {
+    let offsets = step_offsets(CompilationMode::Eager);
+    let (mut store, module, instance, debugger) = setup(CompilationMode::Eager);
+    let f = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "f")
+        .unwrap();
+    // Position of `i32.add` and `i32.mul` respectively.
+    let (add, mul) = (offsets[2], offsets[6]);
+    assert!(store.breakpoints_mut().insert(&module, 0, add));
+    assert!(store.breakpoints_mut().insert(&module, 0, mul));
+    assert!(!store.breakpoints_mut().insert(&module, 2, mul));
+    assert_eq!(f.call(&mut store, (3, 4)).unwrap(), 14);
+    assert_eq!(
+        debugger.events(),
+        [
+            Event::Breakpoint {
+                func_index: 0,
+                offset: add,
+                locals: vec![3, 4, 0],
+                operands: vec![3, 4],
+            },
+            Event::Breakpoint {
+                func_index: 0,
+                offset: mul,
+                locals: vec![3, 4, 7],
+                operands: vec![7, 2],
+            },
+        ]
+    );
+    assert!(store.breakpoints_mut().remove(&module, 0, add));
+    assert!(!store.breakpoints_mut().remove(&module, 0, add));
+    store.breakpoints_mut().clear();
+    assert!(store.breakpoints().is_empty());
+}
+
+#[test]
+fn trap() {
+    let (mut store, _module, instance, debugger) = setup(CompilationMode::Eager);
+    let trap = instance.get_typed_func::<(), ()>(&store, "trap").unwrap();
+    trap.call(&mut store, ()).unwrap_err();
+    let events = debugger.events();
+    assert!(matches!(&events[..], [Event::Trap { func_index: 1, .. }]));
+}
+
+#[test]
+fn call_stack_and_memory() {
+    /// The function indices of the call stack, a byte of memory and a global variable value.
+    type Seen = (Vec<u32>, u8, i32);
+
+    /// Records the call stack and memory at the first step within `callee`.
+    #[derive(Debug, Default, Clone)]
+    struct Inspector {
+        seen: Arc<Mutex<Option<Seen>>>,
+    }
+
+    impl Debugger for Inspector {
+        fn on_step(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+            let call_stack = ctx.call_stack();
+            if call_stack.len() == 2 {
+                let funcs = call_stack.iter().map(DebugLocation::func_index).collect();
+                let byte = ctx.memory(0).unwrap()[42];
+                let global = ctx.global(0).unwrap().i32().unwrap();
+                self.seen
+                    .lock()
+                    .unwrap()
+                    .get_or_insert((funcs, byte, global));
+            }
+            Ok(DebugAction::Step)
+        }
+    }
+
+    let wasm = r#"
+        (module
+            (memory 1)
+            (global i32 (i32.const 7))
+            (data (i32.const 42) "\2A")
+            (func $callee (result i32)
+                (i32.const 1)
+            )
+            (func (export "caller") (result i32)
+                (call $callee)
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.debugging(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let inspector = Inspector::default();
+    store.set_debugger(inspector.clone());
+    store.set_single_stepping(true);
+    let caller = instance
+        .get_typed_func::<(), i32>(&store, "caller")
+        .unwrap();
+    assert_eq!(caller.call(&mut store, ()).unwrap(), 1);
+    assert_eq!(
+        inspector.seen.lock().unwrap().clone(),
+        Some((vec![0, 1], 0x2A, 7))
+    );
+}
+
+#[test]
+fn caller_frames() {
+    /// The locals and operands of the callee and caller frames.
+    type Seen = Vec<(Vec<i32>, Vec<i32>)>;
+
+    /// Records the callee and caller frames at the first step within `callee`.
+    #[derive(Debug, Default, Clone)]
+    struct Inspector {
+        seen: Arc<Mutex<Option<Seen>>>,
+    }
+
+    impl Debugger for Inspector {
+        fn on_step(&mut self, ctx: &mut DebugContext) -> Result<DebugAction, Error> {
+            let call_stack = ctx.call_stack();
+            if call_stack.len() == 2 {
+                assert!(ctx.frame(2).is_none());
+                let frames = (0..2)
+                    .map(|depth| {
+                        let frame = ctx.frame(depth).unwrap();
+                        assert_eq!(frame.location(), call_stack[depth]);
+                        let locals = (0..frame.len_locals() as u32)
+                            .map(|index| frame.local(index).unwrap().i32().unwrap())
+                            .collect();
+                        let operands = (0..frame.len_operands())
+                            .map(|index| i32::from(frame.operand(index).unwrap()))
+                            .collect();
+                        (locals, operands)
+                    })
+                    .collect();
+                self.seen.lock().unwrap().get_or_insert(frames);
+            }
+            Ok(DebugAction::Step)
+        }
+    }
+
+    let wasm = r#"
+        (module
+            (func $callee (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (export "caller") (param i32) (result i32)
+                (local i32)
+                (local.set 1 (i32.const 5))
+                (i32.mul (local.get 1) (call $callee (local.get 0)))
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.debugging(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let inspector = Inspector::default();
+    store.set_debugger(inspector.clone());
+    store.set_single_stepping(true);
+    let caller = instance
+        .get_typed_func::<i32, i32>(&store, "caller")
+        .unwrap();
+    assert_eq!(caller.call(&mut store, 3).unwrap(), 20);
+    assert_eq!(
+        inspector.seen.lock().unwrap().clone(),
+        Some(vec![(vec![3], vec![]), (vec![3, 5], vec![5, 3])])
+    );
+}
+
+#[test]
+fn watchpoints() {
+    /// The function index, accessed memory range and access kind of a watchpoint hit.
+    type Hit = (u32, Range<u64>, WatchKind);
+
+    /// Records all watchpoint hits.
+    #[derive(Debug, Default, Clone)]
+    struct Watcher {
+        hits: Arc<Mutex<Vec<Hit>>>,
+    }
+
+    impl Debugger for Watcher {
+        fn on_watchpoint(
+            &mut self,
+            ctx: &mut DebugContext,
+            hit: &WatchpointHit,
+        ) -> Result<DebugAction, Error> {
+            assert_eq!(hit.watched(), 100..104);
+            let func_index = ctx.location().func_index();
+            self.hits
+                .lock()
+                .unwrap()
+                .push((func_index, hit.accessed(), hit.kind()));
+            Ok(DebugAction::Continue)
+        }
+    }
+
+    let wasm = r#"
+        (module
+            (memory (export "memory") 1)
+            (data "\01\02\03\04")
+            (func (export "run") (param $addr i32)
+                (drop (i32.load (local.get $addr)))
+                (i64.store offset=4 (local.get $addr) (i64.const 0))
+                (drop (i32.load8_u (i32.const 50)))
+                (memory.fill (i32.const 103) (i32.const 0) (i32.const 10))
+                (memory.copy (i32.const 0) (i32.const 98) (i32.const 3))
+                (memory.init 0 (i32.const 90) (i32.const 0) (i32.const 4))
+                (memory.fill (i32.const 100) (i32.const 0) (i32.const 0))
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.debugging(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let watcher = Watcher::default();
+    store.set_debugger(watcher.clone());
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let run = instance.get_typed_func::<i32, ()>(&store, "run").unwrap();
+    assert!(store
+        .add_watchpoint(memory, 100..104, WatchKind::Write)
+        .unwrap());
+    assert!(!store
+        .add_watchpoint(memory, 100..104, WatchKind::Write)
+        .unwrap());
+    run.call(&mut store, 96).unwrap();
+    assert_eq!(
+        *watcher.hits.lock().unwrap(),
+        [
+            (0, 100..108, WatchKind::Write),
+            (0, 103..113, WatchKind::Write),
+        ]
+    );
+    watcher.hits.lock().unwrap().clear();
+    assert!(store.remove_watchpoint(memory, 100..104, WatchKind::Write));
+    assert!(store
+        .add_watchpoint(memory, 100..104, WatchKind::ReadWrite)
+        .unwrap());
+    run.call(&mut store, 99).unwrap();
+    assert_eq!(
+        *watcher.hits.lock().unwrap(),
+        [
+            (0, 99..103, WatchKind::Read),
+            (0, 103..111, WatchKind::Write),
+            (0, 103..113, WatchKind::Write),
+            (0, 98..101, WatchKind::Read),
+        ]
+    );
+    store.watchpoints_mut().clear();
+    assert!(store.watchpoints().is_empty());
+}
+
+#[test]
+fn watchpoints_require_debugging() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let error = store
+        .add_watchpoint(memory, 0..4, WatchKind::Write)
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Config(ConfigError::DebuggingDisabled)
+    ));
+    assert!(store.watchpoints().is_empty());
+}
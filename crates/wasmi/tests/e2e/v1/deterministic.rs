@@ -0,0 +1,251 @@
+//! Tests to check if deterministic execution canonicalizes NaN values as intended.
+
+use wasmi::{
+    errors::{ConfigError, ErrorKind},
+    CompilationMode,
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    WasmParams,
+    WasmResults,
+};
+
+/// The canonical `f32` NaN bit pattern.
+const CANONICAL_NAN_F32: u32 = 0x7FC0_0000;
+
+/// The canonical `f64` NaN bit pattern.
+const CANONICAL_NAN_F64: u64 = 0x7FF8_0000_0000_0000;
+
+/// A non-canonical `f32` NaN bit pattern.
+const PAYLOAD_NAN_F32: u32 = 0xFFA0_0001;
+
+/// A non-canonical `f64` NaN bit pattern.
+const PAYLOAD_NAN_F64: u64 = 0xFFF4_0000_0000_0001;
+
+/// The test module with functions operating on the bits of float values.
+const WASM: &str = r#"
+    (module
+        (func (export "f32.add") (param i32 i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.add
+                    (f32.reinterpret_i32 (local.get 0))
+                    (f32.reinterpret_i32 (local.get 1))
+                )
+            )
+        )
+        (func (export "f32.add.imm") (param i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.add
+                    (f32.reinterpret_i32 (local.get 0))
+                    (f32.const -nan:0x200001)
+                )
+            )
+        )
+        (func (export "f32.add.consteval") (result i32)
+            (i32.reinterpret_f32
+                (f32.add
+                    (f32.const -nan:0x200001)
+                    (f32.const 1.0)
+                )
+            )
+        )
+        (func (export "f32.div") (param i32 i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.div
+                    (f32.reinterpret_i32 (local.get 0))
+                    (f32.reinterpret_i32 (local.get 1))
+                )
+            )
+        )
+        (func (export "f32.min.inf") (param i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.min
+                    (f32.reinterpret_i32 (local.get 0))
+                    (f32.const inf)
+                )
+            )
+        )
+        (func (export "f32.sqrt") (param i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.sqrt (f32.reinterpret_i32 (local.get 0)))
+            )
+        )
+        (func (export "f32.neg") (param i32) (result i32)
+            (i32.reinterpret_f32
+                (f32.neg (f32.reinterpret_i32 (local.get 0)))
+            )
+        )
+        (func (export "f32.demote_f64") (param i64) (result i32)
+            (i32.reinterpret_f32
+                (f32.demote_f64 (f64.reinterpret_i64 (local.get 0)))
+            )
+        )
+        (func (export "f64.mul") (param i64 i64) (result i64)
+            (i64.reinterpret_f64
+                (f64.mul
+                    (f64.reinterpret_i64 (local.get 0))
+                    (f64.reinterpret_i64 (local.get 1))
+                )
+            )
+        )
+        (func (export "f64.sqrt.consteval") (result i64)
+            (i64.reinterpret_f64
+                (f64.sqrt (f64.const -nan:0x4000000000001))
+            )
+        )
+        (func (export "f64.promote_f32") (param i32) (result i64)
+            (i64.reinterpret_f64
+                (f64.promote_f32 (f32.reinterpret_i32 (local.get 0)))
+            )
+        )
+        (func (export "f64.abs") (param i64) (result i64)
+            (i64.reinterpret_f64
+                (f64.abs (f64.reinterpret_i64 (local.get 0)))
+            )
+        )
+    )"#;
+
+/// Creates a [`Store`] with deterministic execution enabled and instantiates [`WASM`].
+fn setup() -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.deterministic(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, WASM).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of `instance` with `params` and returns its result.
+fn call<Params, Result>(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    params: Params,
+) -> Result
+where
+    Params: WasmParams,
+    Result: WasmResults,
+{
+    instance
+        .get_typed_func::<Params, Result>(&*store, name)
+        .unwrap()
+        .call(store, params)
+        .unwrap()
+}
+
+#[test]
+fn canonicalizes_f32_results() {
+    let (mut store, instance) = setup();
+    let nan = PAYLOAD_NAN_F32 as i32;
+    let one = 1.0_f32.to_bits() as i32;
+    let zero = 0.0_f32.to_bits() as i32;
+    let result = |r: i32| r as u32;
+    assert_eq!(
+        result(call(&mut store, instance, "f32.add", (nan, one))),
+        CANONICAL_NAN_F32
+    );
+    assert_eq!(
+        result(call(&mut store, instance, "f32.div", (zero, zero))),
+        CANONICAL_NAN_F32
+    );
+    assert_eq!(
+        result(call(&mut store, instance, "f32.sqrt", (nan,))),
+        CANONICAL_NAN_F32
+    );
+    assert_eq!(
+        result(call(&mut store, instance, "f32.min.inf", (nan,))),
+        CANONICAL_NAN_F32
+    );
+    assert_eq!(
+        result(call(
+            &mut store,
+            instance,
+            "f32.demote_f64",
+            (PAYLOAD_NAN_F64 as i64,)
+        )),
+        CANONICAL_NAN_F32
+    );
+    // Non-NaN results are unaffected.
+    assert_eq!(
+        result(call(&mut store, instance, "f32.add", (one, one))),
+        2.0_f32.to_bits()
+    );
+}
+
+#[test]
+fn canonicalizes_f64_results() {
+    let (mut store, instance) = setup();
+    let nan = PAYLOAD_NAN_F64 as i64;
+    let two = 2.0_f64.to_bits() as i64;
+    let result = |r: i64| r as u64;
+    assert_eq!(
+        result(call(&mut store, instance, "f64.mul", (nan, two))),
+        CANONICAL_NAN_F64
+    );
+    assert_eq!(
+        result(call(
+            &mut store,
+            instance,
+            "f64.promote_f32",
+            (PAYLOAD_NAN_F32 as i32,)
+        )),
+        CANONICAL_NAN_F64
+    );
+    assert_eq!(
+        result(call(&mut store, instance, "f64.mul", (two, two))),
+        4.0_f64.to_bits()
+    );
+}
+
+#[test]
+fn canonicalizes_translation_time_results() {
+    let (mut store, instance) = setup();
+    let one = 1.0_f32.to_bits() as i32;
+    assert_eq!(
+        call::<(), i32>(&mut store, instance, "f32.add.consteval", ()) as u32,
+        CANONICAL_NAN_F32
+    );
+    assert_eq!(
+        call::<(), i64>(&mut store, instance, "f64.sqrt.consteval", ()) as u64,
+        CANONICAL_NAN_F64
+    );
+    assert_eq!(
+        call::<_, i32>(&mut store, instance, "f32.add.imm", (one,)) as u32,
+        CANONICAL_NAN_F32
+    );
+}
+
+#[test]
+fn preserves_bitwise_float_results() {
+    let (mut store, instance) = setup();
+    assert_eq!(
+        call::<_, i32>(&mut store, instance, "f32.neg", (PAYLOAD_NAN_F32 as i32,)) as u32,
+        PAYLOAD_NAN_F32 ^ 0x8000_0000
+    );
+    assert_eq!(
+        call::<_, i64>(&mut store, instance, "f64.abs", (PAYLOAD_NAN_F64 as i64,)) as u64,
+        PAYLOAD_NAN_F64 & !(1 << 63)
+    );
+}
+
+#[test]
+fn rejects_lazy_compilation() {
+    for mode in [CompilationMode::Lazy, CompilationMode::LazyTranslation] {
+        let mut config = Config::default();
+        config.deterministic(true).compilation_mode(mode);
+        let engine = Engine::new(&config);
+        let error = Module::new(&engine, WASM).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::Config(ConfigError::NonDeterministicCompilationMode(_))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Tests for the disassembler of Wasmi.
+
+use wasmi::{CompilationMode, Config, Engine, Module};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (param i64) (result i64)
+            (local i64)
+            (block
+                (loop
+                    (br_if 1 (i64.eqz (local.get 0)))
+                    (local.set 1 (i64.add (local.get 1) (i64.const 100000)))
+                    (local.set 0 (i64.sub (local.get 0) (i64.const 1)))
+                    (br 0)
+                )
+            )
+            (local.get 1)
+        )
+    )
+"#;
+
+/// Asserts that the disassembled functions of [`WASM`] match their expected output using `mode`.
+fn assert_disassemble(mode: CompilationMode) {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    assert_eq!(
+        module.disassemble(1).unwrap(),
+        "\
+registers: 3
+  params: r0..r2
+  locals and temporaries: r2..r3
+consts: 0
+instrs: 2
+  0: r2 = i32_add lhs=r0 rhs=r1
+  1: return_reg value=r2
+"
+    );
+    assert_eq!(
+        module.disassemble(2).unwrap(),
+        "\
+registers: 3
+  params: r0..r1
+  locals and temporaries: r1..r3
+consts: 1
+  r-1 = 0x00000000000186A0
+instrs: 5
+  0: branch_i64_eq_imm16 lhs=r0 rhs=0 offset=@4
+  1: r1 = i64_add lhs=r1 rhs=r-1
+  2: r0 = i64_add_imm16 lhs=r0 rhs=-1
+  3: branch offset=@0
+  4: return_reg value=r1
+"
+    );
+    // Imported functions and out of bounds indices cannot be disassembled.
+    assert!(module.disassemble(0).is_err());
+    assert!(module.disassemble(3).is_err());
+}
+
+#[test]
+fn eager() {
+    assert_disassemble(CompilationMode::Eager);
+}
+
+#[test]
+fn lazy() {
+    assert_disassemble(CompilationMode::Lazy);
+}
@@ -0,0 +1,420 @@
+//! Tests for the DWARF source locations of [`Module::source_frames`] and Wasm backtraces.
+
+use gimli::{
+    constants,
+    write::{Address, AttributeValue, DwarfUnit, EndianVec, LineProgram, LineString, Sections},
+    Encoding,
+    Format,
+    LineEncoding,
+    LittleEndian,
+};
+use wasmi::{Config, Engine, Linker, Module, SourceFrame, Store};
+use wasmparser::{Operator, Parser, Payload};
+
+const WAT: &str = r#"
+    (module
+        (func (export "run")
+            (call $inner)
+        )
+        (func $inner
+            (nop)
+            (unreachable)
+        )
+    )
+"#;
+
+/// The offsets of the Wasm module of [`WAT`] required to build its DWARF sections.
+#[derive(Debug, Default)]
+struct Offsets {
+    /// The offset of the contents of the code section.
+    code_start: u32,
+    /// The offset one past the end of the code section.
+    code_end: u32,
+    /// The body ranges of `run` and `inner`.
+    bodies: Vec<(u32, u32)>,
+    /// The offset of the `call` operator of `run`.
+    call: u32,
+    /// The offset of the `unreachable` operator of `inner`.
+    unreachable: u32,
+}
+
+/// Returns the [`Offsets`] of the Wasm module `wasm`.
+fn offsets(wasm: &[u8]) -> Offsets {
+    let mut offsets = Offsets::default();
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload.unwrap() {
+            Payload::CodeSectionStart { range, .. } => {
+                offsets.code_start = range.start as u32;
+                offsets.code_end = range.end as u32;
+            }
+            Payload::CodeSectionEntry(body) => {
+                let range = body.range();
+                offsets.bodies.push((range.start as u32, range.end as u32));
+                for operator in body
+                    .get_operators_reader()
+                    .unwrap()
+                    .into_iter_with_offsets()
+                {
+                    match operator.unwrap() {
+                        (Operator::Call { .. }, offset) => offsets.call = offset as u32,
+                        (Operator::Unreachable, offset) => offsets.unreachable = offset as u32,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    offsets
+}
+
+/// Appends a custom section with `name` and `data` to the Wasm binary `wasm`.
+fn push_custom_section(wasm: &mut Vec<u8>, name: &str, data: &[u8]) {
+    let mut section = Vec::new();
+    leb(&mut section, name.len() as u64);
+    section.extend_from_slice(name.as_bytes());
+    section.extend_from_slice(data);
+    wasm.push(0x00);
+    leb(wasm, section.len() as u64);
+    wasm.extend_from_slice(&section);
+}
+
+/// Appends the unsigned LEB128 encoding of `value` to `bytes`.
+fn leb(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Returns a line number program that maps each `(address, line, column)` row and ends at `end`.
+fn line_program(rows: &[(u32, u8, u8)], end: u32) -> Vec<u8> {
+    let mut program = Vec::new();
+    let mut line = 1;
+    for &(address, row_line, column) in rows {
+        // DW_LNE_set_address
+        program.extend_from_slice(&[0x00, 0x05, 0x02]);
+        program.extend_from_slice(&address.to_le_bytes());
+        // DW_LNS_advance_line
+        program.push(0x03);
+        program.push((row_line - line) & 0x7F);
+        line = row_line;
+        // DW_LNS_set_column
+        program.extend_from_slice(&[0x05, column]);
+        // DW_LNS_copy
+        program.push(0x01);
+    }
+    // DW_LNE_set_address + DW_LNE_end_sequence
+    program.extend_from_slice(&[0x00, 0x05, 0x02]);
+    program.extend_from_slice(&end.to_le_bytes());
+    program.extend_from_slice(&[0x00, 0x01, 0x01]);
+    program
+}
+
+/// Returns a DWARF version 4 `.debug_line` unit for `src/lib.rs` with the `program`.
+fn line_unit(program: &[u8]) -> Vec<u8> {
+    let mut params = vec![
+        0x01, // minimum_instruction_length
+        0x01, // maximum_operations_per_instruction
+        0x01, // default_is_stmt
+        0xFB, // line_base = -5
+        0x0E, // line_range
+        0x0D, // opcode_base
+    ];
+    params.extend_from_slice(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1]);
+    // include_directories
+    params.extend_from_slice(b"src\0\0");
+    // file_names: `lib.rs` in directory 1
+    params.extend_from_slice(b"lib.rs\0\x01\x00\x00\0");
+    let mut unit = Vec::new();
+    unit.extend_from_slice(&4_u16.to_le_bytes());
+    unit.extend_from_slice(&(params.len() as u32).to_le_bytes());
+    unit.extend_from_slice(&params);
+    unit.extend_from_slice(program);
+    let mut bytes = (unit.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&unit);
+    bytes
+}
+
+/// Returns the `.debug_abbrev` section for [`info_unit`].
+fn abbrevs() -> Vec<u8> {
+    vec![
+        // 1: DW_TAG_compile_unit with children: DW_AT_stmt_list
+        0x01, 0x11, 0x01, 0x10, 0x17, 0x00, 0x00,
+        // 2: DW_TAG_subprogram without children: DW_AT_name, DW_AT_low_pc, DW_AT_high_pc
+        0x02, 0x2E, 0x00, 0x03, 0x08, 0x11, 0x01, 0x12, 0x06, 0x00, 0x00,
+        // 3: DW_TAG_subprogram with children: DW_AT_name, DW_AT_low_pc, DW_AT_high_pc
+        0x03, 0x2E, 0x01, 0x03, 0x08, 0x11, 0x01, 0x12, 0x06, 0x00, 0x00,
+        // 4: DW_TAG_subprogram without children: DW_AT_name
+        0x04, 0x2E, 0x00, 0x03, 0x08, 0x00, 0x00,
+        // 5: DW_TAG_inlined_subroutine without children:
+        //    DW_AT_abstract_origin, DW_AT_low_pc, DW_AT_high_pc,
+        //    DW_AT_call_file, DW_AT_call_line, DW_AT_call_column
+        0x05, 0x1D, 0x00, 0x31, 0x13, 0x11, 0x01, 0x12, 0x06, 0x58, 0x0B, 0x59, 0x0B, 0x57, 0x0B,
+        0x00, 0x00, 0x00,
+    ]
+}
+
+/// Returns a DWARF version 4 `.debug_info` unit for the code addresses of `offsets`.
+///
+/// The `helper` function is inlined into `inner` at `src/lib.rs:21:9`.
+fn info_unit(offsets: &Offsets) -> Vec<u8> {
+    // Note: the unit header is 11 bytes.
+    const HEADER_LEN: u32 = 11;
+    let address = |offset: u32| (offset - offsets.code_start).to_le_bytes();
+    let mut dies = Vec::new();
+    // DW_TAG_compile_unit
+    dies.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, 0x00]);
+    // DW_TAG_subprogram `run`
+    let (start, end) = offsets.bodies[0];
+    dies.extend_from_slice(b"\x02run\0");
+    dies.extend_from_slice(&address(start));
+    dies.extend_from_slice(&(end - start).to_le_bytes());
+    // DW_TAG_subprogram `helper`
+    let helper = HEADER_LEN + dies.len() as u32;
+    dies.extend_from_slice(b"\x04helper\0");
+    // DW_TAG_subprogram `inner`
+    let (start, end) = offsets.bodies[1];
+    dies.extend_from_slice(b"\x03inner\0");
+    dies.extend_from_slice(&address(start));
+    dies.extend_from_slice(&(end - start).to_le_bytes());
+    // DW_TAG_inlined_subroutine `helper`
+    dies.push(0x05);
+    dies.extend_from_slice(&helper.to_le_bytes());
+    dies.extend_from_slice(&address(offsets.unreachable));
+    dies.extend_from_slice(&1_u32.to_le_bytes());
+    dies.extend_from_slice(&[0x01, 21, 9]);
+    // End of children of `inner` and the compile unit.
+    dies.extend_from_slice(&[0x00, 0x00]);
+    let mut unit = Vec::new();
+    unit.extend_from_slice(&4_u16.to_le_bytes());
+    unit.extend_from_slice(&0_u32.to_le_bytes());
+    unit.push(0x04);
+    unit.extend_from_slice(&dies);
+    let mut bytes = (unit.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&unit);
+    bytes
+}
+
+/// Returns the Wasm binary of [`WAT`] with DWARF sections and its [`Offsets`].
+fn wasm_with_dwarf() -> (Vec<u8>, Offsets) {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    let offsets = offsets(&wasm);
+    let address = |offset: u32| offset - offsets.code_start;
+    let rows = [
+        (address(offsets.bodies[0].0), 11, 0),
+        (address(offsets.call), 12, 5),
+        (address(offsets.bodies[1].0), 20, 0),
+        (address(offsets.unreachable), 30, 13),
+    ];
+    let program = line_program(&rows, address(offsets.code_end));
+    push_custom_section(&mut wasm, ".debug_abbrev", &abbrevs());
+    push_custom_section(&mut wasm, ".debug_info", &info_unit(&offsets));
+    push_custom_section(&mut wasm, ".debug_line", &line_unit(&program));
+    (wasm, offsets)
+}
+
+/// Returns the Wasm binary of [`WAT`] with DWARF version 5 sections and its [`Offsets`].
+///
+/// Unlike [`wasm_with_dwarf`] the DWARF sections are written by `gimli` as
+/// compilers do and use the DWARF 5 string sections and a mangled Rust
+/// linkage name for `helper`. The compilation directory is `/work`.
+fn wasm_with_dwarf5() -> (Vec<u8>, Offsets) {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    let offsets = offsets(&wasm);
+    let address = |offset: u32| u64::from(offset - offsets.code_start);
+    let encoding = Encoding {
+        format: Format::Dwarf32,
+        version: 5,
+        address_size: 4,
+    };
+    let mut dwarf = DwarfUnit::new(encoding);
+    let comp_dir = dwarf.line_strings.add("/work");
+    let comp_file = dwarf.line_strings.add("src/lib.rs");
+    let root = dwarf.unit.root();
+    let entry = dwarf.unit.get_mut(root);
+    entry.set(
+        constants::DW_AT_language,
+        AttributeValue::Language(constants::DW_LANG_Rust),
+    );
+    entry.set(
+        constants::DW_AT_name,
+        AttributeValue::LineStringRef(comp_file),
+    );
+    entry.set(
+        constants::DW_AT_comp_dir,
+        AttributeValue::LineStringRef(comp_dir),
+    );
+    let comp_dir = LineString::LineStringRef(comp_dir);
+    let comp_file = LineString::LineStringRef(comp_file);
+    let mut program =
+        LineProgram::new(encoding, LineEncoding::default(), comp_dir, comp_file, None);
+    let dir = program.add_directory(LineString::LineStringRef(dwarf.line_strings.add("src")));
+    let file_name = LineString::LineStringRef(dwarf.line_strings.add("lib.rs"));
+    let file = program.add_file(file_name, dir, None);
+    let start = address(offsets.bodies[0].0);
+    program.begin_sequence(Some(Address::Constant(start)));
+    let rows = [
+        (offsets.bodies[0].0, 11, 0),
+        (offsets.call, 12, 5),
+        (offsets.bodies[1].0, 20, 0),
+        (offsets.unreachable, 30, 13),
+    ];
+    for (offset, line, column) in rows {
+        let row = program.row();
+        row.file = file;
+        row.address_offset = address(offset) - start;
+        row.line = line;
+        row.column = column;
+        program.generate_row();
+    }
+    program.end_sequence(address(offsets.code_end) - start);
+    dwarf.unit.line_program = program;
+    let mut subprogram = |name: &str, linkage_name: Option<&str>, body: Option<(u32, u32)>| {
+        let id = dwarf.unit.add(root, constants::DW_TAG_subprogram);
+        let name = AttributeValue::StringRef(dwarf.strings.add(name));
+        let linkage_name =
+            linkage_name.map(|name| AttributeValue::StringRef(dwarf.strings.add(name)));
+        let entry = dwarf.unit.get_mut(id);
+        entry.set(constants::DW_AT_name, name);
+        if let Some(linkage_name) = linkage_name {
+            entry.set(constants::DW_AT_linkage_name, linkage_name);
+        }
+        if let Some((start, end)) = body {
+            let low_pc = Address::Constant(address(start));
+            entry.set(constants::DW_AT_low_pc, AttributeValue::Address(low_pc));
+            entry.set(
+                constants::DW_AT_high_pc,
+                AttributeValue::Udata(u64::from(end - start)),
+            );
+        }
+        id
+    };
+    subprogram("run", None, Some(offsets.bodies[0]));
+    let helper = subprogram("helper", Some("_ZN3lib6helper17h0123456789abcdefE"), None);
+    let inner = subprogram("inner", None, Some(offsets.bodies[1]));
+    let inlined = dwarf.unit.add(inner, constants::DW_TAG_inlined_subroutine);
+    let entry = dwarf.unit.get_mut(inlined);
+    entry.set(
+        constants::DW_AT_abstract_origin,
+        AttributeValue::UnitRef(helper),
+    );
+    let low_pc = Address::Constant(address(offsets.unreachable));
+    entry.set(constants::DW_AT_low_pc, AttributeValue::Address(low_pc));
+    entry.set(constants::DW_AT_high_pc, AttributeValue::Udata(1));
+    entry.set(
+        constants::DW_AT_call_file,
+        AttributeValue::FileIndex(Some(file)),
+    );
+    entry.set(constants::DW_AT_call_line, AttributeValue::Udata(21));
+    entry.set(constants::DW_AT_call_column, AttributeValue::Udata(9));
+    let mut sections = Sections::new(EndianVec::new(LittleEndian));
+    dwarf.write(&mut sections).unwrap();
+    sections
+        .for_each(|id, data| {
+            if !data.slice().is_empty() {
+                push_custom_section(&mut wasm, id.name(), data.slice());
+            }
+            Ok::<(), ()>(())
+        })
+        .unwrap();
+    (wasm, offsets)
+}
+
+/// Returns the function names and locations of `frames`.
+fn describe(frames: &[SourceFrame]) -> Vec<(Option<&str>, Option<String>)> {
+    frames
+        .iter()
+        .map(|frame| {
+            let location = frame.location().map(ToString::to_string);
+            (frame.function(), location)
+        })
+        .collect()
+}
+
+#[test]
+fn source_frames() {
+    let (wasm, offsets) = wasm_with_dwarf();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(
+        describe(&module.source_frames(offsets.call)),
+        [(Some("run"), Some("src/lib.rs:12:5".into()))],
+    );
+    assert_eq!(
+        describe(&module.source_frames(offsets.unreachable)),
+        [
+            (Some("helper"), Some("src/lib.rs:30:13".into())),
+            (Some("inner"), Some("src/lib.rs:21:9".into())),
+        ],
+    );
+    assert!(module.source_frames(offsets.code_start).is_empty());
+}
+
+#[test]
+fn source_frames_dwarf5() {
+    let (wasm, offsets) = wasm_with_dwarf5();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(
+        describe(&module.source_frames(offsets.call)),
+        [(Some("run"), Some("/work/src/lib.rs:12:5".into()))],
+    );
+    assert_eq!(
+        describe(&module.source_frames(offsets.unreachable)),
+        [
+            (Some("lib::helper"), Some("/work/src/lib.rs:30:13".into())),
+            (Some("inner"), Some("/work/src/lib.rs:21:9".into())),
+        ],
+    );
+    assert!(module.source_frames(offsets.code_start).is_empty());
+}
+
+#[test]
+fn ignore_custom_sections() {
+    let (wasm, offsets) = wasm_with_dwarf();
+    let mut config = Config::default();
+    config.ignore_custom_sections(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert!(module.source_frames(offsets.unreachable).is_empty());
+}
+
+#[test]
+fn backtrace() {
+    let (wasm, _) = wasm_with_dwarf();
+    let mut config = Config::default();
+    config.wasm_backtrace(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    let error = run.call(&mut store, ()).unwrap_err();
+    let frames = error.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(
+        describe(frames[0].source_frames()),
+        [
+            (Some("helper"), Some("src/lib.rs:30:13".into())),
+            (Some("inner"), Some("src/lib.rs:21:9".into())),
+        ],
+    );
+    assert_eq!(
+        describe(frames[1].source_frames()),
+        [(Some("run"), Some("src/lib.rs:12:5".into()))],
+    );
+    let message = error.to_string();
+    assert!(message.contains("at helper (src/lib.rs:30:13)"));
+    assert!(message.contains("at inner (src/lib.rs:21:9)"));
+    assert!(message.contains("at run (src/lib.rs:12:5)"));
+}
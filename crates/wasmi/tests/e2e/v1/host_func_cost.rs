@@ -0,0 +1,164 @@
+//! Tests to check if fuel costs attached to host functions are charged as intended.
+
+use wasmi::{
+    core::TrapCode,
+    Caller,
+    Config,
+    Engine,
+    Func,
+    HostFuncCost,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+/// The test module importing the `host.f` function and calling it from its `"test"` export.
+const WASM: &str = r#"
+    (module
+        (import "host" "f" (func $f (param i32 i32)))
+        (func (export "test") (param i32 i32)
+            (call $f (local.get 0) (local.get 1))
+        )
+    )"#;
+
+/// Setup [`Store`] and [`Linker`] with fuel metering set to `consume_fuel`.
+///
+/// The [`Store`] data counts the number of times `host.f` has been invoked.
+fn test_setup(consume_fuel: bool) -> (Store<u32>, Linker<u32>) {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    let store = Store::new(&engine, 0);
+    let linker = Linker::new(&engine);
+    (store, linker)
+}
+
+/// Instantiates [`WASM`] with `linker` and returns its `"test"` export.
+fn instantiate(store: &mut Store<u32>, linker: &Linker<u32>) -> TypedFunc<(i32, i32), ()> {
+    let module = Module::new(store.engine(), WASM).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+        .get_typed_func::<(i32, i32), ()>(&*store, "test")
+        .unwrap()
+}
+
+/// The host function `host.f` which counts its invocations.
+fn host_f(mut caller: Caller<u32>, _ptr: i32, _len: i32) {
+    *caller.data_mut() += 1;
+}
+
+/// Returns the fuel consumed by calling `test` with `params` given `fuel`.
+fn consumed_fuel(
+    store: &mut Store<u32>,
+    test: TypedFunc<(i32, i32), ()>,
+    params: (i32, i32),
+) -> u64 {
+    let fuel = 1_000;
+    store.set_fuel(fuel).unwrap();
+    test.call(&mut *store, params).unwrap();
+    fuel - store.get_fuel().unwrap()
+}
+
+/// Returns the fuel consumed by calling `test` without any host function costs.
+fn base_fuel(params: (i32, i32)) -> u64 {
+    let (mut store, mut linker) = test_setup(true);
+    linker.func_wrap("host", "f", host_f).unwrap();
+    let test = instantiate(&mut store, &linker);
+    consumed_fuel(&mut store, test, params)
+}
+
+#[test]
+fn fixed_cost_works() {
+    let (mut store, mut linker) = test_setup(true);
+    linker
+        .func_wrap_with_cost("host", "f", 100, host_f)
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    let consumed = consumed_fuel(&mut store, test, (0, 0));
+    assert_eq!(consumed, base_fuel((0, 0)) + 100);
+    assert_eq!(*store.data(), 1);
+}
+
+#[test]
+fn dynamic_cost_works() {
+    let (mut store, mut linker) = test_setup(true);
+    let cost = HostFuncCost::dynamic(|params| u64::from(u32::from(params[1])) / 8);
+    linker
+        .func_wrap_with_cost("host", "f", cost, host_f)
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    for len in [0, 7, 8, 64, 800] {
+        let consumed = consumed_fuel(&mut store, test, (0, len));
+        assert_eq!(consumed, base_fuel((0, len)) + (len as u64) / 8);
+    }
+}
+
+#[test]
+fn func_new_with_cost_works() {
+    let (mut store, mut linker) = test_setup(true);
+    let ty = Func::wrap(&mut store, host_f).ty(&store);
+    linker
+        .func_new_with_cost("host", "f", ty, 42, |mut caller, _params, _results| {
+            *caller.data_mut() += 1;
+            Ok(())
+        })
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    let consumed = consumed_fuel(&mut store, test, (0, 0));
+    assert_eq!(consumed, base_fuel((0, 0)) + 42);
+}
+
+#[test]
+fn out_of_fuel_skips_host_func() {
+    let (mut store, mut linker) = test_setup(true);
+    linker
+        .func_wrap_with_cost("host", "f", 100, host_f)
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    store.set_fuel(base_fuel((0, 0)) + 99).unwrap();
+    let error = test.call(&mut store, (0, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(*store.data(), 0);
+}
+
+#[test]
+fn out_of_fuel_is_not_resumable() {
+    let (mut store, mut linker) = test_setup(true);
+    linker
+        .func_wrap_with_cost("host", "f", 100, host_f)
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    store.set_fuel(base_fuel((0, 0)) + 99).unwrap();
+    let error = test.call_resumable(&mut store, (0, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(*store.data(), 0);
+}
+
+#[test]
+fn root_host_func_call_is_charged() {
+    let (mut store, _linker) = test_setup(true);
+    let f = Func::wrap_with_cost(&mut store, 10, host_f)
+        .typed::<(i32, i32), ()>(&store)
+        .unwrap();
+    store.set_fuel(10).unwrap();
+    f.call(&mut store, (0, 0)).unwrap();
+    assert_eq!(store.get_fuel().unwrap(), 0);
+    let error = f.call(&mut store, (0, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(*store.data(), 1);
+}
+
+#[test]
+fn cost_ignored_without_fuel_metering() {
+    let (mut store, mut linker) = test_setup(false);
+    linker
+        .func_wrap_with_cost("host", "f", u64::MAX, host_f)
+        .unwrap();
+    let test = instantiate(&mut store, &linker);
+    test.call(&mut store, (0, 0)).unwrap();
+    assert_eq!(*store.data(), 1);
+}
@@ -0,0 +1,161 @@
+//! Tests for the inlining of small Wasm functions via [`Config::inlining`].
+
+use super::instantiate;
+use wasmi::{CompilationMode, Config, Instance, Store};
+
+const WAT: &str = r#"
+    (module
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func $swap (param i32 i32) (result i32 i32)
+            (local.get 1) (local.get 0)
+        )
+        (func $sign (param $x i32) (result i32)
+            (if (i32.eqz (local.get $x)) (then (return (i32.const 0))))
+            (drop (br_if 0 (i32.const -1) (i32.lt_s (local.get $x) (i32.const 0))))
+            (i32.const 1)
+        )
+        (func $select (param $n i32) (result i32)
+            (block $c
+                (block $b
+                    (block $a
+                        (br_table $a $b $c (local.get $n))
+                    )
+                    (return (i32.const 10))
+                )
+                (return (i32.const 20))
+            )
+            (i32.const 30)
+        )
+        (func $count (param $n i32) (result i32)
+            (local $acc i32)
+            (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+            (local.get $acc)
+        )
+        (func $div (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func $fib (param $n i32) (result i32)
+            (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "add") (param i32) (result i32)
+            (call $add (call $add (local.get 0) (i32.const 1)) (i32.const 2))
+        )
+        (func (export "swap") (param i32) (result i32)
+            (call $swap (local.get 0) (i32.const 100))
+            (i32.sub)
+        )
+        (func (export "sign") (param i32) (result i32)
+            (local $x i32)
+            (local.set $x (call $sign (local.get 0)))
+            (i32.mul (local.get $x) (i32.const 3))
+        )
+        (func (export "select") (param i32) (result i32)
+            (call $select (local.get 0))
+        )
+        (func (export "count") (param i32) (result i32)
+            (i32.add (call $count (local.get 0)) (call $count (local.get 0)))
+        )
+        (func (export "div") (param i32) (result i32)
+            (call $div (i32.const 100) (local.get 0))
+        )
+        (func (export "fib") (param i32) (result i32)
+            (call $fib (local.get 0))
+        )
+    )
+"#;
+
+/// Returns a [`Config`] with fuel metering and with inlining enabled if `inlining` is `true`.
+fn inlining_config(inlining: bool) -> Config {
+    let mut config = Config::default();
+    config.consume_fuel(true).inlining(inlining);
+    config
+}
+
+/// Calls the exported function `name` of `instance` with `input`.
+///
+/// Returns the result of the call and the fuel it consumed.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, input: i32) -> (Option<i32>, u64) {
+    store.set_fuel(1_000_000).unwrap();
+    let result = instance
+        .get_typed_func::<i32, i32>(&*store, name)
+        .unwrap()
+        .call(&mut *store, input)
+        .ok();
+    (result, 1_000_000 - store.get_fuel().unwrap())
+}
+
+#[test]
+fn same_results_and_fuel() {
+    let (_, _, mut baseline_store, baseline) = instantiate(&inlining_config(false), WAT);
+    let (engine, _, mut store, instance) = instantiate(&inlining_config(true), WAT);
+    let inputs = [0, 1, 2, 3, -5, 7];
+    for name in ["add", "swap", "sign", "select", "count", "div", "fib"] {
+        for input in inputs {
+            assert_eq!(
+                call(&mut store, &instance, name, input),
+                call(&mut baseline_store, &baseline, name, input),
+                "{name}({input})",
+            );
+        }
+    }
+    assert_eq!(call(&mut store, &instance, "div", 0).0, None);
+    assert!(engine.stats().inlined_calls() > 0);
+}
+
+#[test]
+fn inlined_calls() {
+    let (engine, module, _, _) = instantiate(&inlining_config(true), WAT);
+    // Note: the recursive `fib` is no leaf function and thus not inlined.
+    assert_eq!(engine.stats().inlined_calls(), 8);
+    assert!(!module.disassemble(7).unwrap().contains("call_internal"));
+    assert!(module.disassemble(13).unwrap().contains("call_internal"));
+}
+
+#[test]
+fn inlining_lazy() {
+    let mut config = inlining_config(true);
+    config.compilation_mode(CompilationMode::Lazy);
+    let (engine, module, mut store, instance) = instantiate(&config, WAT);
+    // Note: only callees that have been compiled before their callers are inlined.
+    assert!(module.disassemble(7).unwrap().contains("call_internal"));
+    assert_eq!(call(&mut store, &instance, "add", 1).0, Some(4));
+    assert_eq!(engine.stats().inlined_calls(), 0);
+    assert_eq!(call(&mut store, &instance, "count", 5).0, Some(10));
+    assert_eq!(engine.stats().inlined_calls(), 0);
+}
+
+#[test]
+fn inlining_budget() {
+    let mut config = inlining_config(true);
+    config.inlining_budget(0);
+    let (engine, _, mut store, instance) = instantiate(&config, WAT);
+    assert_eq!(engine.stats().inlined_calls(), 0);
+    assert_eq!(call(&mut store, &instance, "add", 1).0, Some(4));
+}
+
+#[test]
+fn inlining_with_backtrace() {
+    let mut config = Config::default();
+    config.inlining(true).wasm_backtrace(true);
+    let (engine, _, mut store, instance) = instantiate(&config, WAT);
+    assert_ne!(engine.stats().inlined_calls(), 0);
+    let error = instance
+        .get_typed_func::<i32, i32>(&store, "div")
+        .unwrap()
+        .call(&mut store, 0)
+        .unwrap_err();
+    // Note: inlined functions are attributed to their callers.
+    let frames = error.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].func_index(), 12);
+}
@@ -0,0 +1,186 @@
+//! Tests for the `tracing` spans and events emitted by Wasmi.
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    field::{Field, Visit},
+    span,
+    subscriber::with_default,
+    Event,
+    Metadata,
+    Subscriber,
+};
+use wasmi::{Caller, Config, Engine, Func, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module $test
+        (import "env" "host" (func $host))
+        (memory 1)
+        (func $grow (export "grow") (param i32) (result i32)
+            (call $host)
+            (memory.grow (local.get 0))
+        )
+        (func $trap (export "trap")
+            (unreachable)
+        )
+    )
+"#;
+
+/// A span or event recorded by the [`Recorder`] with its name and fields.
+#[derive(Debug, Clone, Default)]
+struct Record {
+    name: &'static str,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Record {
+    /// Returns the value of the field `name` if any.
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| &value[..])
+    }
+}
+
+impl Visit for Record {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record_str(field, &format!("{value:?}"))
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.push((field.name(), value.into()));
+    }
+}
+
+/// A [`Subscriber`] that records all spans and events.
+#[derive(Debug, Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<Record>>>,
+    events: Arc<Mutex<Vec<Record>>>,
+}
+
+impl Recorder {
+    /// Returns all recorded spans named `name`.
+    fn spans(&self, name: &str) -> Vec<Record> {
+        filter(&self.spans.lock().unwrap(), name)
+    }
+
+    /// Returns all recorded events with the message `message`.
+    fn events(&self, message: &str) -> Vec<Record> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.field("message") == Some(message))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Returns all `records` named `name`.
+fn filter(records: &[Record], name: &str) -> Vec<Record> {
+    records
+        .iter()
+        .filter(|record| record.name == name)
+        .cloned()
+        .collect()
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut record = Record {
+            name: span.metadata().name(),
+            fields: Vec::new(),
+        };
+        span.record(&mut record);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(record);
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        let index = span.into_u64() as usize - 1;
+        values.record(&mut self.spans.lock().unwrap()[index]);
+    }
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut record = Record {
+            name: event.metadata().name(),
+            fields: Vec::new(),
+        };
+        event.record(&mut record);
+        self.events.lock().unwrap().push(record);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn spans_and_events() {
+    let recorder = Recorder::default();
+    with_default(recorder.clone(), || {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, WAT).unwrap();
+        let mut store = Store::new(&engine, ());
+        store.set_fuel(1_000_000).unwrap();
+        let mut linker = Linker::new(&engine);
+        linker
+            .define(
+                "env",
+                "host",
+                Func::wrap(&mut store, |_caller: Caller<()>| {}),
+            )
+            .unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+        assert_eq!(grow.call(&mut store, 1).unwrap(), 1);
+        assert_eq!(grow.call(&mut store, 65_536).unwrap(), -1);
+        let trap = instance.get_typed_func::<(), ()>(&store, "trap").unwrap();
+        trap.call(&mut store, ()).unwrap_err();
+    });
+    let modules = recorder.spans("compile_module");
+    assert_eq!(modules.len(), 1);
+    assert_eq!(modules[0].field("module"), Some("test"));
+    assert_eq!(modules[0].field("funcs"), Some("3"));
+    let translations = recorder.spans("translate_func");
+    assert_eq!(translations.len(), 2);
+    assert_eq!(translations[0].field("func_index"), Some("1"));
+    assert!(translations[0].field("size").is_some());
+    assert_eq!(
+        recorder.spans("instantiate")[0].field("module"),
+        Some("test")
+    );
+    assert_eq!(recorder.spans("start").len(), 1);
+    let calls = recorder.spans("call");
+    assert_eq!(calls.len(), 3);
+    for call in &calls {
+        assert_eq!(call.field("module"), Some("test"));
+        assert!(call.field("fuel_consumed").is_some());
+    }
+    assert_eq!(calls[0].field("func"), Some("grow"));
+    assert_eq!(calls[2].field("func"), Some("trap"));
+    assert_eq!(recorder.spans("host_call").len(), 2);
+    let grown = recorder.events("memory grown");
+    assert_eq!(grown.len(), 1);
+    assert_eq!(grown[0].field("old_pages"), Some("1"));
+    assert_eq!(grown[0].field("new_pages"), Some("2"));
+    assert_eq!(recorder.events("memory growth failed").len(), 1);
+    assert_eq!(recorder.events("call trapped").len(), 1);
+}
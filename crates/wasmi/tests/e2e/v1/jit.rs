@@ -0,0 +1,190 @@
+//! Tests for the execution of tiered up Wasm functions via the `jit` crate feature.
+
+use super::instantiate;
+use wasmi::{core::TrapCode, Config, Instance, Store};
+
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (func (export "sum") (param $n i32) (result i64)
+            (local $acc i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc
+                        (i64.add (local.get $acc) (i64.extend_i32_u (local.get $n)))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func (export "fill") (param $ptr i32) (param $len i32) (result i32)
+            (local $i i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.ge_u (local.get $i) (local.get $len)))
+                    (i32.store8
+                        (i32.add (local.get $ptr) (local.get $i))
+                        (i32.mul (local.get $i) (i32.const 3))
+                    )
+                    (local.set $acc
+                        (i32.add
+                            (local.get $acc)
+                            (i32.load8_u (i32.add (local.get $ptr) (local.get $i)))
+                        )
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func (export "div") (param $n i32) (param $d i32) (result i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc (i32.add (local.get $acc) (i32.div_s (local.get $n) (local.get $d))))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Returns a [`Config`] that compiles Wasm functions to machine code after their first call.
+fn jit_config() -> Config {
+    let mut config = Config::default();
+    config.tiering(true).tier_up_threshold(1);
+    config
+}
+
+/// Calls the exported function `name` of `instance` with `params` twice.
+///
+/// The first call tiers up the function, the second call executes its machine code.
+fn call_twice<Params, Results>(
+    store: &mut Store<()>,
+    instance: &Instance,
+    name: &str,
+    params: Params,
+) -> Result<Results, wasmi::Error>
+where
+    Params: wasmi::WasmParams + Copy,
+    Results: wasmi::WasmResults,
+{
+    let func = instance
+        .get_typed_func::<Params, Results>(&*store, name)
+        .unwrap();
+    let _ = func.call(&mut *store, params);
+    func.call(store, params)
+}
+
+#[test]
+fn jit_matches_interpreter() {
+    let (_, _, mut interp, interp_instance) = instantiate(&Config::default(), WAT);
+    let (engine, _, mut jit, jit_instance) = instantiate(&jit_config(), WAT);
+    for n in [0, 1, 100, 100_000] {
+        assert_eq!(
+            call_twice::<i32, i64>(&mut jit, &jit_instance, "sum", n).unwrap(),
+            call_twice::<i32, i64>(&mut interp, &interp_instance, "sum", n).unwrap(),
+        );
+    }
+    for (ptr, len) in [(0, 0), (100, 1000), (65000, 536)] {
+        assert_eq!(
+            call_twice::<(i32, i32), i32>(&mut jit, &jit_instance, "fill", (ptr, len)).unwrap(),
+            call_twice::<(i32, i32), i32>(&mut interp, &interp_instance, "fill", (ptr, len))
+                .unwrap(),
+        );
+    }
+    assert_eq!(
+        call_twice::<i32, i32>(&mut jit, &jit_instance, "fib", 20).unwrap(),
+        6765
+    );
+    assert!(engine.stats().jit_compiled_funcs() >= 3);
+}
+
+#[test]
+fn jit_traps() {
+    let (_, _, mut store, instance) = instantiate(&jit_config(), WAT);
+    let error = call_twice::<(i32, i32), i32>(&mut store, &instance, "div", (10, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    let error =
+        call_twice::<(i32, i32), i32>(&mut store, &instance, "div", (i32::MIN, -1)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerOverflow));
+    assert_eq!(
+        call_twice::<(i32, i32), i32>(&mut store, &instance, "div", (10, -1)).unwrap(),
+        -55
+    );
+    let error =
+        call_twice::<(i32, i32), i32>(&mut store, &instance, "fill", (65000, 537)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+}
+
+#[test]
+fn jit_keeps_fuel_consumption() {
+    let mut interp_config = Config::default();
+    interp_config.consume_fuel(true);
+    let mut jit_config = jit_config();
+    jit_config.consume_fuel(true);
+    let (_, _, mut interp, interp_instance) = instantiate(&interp_config, WAT);
+    let (engine, _, mut jit, jit_instance) = instantiate(&jit_config, WAT);
+    for fuel in [10_000, 1_000, 123] {
+        interp.set_fuel(fuel).unwrap();
+        jit.set_fuel(fuel).unwrap();
+        let expected = call_twice::<i32, i64>(&mut interp, &interp_instance, "sum", 100);
+        let result = call_twice::<i32, i64>(&mut jit, &jit_instance, "sum", 100);
+        match (result, expected) {
+            (Ok(result), Ok(expected)) => assert_eq!(result, expected),
+            (Err(error), Err(expected)) => {
+                assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+                assert_eq!(expected.as_trap_code(), Some(TrapCode::OutOfFuel));
+            }
+            (result, expected) => panic!("mismatch: {result:?} != {expected:?}"),
+        }
+        assert_eq!(jit.get_fuel().unwrap(), interp.get_fuel().unwrap());
+    }
+    assert_eq!(engine.stats().jit_compiled_funcs(), 1);
+}
+
+#[test]
+#[cfg(feature = "trace")]
+fn jit_skipped_while_tracing() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    let (engine, _, mut store, instance) = instantiate(&jit_config(), WAT);
+    assert_eq!(
+        call_twice::<i32, i64>(&mut store, &instance, "sum", 100).unwrap(),
+        5050
+    );
+    assert_eq!(engine.stats().jit_compiled_funcs(), 1);
+    let traced = Arc::new(AtomicUsize::new(0));
+    store.trace_hook({
+        let traced = traced.clone();
+        move |_, _| {
+            traced.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    });
+    let sum = instance.get_typed_func::<i32, i64>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 100).unwrap(), 5050);
+    // Note: every loop iteration is traced since the machine code is not executed.
+    assert!(traced.load(Ordering::Relaxed) > 100 * 4);
+}
@@ -1,9 +1,51 @@
+mod background_compilation;
+mod backtrace;
 mod call_hook;
+mod coredump;
+mod coverage;
+mod debugger;
+mod deterministic;
+mod disassemble;
+#[cfg(feature = "dwarf")]
+mod dwarf;
 mod fuel_consumption;
 mod fuel_metering;
 mod func;
 mod host_call_compilation;
 mod host_call_instantiation;
 mod host_calls_wasm;
+mod host_func_cost;
+mod inlining;
+#[cfg(feature = "tracing")]
+mod instrument;
+#[cfg(all(feature = "jit", target_arch = "x86_64", target_os = "linux"))]
+mod jit;
+mod module_cache;
+mod module_error;
+mod names;
+mod optimization;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod profiler;
 mod resource_limiter;
 mod resumable_call;
+mod stats;
+mod tiering;
+#[cfg(feature = "trace")]
+mod trace;
+mod trap_details;
+
+use wasmi::{Config, Engine, Instance, Linker, Module, Store};
+
+/// Instantiates the Wasm module `wat` without imports with `config`.
+fn instantiate(config: &Config, wat: &str) -> (Engine, Module, Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, wat).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (engine, module, store, instance)
+}
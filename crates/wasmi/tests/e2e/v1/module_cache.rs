@@ -0,0 +1,118 @@
+//! Tests for the module cache of an [`Engine`] enabled via [`Config::set_cached_modules`].
+
+use wasmi::{Config, Engine, Linker, Module, ModuleCacheEviction, Store};
+
+/// Returns the Wasm bytecode of a module whose exported function `run` returns `value`.
+fn wasm(value: i32) -> Vec<u8> {
+    let wat = format!(
+        r#"
+        (module
+            (func (export "run") (result i32)
+                (i32.const {value})
+            )
+        )
+    "#
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+/// Returns an [`Engine`] caching up to `amount` modules evicted by `eviction`.
+fn engine(amount: usize, eviction: ModuleCacheEviction) -> Engine {
+    let mut config = Config::default();
+    config
+        .set_cached_modules(amount)
+        .module_cache_eviction(eviction);
+    Engine::new(&config)
+}
+
+/// Creates a [`Module`] from `wasm` and returns `true` if it has been taken from the module cache.
+fn is_cached(engine: &Engine, wasm: &[u8]) -> bool {
+    let compiled_funcs = engine.stats().compiled_funcs();
+    let _module = Module::new(engine, wasm).unwrap();
+    engine.stats().compiled_funcs() == compiled_funcs
+}
+
+/// Instantiates `module` and calls its exported function `run`.
+fn run(module: &Module) -> i32 {
+    let engine = module.engine();
+    let mut store = Store::new(engine, ());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap()
+}
+
+#[test]
+fn shares_compiled_funcs() {
+    let engine = engine(2, ModuleCacheEviction::default());
+    let a = Module::new(&engine, wasm(1)).unwrap();
+    let b = Module::new(&engine, wasm(1)).unwrap();
+    let stats = engine.stats();
+    assert_eq!(stats.compiled_funcs(), 1);
+    assert_eq!(stats.cached_modules(), 1);
+    assert!(Engine::same(a.engine(), b.engine()));
+    assert_eq!(run(&a), 1);
+    assert_eq!(run(&b), 1);
+    let c = Module::new(&engine, wasm(2)).unwrap();
+    assert_eq!(run(&c), 2);
+    assert_eq!(engine.stats().compiled_funcs(), 2);
+    assert_eq!(engine.stats().cached_modules(), 2);
+}
+
+#[test]
+fn disabled_by_default() {
+    let engine = Engine::default();
+    assert!(!is_cached(&engine, &wasm(1)));
+    assert!(!is_cached(&engine, &wasm(1)));
+    assert_eq!(engine.stats().cached_modules(), 0);
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let engine = engine(2, ModuleCacheEviction::LeastRecentlyUsed);
+    let (a, b, c) = (wasm(1), wasm(2), wasm(3));
+    assert!(!is_cached(&engine, &a));
+    assert!(!is_cached(&engine, &b));
+    assert!(is_cached(&engine, &a));
+    assert!(!is_cached(&engine, &c));
+    assert_eq!(engine.stats().cached_modules(), 2);
+    assert!(is_cached(&engine, &a));
+    assert!(is_cached(&engine, &c));
+    assert!(!is_cached(&engine, &b));
+}
+
+#[test]
+fn evicts_first_in_first_out() {
+    let engine = engine(2, ModuleCacheEviction::FirstInFirstOut);
+    let (a, b, c) = (wasm(1), wasm(2), wasm(3));
+    assert!(!is_cached(&engine, &a));
+    assert!(!is_cached(&engine, &b));
+    assert!(is_cached(&engine, &a));
+    assert!(!is_cached(&engine, &c));
+    assert_eq!(engine.stats().cached_modules(), 2);
+    assert!(is_cached(&engine, &b));
+    assert!(is_cached(&engine, &c));
+    assert!(!is_cached(&engine, &a));
+}
+
+#[test]
+fn unchecked_modules_are_not_validated() {
+    let engine = engine(2, ModuleCacheEviction::default());
+    let (a, b) = (wasm(1), wasm(2));
+    // Safety: the Wasm bytecode is valid.
+    let _module = unsafe { Module::new_unchecked(&engine, &a).unwrap() };
+    assert!(!is_cached(&engine, &a));
+    assert_eq!(engine.stats().cached_modules(), 1);
+    assert!(is_cached(&engine, &a));
+    assert!(!is_cached(&engine, &b));
+    let compiled_funcs = engine.stats().compiled_funcs();
+    // Safety: the Wasm bytecode is valid.
+    let _module = unsafe { Module::new_unchecked(&engine, &b).unwrap() };
+    assert_eq!(engine.stats().compiled_funcs(), compiled_funcs);
+}
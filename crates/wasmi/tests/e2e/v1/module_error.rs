@@ -0,0 +1,107 @@
+//! Tests for the [`ModuleError`] attached to errors of [`Module::new`] and [`Module::validate`].
+
+use wasmi::{
+    errors::{EnforcedLimitsError, ModuleError, ModuleErrorKind, ModuleSection},
+    Config,
+    EnforcedLimits,
+    Engine,
+    Error,
+    Module,
+};
+use wasmparser::{Parser, Payload};
+
+/// A Wasm module whose second defined function fails to validate.
+const INVALID_FUNC: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func)
+        (func (result i32)
+            (i64.const 0)
+        )
+    )
+"#;
+
+/// Returns the body range of the `n`-th defined function of the Wasm binary `wasm`.
+fn body_range(wasm: &[u8], n: usize) -> (usize, usize) {
+    Parser::new(0)
+        .parse_all(wasm)
+        .filter_map(|payload| match payload.unwrap() {
+            Payload::CodeSectionEntry(body) => Some((body.range().start, body.range().end)),
+            _ => None,
+        })
+        .nth(n)
+        .unwrap()
+}
+
+/// Returns the [`ModuleError`] of `error`.
+fn module_error(error: &Error) -> &ModuleError {
+    error.module_error().unwrap()
+}
+
+#[test]
+fn invalid_func() {
+    let wasm = wat::parse_str(INVALID_FUNC).unwrap();
+    let (start, end) = body_range(&wasm, 1);
+    let engine = Engine::default();
+    let errors = [
+        Module::new(&engine, &wasm[..]).unwrap_err(),
+        Module::new_streaming(&engine, &wasm[..]).unwrap_err(),
+        Module::validate(&engine, &wasm[..]).unwrap_err(),
+    ];
+    for error in &errors {
+        let module_error = module_error(error);
+        assert_eq!(module_error.kind(), ModuleErrorKind::Wasm);
+        assert_eq!(module_error.section(), Some(ModuleSection::Code));
+        assert_eq!(module_error.func_index(), Some(2));
+        let offset = module_error.offset().unwrap();
+        assert!(start <= offset && offset < end);
+        assert_eq!(
+            module_error.to_string(),
+            format!("code section, func 2, offset {offset:#x}")
+        );
+    }
+}
+
+#[test]
+fn malformed_header() {
+    let engine = Engine::default();
+    let error = Module::new(&engine, &b"\0asm\x02\0\0\0"[..]).unwrap_err();
+    let module_error = module_error(&error);
+    assert_eq!(module_error.kind(), ModuleErrorKind::Wasm);
+    assert_eq!(module_error.section(), None);
+    assert_eq!(module_error.func_index(), None);
+    assert!(module_error.offset().unwrap() < 8);
+}
+
+#[test]
+fn enforced_limits() {
+    let wasm = wat::parse_str("(module (memory 1) (memory 1))").unwrap();
+    let memory_section = Parser::new(0)
+        .parse_all(&wasm)
+        .find_map(|payload| match payload.unwrap() {
+            Payload::MemorySection(section) => Some(section.range().start),
+            _ => None,
+        })
+        .unwrap();
+    let mut config = Config::default();
+    config.enforced_limits(EnforcedLimits::strict());
+    let engine = Engine::new(&config);
+    let error = Module::new(&engine, &wasm[..]).unwrap_err();
+    let module_error = module_error(&error);
+    assert_eq!(
+        module_error.kind(),
+        ModuleErrorKind::Limits(EnforcedLimitsError::TooManyMemories { limit: 1 })
+    );
+    assert_eq!(module_error.section(), Some(ModuleSection::Memory));
+    assert_eq!(module_error.func_index(), None);
+    assert_eq!(module_error.offset(), Some(memory_section));
+}
+
+#[test]
+fn valid_module() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(r#"(module (func (export "f") (unreachable)))"#).unwrap();
+    assert!(Module::validate(&engine, &wasm[..]).is_ok());
+    let error = wasmi::Error::new("not a module error");
+    assert!(error.module_error().is_none());
+}
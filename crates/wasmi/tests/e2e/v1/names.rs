@@ -0,0 +1,89 @@
+//! Tests for the names of the `name` custom section of a [`Module`].
+
+use wasmi::{Config, Engine, Module};
+
+const WASM: &str = r#"
+    (module $example
+        (import "env" "log" (func $log (param i32)))
+        (import "env" "offset" (global $offset i32))
+        (func $add_impl (export "add") (param $lhs i32) (param $rhs i32) (result i32)
+            (local $sum i32)
+            (local.set $sum (i32.add (local.get $lhs) (local.get $rhs)))
+            (call $log (local.get $sum))
+            (local.get $sum)
+        )
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (memory $heap (export "memory") 1)
+        (table $funcs 1 funcref)
+        (data $greeting (i32.const 0) "hello")
+        (elem $callbacks (i32.const 0) $add_impl)
+    )
+"#;
+
+/// Compiles [`WASM`] with a [`Config`] optionally ignoring custom sections.
+fn module(ignore_custom_sections: bool) -> Module {
+    let mut config = Config::default();
+    config.ignore_custom_sections(ignore_custom_sections);
+    let engine = Engine::new(&config);
+    Module::new(&engine, WASM).unwrap()
+}
+
+#[test]
+fn module_names() {
+    let module = module(false);
+    assert_eq!(module.name(), Some("example"));
+    assert_eq!(module.func_name(0), Some("log"));
+    assert_eq!(module.func_name(1), Some("add_impl"));
+    assert_eq!(module.func_name(2), None);
+    assert_eq!(module.local_name(1, 0), Some("lhs"));
+    assert_eq!(module.local_name(1, 1), Some("rhs"));
+    assert_eq!(module.local_name(1, 2), Some("sum"));
+    assert_eq!(module.local_name(1, 3), None);
+    assert_eq!(module.global_name(0), Some("offset"));
+    assert_eq!(module.global_name(1), Some("counter"));
+    assert_eq!(module.memory_name(0), Some("heap"));
+    assert_eq!(module.table_name(0), Some("funcs"));
+    assert_eq!(module.data_name(0), Some("greeting"));
+    assert_eq!(module.elem_name(0), Some("callbacks"));
+}
+
+#[test]
+fn export_and_import_names() {
+    let module = module(false);
+    let exports = module
+        .exports()
+        .map(|export| (export.name(), export.debug_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        [
+            ("add", Some("add_impl")),
+            ("counter", Some("counter")),
+            ("memory", Some("heap")),
+        ]
+    );
+    let imports = module
+        .imports()
+        .map(|import| (import.name(), import.debug_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(imports, [("log", Some("log")), ("offset", Some("offset"))]);
+}
+
+#[test]
+fn disassemble() {
+    let module = module(false);
+    let ir = module.disassemble(1).unwrap();
+    assert!(ir.contains("  r0 = $lhs\n"));
+    assert!(ir.contains("  r1 = $rhs\n"));
+    assert!(ir.contains("  r2 = $sum\n"));
+}
+
+#[test]
+fn ignore_custom_sections() {
+    let module = module(true);
+    assert_eq!(module.name(), None);
+    assert_eq!(module.func_name(1), None);
+    assert_eq!(module.local_name(1, 0), None);
+    assert!(module.exports().all(|export| export.debug_name().is_none()));
+    assert!(!module.disassemble(1).unwrap().contains('$'));
+}
@@ -0,0 +1,231 @@
+//! Tests for the optimization of Wasmi bytecode via [`Config::optimization_level`].
+
+use super::instantiate;
+use wasmi::{CompilationMode, Config, Engine, Instance, Module, OptimizationLevel, Store};
+
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (func $fold (export "fold") (param $x i32) (result i32)
+            (local $a i32)
+            (local $b i32)
+            (local.set $a (i32.const 10))
+            (local.set $b (i32.mul (local.get $a) (i32.const 2)))
+            (if (i32.gt_u (local.get $b) (i32.const 5))
+                (then (local.set $x (i32.add (local.get $x) (local.get $b))))
+            )
+            (local.get $x)
+        )
+        (func $sum_even (export "sum_even") (param $n i32) (result i32)
+            (local $acc i32)
+            (local $step i32)
+            (local.set $step (i32.const 1))
+            (block $exit
+                (loop $continue
+                    (block $skip
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (br_if $skip (i32.and (local.get $n) (local.get $step)))
+                        (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (local.get $step)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (local $a i64)
+            (local $b i64)
+            (local $t i64)
+            (local.set $b (i64.const 1))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $t (local.get $b))
+                    (local.set $b (i64.add (local.get $a) (local.get $b)))
+                    (local.set $a (local.get $t))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i32.wrap_i64 (local.get $a))
+        )
+        (func $select (export "select") (param $n i32) (result i32)
+            (local $mode i32)
+            (local.set $mode (i32.const 2))
+            (block $c
+                (block $b
+                    (block $a
+                        (br_table $a $b $c (local.get $mode))
+                    )
+                    (return (i32.const -1))
+                )
+                (return (i32.const -2))
+            )
+            (i32.store (local.get $n) (i32.add (local.get $n) (local.get $mode)))
+            (i32.load (local.get $n))
+        )
+        (func $div (export "div") (param $n i32) (result i32)
+            (local $unused i32)
+            (local.set $unused (i32.div_u (i32.const 1) (local.get $n)))
+            (local.get $n)
+        )
+    )
+"#;
+
+/// Returns a [`Config`] with fuel metering and an optimization level of `level`.
+fn optimizing_config(level: OptimizationLevel) -> Config {
+    let mut config = Config::default();
+    config.consume_fuel(true).optimization_level(level);
+    config
+}
+
+/// Calls the exported function `name` of `instance` with `input`.
+///
+/// Returns the result or the trap message of the call as well as its consumed fuel.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, input: i32) -> (String, u64) {
+    store.set_fuel(1_000_000).unwrap();
+    let result = instance
+        .get_typed_func::<i32, i32>(&*store, name)
+        .unwrap()
+        .call(&mut *store, input)
+        .map(|result| result.to_string())
+        .unwrap_or_else(|error| error.to_string());
+    (result, 1_000_000 - store.get_fuel().unwrap())
+}
+
+/// Asserts that all calls behave the same with `config` as without optimizations
+/// and consume at most as much fuel.
+fn assert_same_behavior(config: &Config) {
+    let (_, _, mut store, instance) = instantiate(config, WAT);
+    let (_, _, mut expected_store, expected_instance) =
+        instantiate(&optimizing_config(OptimizationLevel::None), WAT);
+    for name in ["fold", "sum_even", "fib", "select", "div"] {
+        for input in [0, 1, 2, 7, 50, 100, -1] {
+            let (result, fuel) = call(&mut store, &instance, name, input);
+            let (expected_result, expected_fuel) =
+                call(&mut expected_store, &expected_instance, name, input);
+            assert_eq!(result, expected_result, "{name}({input})");
+            assert!(fuel <= expected_fuel, "{name}({input})");
+        }
+    }
+}
+
+#[test]
+fn same_behavior() {
+    for level in [OptimizationLevel::Basic, OptimizationLevel::Full] {
+        assert_same_behavior(&optimizing_config(level));
+    }
+}
+
+#[test]
+fn same_behavior_lazy() {
+    let mut config = optimizing_config(OptimizationLevel::Full);
+    config.compilation_mode(CompilationMode::Lazy);
+    let (_, _, mut store, instance) = instantiate(&config, WAT);
+    let mut expected_config = optimizing_config(OptimizationLevel::None);
+    expected_config.compilation_mode(CompilationMode::Lazy);
+    let (_, _, mut expected_store, expected_instance) = instantiate(&expected_config, WAT);
+    for input in [0, 10, 40] {
+        assert_eq!(
+            call(&mut store, &instance, "fib", input),
+            call(&mut expected_store, &expected_instance, "fib", input),
+        );
+    }
+}
+
+#[test]
+fn same_behavior_tiering() {
+    for level in [OptimizationLevel::None, OptimizationLevel::Basic] {
+        let mut config = optimizing_config(level);
+        config.tiering(true).tier_up_threshold(10);
+        assert_same_behavior(&config);
+    }
+}
+
+/// Returns the disassembled `fold` function of [`WAT`] with `config`.
+fn disassemble_fold(config: &Config) -> String {
+    let engine = Engine::new(config);
+    Module::new(&engine, WAT).unwrap().disassemble(0).unwrap()
+}
+
+#[test]
+fn optimizes_bytecode() {
+    let mut config = Config::default();
+    assert_eq!(
+        disassemble_fold(&config),
+        "\
+registers: 4
+  params: r0..r1
+  locals and temporaries: r1..r4
+  r0 = $x
+  r1 = $a
+  r2 = $b
+consts: 0
+instrs: 5
+  0: r1 = copy_imm32 value=0x0000000A
+  1: r2 = i32_mul_imm16 lhs=r1 rhs=2
+  2: branch_i32_le_u_imm16_rhs lhs=r2 rhs=5 offset=@4
+  3: r0 = i32_add lhs=r0 rhs=r2
+  4: return_reg value=r0
+",
+    );
+    config.optimization_level(OptimizationLevel::Full);
+    assert_eq!(
+        disassemble_fold(&config),
+        "\
+registers: 4
+  params: r0..r1
+  locals and temporaries: r1..r4
+  r0 = $x
+  r1 = $a
+  r2 = $b
+consts: 0
+instrs: 3
+  0: r2 = copy_imm32 value=0x00000014
+  1: r0 = i32_add lhs=r0 rhs=r2
+  2: return_reg value=r0
+",
+    );
+}
+
+/// Returns the number of instructions of the disassembled function at `func_index` of [`WAT`].
+fn len_instrs(config: &Config, func_index: u32) -> usize {
+    let engine = Engine::new(config);
+    let disassembled = Module::new(&engine, WAT)
+        .unwrap()
+        .disassemble(func_index)
+        .unwrap();
+    disassembled
+        .lines()
+        .find_map(|line| line.strip_prefix("instrs: "))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn removes_instructions() {
+    let unoptimized = optimizing_config(OptimizationLevel::None);
+    let optimized = optimizing_config(OptimizationLevel::Full);
+    for func_index in 0..5 {
+        assert!(len_instrs(&optimized, func_index) <= len_instrs(&unoptimized, func_index));
+    }
+    let removed = len_instrs(&unoptimized, 0) - len_instrs(&optimized, 0);
+    assert_eq!(removed, 2);
+    // Note: all removed instructions of `fold` are executed and charged the base fuel costs.
+    let (_, _, mut store, instance) = instantiate(&optimized, WAT);
+    let (_, _, mut expected_store, expected_instance) = instantiate(&unoptimized, WAT);
+    let (_, fuel) = call(&mut store, &instance, "fold", 1);
+    let (_, expected_fuel) = call(&mut expected_store, &expected_instance, "fold", 1);
+    assert_eq!(fuel + removed as u64, expected_fuel);
+}
+
+#[test]
+fn no_optimization_with_debugging() {
+    let mut config = Config::default();
+    config.debugging(true);
+    let expected = disassemble_fold(&config);
+    config.optimization_level(OptimizationLevel::Full);
+    assert_eq!(disassemble_fold(&config), expected);
+}
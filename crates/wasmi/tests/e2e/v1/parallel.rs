@@ -0,0 +1,107 @@
+//! Tests for the parallel eager translation of Wasm functions via the `parallel` crate feature.
+//!
+//! Wasm modules created via [`Module::new_streaming`] are still translated one
+//! function after another and serve as reference.
+
+use std::fmt::Write as _;
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+/// Returns a Wasm module with `len` functions that each call up to 8 of their predecessors.
+///
+/// Calls to small predecessors are inlined if [`Config::inlining`] is enabled.
+fn chain(len: usize) -> Vec<u8> {
+    let mut wat = String::from(
+        "(module (func $f0 (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))",
+    );
+    for n in 1..len {
+        write!(
+            wat,
+            "(func $f{n} (export \"f{n}\") (param i32) (result i32) (local.get 0)"
+        )
+        .unwrap();
+        for callee in (n.saturating_sub(8))..n {
+            write!(wat, "(call $f{callee}) (i32.mul (i32.const {n}))").unwrap();
+        }
+        wat.push(')');
+    }
+    wat.push(')');
+    wat::parse_str(wat).unwrap()
+}
+
+/// Returns a [`Config`] that eagerly translates and inlines Wasm functions.
+fn config() -> Config {
+    let mut config = Config::default();
+    config.inlining(true);
+    config
+}
+
+/// Calls the exported function `f{n}` of `module` with `input`.
+fn call(engine: &Engine, module: &Module, n: usize, input: i32) -> i32 {
+    let mut store = Store::new(engine, ());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&store, &format!("f{n}"))
+        .unwrap()
+        .call(&mut store, input)
+        .unwrap()
+}
+
+#[test]
+fn parallel_matches_sequential() {
+    let wasm = chain(500);
+    let parallel_engine = Engine::new(&config());
+    let sequential_engine = Engine::new(&config());
+    let parallel = Module::new(&parallel_engine, &wasm[..]).unwrap();
+    let sequential = Module::new_streaming(&sequential_engine, &wasm[..]).unwrap();
+    let parallel_stats = parallel_engine.stats();
+    let sequential_stats = sequential_engine.stats();
+    assert_eq!(parallel_stats.compiled_funcs(), 500);
+    assert_eq!(
+        parallel_stats.compiled_funcs(),
+        sequential_stats.compiled_funcs()
+    );
+    assert_eq!(
+        parallel_stats.bytecode_bytes(),
+        sequential_stats.bytecode_bytes()
+    );
+    assert_eq!(
+        parallel_stats.inlined_calls(),
+        sequential_stats.inlined_calls()
+    );
+    assert!(parallel_stats.inlined_calls() > 0);
+    for n in [1, 2, 10, 16] {
+        assert_eq!(
+            call(&parallel_engine, &parallel, n, 7),
+            call(&sequential_engine, &sequential, n, 7),
+        );
+    }
+}
+
+#[test]
+fn parallel_reports_first_error() {
+    // Note: the bodies of `$f3` and `$f7` fail to validate.
+    let wat = r#"
+        (module
+            (func $f0)
+            (func $f1 (call $f0))
+            (func $f2 (result i32) (i32.const 0))
+            (func $f3 (result i32) (i64.const 0))
+            (func $f4)
+            (func $f5)
+            (func $f6)
+            (func $f7 (result i64) (i32.const 0))
+        )
+    "#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::new(&config());
+    for _ in 0..10 {
+        let parallel = Module::new(&engine, &wasm[..]).unwrap_err();
+        let sequential = Module::new_streaming(&engine, &wasm[..]).unwrap_err();
+        assert_eq!(parallel.module_error().unwrap().func_index(), Some(3));
+        assert_eq!(parallel.to_string(), sequential.to_string());
+    }
+}
@@ -0,0 +1,136 @@
+//! Tests for the profiling API of Wasmi.
+
+use wasmi::{Config, Engine, Instance, Linker, Module, Profile, Store};
+
+const WASM: &str = r#"
+    (module
+        (func $leaf (result i32)
+            (i32.const 1)
+        )
+        (func $fac (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (call $leaf))
+                (else
+                    (i32.mul
+                        (local.get 0)
+                        (call $fac (i32.sub (local.get 0) (i32.const 1)))
+                    )
+                )
+            )
+        )
+        (func (export "main") (param i32) (result i32)
+            (drop (call $leaf))
+            (return_call $fac (local.get 0))
+        )
+        (func (export "trap")
+            (drop (call $leaf))
+            (unreachable)
+        )
+    )
+"#;
+
+fn setup(consume_fuel: bool) -> (Store<()>, Module, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    if consume_fuel {
+        store.set_fuel(1_000).unwrap();
+    }
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    store.set_profiling(true);
+    (store, module, instance)
+}
+
+/// Returns the call stacks of `profile` as function indices of `module` with their number of calls.
+fn stacks(profile: &Profile, module: &Module) -> Vec<(Vec<u32>, u64)> {
+    profile
+        .stacks()
+        .iter()
+        .map(|stack| {
+            let funcs = stack
+                .funcs()
+                .iter()
+                .map(|func| func.func_index(module).unwrap())
+                .collect();
+            (funcs, stack.calls())
+        })
+        .collect()
+}
+
+#[test]
+fn call_counts() {
+    let (mut store, module, instance) = setup(false);
+    let main = instance.get_typed_func::<i32, i32>(&store, "main").unwrap();
+    assert_eq!(main.call(&mut store, 3).unwrap(), 6);
+    let profile = store.take_profile();
+    assert_eq!(
+        stacks(&profile, &module),
+        [
+            (vec![2], 1),
+            (vec![2, 0], 1),
+            // `main` tail calls `fac` and thus is replaced by it.
+            (vec![1], 1),
+            (vec![1, 1], 1),
+            (vec![1, 1, 1], 1),
+            (vec![1, 1, 1, 1], 1),
+            (vec![1, 1, 1, 1, 0], 1),
+        ]
+    );
+    let calls = profile
+        .funcs()
+        .iter()
+        .map(|(func, stats)| (func.func_index(&module).unwrap(), stats.calls()))
+        .collect::<Vec<_>>();
+    assert_eq!(calls, [(0, 2), (1, 4), (2, 1)]);
+    assert_eq!(profile.total_fuel(), 0);
+    assert!(store.profile().is_empty());
+}
+
+#[test]
+fn fuel() {
+    let (mut store, _module, instance) = setup(true);
+    let main = instance.get_typed_func::<i32, i32>(&store, "main").unwrap();
+    assert_eq!(main.call(&mut store, 3).unwrap(), 6);
+    let consumed = 1_000 - store.get_fuel().unwrap();
+    let profile = store.take_profile();
+    assert_eq!(profile.total_fuel(), consumed);
+    let funcs = profile.funcs();
+    for stats in funcs.values() {
+        assert!(stats.exclusive_fuel() > 0);
+        assert!(stats.exclusive_fuel() <= stats.inclusive_fuel());
+    }
+    let inclusive = funcs
+        .values()
+        .map(|stats| stats.inclusive_fuel())
+        .max()
+        .unwrap();
+    assert!(inclusive < consumed);
+}
+
+#[test]
+fn trap() {
+    let (mut store, module, instance) = setup(true);
+    let trap = instance.get_typed_func::<(), ()>(&store, "trap").unwrap();
+    let main = instance.get_typed_func::<i32, i32>(&store, "main").unwrap();
+    trap.call(&mut store, ()).unwrap_err();
+    assert_eq!(main.call(&mut store, 0).unwrap(), 1);
+    let profile = store.take_profile();
+    // The call stack of the profiler is unwound upon a trap.
+    assert_eq!(
+        stacks(&profile, &module),
+        [
+            (vec![3], 1),
+            (vec![3, 0], 1),
+            (vec![2], 1),
+            (vec![2, 0], 1),
+            (vec![1], 1),
+            (vec![1, 0], 1),
+        ]
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests for [`Engine::stats`] and [`Store::stats`].
+
+use super::instantiate;
+use wasmi::{CompilationMode, Config, Engine, ExternRef, Module, Store, Val};
+
+const WAT: &str = r#"
+    (module
+        (memory 2)
+        (table 3 funcref)
+        (table 4 externref)
+        (func $recurse (export "recurse") (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (i32.const 1)
+                        (call $recurse (i32.sub (local.get 0) (i32.const 1)))
+                    )
+                )
+            )
+        )
+        (func (export "unused") (result i64)
+            (i64.const 0x1234_5678_9abc)
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with `config` and calls its `recurse` function with `depth`.
+fn setup(config: &Config, depth: i32) -> (Engine, Store<()>) {
+    let (engine, _, mut store, instance) = instantiate(config, WAT);
+    let recurse = instance
+        .get_typed_func::<i32, i32>(&store, "recurse")
+        .unwrap();
+    assert_eq!(recurse.call(&mut store, depth).unwrap(), depth);
+    (engine, store)
+}
+
+#[test]
+fn engine_stats() {
+    let (engine, _store) = setup(&Config::default(), 1);
+    let stats = engine.stats();
+    assert_eq!(stats.compiled_funcs(), 2);
+    assert_eq!(stats.uncompiled_funcs(), 0);
+    assert!(stats.bytecode_bytes() > 0);
+    assert!(stats.const_bytes() > 0);
+    assert_eq!(stats.cached_stacks(), 1);
+}
+
+#[test]
+fn engine_stats_lazy() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    Module::new(&engine, WAT).unwrap();
+    let stats = engine.stats();
+    assert_eq!(stats.compiled_funcs(), 0);
+    assert_eq!(stats.uncompiled_funcs(), 2);
+    assert_eq!(stats.bytecode_bytes(), 0);
+    assert_eq!(stats.cached_stacks(), 0);
+    let (engine, _store) = setup(&config, 1);
+    let stats = engine.stats();
+    assert_eq!(stats.compiled_funcs(), 1);
+    assert_eq!(stats.uncompiled_funcs(), 1);
+    assert!(stats.bytecode_bytes() > 0);
+}
+
+#[test]
+fn store_stats() {
+    let (_engine, mut store) = setup(&Config::default(), 1);
+    let stats = store.stats();
+    assert_eq!(stats.memory_bytes(), 2 * 65536);
+    assert_eq!(stats.table_elements(), 7);
+    assert_eq!(stats.instances(), 1);
+    assert_eq!(stats.extern_objects(), 0);
+    assert_eq!(stats.max_call_stack_depth(), 2);
+    let max_value_stack_bytes = stats.max_value_stack_bytes();
+    assert!(max_value_stack_bytes > 0);
+    let _ = Val::from(ExternRef::new(&mut store, 42_u32));
+    assert_eq!(store.stats().extern_objects(), 1);
+    let (_engine, store) = setup(&Config::default(), 10);
+    let stats = store.stats();
+    assert_eq!(stats.max_call_stack_depth(), 11);
+    assert!(stats.max_value_stack_bytes() > max_value_stack_bytes);
+}
@@ -0,0 +1,112 @@
+//! Tests for tiering of hot Wasm functions via [`Config::tiering`].
+
+use super::instantiate;
+use wasmi::{CompilationMode, Config, Instance, Store};
+
+const WAT: &str = r#"
+    (module
+        (func $sum_even (export "sum_even") (param $n i32) (result i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (block $skip
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (br_if $skip (i32.and (local.get $n) (i32.const 1)))
+                        (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Returns a [`Config`] with tiering enabled and a tier-up threshold of `threshold`.
+fn tiering_config(threshold: u32) -> Config {
+    let mut config = Config::default();
+    config.tiering(true).tier_up_threshold(threshold);
+    config
+}
+
+/// Calls the exported function `name` of `instance` with `input`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, input: i32) -> i32 {
+    instance
+        .get_typed_func::<i32, i32>(&*store, name)
+        .unwrap()
+        .call(store, input)
+        .unwrap()
+}
+
+#[test]
+fn tier_up_on_next_call() {
+    let (engine, _, mut store, instance) = instantiate(&tiering_config(20), WAT);
+    assert_eq!(call(&mut store, &instance, "sum_even", 10), 30);
+    assert_eq!(engine.stats().tiered_up_funcs(), 0);
+    // Note: the loop makes `sum_even` hot but running executions are not tiered up.
+    assert_eq!(call(&mut store, &instance, "sum_even", 100), 2550);
+    assert_eq!(engine.stats().tiered_up_funcs(), 0);
+    assert_eq!(call(&mut store, &instance, "sum_even", 100), 2550);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+    assert_eq!(call(&mut store, &instance, "sum_even", 1000), 250500);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+}
+
+#[test]
+fn tier_up_recursive() {
+    let (engine, _, mut store, instance) = instantiate(&tiering_config(10), WAT);
+    // Note: `fib` is tiered up while executions of its baseline tier are still running.
+    assert_eq!(call(&mut store, &instance, "fib", 20), 6765);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+    assert_eq!(call(&mut store, &instance, "fib", 10), 55);
+}
+
+#[test]
+fn tier_up_lazy() {
+    let mut config = tiering_config(10);
+    config.compilation_mode(CompilationMode::Lazy);
+    let (engine, _, mut store, instance) = instantiate(&config, WAT);
+    assert_eq!(call(&mut store, &instance, "fib", 15), 610);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+    assert_eq!(engine.stats().compiled_funcs(), 1);
+}
+
+#[test]
+fn tier_up_keeps_fuel_consumption() {
+    let mut config = tiering_config(50);
+    config.consume_fuel(true);
+    let (engine, _, mut store, instance) = instantiate(&config, WAT);
+    store.set_fuel(1_000_000).unwrap();
+    let mut consumed = || {
+        let before = store.get_fuel().unwrap();
+        assert_eq!(call(&mut store, &instance, "sum_even", 100), 2550);
+        before - store.get_fuel().unwrap()
+    };
+    let baseline = consumed();
+    assert_eq!(consumed(), baseline);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+}
+
+#[test]
+fn tiering_disabled() {
+    let (engine, _, mut store, instance) = instantiate(&Config::default(), WAT);
+    assert_eq!(call(&mut store, &instance, "fib", 20), 6765);
+    assert_eq!(engine.stats().tiered_up_funcs(), 0);
+    let mut config = tiering_config(10);
+    config.debugging(true);
+    let (engine, _, mut store, instance) = instantiate(&config, WAT);
+    assert_eq!(call(&mut store, &instance, "fib", 20), 6765);
+    assert_eq!(engine.stats().tiered_up_funcs(), 0);
+}
@@ -0,0 +1,205 @@
+//! Tests for the instruction execution trace hook of Wasmi.
+
+use wasmi::{Config, Engine, Error, Linker, Module, Store};
+
+const WASM: &str = r#"
+    (module
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $add (local.get 0) (i32.const 100000))
+        )
+    )
+"#;
+
+#[test]
+fn trace_hook_works() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = <Store<Vec<String>>>::new(&engine, Vec::new());
+    store.trace_hook(|trace, event| {
+        let registers = event.registers();
+        let values = (-1..i16::try_from(registers.len()).unwrap())
+            .filter_map(|n| registers.get(n))
+            .map(|value| i32::from(value).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        trace.push(format!(
+            "func[{}] @{} {:?}: {} [{values}]",
+            event.func_index(),
+            event.pos(),
+            event.offset(),
+            event.instr(),
+        ));
+        Ok(())
+    });
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 5).unwrap(), 100005);
+    assert_eq!(
+        store.data(),
+        &[
+            "func[1] @0 Some(55): r1.. = call_internal func=internal[0] [100000, 5, 0]",
+            "func[0] @0 Some(45): r2 = i32_add lhs=r0 rhs=r1 [5, 100000, 0]",
+            "func[0] @1 Some(46): return_reg value=r2 [5, 100000, 100005]",
+            "func[1] @2 Some(57): return_reg value=r1 [100000, 5, 100005]",
+        ]
+    );
+    // The trace hook can abort the execution.
+    store.trace_hook(|_, _| Err(Error::new("aborted")));
+    let error = run.call(&mut store, 5).unwrap_err();
+    assert_eq!(error.to_string(), "aborted");
+}
+
+#[test]
+fn trace_hook_prevents_tier_up() {
+    let wat = r#"
+        (module
+            (func (export "count") (param $n i32) (result i32)
+                (loop $continue
+                    (br_if $continue
+                        (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                    )
+                )
+                (local.get $n)
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.tiering(true).tier_up_threshold(10);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wat).unwrap();
+    let mut store = <Store<usize>>::new(&engine, 0);
+    store.trace_hook(|traced, _| {
+        *traced += 1;
+        Ok(())
+    });
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let count = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    for _ in 0..2 {
+        assert_eq!(count.call(&mut store, 100).unwrap(), 0);
+    }
+    assert!(*store.data() != 0);
+    assert_eq!(engine.stats().tiered_up_funcs(), 0);
+    // Note: stores without a trace hook still tier up hot functions.
+    let mut store = <Store<usize>>::new(&engine, 0);
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let count = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    for _ in 0..2 {
+        assert_eq!(count.call(&mut store, 100).unwrap(), 0);
+    }
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+}
+
+#[test]
+fn trace_hook_inlined_calls() {
+    let mut config = Config::default();
+    config.inlining(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = <Store<Vec<String>>>::new(&engine, Vec::new());
+    store.trace_hook(|trace, event| {
+        trace.push(format!(
+            "func[{}] @{} {:?}: {}",
+            event.func_index(),
+            event.pos(),
+            event.offset(),
+            event.instr(),
+        ));
+        Ok(())
+    });
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 5).unwrap(), 100005);
+    // Note: instructions of inlined calls are reported at the offset of the call.
+    assert_eq!(
+        store.data(),
+        &[
+            "func[1] @0 Some(55): r4 = copy value=r0",
+            "func[1] @1 Some(55): r5 = copy value=r-1",
+            "func[1] @2 Some(55): r6 = i32_add lhs=r4 rhs=r5",
+            "func[1] @3 Some(55): r1 = copy value=r6",
+            "func[1] @4 Some(57): return_reg value=r1",
+        ]
+    );
+    assert_eq!(engine.stats().inlined_calls(), 1);
+}
+
+#[test]
+fn trace_hook_reports_executing_tier() {
+    use std::sync::{Arc, Mutex};
+    use wasmi::Caller;
+    let wat = r#"
+        (module
+            (import "env" "host" (func $host))
+            (func (export "f") (param $n i32) (result i32)
+                (call $host)
+                (i32.add (local.get $n) (i32.const 1))
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.tiering(true).tier_up_threshold(2);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wat).unwrap();
+    // Note: store A is untraced and thus tiers up `f` while store B still executes its baseline tier.
+    let mut store_a = <Store<()>>::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance_a = linker
+        .instantiate(&mut store_a, &module)
+        .unwrap()
+        .start(&mut store_a)
+        .unwrap();
+    let store_a = Arc::new(Mutex::new((store_a, instance_a)));
+    let mut store_b = <Store<Vec<Option<u32>>>>::new(&engine, Vec::new());
+    store_b.trace_hook(|trace, event| {
+        trace.push(event.offset());
+        Ok(())
+    });
+    let mut linker = <Linker<Vec<Option<u32>>>>::new(&engine);
+    linker
+        .func_wrap("env", "host", move |_: Caller<Vec<Option<u32>>>| {
+            let (store_a, instance_a) = &mut *store_a.lock().unwrap();
+            let f = instance_a
+                .get_typed_func::<i32, i32>(&*store_a, "f")
+                .unwrap();
+            for n in 0..10 {
+                assert_eq!(f.call(&mut *store_a, n).unwrap(), n + 1);
+            }
+        })
+        .unwrap();
+    let instance_b = linker
+        .instantiate(&mut store_b, &module)
+        .unwrap()
+        .start(&mut store_b)
+        .unwrap();
+    let f = instance_b
+        .get_typed_func::<i32, i32>(&store_b, "f")
+        .unwrap();
+    assert_eq!(f.call(&mut store_b, 5).unwrap(), 6);
+    assert_eq!(engine.stats().tiered_up_funcs(), 1);
+    // Note: the baseline tier starts with a tier-up counter that has no Wasm offset.
+    assert_eq!(store_b.data(), &[None, Some(49), Some(55), Some(56)]);
+}
@@ -0,0 +1,193 @@
+//! Tests for the [`TrapDetails`] attached to Wasm traps.
+
+use super::instantiate;
+use wasmi::{
+    core::{TrapCode, ValType},
+    Config,
+    Error,
+    FuncType,
+    Instance,
+    Store,
+    TrapDetails,
+    TrapFault,
+};
+use wasmparser::{Operator, Parser, Payload};
+
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (memory $other 1)
+        (table $funcs 2 funcref)
+        (elem (table $funcs) (i32.const 0) func $callee)
+        (type $unary (func (param i32) (result i32)))
+        (func $callee (param i64))
+        (func (export "load") (param $address i32) (result i64)
+            (i64.load $other offset=8 (local.get $address))
+        )
+        (func (export "store") (param $address i32)
+            (i32.store8 (local.get $address) (i32.const 42))
+        )
+        (func (export "fill") (param $address i32) (param $len i32)
+            (memory.fill (local.get $address) (i32.const 0) (local.get $len))
+        )
+        (func (export "table_get") (param $index i32)
+            (drop (table.get $funcs (local.get $index)))
+        )
+        (func (export "call_indirect") (param $index i32) (result i32)
+            (call_indirect $funcs (type $unary) (i32.const 0) (local.get $index))
+        )
+    )
+"#;
+
+/// Calls the exported function `name` with `params` and returns its trap [`Error`].
+fn call_trap<Params, Results>(
+    store: &mut Store<()>,
+    instance: &Instance,
+    name: &str,
+    params: Params,
+) -> Error
+where
+    Params: wasmi::WasmParams,
+    Results: wasmi::WasmResults + core::fmt::Debug,
+{
+    instance
+        .get_typed_func::<Params, Results>(&*store, name)
+        .unwrap()
+        .call(store, params)
+        .unwrap_err()
+}
+
+/// Returns the [`TrapDetails`] of `error` and asserts that they match its [`TrapCode`].
+fn details(error: &Error) -> &TrapDetails {
+    let details = error.trap_details().unwrap();
+    assert_eq!(error.as_trap_code(), Some(details.trap_code()));
+    details
+}
+
+#[test]
+fn memory_out_of_bounds() {
+    let (_, _, mut store, instance) = instantiate(&Config::default(), WAT);
+    let error = call_trap::<i32, i64>(&mut store, &instance, "load", 65530);
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::MemoryOutOfBounds {
+            memory: 1,
+            address: 65530,
+            offset: 8,
+            len: 8,
+        }
+    );
+    assert_eq!(details(&error).offset(), None);
+    assert_eq!(
+        error.to_string(),
+        "out of bounds memory access (memory 1: 8 byte access at address 0xfffa with offset 0x8)",
+    );
+    let error = call_trap::<i32, ()>(&mut store, &instance, "store", 65536);
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::MemoryOutOfBounds {
+            memory: 0,
+            address: 65536,
+            offset: 0,
+            len: 1,
+        }
+    );
+    let error = call_trap::<(i32, i32), ()>(&mut store, &instance, "fill", (65000, 1000));
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::MemoryOutOfBounds {
+            memory: 0,
+            address: 65000,
+            offset: 0,
+            len: 1000,
+        }
+    );
+}
+
+#[test]
+fn table_out_of_bounds() {
+    let (_, _, mut store, instance) = instantiate(&Config::default(), WAT);
+    let error = call_trap::<i32, ()>(&mut store, &instance, "table_get", 5);
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::TableOutOfBounds {
+            table: 0,
+            index: 5,
+            size: 2,
+        }
+    );
+    let error = call_trap::<i32, i32>(&mut store, &instance, "call_indirect", 2);
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::TableOutOfBounds {
+            table: 0,
+            index: 2,
+            size: 2,
+        }
+    );
+}
+
+#[test]
+fn indirect_call() {
+    let (_, _, mut store, instance) = instantiate(&Config::default(), WAT);
+    let error = call_trap::<i32, i32>(&mut store, &instance, "call_indirect", 1);
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::IndirectCallToNull { table: 0, index: 1 }
+    );
+    let error = call_trap::<i32, i32>(&mut store, &instance, "call_indirect", 0);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+    assert_eq!(
+        details(&error).fault(),
+        &TrapFault::BadSignature {
+            table: 0,
+            index: 0,
+            expected: FuncType::new([ValType::I32], [ValType::I32]),
+            actual: FuncType::new([ValType::I64], []),
+        }
+    );
+    assert!(error.to_string().contains(
+        "table 0: index 0 expected (func (param i32) (result i32)) but found (func (param i64))"
+    ));
+}
+
+/// Returns the offset of the `i64.load` operator of the Wasm binary `wasm`.
+fn load_offset(wasm: &[u8]) -> u32 {
+    for payload in Parser::new(0).parse_all(wasm) {
+        let Payload::CodeSectionEntry(body) = payload.unwrap() else {
+            continue;
+        };
+        for operator in body
+            .get_operators_reader()
+            .unwrap()
+            .into_iter_with_offsets()
+        {
+            if let (Operator::I64Load { .. }, offset) = operator.unwrap() {
+                return offset as u32;
+            }
+        }
+    }
+    panic!("missing `i64.load` operator")
+}
+
+#[test]
+fn offset() {
+    let mut config = Config::default();
+    config.wasm_backtrace(true);
+    let (_, _, mut store, instance) = instantiate(&config, WAT);
+    let error = call_trap::<i32, i64>(&mut store, &instance, "load", 65530);
+    let offset = load_offset(&wat::parse_str(WAT).unwrap());
+    assert_eq!(details(&error).offset(), Some(offset));
+    assert!(error
+        .to_string()
+        .contains(&format!("at wasm offset {offset:#x})")));
+}
+
+#[test]
+fn no_details() {
+    let wat = r#"(module (func (export "f") (unreachable)))"#;
+    let (_, _, mut store, instance) = instantiate(&Config::default(), wat);
+    let error = call_trap::<(), ()>(&mut store, &instance, "f", ());
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert!(error.trap_details().is_none());
+}
@@ -331,3 +331,23 @@ mod streaming {
         let runner = process_wast;
     }
 }
+
+mod optimized {
+    use super::*;
+    use wasmi::OptimizationLevel;
+
+    fn test_config() -> RunnerConfig {
+        let mut runner_config = super::test_config(true, ParsingMode::Buffered);
+        runner_config
+            .config
+            .optimization_level(OptimizationLevel::Full);
+        runner_config
+    }
+
+    expand_tests! {
+        define_spec_tests,
+
+        let config = test_config();
+        let runner = process_wast;
+    }
+}
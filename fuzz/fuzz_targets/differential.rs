@@ -27,9 +27,18 @@ fuzz_target!(|seed: &[u8]| {
     let Ok(_) = smith_module.ensure_termination(1_000 /* fuel */) else {
         return;
     };
+    // Note: read after the `wasm_smith` module so that existing corpus entries
+    //       still decode to the same Wasm modules.
+    let deterministic = bool::arbitrary(&mut u).unwrap_or_default();
     let wasm_bytes = smith_module.to_bytes();
     let wasm = wasm_bytes.as_slice();
-    let Some(mut wasmi_oracle) = WasmiOracle::setup(wasm) else {
+    // Note: Wasmi's deterministic execution must yield the same results as
+    //       the NaN canonicalization applied by `wasm_smith` to the module.
+    let wasmi_oracle = match deterministic {
+        true => WasmiOracle::setup_deterministic(wasm),
+        false => WasmiOracle::setup(wasm),
+    };
+    let Some(mut wasmi_oracle) = wasmi_oracle else {
         return;
     };
     let Some(mut chosen_oracle) = chosen_oracle.setup(wasm) else {